@@ -6,6 +6,7 @@ use crate::state::{
     PriceData, WalletStatusData,
 };
 use crate::symbol_mapping::{self, SymbolMapping, SymbolMappings};
+use serde_json::json;
 use std::sync::Arc;
 use tauri::State;
 use tracing::info;
@@ -28,20 +29,24 @@ pub fn get_stats(state: State<'_, Arc<AppState>>) -> BotStats {
     state.get_stats()
 }
 
-/// Start the bot (sends command to CLI server - not implemented yet).
+/// Resume detection on the CLI server (needs a `trade` API key).
 #[tauri::command]
-pub fn start_bot(_state: State<'_, Arc<AppState>>) -> bool {
-    info!("start_bot called - CLI server controls bot state");
-    // TODO: Send start command to CLI server via WebSocket
-    true
+pub async fn start_bot(state: State<'_, Arc<AppState>>) -> Result<bool, String> {
+    let message = state
+        .send_control_command(json!({ "command": "resume" }))
+        .await?;
+    info!("start_bot: {}", message);
+    Ok(true)
 }
 
-/// Stop the bot (sends command to CLI server - not implemented yet).
+/// Pause detection on the CLI server (needs a `trade` API key).
 #[tauri::command]
-pub fn stop_bot(_state: State<'_, Arc<AppState>>) -> bool {
-    info!("stop_bot called - CLI server controls bot state");
-    // TODO: Send stop command to CLI server via WebSocket
-    true
+pub async fn stop_bot(state: State<'_, Arc<AppState>>) -> Result<bool, String> {
+    let message = state
+        .send_control_command(json!({ "command": "pause" }))
+        .await?;
+    info!("stop_bot: {}", message);
+    Ok(true)
 }
 
 /// Get current configuration.
@@ -50,34 +55,51 @@ pub fn get_config(state: State<'_, Arc<AppState>>) -> ExecutionConfig {
     state.get_config()
 }
 
-/// Update configuration.
+/// Update configuration and push the thresholds to the CLI server.
 #[tauri::command]
-pub fn update_config(config: ExecutionConfig, state: State<'_, Arc<AppState>>) -> bool {
+pub async fn update_config(
+    config: ExecutionConfig,
+    state: State<'_, Arc<AppState>>,
+) -> Result<bool, String> {
+    let message = state
+        .send_control_command(json!({
+            "command": "set_thresholds",
+            "min_premium_bps": config.min_premium_bps,
+            "max_slippage_bps": config.max_slippage_bps,
+        }))
+        .await?;
     state.update_config(config);
-    info!("Config updated via command");
-    true
+    info!("Config updated via command: {}", message);
+    Ok(true)
 }
 
-/// Execute an arbitrage opportunity (manual approval).
+/// Approve an execution pending on the CLI server (manual approval mode).
+/// The server records and announces the approval; it places no orders.
 #[tauri::command]
-pub fn execute_opportunity(
+pub async fn approve_opportunity(
     opportunity_id: u64,
-    _amount: f64,
-    _state: State<'_, Arc<AppState>>,
+    state: State<'_, Arc<AppState>>,
 ) -> Result<String, String> {
-    info!("execute_opportunity called for {}", opportunity_id);
-    // TODO: Send execute command to CLI server via WebSocket
-    Ok(format!(
-        "Execution request sent for opportunity {}",
-        opportunity_id
-    ))
-}
-
-/// Set CLI server WebSocket URL.
-#[tauri::command]
-pub fn set_server_url(url: String, state: State<'_, Arc<AppState>>) -> bool {
+    info!("approve_opportunity called for {}", opportunity_id);
+    state
+        .send_control_command(json!({
+            "command": "approve",
+            "opportunity_id": opportunity_id,
+        }))
+        .await
+}
+
+/// Set CLI server WebSocket URL and API key (`wss://` for TLS servers).
+/// Takes effect on the next reconnect.
+#[tauri::command]
+pub fn set_server_url(
+    url: String,
+    api_key: Option<String>,
+    state: State<'_, Arc<AppState>>,
+) -> bool {
     info!("Setting server URL to: {}", url);
     state.set_server_url(url);
+    state.set_server_api_key(api_key.filter(|key| !key.is_empty()));
     true
 }
 
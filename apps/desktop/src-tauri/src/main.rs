@@ -56,7 +56,7 @@ fn main() {
             commands::stop_bot,
             commands::get_config,
             commands::update_config,
-            commands::execute_opportunity,
+            commands::approve_opportunity,
             commands::set_server_url,
             commands::is_connected,
             commands::get_exchange_rate,
@@ -9,6 +9,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{error, info, warn};
 
@@ -37,6 +39,12 @@ pub struct BotStats {
     pub opportunities_detected: u64,
     pub trades_executed: u64,
     pub is_running: bool,
+    /// Pairs waiting for detection
+    #[serde(default)]
+    pub detection_queue_depth: u64,
+    /// Moving average of price-update-to-detection latency (ms)
+    #[serde(default)]
+    pub detection_latency_ms: f64,
 }
 
 impl Default for BotStats {
@@ -47,6 +55,8 @@ impl Default for BotStats {
             opportunities_detected: 0,
             trades_executed: 0,
             is_running: false,
+            detection_queue_depth: 0,
+            detection_latency_ms: 0.0,
         }
     }
 }
@@ -60,6 +70,40 @@ pub struct UsdlikePremium {
     pub quote: String,
 }
 
+/// Per-factor confidence breakdown (each 0-100, 50 = no data).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ConfidenceBreakdown {
+    pub quote_age: u8,
+    pub depth: u8,
+    pub persistence: u8,
+    pub transfer_path: u8,
+    pub activity: u8,
+    pub symbol_mapping: u8,
+}
+
+/// Rolling premium statistics for a route.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PremiumStats {
+    pub samples: u32,
+    pub mean_bps: f64,
+    pub stddev_bps: f64,
+    pub zscore: f64,
+    pub percentile: f64,
+    #[serde(default)]
+    pub half_life_ms: Option<f64>,
+}
+
+/// Transfer-time price risk for an opportunity.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferRisk {
+    #[serde(default)]
+    pub network: Option<String>,
+    pub transfer_time_ms: u64,
+    pub volatility_bps: f64,
+    pub expected_profit: f64,
+    pub worst_case_profit: f64,
+}
+
 /// Opportunity data from CLI server.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpportunityData {
@@ -85,6 +129,12 @@ pub struct OpportunityData {
     pub target_price: f64,
     pub net_profit_bps: i32,
     pub confidence_score: u8,
+    /// Per-factor breakdown of confidence_score
+    #[serde(default)]
+    pub confidence_breakdown: Option<ConfidenceBreakdown>,
+    /// Rolling premium statistics for this route
+    #[serde(default)]
+    pub premium_stats: Option<PremiumStats>,
     pub timestamp: u64,
     /// Common networks available for transfer (canonical names)
     #[serde(default)]
@@ -107,9 +157,16 @@ pub struct OpportunityData {
     /// Expected profit at optimal_size (in quote currency, e.g., USDT)
     #[serde(default)]
     pub optimal_profit: f64,
-    /// Reason for optimal_size value: "ok" | "no_orderbook" | "not_profitable"
+    /// Gas for DEX legs and the withdrawal (same quote currency as optimal_profit)
+    #[serde(default)]
+    pub estimated_gas_cost: f64,
+    /// Reason for optimal_size value: "ok" | "no_orderbook" | "not_profitable" |
+    /// "no_conversion_rate" | "depth_exhausted" | "below_min_order" | "no_transfer_network"
     #[serde(default)]
     pub optimal_size_reason: Option<String>,
+    /// Transfer-time price risk at optimal_size
+    #[serde(default)]
+    pub transfer_risk: Option<TransferRisk>,
     /// Raw price from source exchange in original quote currency (e.g., KRW for Korean exchanges)
     #[serde(default)]
     pub source_raw_price: f64,
@@ -240,6 +297,134 @@ pub struct PremiumMatrixData {
     pub timestamp: u64,
 }
 
+/// Kimchi premium index for a single Korean exchange.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KimchiExchangeIndex {
+    pub exchange: String,
+    pub index_bps: f64,
+    pub markets: u32,
+    pub weight_usd: f64,
+}
+
+/// Market deviating from its exchange's kimchi index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KimchiOutlier {
+    pub symbol: String,
+    pub exchange: String,
+    pub premium_bps: i32,
+    pub deviation_bps: f64,
+}
+
+/// Market-wide kimchi premium index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KimchiIndexData {
+    pub index_bps: f64,
+    pub markets: u32,
+    pub exchanges: Vec<KimchiExchangeIndex>,
+    pub outliers: Vec<KimchiOutlier>,
+    pub timestamp: u64,
+}
+
+/// Stablecoin peg level change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepegData {
+    pub exchange: String,
+    pub stablecoin: String,
+    pub reference: String,
+    pub rate: f64,
+    pub deviation_bps: i32,
+    /// "normal", "warning" or "critical"
+    pub level: String,
+    pub previous_level: String,
+    pub timestamp: u64,
+}
+
+/// Opportunity episode state change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpportunityLifecycleData {
+    /// "opened", "updated" or "closed"
+    pub event: String,
+    pub id: u64,
+    pub symbol: String,
+    pub source_exchange: String,
+    pub target_exchange: String,
+    pub source_quote: String,
+    pub target_quote: String,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub duration_ms: u64,
+    pub peak_premium_bps: i32,
+    pub last_premium_bps: i32,
+    pub time_above_threshold_ms: u64,
+    pub close_reason: Option<String>,
+}
+
+/// Configuration applied by a live reload on the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChangedData {
+    /// Changed settings as dotted paths (e.g. "detector.min_premium_bps")
+    pub changed: Vec<String>,
+    /// Changed settings that only take effect after a server restart
+    pub restart_required: Vec<String>,
+    pub min_premium_bps: i32,
+    pub enabled_exchanges: Vec<String>,
+    pub timestamp: u64,
+}
+
+/// Acknowledgement of a subscription command sent to the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionData {
+    pub channels: Vec<String>,
+    /// Subscribed symbols (None = all)
+    pub symbols: Option<Vec<String>>,
+    pub excluded_symbols: Vec<String>,
+    /// Subscribed exchanges (None = all)
+    pub exchanges: Option<Vec<String>>,
+    pub excluded_exchanges: Vec<String>,
+    pub throttle_ms: Option<u64>,
+    pub dropped: u64,
+}
+
+/// Server dropped messages because this client fell behind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaggedData {
+    pub dropped: u64,
+    pub total_dropped: u64,
+    pub timestamp: u64,
+}
+
+/// Server rejected a client command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerErrorData {
+    pub message: String,
+}
+
+/// Remote control command applied or rejected by the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlData {
+    /// API key name of the client that sent the command
+    pub client: String,
+    /// Command name (e.g. "pause", "approve")
+    pub command: String,
+    pub params: serde_json::Value,
+    pub ok: bool,
+    /// What was done, or why the command was rejected
+    pub message: String,
+    pub timestamp: u64,
+}
+
+/// Execution waiting for manual approval on the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingExecutionData {
+    pub opportunity_id: u64,
+    pub symbol: String,
+    pub buy_exchange: String,
+    pub sell_exchange: String,
+    pub premium_bps: i32,
+    pub net_profit_bps: i32,
+    pub queued_at: u64,
+}
+
 /// WebSocket message types from CLI server.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "type", content = "data")]
@@ -268,6 +453,33 @@ pub enum WsServerMessage {
     /// Premium matrix for a symbol (all exchange pairs)
     #[serde(rename = "premium_matrix")]
     PremiumMatrix(PremiumMatrixData),
+    /// Market-wide kimchi premium index
+    #[serde(rename = "kimchi_index")]
+    KimchiIndex(KimchiIndexData),
+    /// Stablecoin depeg level change
+    #[serde(rename = "depeg")]
+    Depeg(DepegData),
+    /// Opportunity opened, reached a new peak or closed
+    #[serde(rename = "opportunity_lifecycle")]
+    OpportunityLifecycle(OpportunityLifecycleData),
+    /// Server configuration reloaded without restart
+    #[serde(rename = "config_changed")]
+    ConfigChanged(ConfigChangedData),
+    /// Subscription command acknowledged
+    #[serde(rename = "subscription")]
+    Subscription(SubscriptionData),
+    /// Messages dropped because this client fell behind
+    #[serde(rename = "lagged")]
+    Lagged(LaggedData),
+    /// Client command rejected
+    #[serde(rename = "error")]
+    Error(ServerErrorData),
+    /// Remote control command applied or rejected
+    #[serde(rename = "control")]
+    Control(ControlData),
+    /// Execution queued for manual approval
+    #[serde(rename = "pending_execution")]
+    PendingExecution(PendingExecutionData),
 }
 
 /// Application state shared across commands.
@@ -276,6 +488,8 @@ pub struct AppState {
     connected: AtomicBool,
     /// CLI server WebSocket URL
     server_url: std::sync::RwLock<String>,
+    /// CLI server API key (sent as a bearer token)
+    server_api_key: std::sync::RwLock<Option<String>>,
     /// Cached prices from CLI server (key: "exchange:symbol")
     prices: DashMap<String, PriceData>,
     /// Cached stats from CLI server
@@ -297,6 +511,7 @@ impl AppState {
         Self {
             connected: AtomicBool::new(false),
             server_url: std::sync::RwLock::new("ws://127.0.0.1:9001/ws".to_string()),
+            server_api_key: std::sync::RwLock::new(None),
             prices: DashMap::new(),
             stats: std::sync::RwLock::new(BotStats::default()),
             opportunities: std::sync::RwLock::new(Vec::new()),
@@ -323,6 +538,43 @@ impl AppState {
         self.server_url.read().unwrap().clone()
     }
 
+    pub fn set_server_api_key(&self, api_key: Option<String>) {
+        *self.server_api_key.write().unwrap() = api_key;
+    }
+
+    pub fn get_server_api_key(&self) -> Option<String> {
+        self.server_api_key.read().unwrap().clone()
+    }
+
+    /// Send a remote control command to the CLI server's `/api/control`.
+    /// Returns the server's description of what was done.
+    pub async fn send_control_command(&self, command: serde_json::Value) -> Result<String, String> {
+        let url = control_url(&self.get_server_url());
+        let mut request = reqwest::Client::new().post(&url).json(&command);
+        if let Some(key) = self.get_server_api_key() {
+            request = request.bearer_auth(key);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach CLI server: {}", e))?;
+
+        if response.status().is_success() {
+            let control: ControlData = response
+                .json()
+                .await
+                .map_err(|e| format!("Invalid control response: {}", e))?;
+            Ok(control.message)
+        } else {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            Err(body["error"]
+                .as_str()
+                .map(String::from)
+                .unwrap_or_else(|| format!("CLI server returned {}", status)))
+        }
+    }
+
     pub fn update_price(&self, price: PriceData) {
         // Key includes quote to differentiate USDT vs USDC markets for same exchange/symbol
         let quote = price.quote.as_deref().unwrap_or("USD");
@@ -414,15 +666,31 @@ impl Default for AppState {
     }
 }
 
+/// REST control endpoint of the server behind a WebSocket URL
+/// (`ws://host:port/ws` -> `http://host:port/api/control`).
+fn control_url(ws_url: &str) -> String {
+    let base = ws_url.trim_end_matches('/');
+    let base = base.strip_suffix("/ws").unwrap_or(base);
+    let base = if let Some(rest) = base.strip_prefix("wss://") {
+        format!("https://{}", rest)
+    } else if let Some(rest) = base.strip_prefix("ws://") {
+        format!("http://{}", rest)
+    } else {
+        base.to_string()
+    };
+    format!("{}/api/control", base)
+}
+
 /// Connect to CLI server WebSocket and receive real-time data.
 pub async fn run_server_connection(state: Arc<AppState>, app: AppHandle) {
     info!("Starting CLI server connection loop");
 
     loop {
         let url = state.get_server_url();
+        let api_key = state.get_server_api_key();
         info!("Connecting to CLI server: {}", url);
 
-        match connect_to_server(&state, &app, &url).await {
+        match connect_to_server(&state, &app, &url, api_key.as_deref()).await {
             Ok(_) => {
                 info!("Disconnected from CLI server, reconnecting...");
             }
@@ -442,8 +710,15 @@ async fn connect_to_server(
     state: &Arc<AppState>,
     app: &AppHandle,
     url: &str,
+    api_key: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let (ws_stream, _) = connect_async(url).await?;
+    let mut request = url.into_client_request()?;
+    if let Some(api_key) = api_key {
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, format!("Bearer {}", api_key).parse()?);
+    }
+    let (ws_stream, _) = connect_async(request).await?;
     let (mut write, mut read) = ws_stream.split();
 
     state.set_connected(true);
@@ -492,6 +767,34 @@ async fn connect_to_server(
                         WsServerMessage::PremiumMatrix(matrix) => {
                             let _ = app.emit("premium_matrix", &matrix);
                         }
+                        WsServerMessage::KimchiIndex(index) => {
+                            let _ = app.emit("kimchi_index", &index);
+                        }
+                        WsServerMessage::Depeg(depeg) => {
+                            let _ = app.emit("depeg", &depeg);
+                        }
+                        WsServerMessage::OpportunityLifecycle(lifecycle) => {
+                            let _ = app.emit("opportunity_lifecycle", &lifecycle);
+                        }
+                        WsServerMessage::ConfigChanged(config) => {
+                            let _ = app.emit("config_changed", &config);
+                        }
+                        WsServerMessage::Subscription(subscription) => {
+                            let _ = app.emit("subscription", &subscription);
+                        }
+                        WsServerMessage::Lagged(lagged) => {
+                            warn!("Server dropped {} messages", lagged.dropped);
+                            let _ = app.emit("lagged", &lagged);
+                        }
+                        WsServerMessage::Error(e) => {
+                            warn!("Server rejected command: {}", e.message);
+                        }
+                        WsServerMessage::Control(control) => {
+                            let _ = app.emit("control", &control);
+                        }
+                        WsServerMessage::PendingExecution(pending) => {
+                            let _ = app.emit("pending_execution", &pending);
+                        }
                     }
                 }
             }
@@ -561,4 +864,16 @@ mod tests {
         let updated = state.get_config();
         assert_eq!(updated.min_premium_bps, 50);
     }
+
+    #[test]
+    fn test_control_url() {
+        assert_eq!(
+            control_url("ws://127.0.0.1:9001/ws"),
+            "http://127.0.0.1:9001/api/control"
+        );
+        assert_eq!(
+            control_url("wss://bot.example.com/ws/"),
+            "https://bot.example.com/api/control"
+        );
+    }
 }
@@ -83,11 +83,11 @@ const ExchangeLink = ({
 };
 
 function Opportunities() {
-  const { opportunities: rawOpportunities, executeOpportunity } = useOpportunities();
+  const { opportunities: rawOpportunities, approveOpportunity } = useOpportunities();
   const prices = usePrices();
   const walletStatuses = useWalletStatus();
   const exchangeRate = useExchangeRate();
-  const [executing, setExecuting] = useState<number | null>(null);
+  const [approving, setApproving] = useState<number | null>(null);
   const [message, setMessage] = useState<string | null>(null);
   const [priceChanges, setPriceChanges] = useState<Map<OppKey, PriceChanges>>(new Map());
   const prevDataRef = useRef<Map<OppKey, { source: number; target: number; spread: number }>>(new Map());
@@ -249,16 +249,16 @@ function Opportunities() {
     return "";
   };
 
-  const handleExecute = async (id: number) => {
-    setExecuting(id);
+  const handleApprove = async (id: number) => {
+    setApproving(id);
     setMessage(null);
     try {
-      const result = await executeOpportunity(id, 1000);
+      const result = await approveOpportunity(id);
       setMessage(result);
     } catch (err) {
       setMessage(`Error: ${err}`);
     } finally {
-      setExecuting(null);
+      setApproving(null);
     }
   };
 
@@ -748,8 +748,16 @@ function Opportunities() {
                         <div className="flex flex-col items-end text-xs font-mono">
                           {optimalSize > 0 ? (
                             <>
-                              <span className="text-primary-400" title={`Optimal trade size from depth walking: ${optimalSize} ${opp.symbol}`}>
-                                {formatQty(optimalSize)} <span className="text-gray-500">({formatUsd(optimalUsd)})</span>
+                              <span
+                                className="text-primary-400"
+                                title={
+                                  reason === "depth_exhausted"
+                                    ? `Optimal trade size exceeds cached orderbook depth (slippage extrapolated): ${optimalSize} ${opp.symbol}`
+                                    : `Optimal trade size from depth walking: ${optimalSize} ${opp.symbol}`
+                                }
+                              >
+                                {formatQty(optimalSize)}{reason === "depth_exhausted" ? "+" : ""}{' '}
+                                <span className="text-gray-500">({formatUsd(optimalUsd)})</span>
                               </span>
                               <span
                                 className={`${optimalProfit > 0 ? 'text-success-400' : 'text-danger-400'}`}
@@ -762,6 +770,14 @@ function Opportunities() {
                             <span className="text-yellow-500" title="Orderbook data not available for one or both exchanges">
                               No OB
                             </span>
+                          ) : reason === "below_min_order" ? (
+                            <span className="text-gray-500" title="Profitable size is below the exchange minimum order size">
+                              Min Order
+                            </span>
+                          ) : reason === "no_transfer_network" ? (
+                            <span className="text-danger-400" title="No network is open for withdrawal on the buy exchange and deposit on the sell exchange">
+                              No Route
+                            </span>
                           ) : reason === "not_profitable" ? (
                             <span className="text-gray-500" title="Trade is not profitable after considering orderbook depth and fees">
                               No Profit
@@ -822,15 +838,15 @@ function Opportunities() {
                   </td>
                   <td className="p-4 text-center">
                     <button
-                      onClick={() => handleExecute(opp.id)}
-                      disabled={executing === opp.id}
+                      onClick={() => handleApprove(opp.id)}
+                      disabled={approving === opp.id}
                       className={`px-3 py-1 rounded text-sm font-medium ${
-                        executing === opp.id
+                        approving === opp.id
                           ? "bg-gray-600 cursor-not-allowed"
                           : "bg-primary-600 hover:bg-primary-500"
                       }`}
                     >
-                      {executing === opp.id ? "..." : "Execute"}
+                      {approving === opp.id ? "..." : "Approve"}
                     </button>
                   </td>
                 </tr>
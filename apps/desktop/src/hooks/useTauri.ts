@@ -389,17 +389,16 @@ export function useOpportunities() {
     };
   }, []);
 
-  const executeOpportunity = useCallback(
-    async (id: number, amount: number): Promise<string> => {
-      return invoke<string>("execute_opportunity", {
+  const approveOpportunity = useCallback(
+    async (id: number): Promise<string> => {
+      return invoke<string>("approve_opportunity", {
         opportunityId: id,
-        amount,
       });
     },
     []
   );
 
-  return { opportunities, executeOpportunity };
+  return { opportunities, approveOpportunity };
 }
 
 export function useStats() {
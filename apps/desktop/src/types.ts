@@ -31,6 +31,40 @@ export interface UsdlikePremium {
   quote: UsdlikeQuote;
 }
 
+// Per-factor confidence breakdown (each 0-100, 50 = no data)
+export interface ConfidenceBreakdown {
+  quote_age: number;
+  depth: number;
+  persistence: number;
+  transfer_path: number;
+  activity: number;
+  symbol_mapping: number;
+}
+
+// Rolling premium statistics for a route
+export interface PremiumStats {
+  samples: number;
+  mean_bps: number;
+  stddev_bps: number;
+  zscore: number;
+  // Percentage of window samples at or below the current premium
+  percentile: number;
+  half_life_ms?: number | null;
+}
+
+// Transfer-time price risk at optimal size
+export interface TransferRisk {
+  network?: string;
+  // Expected confirmation plus deposit crediting time
+  transfer_time_ms: number;
+  // Price move standard deviation over the transfer time
+  volatility_bps: number;
+  // Profit after a volatility penalty
+  expected_profit: number;
+  // Profit after a worst-case adverse price move
+  worst_case_profit: number;
+}
+
 export interface ArbitrageOpportunity {
   id: number;
   symbol: string;
@@ -52,6 +86,10 @@ export interface ArbitrageOpportunity {
   kimchi_premium_bps: number;
   net_profit_bps: number;
   confidence_score: number;
+  // Per-factor breakdown of confidence_score
+  confidence_breakdown?: ConfidenceBreakdown;
+  // Rolling premium statistics for this route
+  premium_stats?: PremiumStats;
   timestamp: number;
   // Common networks available for transfer (canonical names)
   common_networks?: string[];
@@ -67,8 +105,19 @@ export interface ArbitrageOpportunity {
   optimal_size?: number;
   // Expected profit at optimal size (after fees)
   optimal_profit?: number;
-  // Reason for optimal_size value: "ok" | "no_orderbook" | "not_profitable"
-  optimal_size_reason?: "ok" | "no_orderbook" | "not_profitable";
+  // Gas for DEX legs and the withdrawal (same quote as optimal_profit)
+  estimated_gas_cost?: number;
+  // Reason for optimal_size value
+  optimal_size_reason?:
+    | "ok"
+    | "no_orderbook"
+    | "not_profitable"
+    | "no_conversion_rate"
+    | "depth_exhausted"
+    | "below_min_order"
+    | "no_transfer_network";
+  // Transfer-time price risk at optimal size
+  transfer_risk?: TransferRisk;
   // Raw price from source exchange in original quote currency (e.g., KRW for Korean exchanges)
   source_raw_price?: number;
   // Raw price from target exchange in original quote currency
@@ -83,6 +132,10 @@ export interface BotStats {
   opportunities_detected: number;
   trades_executed: number;
   is_running: boolean;
+  // Pairs waiting for detection
+  detection_queue_depth?: number;
+  // Moving average of price-update-to-detection latency (ms)
+  detection_latency_ms?: number;
 }
 
 export interface ExecutionConfig {
@@ -214,3 +267,116 @@ export interface PremiumMatrixData {
   entries: PremiumEntry[];
   timestamp: number;
 }
+
+// Kimchi premium index for a single Korean exchange
+export interface KimchiExchangeIndex {
+  exchange: string;
+  index_bps: number;
+  markets: number;
+  weight_usd: number;
+}
+
+// Market deviating from its exchange's kimchi index
+export interface KimchiOutlier {
+  symbol: string;
+  exchange: string;
+  premium_bps: number;
+  deviation_bps: number;
+}
+
+// Market-wide kimchi premium index (volume-weighted)
+export interface KimchiIndexData {
+  index_bps: number;
+  markets: number;
+  exchanges: KimchiExchangeIndex[];
+  outliers: KimchiOutlier[];
+  timestamp: number;
+}
+
+// Stablecoin peg level change
+export type DepegLevel = 'normal' | 'warning' | 'critical';
+
+export interface DepegData {
+  exchange: string;
+  stablecoin: string;
+  reference: string;
+  rate: number;
+  deviation_bps: number;
+  level: DepegLevel;
+  previous_level: DepegLevel;
+  timestamp: number;
+}
+
+// Opportunity episode state change
+export type OpportunityLifecycleEvent = 'opened' | 'updated' | 'closed';
+
+export type OpportunityCloseReason =
+  | 'below_threshold'
+  | 'stale'
+  | 'exchange_disconnected'
+  | 'shutdown';
+
+export interface OpportunityLifecycleData {
+  event: OpportunityLifecycleEvent;
+  id: number;
+  symbol: string;
+  source_exchange: string;
+  target_exchange: string;
+  source_quote: string;
+  target_quote: string;
+  first_seen: number;
+  last_seen: number;
+  duration_ms: number;
+  peak_premium_bps: number;
+  last_premium_bps: number;
+  time_above_threshold_ms: number;
+  close_reason: OpportunityCloseReason | null;
+}
+
+// Server configuration reloaded without restart
+export interface ConfigChangedData {
+  changed: string[]; // dotted paths, e.g. "detector.min_premium_bps"
+  restart_required: string[];
+  min_premium_bps: number;
+  enabled_exchanges: string[];
+  timestamp: number;
+}
+
+// Acknowledgement of a /ws subscription command
+export interface SubscriptionData {
+  channels: string[];
+  symbols: string[] | null; // null = all
+  excluded_symbols: string[];
+  exchanges: string[] | null; // null = all
+  excluded_exchanges: string[];
+  throttle_ms: number | null;
+  dropped: number;
+}
+
+// Server dropped messages because this client fell behind
+export interface LaggedData {
+  dropped: number;
+  total_dropped: number;
+  timestamp: number;
+}
+
+// Remote control command applied or rejected by the server
+export interface ControlData {
+  client: string; // API key name
+  command: string; // e.g. "pause", "approve"
+  params: Record<string, unknown>;
+  ok: boolean;
+  message: string;
+  timestamp: number;
+}
+
+// Execution waiting for manual approval on the server
+export interface PendingExecutionData {
+  opportunity_id: number;
+  symbol: string;
+  buy_exchange: string;
+  sell_exchange: string;
+  premium_bps: number;
+  net_profit_bps: number;
+  queued_at: number;
+}
@@ -0,0 +1,440 @@
+//! Client authentication, origin allowlisting and rate limiting for the
+//! WebSocket and REST server.
+//!
+//! Clients send an API key as `Authorization: Bearer <key>`, `X-API-Key: <key>`
+//! or, for browser WebSockets that cannot set headers, a `token` query
+//! parameter. Each key grants a scope; trading and control commands need
+//! `trade`. Without configured keys every client is anonymous with `read`
+//! scope, so such a server never accepts commands.
+//!
+//! Browser requests carrying an `Origin` header must come from an allowed
+//! origin; CORS alone does not protect WebSocket upgrades. A server with API
+//! keys and no listed origins answers no CORS requests.
+
+use crate::config::{ApiKeySettings, ApiScope, ServerSettings};
+use crate::rest_api::ApiError;
+use axum::{
+    extract::{ConnectInfo, Query, Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::debug;
+
+/// Rate limit buckets kept at most; idle ones are pruned first, then the
+/// least recently used.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Authenticated client, added to the request extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Client {
+    /// API key name, or "anonymous".
+    pub name: String,
+    pub scope: ApiScope,
+}
+
+impl Client {
+    /// Reject the request unless the client has `scope`.
+    pub fn require(&self, scope: ApiScope) -> Result<(), ApiError> {
+        if self.scope >= scope {
+            Ok(())
+        } else {
+            let scope = match scope {
+                ApiScope::Read => "read",
+                ApiScope::Trade => "trade",
+            };
+            Err(ApiError::Forbidden(format!(
+                "API key {:?} lacks {} scope",
+                self.name, scope
+            )))
+        }
+    }
+}
+
+/// Access control for the server.
+#[derive(Debug)]
+pub struct ServerAuth {
+    api_keys: Vec<ApiKeySettings>,
+    allowed_origins: Vec<String>,
+    rate_limiter: RateLimiter,
+}
+
+impl ServerAuth {
+    pub fn new(settings: &ServerSettings) -> Self {
+        Self {
+            api_keys: settings.api_keys.clone(),
+            allowed_origins: settings
+                .allowed_origins
+                .iter()
+                .map(|origin| origin.trim_end_matches('/').to_string())
+                .collect(),
+            rate_limiter: RateLimiter::new(settings.rate_limit_per_minute),
+        }
+    }
+
+    /// Whether clients must present an API key.
+    pub fn requires_key(&self) -> bool {
+        !self.api_keys.is_empty()
+    }
+
+    /// CORS layer answering browsers from the allowed origins.
+    pub fn cors(&self) -> CorsLayer {
+        let cors = CorsLayer::new().allow_methods(Any).allow_headers(Any);
+        match self.cors_origins() {
+            Some(origins) => cors.allow_origin(AllowOrigin::list(origins)),
+            None => cors.allow_origin(Any),
+        }
+    }
+
+    /// Origins CORS answers, or `None` for any origin. A server that requires
+    /// API keys answers no cross-origin requests unless origins are listed.
+    fn cors_origins(&self) -> Option<Vec<HeaderValue>> {
+        if self.allowed_origins.is_empty() && !self.requires_key() {
+            return None;
+        }
+        Some(
+            self.allowed_origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok())
+                .collect(),
+        )
+    }
+
+    pub fn origin_allowed(&self, origin: &str) -> bool {
+        let origin = origin.trim_end_matches('/');
+        self.allowed_origins.is_empty()
+            || self
+                .allowed_origins
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+    }
+
+    /// Client for a presented key.
+    pub fn authenticate(&self, key: Option<&str>) -> Result<Client, ApiError> {
+        if !self.requires_key() {
+            return Ok(Client {
+                name: "anonymous".to_string(),
+                scope: ApiScope::Read,
+            });
+        }
+        let key = key.ok_or(ApiError::Unauthorized)?;
+        // Compare against every key so timing does not reveal which matched
+        self.api_keys
+            .iter()
+            .fold(None, |matched, api_key| {
+                let is_match = keys_match(&api_key.key, key);
+                matched.or(is_match.then_some(api_key))
+            })
+            .map(|api_key| Client {
+                name: api_key.name.clone(),
+                scope: api_key.scope,
+            })
+            .ok_or(ApiError::Unauthorized)
+    }
+}
+
+/// Constant-time key comparison. Both keys are hashed first so neither the
+/// comparison nor its duration depends on the key length.
+fn keys_match(expected: &str, presented: &str) -> bool {
+    let expected = Sha256::digest(expected.as_bytes());
+    let presented = Sha256::digest(presented.as_bytes());
+    expected
+        .iter()
+        .zip(presented.iter())
+        .fold(0, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+/// Rate limit allowance of an authorized client, added to the request
+/// extensions for charging work done after the request (WebSocket messages).
+#[derive(Debug, Clone)]
+pub struct ClientLimit {
+    auth: Arc<ServerAuth>,
+    key: String,
+}
+
+impl ClientLimit {
+    /// Take one request from the client's allowance.
+    pub fn check(&self) -> Result<(), ApiError> {
+        self.auth.rate_limiter.check(&self.key)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+/// API key presented with a request.
+fn presented_key(request: &Request) -> Option<String> {
+    let headers = request.headers();
+    if let Some(bearer) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    {
+        return Some(bearer.trim().to_string());
+    }
+    if let Some(key) = headers
+        .get("x-api-key")
+        .and_then(|value| value.to_str().ok())
+    {
+        return Some(key.trim().to_string());
+    }
+    Query::<TokenQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(query)| query.token)
+}
+
+/// Middleware checking origin, API key and rate limit, in that order.
+pub async fn authorize(
+    State(auth): State<Arc<ServerAuth>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if let Some(origin) = request
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|value| value.to_str().ok())
+    {
+        if !auth.origin_allowed(origin) {
+            debug!("Rejected request from origin {}", origin);
+            return ApiError::Forbidden(format!("origin {} not allowed", origin)).into_response();
+        }
+    }
+
+    let client = auth.authenticate(presented_key(&request).as_deref());
+    // Anonymous and failed requests are limited by address
+    let limit_key = match &client {
+        Ok(client) if auth.requires_key() => format!("key:{}", client.name),
+        _ => match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+            None => "anonymous".to_string(),
+        },
+    };
+    if let Err(e) = auth.rate_limiter.check(&limit_key) {
+        return e.into_response();
+    }
+
+    match client {
+        Ok(client) => {
+            request.extensions_mut().insert(client);
+            request.extensions_mut().insert(ClientLimit {
+                auth: auth.clone(),
+                key: limit_key,
+            });
+            next.run(request).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Token bucket per client: bursts up to a minute's allowance, refilled
+/// continuously.
+#[derive(Debug)]
+struct RateLimiter {
+    per_minute: u32,
+    buckets: DashMap<String, Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// Take one request from the client's allowance.
+    fn check(&self, client: &str) -> Result<(), ApiError> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: &str, now: Instant) -> Result<(), ApiError> {
+        if self.per_minute == 0 {
+            return Ok(());
+        }
+        let capacity = self.per_minute as f64;
+        let per_second = capacity / 60.0;
+
+        if self.buckets.len() >= MAX_TRACKED_CLIENTS {
+            // Idle for a minute means full again, same as untracked
+            self.buckets
+                .retain(|_, bucket| now.duration_since(bucket.updated) < Duration::from_secs(60));
+        }
+        if self.buckets.len() >= MAX_TRACKED_CLIENTS && !self.buckets.contains_key(client) {
+            // Every bucket is active: make room by dropping the least recently used
+            let oldest = self
+                .buckets
+                .iter()
+                .min_by_key(|entry| entry.updated)
+                .map(|entry| entry.key().clone());
+            if let Some(oldest) = oldest {
+                self.buckets.remove(&oldest);
+            }
+        }
+
+        let mut bucket = self.buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let retry_after = ((1.0 - bucket.tokens) / per_second).ceil() as u64;
+            Err(ApiError::RateLimited(retry_after.max(1)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> ServerSettings {
+        ServerSettings {
+            api_keys: vec![
+                ApiKeySettings {
+                    name: "dashboard".to_string(),
+                    key: "read-only-key-0001".to_string(),
+                    scope: ApiScope::Read,
+                },
+                ApiKeySettings {
+                    name: "desktop".to_string(),
+                    key: "trading-key-000001".to_string(),
+                    scope: ApiScope::Trade,
+                },
+            ],
+            allowed_origins: vec!["https://dash.example.com/".to_string()],
+            ..ServerSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_authenticate_scopes() {
+        let auth = ServerAuth::new(&settings());
+        assert!(matches!(
+            auth.authenticate(None),
+            Err(ApiError::Unauthorized)
+        ));
+        assert!(matches!(
+            auth.authenticate(Some("read-only-key-0002")),
+            Err(ApiError::Unauthorized)
+        ));
+
+        let reader = auth.authenticate(Some("read-only-key-0001")).unwrap();
+        assert_eq!(reader.name, "dashboard");
+        assert!(reader.require(ApiScope::Read).is_ok());
+        assert!(matches!(
+            reader.require(ApiScope::Trade),
+            Err(ApiError::Forbidden(_))
+        ));
+
+        let trader = auth.authenticate(Some("trading-key-000001")).unwrap();
+        assert!(trader.require(ApiScope::Trade).is_ok());
+
+        // No keys configured: anonymous read-only access
+        let open = ServerAuth::new(&ServerSettings::default());
+        let anonymous = open.authenticate(None).unwrap();
+        assert_eq!(anonymous.scope, ApiScope::Read);
+        assert!(anonymous.require(ApiScope::Trade).is_err());
+    }
+
+    #[test]
+    fn test_origin_allowlist() {
+        let auth = ServerAuth::new(&settings());
+        assert!(auth.origin_allowed("https://dash.example.com"));
+        assert!(auth.origin_allowed("HTTPS://DASH.EXAMPLE.COM/"));
+        assert!(!auth.origin_allowed("https://evil.example.com"));
+        assert!(ServerAuth::new(&ServerSettings::default()).origin_allowed("https://any.example"));
+    }
+
+    #[test]
+    fn test_keys_match() {
+        assert!(keys_match("trading-key-000001", "trading-key-000001"));
+        assert!(!keys_match("trading-key-000001", "trading-key-000002"));
+        assert!(!keys_match("trading-key-000001", "trading-key-00000"));
+        assert!(!keys_match("trading-key-000001", ""));
+    }
+
+    #[test]
+    fn test_cors_origins() {
+        let listed = ServerAuth::new(&settings()).cors_origins().unwrap();
+        assert_eq!(
+            listed,
+            vec![HeaderValue::from_static("https://dash.example.com")]
+        );
+
+        // Keys without listed origins: no cross-origin access
+        let keyed = ServerAuth::new(&ServerSettings {
+            allowed_origins: Vec::new(),
+            ..settings()
+        });
+        assert_eq!(keyed.cors_origins(), Some(Vec::new()));
+
+        // Open read-only server: any origin
+        assert_eq!(
+            ServerAuth::new(&ServerSettings::default()).cors_origins(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_refills() {
+        let limiter = RateLimiter::new(60);
+        let start = Instant::now();
+        for _ in 0..60 {
+            assert!(limiter.check_at("desktop", start).is_ok());
+        }
+        assert!(matches!(
+            limiter.check_at("desktop", start),
+            Err(ApiError::RateLimited(1))
+        ));
+        // Other clients have their own allowance
+        assert!(limiter.check_at("dashboard", start).is_ok());
+        // One request per second comes back
+        assert!(limiter
+            .check_at("desktop", start + Duration::from_secs(1))
+            .is_ok());
+        assert!(limiter
+            .check_at("desktop", start + Duration::from_secs(1))
+            .is_err());
+
+        let unlimited = RateLimiter::new(0);
+        assert!((0..1_000).all(|_| unlimited.check_at("desktop", start).is_ok()));
+    }
+
+    #[test]
+    fn test_rate_limiter_bounds_tracked_clients() {
+        let limiter = RateLimiter::new(60);
+        let start = Instant::now();
+        assert!(limiter.check_at("ip:oldest", start).is_ok());
+        let later = start + Duration::from_secs(1);
+        for i in 1..MAX_TRACKED_CLIENTS {
+            assert!(limiter.check_at(&format!("ip:{}", i), later).is_ok());
+        }
+
+        // All buckets are active, so the least recently used one gives way
+        assert!(limiter.check_at("ip:newcomer", later).is_ok());
+        assert_eq!(limiter.buckets.len(), MAX_TRACKED_CLIENTS);
+        assert!(!limiter.buckets.contains_key("ip:oldest"));
+        assert!(limiter.buckets.contains_key("ip:newcomer"));
+    }
+}
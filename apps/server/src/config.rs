@@ -1,11 +1,86 @@
 //! Application configuration.
+//!
+//! Configuration is layered: built-in defaults, then the config file (JSON or
+//! TOML, merged key by key so a file only needs the settings it changes), then
+//! `ARBITRAGE_*` environment variables, then command-line flags. The merged
+//! configuration is checked by [`AppConfig::validate`] before startup.
 
-use arbitrage_core::Exchange;
-use arbitrage_engine::DetectorConfig;
+use arbitrage_alerts::NotifierConfig;
+use arbitrage_core::{Chain, Exchange};
+use arbitrage_engine::{
+    AllocatorLimits, DepegConfig, DepegPolicy, DetectorConfig, FeeRole, FeeSchedule,
+    GasOracleConfig, PremiumHistoryConfig,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+use thiserror::Error;
+
+/// Prefix of environment variables overriding configuration values. Nested
+/// keys are separated by `__`, and list entries are addressed by index or by
+/// their `exchange`/`chain` name, e.g. `ARBITRAGE_DETECTOR__MIN_PREMIUM_BPS=40`
+/// or `ARBITRAGE_EXCHANGES__UPBIT__ENABLED=false`.
+pub const ENV_PREFIX: &str = "ARBITRAGE_";
+
+/// Config files loaded when no `--config` is given, if present.
+pub const DEFAULT_CONFIG_PATHS: [&str; 2] = ["config.json", "config.toml"];
+
+/// Exchanges with live feeds, by market discovery name.
+pub const LIVE_EXCHANGES: [(Exchange, &str); 6] = [
+    (Exchange::Binance, "Binance"),
+    (Exchange::Coinbase, "Coinbase"),
+    (Exchange::Upbit, "Upbit"),
+    (Exchange::Bithumb, "Bithumb"),
+    (Exchange::Bybit, "Bybit"),
+    (Exchange::GateIO, "GateIO"),
+];
+
+/// Configuration loading or validation failure.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("cannot read config file {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("cannot parse config file {path}: {message}")]
+    Parse { path: String, message: String },
+    #[error("invalid environment variable {var}: {message}")]
+    Env { var: String, message: String },
+    #[error("invalid setting `{path}`: {message}")]
+    Schema { path: String, message: String },
+    #[error("invalid configuration:\n  {}", .0.join("\n  "))]
+    Invalid(Vec<String>),
+}
+
+/// Config file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Format from the file extension (JSON unless `.toml`).
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+
+    fn parse(self, contents: &str) -> Result<Value, String> {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(contents).map_err(|e| e.to_string()),
+            ConfigFormat::Toml => toml::from_str(contents).map_err(|e| e.to_string()),
+        }
+    }
+}
 
 /// Application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AppConfig {
     /// Detector configuration.
     pub detector: DetectorSettings,
@@ -13,6 +88,15 @@ pub struct AppConfig {
     pub execution: ExecutionSettings,
     /// Exchange configurations.
     pub exchanges: Vec<ExchangeSettings>,
+    /// Gas price oracle configuration.
+    #[serde(default)]
+    pub gas: GasSettings,
+    /// Alert settings.
+    #[serde(default)]
+    pub alerts: AlertSettings,
+    /// Server settings.
+    #[serde(default)]
+    pub server: ServerSettings,
     /// Logging level.
     pub log_level: String,
 }
@@ -22,24 +106,460 @@ impl Default for AppConfig {
         Self {
             detector: DetectorSettings::default(),
             execution: ExecutionSettings::default(),
-            exchanges: vec![
-                ExchangeSettings::new(Exchange::Binance),
-                ExchangeSettings::new(Exchange::Coinbase),
-            ],
+            exchanges: LIVE_EXCHANGES
+                .iter()
+                .map(|&(exchange, _)| ExchangeSettings::new(exchange))
+                .collect(),
+            gas: GasSettings::default(),
+            alerts: AlertSettings::default(),
+            server: ServerSettings::default(),
             log_level: "info".to_string(),
         }
     }
 }
 
+impl AppConfig {
+    /// Load the defaults, overlaid with the config file (if any) and then the
+    /// `ARBITRAGE_*` variables in `env`. Call [`AppConfig::validate`] after
+    /// applying command-line overrides.
+    pub fn load(
+        path: Option<&Path>,
+        env: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let mut value = serde_json::to_value(Self::default()).expect("default config serializes");
+
+        if let Some(path) = path {
+            let display = path.display().to_string();
+            let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+                path: display.clone(),
+                source,
+            })?;
+            let file = ConfigFormat::from_path(path)
+                .parse(&contents)
+                .map_err(|message| ConfigError::Parse {
+                    path: display,
+                    message,
+                })?;
+            merge(&mut value, file);
+            // Fill list entries from the file with their defaults, so the
+            // environment can address every setting
+            value = serde_json::to_value(from_value(value)?).expect("config serializes");
+        }
+
+        let mut overrides: Vec<(String, String)> = env
+            .into_iter()
+            .filter(|(var, _)| var.starts_with(ENV_PREFIX))
+            .collect();
+        overrides.sort();
+        for (var, raw) in overrides {
+            apply_env(&mut value, &var[ENV_PREFIX.len()..], &raw)
+                .map_err(|message| ConfigError::Env { var, message })?;
+        }
+
+        from_value(value)
+    }
+
+    /// Check settings against their allowed ranges and each other, reporting
+    /// every problem at once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+        let mut check = |ok: bool, path: &str, message: String| {
+            if !ok {
+                errors.push(format!("{}: {}", path, message));
+            }
+        };
+
+        let detector = &self.detector;
+        check(
+            (0..=10_000).contains(&detector.min_premium_bps),
+            "detector.min_premium_bps",
+            format!("{} is outside 0..=10000", detector.min_premium_bps),
+        );
+        check(
+            detector.premium_window_ms > 0,
+            "detector.premium_window_ms",
+            "must be positive".to_string(),
+        );
+        if let Some(zscore) = detector.min_premium_zscore {
+            check(
+                zscore.is_finite() && zscore > 0.0,
+                "detector.min_premium_zscore",
+                format!("{} must be a positive number", zscore),
+            );
+        }
+        if let Some(profit) = detector.min_worst_case_profit_usd {
+            check(
+                profit.is_finite(),
+                "detector.min_worst_case_profit_usd",
+                format!("{} is not a number", profit),
+            );
+        }
+        check(
+            detector.depeg_warn_bps > 0 && detector.depeg_warn_bps <= detector.depeg_critical_bps,
+            "detector.depeg_warn_bps",
+            format!(
+                "{} must be positive and at most depeg_critical_bps ({})",
+                detector.depeg_warn_bps, detector.depeg_critical_bps
+            ),
+        );
+        check(
+            detector.detection_workers <= 64,
+            "detector.detection_workers",
+            format!("{} exceeds 64", detector.detection_workers),
+        );
+
+        let execution = &self.execution;
+        check(
+            execution.max_position_usd > 0,
+            "execution.max_position_usd",
+            "must be positive".to_string(),
+        );
+        check(
+            execution.max_slippage_bps <= 10_000,
+            "execution.max_slippage_bps",
+            format!("{} exceeds 10000", execution.max_slippage_bps),
+        );
+        check(
+            (-10_000..=10_000).contains(&execution.min_profit_bps),
+            "execution.min_profit_bps",
+            format!("{} is outside -10000..=10000", execution.min_profit_bps),
+        );
+
+        let mut seen = HashSet::new();
+        for (i, settings) in self.exchanges.iter().enumerate() {
+            let path = format!("exchanges[{}]", i);
+            check(
+                seen.insert(settings.exchange),
+                &path,
+                format!("{:?} is listed more than once", settings.exchange),
+            );
+            for pair in &settings.pairs {
+                let valid = pair
+                    .split_once('/')
+                    .is_some_and(|(base, quote)| !base.is_empty() && !quote.is_empty());
+                check(
+                    valid,
+                    &format!("{}.pairs", path),
+                    format!("{:?} is not BASE/QUOTE", pair),
+                );
+            }
+            if let Some(tier) = settings.vip_tier {
+                let known = FeeSchedule::default_for_exchange(settings.exchange)
+                    .is_some_and(|schedule| schedule.tier(tier).is_some());
+                check(
+                    known,
+                    &format!("{}.vip_tier", path),
+                    format!("{:?} has no VIP tier {}", settings.exchange, tier),
+                );
+            }
+            for (name, fee) in [
+                ("maker_fee_bps", settings.maker_fee_bps),
+                ("taker_fee_bps", settings.taker_fee_bps),
+            ] {
+                if let Some(fee) = fee {
+                    check(
+                        (-100..=1_000).contains(&fee),
+                        &format!("{}.{}", path, name),
+                        format!("{} is outside -100..=1000", fee),
+                    );
+                }
+            }
+            if let Some(levels) = settings.orderbook_levels {
+                check(
+                    (1..=1_000).contains(&levels),
+                    &format!("{}.orderbook_levels", path),
+                    format!("{} is outside 1..=1000", levels),
+                );
+            }
+        }
+        if self.server.live {
+            check(
+                self.live_exchange_names().len() >= 2,
+                "exchanges",
+                "live feeds need at least two enabled exchanges".to_string(),
+            );
+        }
+
+        let gas = &self.gas;
+        check(
+            gas.poll_interval_ms >= 1_000,
+            "gas.poll_interval_ms",
+            format!("{} is below 1000", gas.poll_interval_ms),
+        );
+        check(
+            (0.0..=100.0).contains(&gas.priority_fee_percentile),
+            "gas.priority_fee_percentile",
+            format!("{} is outside 0..=100", gas.priority_fee_percentile),
+        );
+        let mut chains = HashSet::new();
+        for (i, endpoint) in gas.rpc_endpoints.iter().enumerate() {
+            let path = format!("gas.rpc_endpoints[{}]", i);
+            check(
+                chains.insert(endpoint.chain),
+                &path,
+                format!("{:?} is listed more than once", endpoint.chain),
+            );
+            check(
+                endpoint.rpc_url.starts_with("http://") || endpoint.rpc_url.starts_with("https://"),
+                &format!("{}.rpc_url", path),
+                format!("{:?} is not an http(s) URL", endpoint.rpc_url),
+            );
+        }
+
+        check(
+            self.alerts.cooldown_minutes >= 0,
+            "alerts.cooldown_minutes",
+            "must not be negative".to_string(),
+        );
+        check(
+            self.alerts.history_retention_days > 0,
+            "alerts.history_retention_days",
+            "must be positive".to_string(),
+        );
+        check(
+            !self.alerts.db_path.is_empty(),
+            "alerts.db_path",
+            "must not be empty".to_string(),
+        );
+
+        check(
+            self.server.ws_port != 0,
+            "server.ws_port",
+            "must not be 0".to_string(),
+        );
+        check(
+            !self.server.pair_registry_path.is_empty(),
+            "server.pair_registry_path",
+            "must not be empty".to_string(),
+        );
+        let mut key_names = HashSet::new();
+        for (i, api_key) in self.server.api_keys.iter().enumerate() {
+            let path = format!("server.api_keys[{}]", i);
+            check(
+                key_names.insert(api_key.name.as_str()),
+                &format!("{}.name", path),
+                format!("{:?} is listed more than once", api_key.name),
+            );
+            check(
+                api_key.key.len() >= 16,
+                &format!("{}.key", path),
+                "must be at least 16 characters".to_string(),
+            );
+        }
+        for origin in &self.server.allowed_origins {
+            check(
+                origin.starts_with("http://")
+                    || origin.starts_with("https://")
+                    || origin.starts_with("tauri://"),
+                "server.allowed_origins",
+                format!("{:?} is not an origin like https://example.com", origin),
+            );
+        }
+        if let Some(tls) = &self.server.tls {
+            check(
+                !tls.cert_path.is_empty() && !tls.key_path.is_empty(),
+                "server.tls",
+                "cert_path and key_path must not be empty".to_string(),
+            );
+        }
+        check(
+            tracing_subscriber::EnvFilter::try_new(&self.log_level).is_ok(),
+            "log_level",
+            format!("{:?} is not a log level or filter", self.log_level),
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+
+    /// Effective configuration in `format`, with API credentials redacted.
+    pub fn dump(&self, format: ConfigFormat) -> String {
+        let mut value = serde_json::to_value(self).expect("config serializes");
+        if let Some(exchanges) = value.get_mut("exchanges").and_then(Value::as_array_mut) {
+            for exchange in exchanges {
+                for key in ["api_key", "api_secret"] {
+                    if let Some(secret) = exchange.get_mut(key).filter(|v| !v.is_null()) {
+                        *secret = Value::String("<redacted>".to_string());
+                    }
+                }
+            }
+        }
+        if let Some(api_keys) = value
+            .pointer_mut("/server/api_keys")
+            .and_then(Value::as_array_mut)
+        {
+            for api_key in api_keys {
+                api_key["key"] = Value::String("<redacted>".to_string());
+            }
+        }
+        match format {
+            ConfigFormat::Json => serde_json::to_string_pretty(&value).expect("config serializes"),
+            ConfigFormat::Toml => {
+                // TOML has no null: unset optional settings are left out
+                strip_nulls(&mut value);
+                toml::to_string_pretty(&value).expect("config serializes")
+            }
+        }
+    }
+
+    /// Settings for an exchange, if listed.
+    pub fn exchange(&self, exchange: Exchange) -> Option<&ExchangeSettings> {
+        self.exchanges
+            .iter()
+            .find(|settings| settings.exchange == exchange)
+    }
+
+    /// Whether an exchange is listed and enabled.
+    pub fn is_exchange_enabled(&self, exchange: Exchange) -> bool {
+        self.exchange(exchange)
+            .is_some_and(|settings| settings.enabled)
+    }
+
+    /// Market discovery names of the enabled live feed exchanges.
+    pub fn live_exchange_names(&self) -> Vec<&'static str> {
+        LIVE_EXCHANGES
+            .iter()
+            .filter(|(exchange, _)| self.is_exchange_enabled(*exchange))
+            .map(|&(_, name)| name)
+            .collect()
+    }
+
+    /// Whether a market (`BASE/QUOTE`, e.g. `BTC/USDT`) is monitored on the
+    /// exchange with this market discovery name. Exchanges without a pair list
+    /// monitor every common market.
+    pub fn monitors_market(&self, exchange_name: &str, market: &str) -> bool {
+        let Some(&(exchange, _)) = LIVE_EXCHANGES
+            .iter()
+            .find(|(_, name)| *name == exchange_name)
+        else {
+            return false;
+        };
+        self.exchange(exchange).is_some_and(|settings| {
+            settings.enabled
+                && (settings.pairs.is_empty()
+                    || settings
+                        .pairs
+                        .iter()
+                        .any(|pair| pair.eq_ignore_ascii_case(market)))
+        })
+    }
+}
+
+fn from_value(value: Value) -> Result<AppConfig, ConfigError> {
+    serde_path_to_error::deserialize(value).map_err(|e| ConfigError::Schema {
+        path: e.path().to_string(),
+        message: e.inner().to_string(),
+    })
+}
+
+/// Overlay `overlay` onto `base`: objects merge key by key, anything else
+/// (including lists) replaces the base value.
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Set the setting at `key` (`__`-separated path) from an environment value.
+/// Values parse as JSON (numbers, booleans, lists) where the setting is not
+/// a string, and are taken verbatim otherwise.
+fn apply_env(value: &mut Value, key: &str, raw: &str) -> Result<(), String> {
+    let mut current = value;
+    for segment in key.split("__").map(str::to_lowercase) {
+        current = match current {
+            Value::Object(map) => map.get_mut(&segment),
+            Value::Array(items) => match segment.parse::<usize>() {
+                Ok(index) => items.get_mut(index),
+                Err(_) => items.iter_mut().find(|item| {
+                    ["exchange", "chain"].iter().any(|name| {
+                        item.get(name)
+                            .and_then(Value::as_str)
+                            .is_some_and(|id| id.eq_ignore_ascii_case(&segment))
+                    })
+                }),
+            },
+            _ => None,
+        }
+        .ok_or_else(|| format!("unknown setting `{}`", key.to_lowercase()))?;
+    }
+    *current = match current {
+        Value::String(_) => Value::String(raw.to_string()),
+        _ => serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string())),
+    };
+    Ok(())
+}
+
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
 /// Detector settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct DetectorSettings {
     /// Minimum premium in basis points.
     pub min_premium_bps: i32,
     /// Maximum price staleness in milliseconds.
     pub max_staleness_ms: u64,
-    /// Scan interval in milliseconds.
+    /// Interval in milliseconds at which every registered pair is re-detected
+    /// on top of event-driven detection, so opportunities are re-evaluated on
+    /// quiet markets (0 = event-driven only).
+    #[serde(default)]
     pub scan_interval_ms: u64,
+    /// Premium history window in milliseconds (for premium statistics).
+    #[serde(default = "default_premium_window_ms")]
+    pub premium_window_ms: u64,
+    /// Also detect routes whose premium z-score reaches this value (None = disabled).
+    #[serde(default)]
+    pub min_premium_zscore: Option<f64>,
+    /// Drop sized opportunities whose worst-case profit after transfer-time price
+    /// risk is below this amount in USD (None = keep all).
+    #[serde(default)]
+    pub min_worst_case_profit_usd: Option<f64>,
+    /// Stablecoin deviation from parity that raises a depeg warning (bps).
+    #[serde(default = "default_depeg_warn_bps")]
+    pub depeg_warn_bps: u32,
+    /// Stablecoin deviation from parity that is a critical depeg (bps).
+    #[serde(default = "default_depeg_critical_bps")]
+    pub depeg_critical_bps: u32,
+    /// Reprice or suppress routes with an off-peg stablecoin leg.
+    #[serde(default)]
+    pub depeg_policy: DepegPolicy,
+    /// Detection worker tasks (0 = one per core, at most 8).
+    #[serde(default)]
+    pub detection_workers: usize,
+}
+
+fn default_premium_window_ms() -> u64 {
+    PremiumHistoryConfig::default().retention_ms
+}
+
+fn default_depeg_warn_bps() -> u32 {
+    DepegConfig::default().warn_bps
+}
+
+fn default_depeg_critical_bps() -> u32 {
+    DepegConfig::default().critical_bps
 }
 
 impl Default for DetectorSettings {
@@ -47,7 +567,14 @@ impl Default for DetectorSettings {
         Self {
             min_premium_bps: 30,
             max_staleness_ms: 0, // Disabled - prices are managed by WebSocket reconnection logic
-            scan_interval_ms: 100,
+            scan_interval_ms: 0,
+            premium_window_ms: default_premium_window_ms(),
+            min_premium_zscore: None,
+            min_worst_case_profit_usd: None,
+            depeg_warn_bps: default_depeg_warn_bps(),
+            depeg_critical_bps: default_depeg_critical_bps(),
+            depeg_policy: DepegPolicy::default(),
+            detection_workers: 0,
         }
     }
 }
@@ -57,6 +584,17 @@ impl From<&DetectorSettings> for DetectorConfig {
         DetectorConfig {
             min_premium_bps: settings.min_premium_bps,
             max_staleness_ms: settings.max_staleness_ms,
+            premium_history: PremiumHistoryConfig {
+                retention_ms: settings.premium_window_ms,
+                ..Default::default()
+            },
+            min_premium_zscore: settings.min_premium_zscore,
+            depeg: DepegConfig {
+                warn_bps: settings.depeg_warn_bps,
+                critical_bps: settings.depeg_critical_bps,
+                policy: settings.depeg_policy,
+                ..Default::default()
+            },
             ..Default::default()
         }
     }
@@ -64,6 +602,7 @@ impl From<&DetectorSettings> for DetectorConfig {
 
 /// Execution settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ExecutionSettings {
     /// Execution mode.
     pub mode: ExecutionMode,
@@ -73,6 +612,24 @@ pub struct ExecutionSettings {
     pub max_slippage_bps: u16,
     /// Minimum profit in basis points to execute.
     pub min_profit_bps: i32,
+    /// Order role of the buy leg (maker or taker fee).
+    #[serde(default)]
+    pub buy_fee_role: FeeRole,
+    /// Order role of the sell leg (maker or taker fee).
+    #[serde(default)]
+    pub sell_fee_role: FeeRole,
+    /// Maximum notional per exchange across a plan in USD (0 = unlimited).
+    #[serde(default)]
+    pub max_exchange_notional_usd: u64,
+    /// Maximum notional per asset across a plan in USD (0 = unlimited).
+    #[serde(default)]
+    pub max_asset_notional_usd: u64,
+    /// Maximum trades executed together (0 = unlimited).
+    #[serde(default)]
+    pub max_concurrent_trades: usize,
+    /// Sell from inventory held on the sell exchange instead of transferring.
+    #[serde(default)]
+    pub require_sell_inventory: bool,
 }
 
 impl Default for ExecutionSettings {
@@ -82,23 +639,121 @@ impl Default for ExecutionSettings {
             max_position_usd: 10000,
             max_slippage_bps: 50,
             min_profit_bps: 20,
+            buy_fee_role: FeeRole::Taker,
+            sell_fee_role: FeeRole::Taker,
+            max_exchange_notional_usd: 0,
+            max_asset_notional_usd: 0,
+            max_concurrent_trades: 0,
+            require_sell_inventory: false,
+        }
+    }
+}
+
+impl From<&ExecutionSettings> for AllocatorLimits {
+    fn from(settings: &ExecutionSettings) -> Self {
+        AllocatorLimits {
+            max_total_notional_usd: settings.max_position_usd as f64,
+            max_exchange_notional_usd: settings.max_exchange_notional_usd as f64,
+            max_asset_notional_usd: settings.max_asset_notional_usd as f64,
+            max_trades: settings.max_concurrent_trades,
+            require_sell_inventory: settings.require_sell_inventory,
+        }
+    }
+}
+
+/// Gas price oracle settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GasSettings {
+    /// JSON-RPC endpoints to sample fees from. Chains without one are priced
+    /// at typical default gas prices.
+    #[serde(default)]
+    pub rpc_endpoints: Vec<ChainRpcSettings>,
+    /// Fee sampling interval in milliseconds.
+    #[serde(default = "default_gas_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Percentile of recent priority fees to pay (0-100).
+    #[serde(default = "default_priority_fee_percentile")]
+    pub priority_fee_percentile: f64,
+}
+
+/// JSON-RPC endpoint for one chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainRpcSettings {
+    pub chain: Chain,
+    pub rpc_url: String,
+}
+
+fn default_gas_poll_interval_ms() -> u64 {
+    12_000
+}
+
+fn default_priority_fee_percentile() -> f64 {
+    GasOracleConfig::default().priority_percentile
+}
+
+impl Default for GasSettings {
+    fn default() -> Self {
+        Self {
+            rpc_endpoints: Vec::new(),
+            poll_interval_ms: default_gas_poll_interval_ms(),
+            priority_fee_percentile: default_priority_fee_percentile(),
+        }
+    }
+}
+
+impl From<&GasSettings> for GasOracleConfig {
+    fn from(settings: &GasSettings) -> Self {
+        GasOracleConfig {
+            priority_percentile: settings.priority_fee_percentile,
+            // Samples older than a few polls are stale
+            max_age_ms: settings.poll_interval_ms.saturating_mul(5),
+            ..Default::default()
         }
     }
 }
 
 /// Per-exchange settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ExchangeSettings {
     /// Exchange identifier.
     pub exchange: Exchange,
     /// Whether enabled.
+    #[serde(default = "default_enabled")]
     pub enabled: bool,
     /// API key (optional).
+    #[serde(default)]
     pub api_key: Option<String>,
     /// API secret (optional).
+    #[serde(default)]
     pub api_secret: Option<String>,
-    /// Trading pairs to monitor.
+    /// Markets to monitor as `BASE/QUOTE`, e.g. `BTC/USDT` or `BTC/KRW`
+    /// (empty = every market common to two or more exchanges).
+    #[serde(default)]
     pub pairs: Vec<String>,
+    /// Account VIP fee tier (None = regular rates).
+    #[serde(default)]
+    pub vip_tier: Option<u8>,
+    /// Pay trading fees with the exchange token (BNB, GT) for a discount.
+    #[serde(default)]
+    pub use_fee_token: bool,
+    /// Orderbook levels to cache per side (None = exchange default).
+    #[serde(default)]
+    pub orderbook_levels: Option<usize>,
+    /// Maker fee override in basis points, until account rates are fetched
+    /// (None = published or VIP tier rate).
+    #[serde(default)]
+    pub maker_fee_bps: Option<i32>,
+    /// Taker fee override in basis points, until account rates are fetched
+    /// (None = published or VIP tier rate).
+    #[serde(default)]
+    pub taker_fee_bps: Option<i32>,
+}
+
+fn default_enabled() -> bool {
+    true
 }
 
 impl ExchangeSettings {
@@ -108,20 +763,138 @@ impl ExchangeSettings {
             enabled: true,
             api_key: None,
             api_secret: None,
-            pairs: vec!["BTC/USDT".to_string(), "ETH/USDT".to_string()],
+            pairs: Vec::new(),
+            vip_tier: None,
+            use_fee_token: false,
+            orderbook_levels: None,
+            maker_fee_bps: None,
+            taker_fee_bps: None,
         }
     }
 }
 
+/// Alert settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertSettings {
+    /// Send opportunity alerts to Telegram (requires TELEGRAM_BOT_TOKEN).
+    pub telegram: bool,
+    /// SQLite database for alert configuration and kimchi index history.
+    pub db_path: String,
+    /// Minutes before alerting again on the same opportunity.
+    pub cooldown_minutes: i64,
+    /// Days of alert history to keep.
+    pub history_retention_days: i64,
+    /// Only alert on opportunities with a working transfer path.
+    pub require_transfer_path: bool,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        let notifier = NotifierConfig::default();
+        Self {
+            telegram: false,
+            db_path: "data/alerts.db".to_string(),
+            cooldown_minutes: notifier.cooldown_minutes,
+            history_retention_days: notifier.history_retention_days,
+            require_transfer_path: notifier.require_transfer_path,
+        }
+    }
+}
+
+impl From<&AppConfig> for NotifierConfig {
+    fn from(config: &AppConfig) -> Self {
+        NotifierConfig {
+            cooldown_minutes: config.alerts.cooldown_minutes,
+            history_retention_days: config.alerts.history_retention_days,
+            require_transfer_path: config.alerts.require_transfer_path,
+            min_premium_zscore: config.detector.min_premium_zscore,
+        }
+    }
+}
+
+/// Server settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    /// WebSocket server port for clients (Tauri app).
+    pub ws_port: u16,
+    /// Use live WebSocket feeds instead of the simulator.
+    pub live: bool,
+    /// Pair ID registry path (keeps pair IDs stable across restarts).
+    pub pair_registry_path: String,
+    /// API keys accepted by the WebSocket and REST server. Without keys,
+    /// clients connect anonymously with read-only access.
+    pub api_keys: Vec<ApiKeySettings>,
+    /// Browser origins allowed to connect (empty = any origin, but no CORS
+    /// access when API keys are configured).
+    pub allowed_origins: Vec<String>,
+    /// Requests per minute per client (0 = unlimited).
+    pub rate_limit_per_minute: u32,
+    /// Serve `wss://` and `https://` with this certificate.
+    pub tls: Option<TlsSettings>,
+    /// Append-only log of remote control commands (JSON lines, empty = memory only).
+    pub audit_log_path: String,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            ws_port: 9001,
+            live: false,
+            pair_registry_path: "data/pair_ids.json".to_string(),
+            api_keys: Vec::new(),
+            allowed_origins: Vec::new(),
+            rate_limit_per_minute: 600,
+            tls: None,
+            audit_log_path: "data/control_audit.jsonl".to_string(),
+        }
+    }
+}
+
+/// Access granted to an API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// Market data, opportunities and status.
+    Read,
+    /// Read access plus execution and control commands.
+    Trade,
+}
+
+/// API key accepted by the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeySettings {
+    /// Client name for logs and rate limiting.
+    pub name: String,
+    /// Secret sent by the client as a bearer token.
+    pub key: String,
+    pub scope: ApiScope,
+}
+
+/// TLS certificate for the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsSettings {
+    /// PEM certificate chain.
+    pub cert_path: String,
+    /// PEM private key.
+    pub key_path: String,
+}
+
 /// Execution mode for the bot.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum ExecutionMode {
     /// Automatically execute trades.
+    #[serde(alias = "auto")]
     Auto,
     /// Require manual approval.
+    #[serde(alias = "manual")]
     ManualApproval,
     /// Only show alerts, no execution.
     #[default]
+    #[serde(alias = "alert")]
     AlertOnly,
 }
 
@@ -149,7 +922,18 @@ mod tests {
         let settings = ExchangeSettings::new(Exchange::Binance);
         assert_eq!(settings.exchange, Exchange::Binance);
         assert!(settings.enabled);
-        assert!(!settings.pairs.is_empty());
+        // No pair list: every common market
+        assert!(settings.pairs.is_empty());
+    }
+
+    #[test]
+    fn test_gas_settings_default_when_missing() {
+        let mut json = serde_json::to_value(AppConfig::default()).unwrap();
+        json.as_object_mut().unwrap().remove("gas");
+        let parsed: AppConfig = serde_json::from_value(json).unwrap();
+        assert!(parsed.gas.rpc_endpoints.is_empty());
+        let config: GasOracleConfig = (&parsed.gas).into();
+        assert_eq!(config.max_age_ms, 60_000);
     }
 
     #[test]
@@ -162,4 +946,173 @@ mod tests {
             config.detector.min_premium_bps
         );
     }
+
+    fn write_config(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("arbitrage-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_load_layers_file_then_env() {
+        let path = write_config(
+            "layers.json",
+            r#"{"detector": {"min_premium_bps": 45}, "execution": {"mode": "auto"},
+                "exchanges": [{"exchange": "Upbit", "pairs": ["BTC/KRW"]},
+                              {"exchange": "Binance", "vip_tier": 1}]}"#,
+        );
+        let config = AppConfig::load(
+            Some(&path),
+            env(&[
+                ("ARBITRAGE_DETECTOR__MAX_STALENESS_MS", "5000"),
+                ("ARBITRAGE_EXCHANGES__BINANCE__ENABLED", "false"),
+                ("ARBITRAGE_ALERTS__DB_PATH", "1234"),
+                ("UNRELATED", "ignored"),
+            ]),
+        )
+        .unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        // File values over defaults, unset settings keep their defaults
+        assert_eq!(config.detector.min_premium_bps, 45);
+        assert_eq!(config.detector.depeg_warn_bps, default_depeg_warn_bps());
+        assert_eq!(config.execution.mode, ExecutionMode::Auto);
+        // Lists replace the default list
+        assert_eq!(config.exchanges.len(), 2);
+        assert_eq!(config.exchanges[1].vip_tier, Some(1));
+        // Environment over the file; string settings stay strings
+        assert_eq!(config.detector.max_staleness_ms, 5_000);
+        assert!(!config.is_exchange_enabled(Exchange::Binance));
+        assert_eq!(config.alerts.db_path, "1234");
+        assert_eq!(config.live_exchange_names(), vec!["Upbit"]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_load_toml_file() {
+        let path = write_config(
+            "config.toml",
+            "log_level = \"debug\"\n\n[server]\nws_port = 9100\n\n[[gas.rpc_endpoints]]\nchain = \"Ethereum\"\nrpc_url = \"https://eth.example\"\n",
+        );
+        let config = AppConfig::load(Some(&path), Vec::new()).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.server.ws_port, 9100);
+        assert_eq!(config.gas.rpc_endpoints[0].chain, Chain::Ethereum);
+    }
+
+    #[test]
+    fn test_load_errors_name_the_setting() {
+        let path = write_config("typo.json", r#"{"detector": {"min_premum_bps": 45}}"#);
+        let err = AppConfig::load(Some(&path), Vec::new()).unwrap_err();
+        std::fs::remove_file(path).unwrap();
+        assert!(
+            matches!(&err, ConfigError::Schema { message, .. } if message.contains("min_premum_bps"))
+        );
+
+        let err = AppConfig::load(None, env(&[("ARBITRAGE_DETECTOR__NOPE", "1")])).unwrap_err();
+        assert!(err.to_string().contains("detector__nope"));
+
+        let err = AppConfig::load(None, env(&[("ARBITRAGE_SERVER__WS_PORT", "port")])).unwrap_err();
+        assert!(matches!(&err, ConfigError::Schema { path, .. } if path == "server.ws_port"));
+
+        let err = AppConfig::load(Some(Path::new("/nonexistent/config.json")), Vec::new());
+        assert!(matches!(err, Err(ConfigError::Read { .. })));
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let mut config = AppConfig::default();
+        config.detector.min_premium_bps = -5;
+        config.detector.depeg_warn_bps = config.detector.depeg_critical_bps + 1;
+        config.exchanges[0].pairs = vec!["BTCUSDT".to_string()];
+        config.exchanges[0].vip_tier = Some(42);
+        config
+            .exchanges
+            .push(ExchangeSettings::new(Exchange::Upbit));
+        config.gas.rpc_endpoints.push(ChainRpcSettings {
+            chain: Chain::Ethereum,
+            rpc_url: "eth.example".to_string(),
+        });
+        config.server.api_keys.push(ApiKeySettings {
+            name: "desktop".to_string(),
+            key: "short".to_string(),
+            scope: ApiScope::Trade,
+        });
+
+        let Err(ConfigError::Invalid(errors)) = config.validate() else {
+            panic!("expected validation errors");
+        };
+        let paths: Vec<&str> = errors
+            .iter()
+            .map(|e| e.split(':').next().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "detector.min_premium_bps",
+                "detector.depeg_warn_bps",
+                "exchanges[0].pairs",
+                "exchanges[0].vip_tier",
+                "exchanges[6]",
+                "gas.rpc_endpoints[0].rpc_url",
+                "server.api_keys[0].key",
+            ]
+        );
+        assert!(AppConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_dump_redacts_credentials_and_reloads() {
+        let mut config = AppConfig::default();
+        config.exchanges[0].api_secret = Some("secret".to_string());
+        config.detector.min_premium_bps = 55;
+        config.server.api_keys.push(ApiKeySettings {
+            name: "desktop".to_string(),
+            key: "desktop-secret-key".to_string(),
+            scope: ApiScope::Read,
+        });
+
+        let json = config.dump(ConfigFormat::Json);
+        assert!(!json.contains("\"secret\""));
+        assert!(!json.contains("desktop-secret-key"));
+        assert!(json.contains("<redacted>"));
+
+        let path = write_config("dump.toml", &config.dump(ConfigFormat::Toml));
+        let reloaded = AppConfig::load(Some(&path), Vec::new()).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(reloaded.detector.min_premium_bps, 55);
+        assert_eq!(reloaded.exchanges.len(), config.exchanges.len());
+    }
+
+    #[test]
+    fn test_monitors_market() {
+        let mut config = AppConfig::default();
+        config.exchanges[0].pairs = vec!["BTC/USDT".to_string()];
+        assert!(config.monitors_market("Binance", "btc/usdt"));
+        assert!(!config.monitors_market("Binance", "ETH/USDT"));
+        // No pair list: every market
+        assert!(config.monitors_market("Bybit", "ETH/USDT"));
+
+        config.exchanges.retain(|e| e.exchange != Exchange::Bybit);
+        assert!(!config.monitors_market("Bybit", "ETH/USDT"));
+        assert!(!config.monitors_market("Kraken", "ETH/USDT"));
+    }
+
+    #[test]
+    fn test_notifier_config_from_app_config() {
+        let mut config = AppConfig::default();
+        config.alerts.cooldown_minutes = 15;
+        config.detector.min_premium_zscore = Some(2.5);
+        let notifier = NotifierConfig::from(&config);
+        assert_eq!(notifier.cooldown_minutes, 15);
+        assert_eq!(notifier.min_premium_zscore, Some(2.5));
+    }
 }
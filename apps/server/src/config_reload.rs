@@ -0,0 +1,370 @@
+//! Hot configuration reload.
+//!
+//! Polls the configuration file and `symbol_mappings.json` and applies changes
+//! without a restart, so feeds keep their WebSocket connections and orderbooks:
+//! - detector thresholds, depeg settings and fee tables are swapped in place
+//! - enabled exchanges and pair lists re-run market discovery, which subscribes
+//!   and unsubscribes markets through the `SubscriptionManager`
+//! - symbol mappings replace the canonical names and exclusions used by the
+//!   feed handler and market discovery
+//!
+//! Settings that are only read at startup are reported as requiring a restart.
+//! Every applied reload is broadcast to clients as a `config_changed` event.
+
+use crate::config::{AppConfig, ConfigError, LIVE_EXCHANGES};
+use crate::state::SharedState;
+use crate::ws_server::{self, BroadcastSender, WsConfigChangedData};
+use arbitrage_feeds::{load_mappings, SubscriptionManager};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+/// How often watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Settings only read at startup, as dotted path prefixes (`*` matches any
+/// single segment, e.g. an exchange name).
+const RESTART_REQUIRED: &[&str] = &[
+    "log_level",
+    "server",
+    "alerts",
+    "gas",
+    "detector.detection_workers",
+    "detector.scan_interval_ms",
+    "detector.premium_window_ms",
+    "exchanges.*.orderbook_levels",
+];
+
+/// Watched inputs that changed since the previous poll.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WatchedChanges {
+    pub config: bool,
+    pub mappings: bool,
+}
+
+/// Detects changes to the configuration and symbol mapping files by
+/// modification time. Files that appear or disappear count as changed.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    config_paths: Vec<PathBuf>,
+    mappings_path: PathBuf,
+    config_modified: Vec<Option<SystemTime>>,
+    mappings_modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Watch the given configuration file candidates and symbol mapping file,
+    /// taking their current state as unchanged.
+    pub fn new(config_paths: Vec<PathBuf>, mappings_path: PathBuf) -> Self {
+        let config_modified = config_paths.iter().map(modified).collect();
+        let mappings_modified = modified(&mappings_path);
+        Self {
+            config_paths,
+            mappings_path,
+            config_modified,
+            mappings_modified,
+        }
+    }
+
+    /// Check the watched files and remember their current state.
+    pub fn poll(&mut self) -> WatchedChanges {
+        let config_modified: Vec<_> = self.config_paths.iter().map(modified).collect();
+        let mappings_modified = modified(&self.mappings_path);
+        let changes = WatchedChanges {
+            config: config_modified != self.config_modified,
+            mappings: mappings_modified != self.mappings_modified,
+        };
+        self.config_modified = config_modified;
+        self.mappings_modified = mappings_modified;
+        changes
+    }
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Dotted paths of the settings that differ between two configurations.
+/// Exchange and chain list entries are addressed by name
+/// (e.g. `exchanges.Upbit.enabled`).
+pub fn changed_settings(old: &AppConfig, new: &AppConfig) -> Vec<String> {
+    let mut changed = Vec::new();
+    diff_values(
+        "",
+        &serde_json::to_value(old).unwrap_or_default(),
+        &serde_json::to_value(new).unwrap_or_default(),
+        &mut changed,
+    );
+    changed
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, changed: &mut Vec<String>) {
+    if old == new {
+        return;
+    }
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                diff_values(
+                    &join(key),
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    changed,
+                );
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => match (by_name(old), by_name(new)) {
+            (Some(old), Some(new)) => {
+                let names: BTreeSet<&str> = old.keys().chain(new.keys()).copied().collect();
+                for name in names {
+                    diff_values(
+                        &join(name),
+                        old.get(name).copied().unwrap_or(&Value::Null),
+                        new.get(name).copied().unwrap_or(&Value::Null),
+                        changed,
+                    );
+                }
+            }
+            _ => changed.push(path.to_string()),
+        },
+        _ => changed.push(path.to_string()),
+    }
+}
+
+/// Index list entries by their `exchange` or `chain` name, if they all have one.
+fn by_name(entries: &[Value]) -> Option<BTreeMap<&str, &Value>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let name = ["exchange", "chain"]
+                .iter()
+                .find_map(|key| entry.get(key)?.as_str())?;
+            Some((name, entry))
+        })
+        .collect()
+}
+
+/// Whether a changed setting only takes effect after a restart.
+pub fn requires_restart(path: &str) -> bool {
+    RESTART_REQUIRED.iter().any(|pattern| {
+        let mut segments = path.split('.');
+        pattern.split('.').all(|expected| {
+            segments
+                .next()
+                .is_some_and(|s| expected == "*" || s == expected)
+        })
+    })
+}
+
+/// Poll the watched files and apply changes until shutdown.
+///
+/// `load_config` produces the validated configuration (file, environment and
+/// command-line overrides); a configuration that fails to load is logged and
+/// the running one is kept. `discovery` wakes market discovery so subscription
+/// changes are applied immediately.
+pub async fn run_config_reload(
+    state: SharedState,
+    broadcast_tx: BroadcastSender,
+    mut watcher: ConfigWatcher,
+    load_config: impl Fn() -> Result<AppConfig, ConfigError>,
+    subscription_manager: Option<Arc<SubscriptionManager>>,
+    discovery: Arc<Notify>,
+) {
+    info!(
+        "Watching configuration for changes every {}s",
+        POLL_INTERVAL.as_secs()
+    );
+
+    while state.is_running() {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let changes = watcher.poll();
+        let mut changed = Vec::new();
+        let mut restart_required = Vec::new();
+
+        if changes.config {
+            match load_config() {
+                Ok(config) => {
+                    let _update = state.lock_config_update().await;
+                    let previous = state.config.read().await.clone();
+                    changed = changed_settings(&previous, &config);
+                    restart_required = changed
+                        .iter()
+                        .filter(|path| requires_restart(path))
+                        .cloned()
+                        .collect();
+                    // Feeds are only started for exchanges enabled at startup
+                    if let Some(ref manager) = subscription_manager {
+                        for &(exchange, name) in LIVE_EXCHANGES.iter() {
+                            if config.is_exchange_enabled(exchange)
+                                && !previous.is_exchange_enabled(exchange)
+                                && !manager.is_registered(exchange)
+                            {
+                                restart_required.push(format!("exchanges.{}.enabled", name));
+                            }
+                        }
+                    }
+                    if !changed.is_empty() {
+                        let closed = state.apply_config(config).await;
+                        ws_server::broadcast_opportunity_lifecycle(&broadcast_tx, &closed);
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "Configuration reload failed, keeping current settings: {}",
+                        e
+                    );
+                }
+            }
+        }
+
+        if changes.mappings {
+            let mappings = load_mappings();
+            info!(
+                "Symbol mappings reloaded: {} excluded pairs",
+                mappings.excluded_pairs().len()
+            );
+            state.set_symbol_mappings(Arc::new(mappings)).await;
+            changed.push("symbol_mappings".to_string());
+        }
+
+        if changed.is_empty() {
+            continue;
+        }
+        info!("Configuration applied: {}", changed.join(", "));
+        if !restart_required.is_empty() {
+            warn!(
+                "Changed settings take effect after a restart: {}",
+                restart_required.join(", ")
+            );
+        }
+        if changed
+            .iter()
+            .any(|path| path.starts_with("exchanges") || path == "symbol_mappings")
+        {
+            discovery.notify_one();
+        }
+
+        announce_config_changed(&state, &broadcast_tx, changed, restart_required).await;
+    }
+}
+
+/// Broadcast a `config_changed` event with the running configuration.
+pub async fn announce_config_changed(
+    state: &SharedState,
+    broadcast_tx: &BroadcastSender,
+    changed: Vec<String>,
+    restart_required: Vec<String>,
+) {
+    let config = state.config.read().await;
+    ws_server::broadcast_config_changed(
+        broadcast_tx,
+        WsConfigChangedData {
+            changed,
+            restart_required,
+            min_premium_bps: config.detector.min_premium_bps,
+            enabled_exchanges: config
+                .exchanges
+                .iter()
+                .filter(|settings| settings.enabled)
+                .map(|settings| format!("{:?}", settings.exchange))
+                .collect(),
+            timestamp: crate::wallet_status::timestamp_ms(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrage_core::Exchange;
+
+    #[test]
+    fn test_changed_settings_names_exchanges() {
+        let old = AppConfig::default();
+        let mut new = old.clone();
+        new.detector.min_premium_bps += 10;
+        new.server.ws_port = 9100;
+        for settings in &mut new.exchanges {
+            if settings.exchange == Exchange::Upbit {
+                settings.enabled = false;
+                settings.orderbook_levels = Some(5);
+            }
+        }
+
+        assert_eq!(
+            changed_settings(&old, &new),
+            vec![
+                "detector.min_premium_bps",
+                "exchanges.Upbit.enabled",
+                "exchanges.Upbit.orderbook_levels",
+                "server.ws_port",
+            ]
+        );
+        assert!(changed_settings(&old, &old.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_requires_restart() {
+        assert!(requires_restart("server.ws_port"));
+        assert!(requires_restart("log_level"));
+        assert!(requires_restart("exchanges.Upbit.orderbook_levels"));
+        assert!(!requires_restart("exchanges.Upbit.enabled"));
+        assert!(!requires_restart("detector.min_premium_bps"));
+        assert!(!requires_restart("detector.depeg_warn_bps"));
+    }
+
+    #[test]
+    fn test_watcher_detects_modification_and_creation() {
+        let dir = std::env::temp_dir().join(format!("config-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        let mappings_path = dir.join("symbol_mappings.json");
+        std::fs::write(&config_path, "log_level = \"info\"\n").unwrap();
+        let _ = std::fs::remove_file(&mappings_path);
+
+        let mut watcher = ConfigWatcher::new(vec![config_path.clone()], mappings_path.clone());
+        assert_eq!(watcher.poll(), WatchedChanges::default());
+
+        std::fs::write(&mappings_path, "{\"mappings\":[]}").unwrap();
+        assert_eq!(
+            watcher.poll(),
+            WatchedChanges {
+                config: false,
+                mappings: true
+            }
+        );
+
+        let later = SystemTime::now() + Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&config_path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(
+            watcher.poll(),
+            WatchedChanges {
+                config: true,
+                mappings: false
+            }
+        );
+        assert_eq!(watcher.poll(), WatchedChanges::default());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
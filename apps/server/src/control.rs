@@ -0,0 +1,791 @@
+//! Remote control commands.
+//!
+//! Clients with `trade` scope post commands to `/api/control`:
+//!
+//! ```text
+//! {"command":"pause"}
+//! {"command":"resume"}
+//! {"command":"set_exchange","exchange":"Upbit","enabled":false}
+//! {"command":"set_symbol","symbol":"XRP","enabled":false}
+//! {"command":"set_thresholds","min_premium_bps":40,"min_profit_bps":20}
+//! {"command":"approve","opportunity_id":42}
+//! {"command":"reject","opportunity_id":42,"reason":"thin book"}
+//! {"command":"kill_switch"}
+//! {"command":"reset_kill_switch"}
+//! ```
+//!
+//! Every command from a `trade` client, applied or rejected, is answered with
+//! its outcome, kept in the audit log and broadcast to clients as a `control`
+//! message. Clients without `trade` scope get 403 and nothing is recorded.
+//!
+//! In `ManualApproval` mode, detected opportunities clearing
+//! `execution.min_profit_bps` wait for approval until they are approved,
+//! rejected or closed. The server does not place orders yet: approvals are
+//! recorded and announced for an executor to act on.
+//!
+//! Exchange and threshold changes update the running configuration. They are
+//! serialized with config file reloads; editing the config file later reloads
+//! it over them.
+
+use crate::auth::Client;
+use crate::config::{ApiScope, AppConfig, ExecutionMode};
+use crate::config_reload;
+use crate::rest_api::{self, ApiError};
+use crate::state::SharedState;
+use crate::wallet_status::timestamp_ms;
+use crate::ws_server::{self, BroadcastSender, WsControlData, WsControlStatusData, WsServerState};
+use arbitrage_core::{ArbitrageOpportunity, Exchange};
+use arbitrage_engine::LifecycleEvent;
+use axum::{
+    extract::{rejection::JsonRejection, State},
+    routing::get,
+    Extension, Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+
+/// Executions waiting for approval at most; further ones are not queued.
+const MAX_PENDING: usize = 100;
+/// Audit entries kept in memory for `GET /api/control`.
+const AUDIT_HISTORY: usize = 200;
+
+/// Remote control command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Stop detecting opportunities (feeds keep running).
+    Pause,
+    Resume,
+    SetExchange {
+        exchange: String,
+        enabled: bool,
+    },
+    /// Include or exclude a symbol from detection.
+    SetSymbol {
+        symbol: String,
+        enabled: bool,
+    },
+    SetThresholds {
+        #[serde(default)]
+        min_premium_bps: Option<i32>,
+        #[serde(default)]
+        min_profit_bps: Option<i32>,
+        #[serde(default)]
+        max_slippage_bps: Option<u16>,
+    },
+    /// Approve a pending execution.
+    Approve {
+        opportunity_id: u64,
+    },
+    /// Reject a pending execution.
+    Reject {
+        opportunity_id: u64,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// Pause detection, drop pending executions and refuse approvals and
+    /// resuming until reset.
+    KillSwitch,
+    ResetKillSwitch,
+}
+
+impl ControlCommand {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlCommand::Pause => "pause",
+            ControlCommand::Resume => "resume",
+            ControlCommand::SetExchange { .. } => "set_exchange",
+            ControlCommand::SetSymbol { .. } => "set_symbol",
+            ControlCommand::SetThresholds { .. } => "set_thresholds",
+            ControlCommand::Approve { .. } => "approve",
+            ControlCommand::Reject { .. } => "reject",
+            ControlCommand::KillSwitch => "kill_switch",
+            ControlCommand::ResetKillSwitch => "reset_kill_switch",
+        }
+    }
+}
+
+/// Execution waiting for manual approval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingExecution {
+    /// Stable opportunity (lifecycle episode) id.
+    pub opportunity_id: u64,
+    pub symbol: String,
+    pub buy_exchange: Exchange,
+    pub sell_exchange: Exchange,
+    pub premium_bps: i32,
+    pub net_profit_bps: i32,
+    pub queued_at_ms: u64,
+}
+
+/// Remote control state.
+#[derive(Debug)]
+pub struct Control {
+    paused: AtomicBool,
+    /// Wakes detection workers waiting for resume.
+    resumed: tokio::sync::Notify,
+    kill_switch: AtomicBool,
+    disabled_symbols: RwLock<BTreeSet<String>>,
+    pending: Mutex<BTreeMap<u64, PendingExecution>>,
+    audit: Mutex<VecDeque<WsControlData>>,
+    audit_path: Option<PathBuf>,
+    audit_writer: tokio::sync::Mutex<()>,
+}
+
+impl Control {
+    /// Control state writing its audit log to `audit_log_path` (empty = memory only).
+    pub fn new(audit_log_path: &str) -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            resumed: tokio::sync::Notify::new(),
+            kill_switch: AtomicBool::new(false),
+            disabled_symbols: RwLock::new(BTreeSet::new()),
+            pending: Mutex::new(BTreeMap::new()),
+            audit: Mutex::new(VecDeque::new()),
+            audit_path: (!audit_log_path.is_empty()).then(|| PathBuf::from(audit_log_path)),
+            audit_writer: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Wait until detection is not paused.
+    pub async fn wait_until_running(&self) {
+        loop {
+            let resumed = self.resumed.notified();
+            tokio::pin!(resumed);
+            resumed.as_mut().enable();
+            if !self.is_paused() {
+                return;
+            }
+            resumed.await;
+        }
+    }
+
+    pub fn is_kill_switch_engaged(&self) -> bool {
+        self.kill_switch.load(Ordering::SeqCst)
+    }
+
+    /// Whether a symbol is included in detection.
+    pub fn detects(&self, symbol: &str) -> bool {
+        !self.disabled_symbols.read().unwrap().contains(symbol)
+    }
+
+    pub fn disabled_symbols(&self) -> Vec<String> {
+        self.disabled_symbols
+            .read()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Executions waiting for approval, by opportunity id.
+    pub fn pending(&self) -> Vec<PendingExecution> {
+        self.pending.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Recent audit entries, oldest first.
+    pub fn recent_audit(&self) -> Vec<WsControlData> {
+        self.audit.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Queue an opportunity for approval if it clears `min_profit_bps` and is
+    /// not queued yet. Returns the new entry.
+    pub fn offer(
+        &self,
+        opp: &ArbitrageOpportunity,
+        min_profit_bps: i32,
+    ) -> Option<PendingExecution> {
+        if self.is_kill_switch_engaged() {
+            return None;
+        }
+        let net_profit_bps = opp.net_profit_bps()?;
+        if net_profit_bps < min_profit_bps {
+            return None;
+        }
+        let mut pending = self.pending.lock().unwrap();
+        if pending.contains_key(&opp.id) || pending.len() >= MAX_PENDING {
+            return None;
+        }
+        let entry = PendingExecution {
+            opportunity_id: opp.id,
+            symbol: opp.asset.symbol.to_string(),
+            buy_exchange: opp.source_exchange,
+            sell_exchange: opp.target_exchange,
+            premium_bps: opp.premium_bps,
+            net_profit_bps,
+            queued_at_ms: timestamp_ms(),
+        };
+        pending.insert(opp.id, entry.clone());
+        Some(entry)
+    }
+
+    /// Drop pending executions of closed opportunities.
+    pub fn expire(&self, events: &[LifecycleEvent]) {
+        let closed: Vec<u64> = events
+            .iter()
+            .filter(|event| matches!(event, LifecycleEvent::Closed(_)))
+            .map(|event| event.episode().id)
+            .collect();
+        if !closed.is_empty() {
+            self.pending
+                .lock()
+                .unwrap()
+                .retain(|id, _| !closed.contains(id));
+        }
+    }
+
+    fn pause(&self) -> String {
+        if self.paused.swap(true, Ordering::SeqCst) {
+            "detection already paused".to_string()
+        } else {
+            "detection paused".to_string()
+        }
+    }
+
+    fn resume(&self) -> Result<String, ApiError> {
+        if self.is_kill_switch_engaged() {
+            return Err(ApiError::Rejected(
+                "kill switch engaged; reset it before resuming".to_string(),
+            ));
+        }
+        if self.paused.swap(false, Ordering::SeqCst) {
+            self.resumed.notify_waiters();
+            Ok("detection resumed".to_string())
+        } else {
+            Ok("detection already running".to_string())
+        }
+    }
+
+    fn set_symbol(&self, symbol: &str, enabled: bool) -> String {
+        let mut disabled = self.disabled_symbols.write().unwrap();
+        if enabled {
+            disabled.remove(symbol);
+            format!("{} detection enabled", symbol)
+        } else {
+            disabled.insert(symbol.to_string());
+            self.pending
+                .lock()
+                .unwrap()
+                .retain(|_, pending| pending.symbol != symbol);
+            format!("{} detection disabled", symbol)
+        }
+    }
+
+    /// Take a pending execution for approval; it must still be open.
+    fn approve(&self, opportunity_id: u64, open: bool) -> Result<PendingExecution, ApiError> {
+        if self.is_kill_switch_engaged() {
+            return Err(ApiError::Rejected(
+                "kill switch engaged; approvals are refused".to_string(),
+            ));
+        }
+        let pending = self.take(opportunity_id)?;
+        if !open {
+            return Err(ApiError::Rejected(format!(
+                "opportunity {} has closed",
+                opportunity_id
+            )));
+        }
+        Ok(pending)
+    }
+
+    fn take(&self, opportunity_id: u64) -> Result<PendingExecution, ApiError> {
+        self.pending
+            .lock()
+            .unwrap()
+            .remove(&opportunity_id)
+            .ok_or_else(|| {
+                ApiError::NotFound(format!(
+                    "no execution pending approval for opportunity {}",
+                    opportunity_id
+                ))
+            })
+    }
+
+    fn engage_kill_switch(&self) -> String {
+        self.kill_switch.store(true, Ordering::SeqCst);
+        self.paused.store(true, Ordering::SeqCst);
+        let mut pending = self.pending.lock().unwrap();
+        let dropped = pending.len();
+        pending.clear();
+        format!(
+            "kill switch engaged: detection paused, {} pending executions dropped",
+            dropped
+        )
+    }
+
+    fn reset_kill_switch(&self) -> String {
+        if self.kill_switch.swap(false, Ordering::SeqCst) {
+            "kill switch released; detection stays paused until resumed".to_string()
+        } else {
+            "kill switch not engaged".to_string()
+        }
+    }
+
+    /// Keep an audit entry in memory and append it to the audit log file.
+    async fn record(&self, entry: &WsControlData) {
+        {
+            let mut audit = self.audit.lock().unwrap();
+            if audit.len() >= AUDIT_HISTORY {
+                audit.pop_front();
+            }
+            audit.push_back(entry.clone());
+        }
+        if let Some(ref path) = self.audit_path {
+            // One writer at a time keeps lines whole and in order
+            let _writer = self.audit_writer.lock().await;
+            if let Err(e) = append_json_line(path, entry).await {
+                warn!("Failed to write audit log {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+async fn append_json_line(path: &Path, entry: &WsControlData) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(line.as_bytes()).await?;
+    // tokio finishes the write in the background unless flushed
+    file.flush().await
+}
+
+/// Control routes, merged into the WebSocket server router.
+pub fn routes() -> Router<Arc<WsServerState>> {
+    Router::new().route("/api/control", get(status_handler).post(command_handler))
+}
+
+/// Control state, pending executions and recent commands.
+async fn status_handler(State(state): State<Arc<WsServerState>>) -> Json<WsControlStatusData> {
+    Json(ws_server::collect_control_status(&state.app_state))
+}
+
+/// Apply a command; needs `trade` scope.
+async fn command_handler(
+    State(state): State<Arc<WsServerState>>,
+    Extension(client): Extension<Client>,
+    body: Result<Json<ControlCommand>, JsonRejection>,
+) -> Result<Json<WsControlData>, ApiError> {
+    let Json(command) = body.map_err(|e| ApiError::Invalid(e.body_text()))?;
+    execute(&state.app_state, &state.broadcast_tx, &client, command)
+        .await
+        .map(Json)
+}
+
+/// Apply a command from `client`, audit and broadcast the outcome.
+pub async fn execute(
+    state: &SharedState,
+    broadcast_tx: &BroadcastSender,
+    client: &Client,
+    command: ControlCommand,
+) -> Result<WsControlData, ApiError> {
+    let name = command.name();
+    // Refused before auditing so unauthorized clients cannot flood the log
+    if let Err(e) = client.require(ApiScope::Trade) {
+        warn!("Control {} by {} refused: {}", name, client.name, e);
+        return Err(e);
+    }
+    let params = serde_json::to_value(&command).unwrap_or_default();
+    let result = apply(state, broadcast_tx, command).await;
+
+    let entry = WsControlData {
+        client: client.name.clone(),
+        command: name.to_string(),
+        params,
+        ok: result.is_ok(),
+        message: match &result {
+            Ok(message) => message.clone(),
+            Err(e) => e.to_string(),
+        },
+        timestamp: timestamp_ms(),
+    };
+    if entry.ok {
+        info!("Control {} by {}: {}", name, entry.client, entry.message);
+    } else {
+        warn!(
+            "Control {} by {} rejected: {}",
+            name, entry.client, entry.message
+        );
+    }
+    state.control().record(&entry).await;
+    ws_server::broadcast_control(broadcast_tx, entry.clone());
+    result.map(|_| entry)
+}
+
+async fn apply(
+    state: &SharedState,
+    broadcast_tx: &BroadcastSender,
+    command: ControlCommand,
+) -> Result<String, ApiError> {
+    let control = state.control();
+    match command {
+        ControlCommand::Pause => Ok(control.pause()),
+        ControlCommand::Resume => control.resume(),
+        ControlCommand::SetExchange { exchange, enabled } => {
+            set_exchange(state, broadcast_tx, &exchange, enabled).await
+        }
+        ControlCommand::SetSymbol { symbol, enabled } => {
+            let (_, symbol) = rest_api::lookup_pair(&symbol)?;
+            Ok(control.set_symbol(&symbol, enabled))
+        }
+        ControlCommand::SetThresholds {
+            min_premium_bps,
+            min_profit_bps,
+            max_slippage_bps,
+        } => {
+            let _update = state.lock_config_update().await;
+            let mut config = state.config.read().await.clone();
+            if let Some(bps) = min_premium_bps {
+                config.detector.min_premium_bps = bps;
+            }
+            if let Some(bps) = min_profit_bps {
+                config.execution.min_profit_bps = bps;
+            }
+            if let Some(bps) = max_slippage_bps {
+                config.execution.max_slippage_bps = bps;
+            }
+            apply_config(state, broadcast_tx, config).await
+        }
+        ControlCommand::Approve { opportunity_id } => {
+            let open = state
+                .opportunity_lifecycle()
+                .open_episodes()
+                .iter()
+                .any(|episode| episode.id == opportunity_id);
+            let pending = control.approve(opportunity_id, open)?;
+            Ok(format!(
+                "opportunity {} approved ({} {:?} -> {:?}, {} bps net); no orders placed",
+                opportunity_id,
+                pending.symbol,
+                pending.buy_exchange,
+                pending.sell_exchange,
+                pending.net_profit_bps
+            ))
+        }
+        ControlCommand::Reject {
+            opportunity_id,
+            reason,
+        } => {
+            control.take(opportunity_id)?;
+            Ok(match reason {
+                Some(reason) => format!(
+                    "execution of opportunity {} rejected: {}",
+                    opportunity_id, reason
+                ),
+                None => format!("execution of opportunity {} rejected", opportunity_id),
+            })
+        }
+        ControlCommand::KillSwitch => Ok(control.engage_kill_switch()),
+        ControlCommand::ResetKillSwitch => Ok(control.reset_kill_switch()),
+    }
+}
+
+async fn set_exchange(
+    state: &SharedState,
+    broadcast_tx: &BroadcastSender,
+    name: &str,
+    enabled: bool,
+) -> Result<String, ApiError> {
+    let exchange = rest_api::parse_exchange(name)?;
+    let _update = state.lock_config_update().await;
+    let mut config = state.config.read().await.clone();
+    let live = config.server.live;
+    let Some(settings) = config
+        .exchanges
+        .iter_mut()
+        .find(|settings| settings.exchange == exchange)
+    else {
+        return Err(ApiError::Rejected(format!(
+            "{:?} is not configured",
+            exchange
+        )));
+    };
+    // Feeds are only started for exchanges enabled at startup
+    let feed_started = state
+        .feed_health()
+        .snapshot()
+        .iter()
+        .any(|(feed, _)| *feed == exchange);
+    if enabled && !settings.enabled && live && !feed_started {
+        return Err(ApiError::Rejected(format!(
+            "{:?} feed is not running; enabling it needs a restart",
+            exchange
+        )));
+    }
+    settings.enabled = enabled;
+    let message = apply_config(state, broadcast_tx, config).await?;
+    state.market_discovery().notify_one();
+    Ok(message)
+}
+
+/// Validate and apply a changed configuration, announcing it to clients.
+async fn apply_config(
+    state: &SharedState,
+    broadcast_tx: &BroadcastSender,
+    config: AppConfig,
+) -> Result<String, ApiError> {
+    config
+        .validate()
+        .map_err(|e| ApiError::Invalid(e.to_string()))?;
+    let changed = config_reload::changed_settings(&*state.config.read().await, &config);
+    if changed.is_empty() {
+        return Ok("settings unchanged".to_string());
+    }
+    let message = format!("updated {}", changed.join(", "));
+    let closed = state.apply_config(config).await;
+    ws_server::broadcast_opportunity_lifecycle(broadcast_tx, &closed);
+    state.control().expire(&closed);
+    config_reload::announce_config_changed(state, broadcast_tx, changed, Vec::new()).await;
+    Ok(message)
+}
+
+/// Queue detected opportunities for approval in `ManualApproval` mode and
+/// announce the new entries.
+pub async fn queue_for_approval(
+    state: &SharedState,
+    broadcast_tx: &BroadcastSender,
+    opps: &[ArbitrageOpportunity],
+) {
+    let min_profit_bps = {
+        let config = state.config.read().await;
+        if config.execution.mode != ExecutionMode::ManualApproval {
+            return;
+        }
+        config.execution.min_profit_bps
+    };
+    for opp in opps {
+        if let Some(pending) = state.control().offer(opp, min_profit_bps) {
+            ws_server::broadcast_pending_execution(broadcast_tx, &pending);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrage_core::{Asset, FixedPoint};
+
+    fn opportunity(id: u64, symbol: &str, net_profit_bps: i64) -> ArbitrageOpportunity {
+        let mut opp = ArbitrageOpportunity::new(
+            id,
+            Exchange::Binance,
+            Exchange::Upbit,
+            Asset::from_symbol(symbol),
+            FixedPoint::from_f64(100.0),
+            FixedPoint::from_f64(101.0),
+        )
+        .with_optimal_size(FixedPoint::from_f64(10.0).0, 0);
+        // 10 units at $100 = $1000 notional
+        opp.net_profit_estimate = FixedPoint::from_f64(1000.0).0 as i64 * net_profit_bps / 10_000;
+        opp
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let parse = |json: &str| serde_json::from_str::<ControlCommand>(json).unwrap();
+        assert_eq!(parse(r#"{"command":"pause"}"#), ControlCommand::Pause);
+        assert_eq!(
+            parse(r#"{"command":"set_thresholds","min_premium_bps":40}"#),
+            ControlCommand::SetThresholds {
+                min_premium_bps: Some(40),
+                min_profit_bps: None,
+                max_slippage_bps: None,
+            }
+        );
+        assert_eq!(
+            parse(r#"{"command":"approve","opportunity_id":42}"#).name(),
+            "approve"
+        );
+        assert!(serde_json::from_str::<ControlCommand>(r#"{"command":"launch"}"#).is_err());
+    }
+
+    #[test]
+    fn test_approval_queue() {
+        let control = Control::new("");
+        assert!(control.offer(&opportunity(1, "BTC", 50), 30).is_some());
+        // Below the execution threshold, or already queued
+        assert!(control.offer(&opportunity(2, "ETH", 10), 30).is_none());
+        assert!(control.offer(&opportunity(1, "BTC", 50), 30).is_none());
+        assert!(control.offer(&opportunity(3, "XRP", 40), 30).is_some());
+        assert_eq!(control.pending().len(), 2);
+
+        let approved = control.approve(1, true).unwrap();
+        assert_eq!(approved.symbol, "BTC");
+        assert_eq!(approved.net_profit_bps, 50);
+        assert!(matches!(
+            control.approve(1, true),
+            Err(ApiError::NotFound(_))
+        ));
+        // Closed opportunities cannot be approved
+        assert!(matches!(
+            control.approve(3, false),
+            Err(ApiError::Rejected(_))
+        ));
+        assert!(control.pending().is_empty());
+
+        control.offer(&opportunity(4, "SOL", 40), 30);
+        control.set_symbol("SOL", false);
+        assert!(!control.detects("SOL"));
+        assert!(control.pending().is_empty());
+    }
+
+    #[test]
+    fn test_kill_switch_blocks_resume_and_approvals() {
+        let control = Control::new("");
+        control.offer(&opportunity(1, "BTC", 50), 30);
+
+        control.engage_kill_switch();
+        assert!(control.is_paused());
+        assert!(control.pending().is_empty());
+        assert!(control.offer(&opportunity(2, "BTC", 50), 30).is_none());
+        assert!(control.resume().is_err());
+
+        control.reset_kill_switch();
+        assert!(control.is_paused());
+        assert!(control.resume().is_ok());
+        assert!(!control.is_paused());
+    }
+
+    #[tokio::test]
+    async fn test_execute_requires_trade_scope_and_audits() {
+        let mut config = AppConfig::default();
+        config.server.audit_log_path = String::new();
+        let (state, _rx) = crate::state::create_state(config);
+        let (broadcast_tx, mut broadcast_rx) = tokio::sync::broadcast::channel(8);
+        let reader = Client {
+            name: "dashboard".to_string(),
+            scope: ApiScope::Read,
+        };
+        let trader = Client {
+            name: "desktop".to_string(),
+            scope: ApiScope::Trade,
+        };
+
+        let denied = execute(&state, &broadcast_tx, &reader, ControlCommand::Pause).await;
+        assert!(matches!(denied, Err(ApiError::Forbidden(_))));
+        assert!(!state.control().is_paused());
+        assert!(state.control().recent_audit().is_empty());
+        assert!(broadcast_rx.try_recv().is_err());
+
+        let ack = execute(&state, &broadcast_tx, &trader, ControlCommand::Pause)
+            .await
+            .unwrap();
+        assert!(ack.ok);
+        assert!(state.control().is_paused());
+
+        let thresholds = ControlCommand::SetThresholds {
+            min_premium_bps: Some(45),
+            min_profit_bps: None,
+            max_slippage_bps: None,
+        };
+        let ack = execute(&state, &broadcast_tx, &trader, thresholds)
+            .await
+            .unwrap();
+        assert_eq!(ack.message, "updated detector.min_premium_bps");
+        assert_eq!(state.config.read().await.detector.min_premium_bps, 45);
+
+        let invalid = ControlCommand::SetThresholds {
+            min_premium_bps: Some(-1),
+            min_profit_bps: None,
+            max_slippage_bps: None,
+        };
+        assert!(matches!(
+            execute(&state, &broadcast_tx, &trader, invalid).await,
+            Err(ApiError::Invalid(_))
+        ));
+
+        let audit = state.control().recent_audit();
+        let outcomes: Vec<(&str, &str, bool)> = audit
+            .iter()
+            .map(|entry| (entry.client.as_str(), entry.command.as_str(), entry.ok))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("desktop", "pause", true),
+                ("desktop", "set_thresholds", true),
+                ("desktop", "set_thresholds", false),
+            ]
+        );
+        assert!(matches!(
+            broadcast_rx.recv().await,
+            Ok(ws_server::WsServerMessage::Control(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_threshold_updates_all_apply() {
+        let mut config = AppConfig::default();
+        config.server.audit_log_path = String::new();
+        let (state, _rx) = crate::state::create_state(config);
+        let (broadcast_tx, _) = tokio::sync::broadcast::channel(64);
+        let trader = Client {
+            name: "desktop".to_string(),
+            scope: ApiScope::Trade,
+        };
+
+        let premium = ControlCommand::SetThresholds {
+            min_premium_bps: Some(45),
+            min_profit_bps: None,
+            max_slippage_bps: None,
+        };
+        let profit = ControlCommand::SetThresholds {
+            min_premium_bps: None,
+            min_profit_bps: Some(25),
+            max_slippage_bps: None,
+        };
+        let (a, b) = tokio::join!(
+            execute(&state, &broadcast_tx, &trader, premium),
+            execute(&state, &broadcast_tx, &trader, profit),
+        );
+        assert!(a.is_ok() && b.is_ok());
+
+        let config = state.config.read().await;
+        assert_eq!(config.detector.min_premium_bps, 45);
+        assert_eq!(config.execution.min_profit_bps, 25);
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_appends_json_lines() {
+        let path = std::env::temp_dir().join(format!("control_audit_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let control = Control::new(path.to_str().unwrap());
+        for command in ["pause", "resume"] {
+            control
+                .record(&WsControlData {
+                    client: "desktop".to_string(),
+                    command: command.to_string(),
+                    params: serde_json::Value::Null,
+                    ok: true,
+                    message: String::new(),
+                    timestamp: 0,
+                })
+                .await;
+        }
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let commands: Vec<String> = written
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<serde_json::Value>(line).unwrap()["command"].to_string()
+            })
+            .collect();
+        assert_eq!(commands, vec!["\"pause\"", "\"resume\""]);
+        let _ = std::fs::remove_file(&path);
+    }
+}
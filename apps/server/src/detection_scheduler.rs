@@ -0,0 +1,436 @@
+//! Coalescing detection scheduler.
+//!
+//! Price updates mark their pair dirty instead of queueing a detection each:
+//! a burst of updates for one pair runs detection once, on the latest prices.
+//! Workers take the dirty pair closest to the premium threshold first (pairs
+//! with an open opportunity, or not yet seen, come first), and a pair is never
+//! detected by two workers at once; updates arriving meanwhile leave it dirty
+//! for another pass. A pair dirty for longer than `MAX_DIRTY_WAIT` is served
+//! before any other, so pairs far from the threshold (whose distance is from
+//! their previous detection) cannot starve under sustained load.
+
+use crate::state::PriceUpdateEvent;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// A pair waiting for detection.
+#[derive(Debug, Clone)]
+pub struct DirtyPair {
+    pub pair_id: u32,
+    pub symbol: String,
+    /// When the oldest pending update arrived.
+    pub since: Instant,
+    /// Updates coalesced into this detection.
+    pub updates: u64,
+}
+
+/// Longest a dirty pair waits before it is served ahead of priority order.
+pub const MAX_DIRTY_WAIT: Duration = Duration::from_millis(500);
+
+/// Dirty pairs, indexed by priority and by age. Pairs being detected are not
+/// indexed; updates arriving meanwhile wait in `dirty` until they finish.
+#[derive(Debug, Default)]
+struct Queue {
+    dirty: HashMap<u32, DirtyPair>,
+    /// (distance_bps, since, pair_id) of takeable pairs.
+    by_priority: BTreeSet<(i32, Instant, u32)>,
+    /// (since, pair_id) of takeable pairs.
+    by_age: BTreeSet<(Instant, u32)>,
+    in_flight: HashSet<u32>,
+    /// Last best premium's distance below the threshold (0 = at or above).
+    distance_bps: HashMap<u32, i32>,
+    /// Moving average of detection latency (ms).
+    latency_ema_ms: Option<f64>,
+    /// Peak detection latency (ms).
+    max_latency_ms: f64,
+}
+
+impl Queue {
+    fn distance(&self, pair_id: u32) -> i32 {
+        self.distance_bps.get(&pair_id).copied().unwrap_or(0)
+    }
+
+    /// Make a dirty pair takeable (not while it is being detected).
+    fn index(&mut self, pair_id: u32) {
+        if self.in_flight.contains(&pair_id) {
+            return;
+        }
+        if let Some(since) = self.dirty.get(&pair_id).map(|pair| pair.since) {
+            self.by_priority
+                .insert((self.distance(pair_id), since, pair_id));
+            self.by_age.insert((since, pair_id));
+        }
+    }
+
+    /// Mark a pair dirty, or count one more update for it.
+    fn insert(&mut self, pair: DirtyPair) {
+        let pair_id = pair.pair_id;
+        match self.dirty.get_mut(&pair_id) {
+            Some(pending) => {
+                pending.updates += pair.updates;
+                pending.symbol = pair.symbol;
+            }
+            None => {
+                self.dirty.insert(pair_id, pair);
+                self.index(pair_id);
+            }
+        }
+    }
+
+    /// Pair waiting longer than `MAX_DIRTY_WAIT`, else the highest-priority
+    /// one, among pairs not being detected.
+    fn take(&mut self, now: Instant) -> Option<DirtyPair> {
+        let pair_id = match self.by_age.first() {
+            Some(&(since, pair_id)) if now.duration_since(since) >= MAX_DIRTY_WAIT => pair_id,
+            _ => self.by_priority.first()?.2,
+        };
+        let pair = self.dirty.remove(&pair_id)?;
+        self.by_priority
+            .remove(&(self.distance(pair_id), pair.since, pair_id));
+        self.by_age.remove(&(pair.since, pair_id));
+        self.in_flight.insert(pair_id);
+        Some(pair)
+    }
+
+    /// A pair's detection ended; updates that arrived meanwhile become takeable.
+    fn finish(&mut self, pair_id: u32) {
+        self.in_flight.remove(&pair_id);
+        self.index(pair_id);
+    }
+}
+
+/// Weight of the latest detection in the latency moving average.
+const LATENCY_EMA_ALPHA: f64 = 0.1;
+
+/// Point-in-time scheduler metrics.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchedulerSnapshot {
+    /// Pairs waiting for detection.
+    pub queue_depth: usize,
+    /// Pairs being detected.
+    pub in_flight: usize,
+    /// Price updates received.
+    pub updates: u64,
+    /// Detection passes run.
+    pub detections: u64,
+    /// Moving average of the latency from a pair's first pending update to
+    /// its detection finishing (ms).
+    pub avg_latency_ms: f64,
+    /// Peak latency (ms).
+    pub max_latency_ms: f64,
+}
+
+impl SchedulerSnapshot {
+    /// Updates absorbed by coalescing.
+    pub fn coalesced(&self) -> u64 {
+        self.updates.saturating_sub(self.detections)
+    }
+}
+
+/// Dirty-pair scheduler shared by the update ingest and detection workers.
+#[derive(Debug, Default)]
+pub struct DetectionScheduler {
+    queue: Mutex<Queue>,
+    notify: Notify,
+    /// Wakes tasks waiting for the scheduler to close.
+    shutdown: Notify,
+    closed: AtomicBool,
+    updates: AtomicU64,
+    detections: AtomicU64,
+}
+
+impl DetectionScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark a pair dirty, coalescing with any pending update.
+    pub fn mark_dirty(&self, event: PriceUpdateEvent) {
+        self.updates.fetch_add(1, Ordering::Relaxed);
+        self.queue.lock().unwrap().insert(DirtyPair {
+            pair_id: event.pair_id,
+            symbol: event.symbol,
+            since: Instant::now(),
+            updates: 1,
+        });
+        self.notify.notify_one();
+    }
+
+    /// Queue a periodic re-detection of pairs (pair ID and symbol) without
+    /// counting price updates; pairs already dirty are left as they are.
+    pub fn rescan(&self, pairs: impl IntoIterator<Item = (u32, String)>) {
+        let queued = {
+            let mut queue = self.queue.lock().unwrap();
+            let before = queue.dirty.len();
+            for (pair_id, symbol) in pairs {
+                if !queue.dirty.contains_key(&pair_id) {
+                    queue.insert(DirtyPair {
+                        pair_id,
+                        symbol,
+                        since: Instant::now(),
+                        updates: 0,
+                    });
+                }
+            }
+            queue.dirty.len() - before
+        };
+        for _ in 0..queued {
+            self.notify.notify_one();
+        }
+    }
+
+    /// Take the highest-priority dirty pair without waiting.
+    pub fn try_next(&self) -> Option<DirtyPair> {
+        self.queue.lock().unwrap().take(Instant::now())
+    }
+
+    /// Wait for the next dirty pair. None once the scheduler is closed.
+    pub async fn next(&self) -> Option<DirtyPair> {
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.closed.load(Ordering::Acquire) {
+                return None;
+            }
+            if let Some(pair) = self.try_next() {
+                return Some(pair);
+            }
+            notified.await;
+        }
+    }
+
+    /// Hand back a taken pair without detecting it (e.g. detection paused):
+    /// it stays dirty, merged with updates that arrived meanwhile.
+    pub fn release(&self, pair: DirtyPair) {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            let pair_id = pair.pair_id;
+            // Not indexed while in flight, so the pending entry can be edited
+            match queue.dirty.get_mut(&pair_id) {
+                Some(pending) => {
+                    pending.since = pending.since.min(pair.since);
+                    pending.updates += pair.updates;
+                }
+                None => {
+                    queue.dirty.insert(pair_id, pair);
+                }
+            }
+            queue.finish(pair_id);
+        }
+        self.notify.notify_one();
+    }
+
+    /// Finish a pair's detection: record latency and the best premium seen
+    /// (None if no exchange pair was priced) against the threshold.
+    pub fn complete(&self, pair: &DirtyPair, best_premium_bps: Option<i32>, threshold_bps: i32) {
+        let latency_ms = pair.since.elapsed().as_secs_f64() * 1_000.0;
+        self.detections.fetch_add(1, Ordering::Relaxed);
+
+        let redirty = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.latency_ema_ms = Some(queue.latency_ema_ms.map_or(latency_ms, |ema| {
+                ema + LATENCY_EMA_ALPHA * (latency_ms - ema)
+            }));
+            queue.max_latency_ms = queue.max_latency_ms.max(latency_ms);
+            let distance =
+                best_premium_bps.map_or(i32::MAX, |bps| threshold_bps.saturating_sub(bps).max(0));
+            queue.distance_bps.insert(pair.pair_id, distance);
+            queue.finish(pair.pair_id);
+            queue.dirty.contains_key(&pair.pair_id)
+        };
+        // Updates arrived while detecting: let another worker pick it up
+        if redirty {
+            self.notify.notify_one();
+        }
+    }
+
+    /// Stop handing out pairs; waiting workers return None.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.notify.notify_waiters();
+        self.shutdown.notify_waiters();
+    }
+
+    /// Wait until the scheduler is closed.
+    pub async fn closed(&self) {
+        let shutdown = self.shutdown.notified();
+        tokio::pin!(shutdown);
+        shutdown.as_mut().enable();
+        if !self.closed.load(Ordering::Acquire) {
+            shutdown.await;
+        }
+    }
+
+    /// Current metrics.
+    pub fn snapshot(&self) -> SchedulerSnapshot {
+        let queue = self.queue.lock().unwrap();
+        SchedulerSnapshot {
+            queue_depth: queue.dirty.len(),
+            in_flight: queue.in_flight.len(),
+            updates: self.updates.load(Ordering::Relaxed),
+            detections: self.detections.load(Ordering::Relaxed),
+            avg_latency_ms: queue.latency_ema_ms.unwrap_or(0.0),
+            max_latency_ms: queue.max_latency_ms,
+        }
+    }
+}
+
+/// Default detection workers: one per core, at most 8.
+pub fn default_worker_count() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .clamp(1, 8)
+}
+
+/// Latency above which a detection pass is logged as lagging.
+pub const SLOW_DETECTION: Duration = Duration::from_millis(250);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrage_core::Exchange;
+
+    fn event(pair_id: u32) -> PriceUpdateEvent {
+        PriceUpdateEvent {
+            exchange: Exchange::Binance,
+            pair_id,
+            symbol: format!("P{}", pair_id),
+        }
+    }
+
+    #[test]
+    fn test_updates_coalesce_per_pair() {
+        let scheduler = DetectionScheduler::new();
+        for _ in 0..5 {
+            scheduler.mark_dirty(event(1));
+        }
+        scheduler.mark_dirty(event(2));
+        assert_eq!(scheduler.snapshot().queue_depth, 2);
+
+        let first = scheduler.try_next().unwrap();
+        let second = scheduler.try_next().unwrap();
+        assert_eq!(first.updates + second.updates, 6);
+        assert!(scheduler.try_next().is_none());
+
+        scheduler.complete(&first, None, 30);
+        scheduler.complete(&second, None, 30);
+        let snapshot = scheduler.snapshot();
+        assert_eq!((snapshot.updates, snapshot.detections), (6, 2));
+        assert_eq!(snapshot.coalesced(), 4);
+        assert_eq!((snapshot.queue_depth, snapshot.in_flight), (0, 0));
+    }
+
+    #[test]
+    fn test_pairs_near_threshold_first() {
+        let scheduler = DetectionScheduler::new();
+        for (pair_id, premium) in [(1, 5), (2, 28), (3, 40)] {
+            scheduler.mark_dirty(event(pair_id));
+            let pair = scheduler.try_next().unwrap();
+            scheduler.complete(&pair, Some(premium), 30);
+        }
+
+        for pair_id in [1, 2, 3] {
+            scheduler.mark_dirty(event(pair_id));
+        }
+        let order: Vec<u32> = std::iter::from_fn(|| scheduler.try_next())
+            .map(|pair| pair.pair_id)
+            .collect();
+        // Above threshold, then 2 bps short, then 25 bps short
+        assert_eq!(order, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_far_pair_served_after_max_wait() {
+        let scheduler = DetectionScheduler::new();
+        for (pair_id, premium) in [(1, -500), (2, 29), (3, 28)] {
+            scheduler.mark_dirty(event(pair_id));
+            let pair = scheduler.try_next().unwrap();
+            scheduler.complete(&pair, Some(premium), 30);
+        }
+
+        // Pair 1 is far from the threshold; 2 and 3 keep getting updates
+        scheduler.mark_dirty(event(1));
+        let start = Instant::now();
+        let mut queue = scheduler.queue.lock().unwrap();
+        for round in 0..100 {
+            for pair_id in [2, 3] {
+                queue.insert(DirtyPair {
+                    pair_id,
+                    symbol: format!("P{}", pair_id),
+                    since: start + Duration::from_millis(round),
+                    updates: 1,
+                });
+            }
+            let now = start + Duration::from_millis(round);
+            let pair = queue.take(now).unwrap();
+            assert_ne!(pair.pair_id, 1, "far pair served before its wait bound");
+            queue.finish(pair.pair_id);
+        }
+
+        let pair = queue.take(start + MAX_DIRTY_WAIT).unwrap();
+        assert_eq!(pair.pair_id, 1);
+    }
+
+    #[test]
+    fn test_in_flight_pair_not_taken_twice() {
+        let scheduler = DetectionScheduler::new();
+        scheduler.mark_dirty(event(1));
+        let pair = scheduler.try_next().unwrap();
+
+        // Update while detecting: stays dirty but is not handed out
+        scheduler.mark_dirty(event(1));
+        assert!(scheduler.try_next().is_none());
+        assert_eq!(scheduler.snapshot().in_flight, 1);
+
+        scheduler.complete(&pair, Some(10), 30);
+        assert_eq!(scheduler.try_next().unwrap().pair_id, 1);
+    }
+
+    #[test]
+    fn test_release_keeps_pair_dirty() {
+        let scheduler = DetectionScheduler::new();
+        scheduler.mark_dirty(event(1));
+        let pair = scheduler.try_next().unwrap();
+        scheduler.mark_dirty(event(1));
+
+        scheduler.release(pair);
+        let snapshot = scheduler.snapshot();
+        assert_eq!((snapshot.queue_depth, snapshot.in_flight), (1, 0));
+        assert_eq!(scheduler.try_next().unwrap().updates, 2);
+    }
+
+    #[test]
+    fn test_rescan_queues_clean_pairs_only() {
+        let scheduler = DetectionScheduler::new();
+        scheduler.mark_dirty(event(1));
+        scheduler.rescan([(1, "P1".to_string()), (2, "P2".to_string())]);
+
+        let snapshot = scheduler.snapshot();
+        assert_eq!((snapshot.queue_depth, snapshot.updates), (2, 1));
+        let mut updates: Vec<(u32, u64)> = std::iter::from_fn(|| scheduler.try_next())
+            .map(|pair| (pair.pair_id, pair.updates))
+            .collect();
+        updates.sort();
+        assert_eq!(updates, vec![(1, 1), (2, 0)]);
+    }
+
+    #[tokio::test]
+    async fn test_next_wakes_on_update_and_close() {
+        let scheduler = std::sync::Arc::new(DetectionScheduler::new());
+        let waiter = {
+            let scheduler = scheduler.clone();
+            tokio::spawn(async move { scheduler.next().await.map(|pair| pair.pair_id) })
+        };
+        tokio::task::yield_now().await;
+        scheduler.mark_dirty(event(7));
+        assert_eq!(waiter.await.unwrap(), Some(7));
+
+        scheduler.close();
+        assert!(scheduler.next().await.is_none());
+    }
+}
@@ -0,0 +1,135 @@
+//! Per-exchange feed health.
+//!
+//! The feed handler records every message and connection event here, so
+//! clients can tell a quiet market from a dead connection without watching
+//! the stream.
+
+use crate::wallet_status::timestamp_ms;
+use arbitrage_core::Exchange;
+use arbitrage_feeds::ConnectionEvent;
+use dashmap::DashMap;
+
+/// Connection state of one exchange feed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedStatus {
+    /// Open WebSocket connections (pooled exchanges use several).
+    pub connections: u32,
+    /// Last message received (ms since epoch).
+    pub last_message_ms: Option<u64>,
+    /// Messages received since startup.
+    pub messages: u64,
+    /// Connections lost since startup.
+    pub disconnects: u64,
+    /// Connections re-established since startup.
+    pub reconnects: u64,
+    /// Circuit breaker blocks reconnecting until this time (ms since epoch).
+    pub circuit_open_until_ms: Option<u64>,
+    /// Most recent non-fatal error.
+    pub last_error: Option<String>,
+}
+
+impl FeedStatus {
+    /// Whether at least one connection is open.
+    pub fn is_connected(&self) -> bool {
+        self.connections > 0
+    }
+}
+
+/// Feed health of all exchanges.
+#[derive(Debug, Default)]
+pub struct FeedHealth {
+    feeds: DashMap<Exchange, FeedStatus>,
+}
+
+impl FeedHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a message from an exchange.
+    pub fn record_message(&self, exchange: Exchange) {
+        let mut status = self.feeds.entry(exchange).or_default();
+        status.last_message_ms = Some(timestamp_ms());
+        status.messages += 1;
+    }
+
+    /// Record a connection event.
+    pub fn record_event(&self, event: &ConnectionEvent) {
+        match event {
+            ConnectionEvent::Connected(exchange) => {
+                let mut status = self.feeds.entry(*exchange).or_default();
+                status.connections += 1;
+                status.circuit_open_until_ms = None;
+            }
+            ConnectionEvent::Reconnected(exchange) => {
+                let mut status = self.feeds.entry(*exchange).or_default();
+                status.connections += 1;
+                status.reconnects += 1;
+                status.circuit_open_until_ms = None;
+            }
+            ConnectionEvent::Disconnected(exchange) => {
+                let mut status = self.feeds.entry(*exchange).or_default();
+                status.connections = status.connections.saturating_sub(1);
+                status.disconnects += 1;
+            }
+            ConnectionEvent::CircuitBreakerOpen(exchange, wait) => {
+                self.feeds
+                    .entry(*exchange)
+                    .or_default()
+                    .circuit_open_until_ms = Some(timestamp_ms() + wait.as_millis() as u64);
+            }
+            ConnectionEvent::Error(exchange, error) => {
+                self.feeds.entry(*exchange).or_default().last_error = Some(error.clone());
+            }
+        }
+    }
+
+    /// Status of every exchange seen so far, by exchange name.
+    pub fn snapshot(&self) -> Vec<(Exchange, FeedStatus)> {
+        let mut feeds: Vec<_> = self
+            .feeds
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+        feeds.sort_by_key(|(exchange, _)| format!("{:?}", exchange));
+        feeds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_feed_health_tracks_pooled_connections() {
+        let health = FeedHealth::new();
+        health.record_event(&ConnectionEvent::Connected(Exchange::Binance));
+        health.record_event(&ConnectionEvent::Connected(Exchange::Binance));
+        health.record_event(&ConnectionEvent::Disconnected(Exchange::Binance));
+        health.record_message(Exchange::Binance);
+
+        let snapshot = health.snapshot();
+        let (exchange, status) = &snapshot[0];
+        assert_eq!(*exchange, Exchange::Binance);
+        assert!(status.is_connected());
+        assert_eq!(status.disconnects, 1);
+        assert_eq!(status.messages, 1);
+        assert!(status.last_message_ms.is_some());
+
+        health.record_event(&ConnectionEvent::Disconnected(Exchange::Binance));
+        health.record_event(&ConnectionEvent::CircuitBreakerOpen(
+            Exchange::Binance,
+            Duration::from_secs(30),
+        ));
+        let (_, status) = &health.snapshot()[0];
+        assert!(!status.is_connected());
+        assert!(status.circuit_open_until_ms.is_some());
+
+        health.record_event(&ConnectionEvent::Reconnected(Exchange::Binance));
+        let (_, status) = &health.snapshot()[0];
+        assert!(status.is_connected());
+        assert_eq!(status.reconnects, 1);
+        assert_eq!(status.circuit_open_until_ms, None);
+    }
+}
@@ -6,6 +6,7 @@
 //! - State updates
 //! - Broadcasting to WebSocket clients
 //! - Status notifications
+//! - Feed health tracking
 
 use super::common::{convert_krw_to_usd_for_exchange, convert_stablecoin_to_usd_for_exchange};
 use super::FeedContext;
@@ -29,9 +30,11 @@ pub async fn run_feed_handler(mut rx: mpsc::Receiver<FeedMessage>, ctx: FeedCont
 
         match msg {
             FeedMessage::Tick(tick) => {
+                ctx.state.feed_health().record_message(tick.exchange());
                 process_tick(tick, &ctx).await;
             }
             FeedMessage::Event(event) => {
+                ctx.state.feed_health().record_event(&event);
                 process_event(event, &ctx);
             }
         }
@@ -95,8 +98,18 @@ async fn process_price_tick(
         _ => return, // Unsupported exchange
     };
 
+    // Drop markets of exchanges disabled or symbols excluded since they were
+    // subscribed (exchanges without unsubscribe keep streaming them)
+    if !ctx.state.config.read().await.is_exchange_enabled(exchange) {
+        return;
+    }
+    let symbol_mappings = ctx.state.symbol_mappings().await;
+    if symbol_mappings.is_excluded(exchange_name, symbol) {
+        return;
+    }
+
     // Use canonical name if mapping exists
-    let display_symbol = ctx.symbol_mappings.canonical_name(exchange_name, symbol);
+    let display_symbol = symbol_mappings.canonical_name(exchange_name, symbol);
     let pair_id = symbol_to_pair_id(&display_symbol);
     let quote_currency = QuoteCurrency::from_str(quote).unwrap_or(QuoteCurrency::USD);
 
@@ -183,9 +196,11 @@ async fn process_price_tick(
         &display_symbol,
         Some(quote),
         &tick,
-        Some(mid_usd.to_f64()),
-        Some(bid_usd.to_f64()),
-        Some(ask_usd.to_f64()),
+        Some(ws_server::UsdPrices {
+            price: mid_usd.to_f64(),
+            bid: bid_usd.to_f64(),
+            ask: ask_usd.to_f64(),
+        }),
     );
 }
 
@@ -199,9 +214,16 @@ fn process_stablecoin_rate(
 ) {
     let rate_f64 = rate.to_f64();
 
-    // Update exchange-specific stablecoin price
-    ctx.state
-        .update_exchange_stablecoin_price(exchange, stablecoin, quote, rate_f64);
+    // Update exchange-specific stablecoin price (also feeds the depeg monitor)
+    if let Some(event) = ctx
+        .state
+        .update_exchange_stablecoin_price(exchange, stablecoin, quote, rate_f64)
+    {
+        ws_server::broadcast_depeg(&ctx.broadcast_tx, &event);
+        if let Some(ref notifier) = ctx.status_notifier {
+            notifier.try_send(StatusEvent::Depeg(event));
+        }
+    }
 
     // Handle specific rate updates
     match (stablecoin, quote) {
@@ -242,6 +264,8 @@ fn process_event(event: ConnectionEvent, ctx: &FeedContext) {
         ConnectionEvent::Reconnected(exchange) => {
             info!("{:?}: Reconnected - clearing cached data", exchange);
             ctx.state.clear_exchange_caches(exchange);
+            let closed = ctx.state.close_exchange_opportunities(exchange);
+            ws_server::broadcast_opportunity_lifecycle(&ctx.broadcast_tx, &closed);
             if let Some(ref notifier) = ctx.status_notifier {
                 notifier.try_send(StatusEvent::Reconnected(exchange));
             }
@@ -249,6 +273,8 @@ fn process_event(event: ConnectionEvent, ctx: &FeedContext) {
         ConnectionEvent::Disconnected(exchange) => {
             warn!("{:?}: Disconnected - clearing caches", exchange);
             ctx.state.clear_exchange_caches(exchange);
+            let closed = ctx.state.close_exchange_opportunities(exchange);
+            ws_server::broadcast_opportunity_lifecycle(&ctx.broadcast_tx, &closed);
             if let Some(ref notifier) = ctx.status_notifier {
                 notifier.try_send(StatusEvent::Disconnected(exchange));
             }
@@ -21,8 +21,6 @@ pub mod handler;
 use crate::state::SharedState;
 use crate::status_notifier::StatusNotifierHandle;
 use crate::ws_server::BroadcastSender;
-use arbitrage_feeds::SymbolMappings;
-use std::sync::Arc;
 
 /// Shared context passed to all feed handlers.
 ///
@@ -32,7 +30,6 @@ use std::sync::Arc;
 pub struct FeedContext {
     pub state: SharedState,
     pub broadcast_tx: BroadcastSender,
-    pub symbol_mappings: Arc<SymbolMappings>,
     pub status_notifier: Option<StatusNotifierHandle>,
 }
 
@@ -41,13 +38,11 @@ impl FeedContext {
     pub fn new(
         state: SharedState,
         broadcast_tx: BroadcastSender,
-        symbol_mappings: Arc<SymbolMappings>,
         status_notifier: Option<StatusNotifierHandle>,
     ) -> Self {
         Self {
             state,
             broadcast_tx,
-            symbol_mappings,
             status_notifier,
         }
     }
@@ -0,0 +1,112 @@
+//! Gas fee sampling for the gas oracle.
+//!
+//! Polls each configured chain's JSON-RPC endpoint: `eth_feeHistory` on EVM
+//! chains, `getRecentPrioritizationFees` on Solana.
+
+use crate::config::{ChainRpcSettings, GasSettings};
+use crate::state::SharedState;
+use arbitrage_core::Chain;
+use arbitrage_engine::LAMPORTS_PER_SIGNATURE;
+use arbitrage_executor::{ExecutorResult, JsonRpcClient};
+use serde_json::json;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Sample fees from every configured endpoint into the state's gas oracle.
+pub async fn run_gas_oracle_updater(state: SharedState, settings: GasSettings) {
+    let clients: Vec<(Chain, JsonRpcClient)> = settings
+        .rpc_endpoints
+        .iter()
+        .filter_map(|ChainRpcSettings { chain, rpc_url }| {
+            match JsonRpcClient::new(rpc_url.clone(), Duration::from_secs(10)) {
+                Ok(client) => Some((*chain, client)),
+                Err(e) => {
+                    warn!(chain = chain.as_str(), error = %e, "Gas oracle: invalid RPC endpoint");
+                    None
+                }
+            }
+        })
+        .collect();
+    if clients.is_empty() {
+        return;
+    }
+    info!(chains = clients.len(), "Gas oracle sampling started");
+
+    let mut interval = tokio::time::interval(Duration::from_millis(settings.poll_interval_ms));
+    loop {
+        interval.tick().await;
+        for (chain, client) in &clients {
+            match sample_fees(*chain, client, settings.priority_fee_percentile).await {
+                Ok((base_fee, priority_fee)) => {
+                    debug!(
+                        chain = chain.as_str(),
+                        base_fee, priority_fee, "Gas fees sampled"
+                    );
+                    state.gas_oracle().record(
+                        *chain,
+                        base_fee,
+                        priority_fee,
+                        crate::wallet_status::timestamp_ms(),
+                    );
+                }
+                Err(e) => warn!(chain = chain.as_str(), error = %e, "Gas fee sampling failed"),
+            }
+        }
+    }
+}
+
+/// (base fee, priority fee) in the units of [`arbitrage_engine::GasPrice`].
+async fn sample_fees(
+    chain: Chain,
+    client: &JsonRpcClient,
+    percentile: f64,
+) -> ExecutorResult<(u128, u128)> {
+    if chain == Chain::Solana {
+        let result = client
+            .request("getRecentPrioritizationFees", json!([]))
+            .await?;
+        let fees: Vec<u128> = result
+            .as_array()
+            .map(|slots| {
+                slots
+                    .iter()
+                    .filter_map(|slot| slot.get("prioritizationFee")?.as_u64())
+                    .map(u128::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        return Ok((LAMPORTS_PER_SIGNATURE, percentile_of(fees, percentile)));
+    }
+
+    let history = client.fee_history(1, &[percentile]).await?;
+    let base_fee = history.next_base_fee().unwrap_or(0);
+    let priority_fee = history
+        .reward
+        .last()
+        .and_then(|rewards| rewards.first())
+        .copied()
+        .unwrap_or(0);
+    Ok((base_fee, priority_fee))
+}
+
+fn percentile_of(mut values: Vec<u128>, percentile: f64) -> u128 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let rank = percentile.clamp(0.0, 100.0) / 100.0 * (values.len() - 1) as f64;
+    values[rank.round() as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_of() {
+        assert_eq!(percentile_of(vec![], 50.0), 0);
+        assert_eq!(percentile_of(vec![40, 10, 30, 20, 50], 50.0), 30);
+        assert_eq!(percentile_of(vec![40, 10, 30, 20, 50], 100.0), 50);
+        assert_eq!(percentile_of(vec![40, 10, 30, 20, 50], 0.0), 10);
+    }
+}
@@ -2,83 +2,155 @@
 //!
 //! A high-performance cryptocurrency arbitrage detection and execution bot.
 
+mod auth;
 mod config;
+mod config_reload;
+mod control;
+mod detection_scheduler;
 mod exchange_rate;
+mod feed_health;
 mod feeds;
+mod gas_oracle;
+mod rest_api;
 mod state;
 mod status_notifier;
+mod trading_fees;
 mod wallet_status;
 mod ws_server;
+mod ws_subscription;
 
 use status_notifier::{StatusEvent, StatusNotifierHandle};
 
 use clap::Parser;
-use config::{AppConfig, ExecutionMode};
+use config::{
+    AppConfig, ConfigError, ConfigFormat, ExecutionMode, DEFAULT_CONFIG_PATHS, LIVE_EXCHANGES,
+};
 use state::{create_state, PriceUpdateEvent, SharedState};
+use std::path::PathBuf;
 use std::time::Duration;
 use tracing::{debug, info, warn};
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-use arbitrage_alerts::{Database, Notifier, NotifierConfig, TelegramBot};
+use arbitrage_alerts::{
+    Database, KimchiIndexRecord, Notifier, NotifierConfig, OpportunityEpisodeRecord, TelegramBot,
+};
 
-use arbitrage_core::{Exchange, FixedPoint, PriceTick, QuoteCurrency};
-use arbitrage_engine::ConversionRates;
+use arbitrage_core::{
+    Exchange, FixedPoint, PairRegistry, PairRegistrySnapshot, PriceTick, QuoteCurrency,
+};
 use arbitrage_feeds::{
-    load_mappings, runner as feed_runner, BinanceAdapter, BinanceConnectionPool, BinanceRestFetcher,
-    BithumbAdapter, BithumbRestFetcher, BithumbSubscriptionBuilder, BybitAdapter, BybitRestFetcher,
-    BybitSubscriptionBuilder, CoinbaseAdapter, CoinbaseConnectionPool, CoinbaseCredentials,
-    CoinbaseRestFetcher, ExchangeAdapter, FeedConfig, FeedMessage, GateIOAdapter, GateIORestFetcher,
-    GateIOSubscriptionBuilder, MarketDiscovery, SubscriptionManager, SymbolMappings, UpbitAdapter,
-    UpbitRestFetcher, UpbitSubscriptionBuilder, WsClient,
+    get_mappings_path, load_mappings, runner as feed_runner, BinanceAdapter, BinanceConnectionPool,
+    BinanceRestFetcher, BithumbAdapter, BithumbRestFetcher, BithumbSubscriptionBuilder,
+    BybitAdapter, BybitRestFetcher, BybitSubscriptionBuilder, CoinbaseAdapter,
+    CoinbaseConnectionPool, CoinbaseCredentials, CoinbaseRestFetcher, ExchangeAdapter, FeedConfig,
+    FeedMessage, GateIOAdapter, GateIORestFetcher, GateIOSubscriptionBuilder, MarketDiscovery,
+    SubscriptionManager, SymbolMappings, UpbitAdapter, UpbitRestFetcher, UpbitSubscriptionBuilder,
+    WsClient,
 };
 use feeds::common::{
     convert_stablecoin_to_usd_for_exchange, extract_binance_base_quote, extract_bybit_base_quote,
 };
 use feeds::FeedContext;
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use ws_server::BroadcastSender;
+use tokio::sync::{mpsc, Notify};
+use ws_server::{BroadcastSender, UsdPrices};
 
 /// Arbitrage Bot CLI
+///
+/// Flags override the config file and `ARBITRAGE_*` environment variables.
 #[derive(Parser, Debug)]
 #[command(name = "arbitrage-bot")]
 #[command(about = "High-performance crypto arbitrage bot", long_about = None)]
 struct Args {
-    /// Configuration file path
-    #[arg(short, long, default_value = "config.json")]
-    config: String,
+    /// Configuration file path, JSON or TOML (default: config.json or
+    /// config.toml if present)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Print the effective configuration (json or toml) and exit
+    #[arg(long, value_name = "FORMAT", num_args = 0..=1, default_missing_value = "json")]
+    dump_config: Option<ConfigFormat>,
 
     /// Minimum premium in basis points
-    #[arg(short = 'p', long, default_value_t = 30)]
-    min_premium: i32,
+    #[arg(short = 'p', long)]
+    min_premium: Option<i32>,
 
     /// Execution mode: auto, manual, alert
-    #[arg(short, long, default_value = "alert")]
-    mode: String,
+    #[arg(short, long)]
+    mode: Option<String>,
 
     /// Log level: trace, debug, info, warn, error
-    #[arg(short, long, default_value = "info")]
-    log_level: String,
+    #[arg(short, long)]
+    log_level: Option<String>,
 
     /// Dry run (no actual trades)
     #[arg(long, default_value_t = true)]
     dry_run: bool,
 
     /// Use live WebSocket feeds instead of simulator
-    #[arg(long, default_value_t = false)]
+    #[arg(long)]
     live: bool,
 
     /// WebSocket server port for clients (Tauri app)
-    #[arg(long, default_value_t = 9001)]
-    ws_port: u16,
+    #[arg(long)]
+    ws_port: Option<u16>,
 
     /// Enable Telegram alerts (requires TELEGRAM_BOT_TOKEN env var)
-    #[arg(long, default_value_t = false)]
+    #[arg(long)]
     telegram: bool,
 
-    /// SQLite database path for alert configuration
-    #[arg(long, default_value = "data/alerts.db")]
-    db_path: String,
+    /// SQLite database path for alert configuration and kimchi index history
+    #[arg(long)]
+    db_path: Option<String>,
+
+    /// Pair ID registry path (keeps pair IDs stable across restarts)
+    #[arg(long)]
+    pair_registry_path: Option<String>,
+}
+
+impl Args {
+    /// Apply the flags given on the command line over `config`.
+    fn apply(&self, config: &mut AppConfig) {
+        if let Some(min_premium) = self.min_premium {
+            config.detector.min_premium_bps = min_premium;
+        }
+        if let Some(mode) = &self.mode {
+            config.execution.mode = parse_mode(mode);
+        }
+        if let Some(log_level) = &self.log_level {
+            config.log_level = log_level.clone();
+        }
+        if self.live {
+            config.server.live = true;
+        }
+        if let Some(ws_port) = self.ws_port {
+            config.server.ws_port = ws_port;
+        }
+        if self.telegram {
+            config.alerts.telegram = true;
+        }
+        if let Some(db_path) = &self.db_path {
+            config.alerts.db_path = db_path.clone();
+        }
+        if let Some(path) = &self.pair_registry_path {
+            config.server.pair_registry_path = path.clone();
+        }
+    }
+}
+
+/// Load the layered configuration (defaults, file, environment, flags) and
+/// validate it.
+fn load_config(args: &Args) -> Result<AppConfig, ConfigError> {
+    let path = args.config.clone().or_else(|| {
+        DEFAULT_CONFIG_PATHS
+            .iter()
+            .map(PathBuf::from)
+            .find(|path| path.exists())
+    });
+    let mut config = AppConfig::load(path.as_deref(), std::env::vars())?;
+    args.apply(&mut config);
+    config.validate()?;
+    Ok(config)
 }
 
 fn init_logging(level: &str) {
@@ -126,138 +198,193 @@ fn broadcast_premium_matrix_for_pair(
     pair_id: u32,
     symbol: &str,
 ) {
-    // Get conversion rates from state
-    let usdt_krw = state.get_upbit_usdt_krw().map(|p| p.to_f64());
-    let usdc_krw = state.get_upbit_usdc_krw().map(|p| p.to_f64());
-    let usd_krw = exchange_rate::get_api_rate().or(usdt_krw);
-    let bithumb_usdt_krw = state.get_bithumb_usdt_krw().map(|p| p.to_f64());
-    let bithumb_usdc_krw = state.get_bithumb_usdc_krw().map(|p| p.to_f64());
-
-    let rates = ConversionRates {
-        usdt_usd: state.get_usdt_usd_price().to_f64(),
-        usdc_usd: state.get_usdc_usd_price().to_f64(),
-        usd_krw: usd_krw.unwrap_or(0.0),
-        upbit_usdt_krw: usdt_krw.unwrap_or(0.0),
-        upbit_usdc_krw: usdc_krw.unwrap_or(0.0),
-        bithumb_usdt_krw: bithumb_usdt_krw.unwrap_or(0.0),
-        bithumb_usdc_krw: bithumb_usdc_krw.unwrap_or(0.0),
+    let Some(matrix) = ws_server::collect_premium_matrix(state, pair_id, symbol) else {
+        return;
     };
 
-    // Get the premium matrix for this pair
-    if let Some(matrix) = state.detector.get_matrix(pair_id) {
-        let premiums = matrix.all_premiums_multi_denomination(&rates);
-
-        // Log conversion rates and entry count for KRW debugging
-        let krw_entries: Vec<_> = premiums
-            .iter()
-            .filter(|p| p.2 == QuoteCurrency::KRW || p.3 == QuoteCurrency::KRW)
-            .collect();
-        if !krw_entries.is_empty() {
+    // Log entry count for KRW debugging
+    let krw_entries: Vec<_> = matrix
+        .entries
+        .iter()
+        .filter(|e| e.buy_quote == "KRW" || e.sell_quote == "KRW")
+        .collect();
+    if !krw_entries.is_empty() {
+        let rates = state.conversion_rates();
+        tracing::debug!(
+            symbol = symbol,
+            total_entries = matrix.entries.len(),
+            krw_entries = krw_entries.len(),
+            upbit_usdt_krw = rates.upbit_usdt_krw,
+            bithumb_usdt_krw = rates.bithumb_usdt_krw,
+            usd_krw = rates.usd_krw,
+            "Premium matrix for symbol with KRW markets"
+        );
+        // Log first few KRW entries for debugging
+        for (i, e) in krw_entries.iter().take(2).enumerate() {
             tracing::debug!(
-                symbol = symbol,
-                total_entries = premiums.len(),
-                krw_entries = krw_entries.len(),
-                upbit_usdt_krw = rates.upbit_usdt_krw,
-                bithumb_usdt_krw = rates.bithumb_usdt_krw,
-                usd_krw = rates.usd_krw,
-                "Premium matrix for symbol with KRW markets"
+                i = i,
+                buy_ex = %e.buy_exchange,
+                sell_ex = %e.sell_exchange,
+                buy_quote = %e.buy_quote,
+                sell_quote = %e.sell_quote,
+                tether_bps = e.tether_premium_bps,
+                kimchi_bps = e.kimchi_premium_bps,
+                "KRW entry details"
             );
-            // Log first few KRW entries for debugging
-            for (i, e) in krw_entries.iter().take(2).enumerate() {
-                tracing::debug!(
-                    i = i,
-                    buy_ex = ?e.0,
-                    sell_ex = ?e.1,
-                    buy_quote = ?e.2,
-                    sell_quote = ?e.3,
-                    tether_bps = e.10,
-                    kimchi_bps = e.12,
-                    "KRW entry details"
-                );
-            }
         }
-
-        // Convert to WsPremiumEntry
-        // Use Display format for quote currency (e.g., "KRW", "USDT")
-        // Use Debug format for exchange (e.g., "Binance", "Upbit")
-        let entries: Vec<ws_server::WsPremiumEntry> = premiums
-            .iter()
-            .map(|p| ws_server::WsPremiumEntry {
-                buy_exchange: format!("{:?}", p.0),
-                sell_exchange: format!("{:?}", p.1),
-                buy_quote: p.2.as_str().to_string(),
-                sell_quote: p.3.as_str().to_string(),
-                tether_premium_bps: p.10, // usdlike_premium_bps
-                kimchi_premium_bps: p.12, // kimchi_premium
-            })
-            .collect();
-
-        ws_server::broadcast_premium_matrix(broadcast_tx, symbol, pair_id, entries);
     }
+
+    ws_server::broadcast_premium_matrix(broadcast_tx, symbol, pair_id, matrix.entries);
 }
 
 /// Event-driven opportunity detector.
 ///
-/// Reacts immediately to price update events from feed handlers.
-/// Only detects opportunities for the pair that had a price update.
+/// Price update events from feed handlers mark their pair dirty in the
+/// detection scheduler, so a burst of updates for one pair runs detection once
+/// and the channel never backs up behind slow detections. Worker tasks detect
+/// the dirty pairs, those nearest the premium threshold first.
 async fn run_event_driven_detector(
     state: SharedState,
     broadcast_tx: BroadcastSender,
     notifier: Option<Arc<Notifier>>,
     mut price_rx: mpsc::Receiver<PriceUpdateEvent>,
 ) {
-    debug!("Starting event-driven detector");
+    let (workers, scan_interval_ms) = {
+        let config = state.config.read().await;
+        let workers = match config.detector.detection_workers {
+            0 => detection_scheduler::default_worker_count(),
+            n => n,
+        };
+        (workers, config.detector.scan_interval_ms)
+    };
+    debug!(workers, scan_interval_ms, "Starting event-driven detector");
+
+    let worker_handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let state = state.clone();
+            let broadcast_tx = broadcast_tx.clone();
+            let notifier = notifier.clone();
+            tokio::spawn(async move { run_detection_worker(state, broadcast_tx, notifier).await })
+        })
+        .collect();
+
+    // Periodic full re-scan on top of price events (disabled at 0)
+    let mut scan = tokio::time::interval(Duration::from_millis(scan_interval_ms.max(1)));
+    scan.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
     while state.is_running() {
-        match price_rx.recv().await {
-            Some(event) => {
-                // Immediately detect opportunities for this pair
-                let opps = state.detect_opportunities(event.pair_id).await;
-
-                // Broadcast each opportunity
-                for opp in &opps {
-                    tracing::debug!(
-                        "🎯 Opportunity: {} {:?} -> {:?} | Premium: {} bps | Buy: {} | Sell: {}",
-                        opp.asset.symbol,
-                        opp.source_exchange,
-                        opp.target_exchange,
-                        opp.premium_bps,
-                        opp.source_price,
-                        opp.target_price
-                    );
-                    ws_server::broadcast_opportunity(&broadcast_tx, &state, opp);
+        tokio::select! {
+            event = price_rx.recv() => match event {
+                Some(event) => state.detection_scheduler().mark_dirty(event),
+                None => {
+                    // Channel closed, exit
+                    break;
                 }
-
-                // Broadcast premium matrix for this symbol (all exchange pairs)
-                broadcast_premium_matrix_for_pair(
-                    &state,
-                    &broadcast_tx,
-                    event.pair_id,
-                    &event.symbol,
+            },
+            _ = scan.tick(), if scan_interval_ms > 0 => {
+                let registry = PairRegistry::global();
+                state.detection_scheduler().rescan(
+                    state
+                        .get_registered_pair_ids()
+                        .into_iter()
+                        .filter_map(|pair_id| Some((pair_id, registry.symbol(pair_id)?))),
                 );
+            }
+        }
+    }
 
-                // Send Telegram alerts
-                if let Some(ref notifier) = notifier {
-                    for opp in &opps {
-                        if let Err(e) = notifier.process_opportunity(opp).await {
-                            tracing::warn!("Failed to send Telegram alert: {}", e);
-                        }
-                    }
+    state.detection_scheduler().close();
+    for handle in worker_handles {
+        let _ = handle.await;
+    }
+    debug!("Event-driven detector stopped");
+}
 
-                    // Clear opportunities that fell below threshold
-                    if let Err(e) = notifier.clear_missing_opportunities(&opps).await {
-                        tracing::warn!("Failed to clear missing opportunities: {}", e);
-                    }
+/// Detect dirty pairs from the scheduler until it closes.
+async fn run_detection_worker(
+    state: SharedState,
+    broadcast_tx: BroadcastSender,
+    notifier: Option<Arc<Notifier>>,
+) {
+    let scheduler = state.detection_scheduler();
+    loop {
+        // While paused, pairs stay dirty in the scheduler until resume
+        tokio::select! {
+            _ = state.control().wait_until_running() => {}
+            _ = scheduler.closed() => break,
+        }
+        let Some(pair) = scheduler.next().await else {
+            break;
+        };
+        let control = state.control();
+        if control.is_paused() {
+            scheduler.release(pair);
+            continue;
+        }
+        // Disabled symbols report nothing, which closes their open episodes
+        let mut opps = if control.detects(&pair.symbol) {
+            state.detect_opportunities(pair.pair_id).await
+        } else {
+            Vec::new()
+        };
+
+        // Stable ids per route; announce opened/new-peak/closed episodes
+        let lifecycle = state.track_opportunities(pair.pair_id, &mut opps);
+        ws_server::broadcast_opportunity_lifecycle(&broadcast_tx, &lifecycle);
+        control.expire(&lifecycle);
+        control::queue_for_approval(&state, &broadcast_tx, &opps).await;
+
+        // Broadcast each opportunity
+        for opp in &opps {
+            tracing::debug!(
+                "🎯 Opportunity: {} {:?} -> {:?} | Premium: {} bps | Buy: {} | Sell: {}",
+                opp.asset.symbol,
+                opp.source_exchange,
+                opp.target_exchange,
+                opp.premium_bps,
+                opp.source_price,
+                opp.target_price
+            );
+            ws_server::broadcast_opportunity(&broadcast_tx, &state, opp);
+        }
+
+        // Broadcast premium matrix for this symbol (all exchange pairs)
+        broadcast_premium_matrix_for_pair(&state, &broadcast_tx, pair.pair_id, &pair.symbol);
+
+        // Send Telegram alerts
+        if let Some(ref notifier) = notifier {
+            for opp in &opps {
+                if let Err(e) = notifier.process_opportunity(opp).await {
+                    tracing::warn!("Failed to send Telegram alert: {}", e);
                 }
             }
-            None => {
-                // Channel closed, exit
-                break;
+
+            // Clear opportunities that fell below threshold
+            if let Err(e) = notifier.clear_missing_opportunities(&opps).await {
+                tracing::warn!("Failed to clear missing opportunities: {}", e);
             }
         }
-    }
 
-    debug!("Event-driven detector stopped");
+        // Best premium across exchange pairs sets the pair's priority next time
+        let best_premium_bps = state
+            .detector
+            .get_matrix(pair.pair_id)
+            .and_then(|matrix| matrix.best_opportunity())
+            .map(|(_, _, bps)| bps)
+            .into_iter()
+            .chain(opps.iter().map(|opp| opp.premium_bps))
+            .max();
+        let threshold_bps = state.config.read().await.detector.min_premium_bps;
+        if pair.since.elapsed() > detection_scheduler::SLOW_DETECTION {
+            debug!(
+                pair_id = pair.pair_id,
+                updates = pair.updates,
+                latency_ms = pair.since.elapsed().as_millis() as u64,
+                "Detection lagging behind price updates"
+            );
+        }
+        scheduler.complete(&pair, best_premium_bps, threshold_bps);
+    }
 }
 
 async fn run_price_simulator(state: SharedState, broadcast_tx: BroadcastSender) {
@@ -278,8 +405,8 @@ async fn run_price_simulator(state: SharedState, broadcast_tx: BroadcastSender)
 
     while state.is_running() {
         for (idx, base_price) in base_prices.iter_mut().enumerate() {
-            let pair_id = (idx + 1) as u32;
             let symbol = symbols[idx];
+            let pair_id = arbitrage_core::symbol_to_pair_id(symbol);
 
             for (i, &exchange) in exchanges.iter().enumerate() {
                 // Add some variance per exchange
@@ -325,18 +452,242 @@ async fn run_stats_reporter(state: SharedState, broadcast_tx: BroadcastSender) {
         }
 
         let summary = state.stats_summary();
+        let scheduler = state.detection_scheduler().snapshot();
         info!(
-            "📊 Stats | Uptime: {}s | Prices: {} | Opportunities: {} | Trades: {}",
+            "📊 Stats | Uptime: {}s | Prices: {} | Opportunities: {} | Trades: {} | Detection queue: {} (avg {:.1}ms, max {:.1}ms, {} coalesced)",
             summary.uptime_secs,
             summary.price_updates,
             summary.opportunities_detected,
-            summary.trades_executed
+            summary.trades_executed,
+            scheduler.queue_depth,
+            scheduler.avg_latency_ms,
+            scheduler.max_latency_ms,
+            scheduler.coalesced()
         );
         // Broadcast stats to clients
         ws_server::broadcast_stats(&broadcast_tx, &state);
     }
 }
 
+/// Restore persisted pair IDs into the global pair registry.
+fn load_pair_registry(path: &str) {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            warn!("Failed to read pair registry {}: {}", path, e);
+            return;
+        }
+    };
+
+    match serde_json::from_str::<PairRegistrySnapshot>(&json) {
+        Ok(snapshot) => {
+            // Keep every ID that can be kept; conflicting entries are reported, not applied
+            let result = PairRegistry::global().restore_partial(&snapshot);
+            for conflict in &result.conflicts {
+                warn!("Pair registry entry not restored: {}", conflict);
+            }
+            info!(
+                "  Pair Registry: {} pair IDs restored, {} conflicts",
+                result.restored,
+                result.conflicts.len()
+            );
+        }
+        Err(e) => warn!("Failed to parse pair registry {}: {}", path, e),
+    }
+}
+
+/// Save the global pair registry. Returns true on success.
+fn save_pair_registry(path: &str) -> bool {
+    let path = std::path::Path::new(path);
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() && !dir.exists() {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                warn!("Failed to create data directory: {}", e);
+                return false;
+            }
+        }
+    }
+
+    let snapshot = PairRegistry::global().snapshot();
+    let json = match serde_json::to_string_pretty(&snapshot) {
+        Ok(json) => json,
+        Err(e) => {
+            warn!("Failed to serialize pair registry: {}", e);
+            return false;
+        }
+    };
+
+    // Write to a temp file first so a crash never leaves a truncated registry
+    let tmp_path = path.with_extension("json.tmp");
+    if let Err(e) = std::fs::write(&tmp_path, json).and_then(|_| std::fs::rename(&tmp_path, path)) {
+        warn!("Failed to save pair registry {}: {}", path.display(), e);
+        return false;
+    }
+    debug!("Saved {} pair IDs", snapshot.pairs.len());
+    true
+}
+
+/// Open the SQLite database, creating its directory if needed.
+async fn open_database(db_path: &str) -> Option<Database> {
+    if let Some(dir) = std::path::Path::new(db_path).parent() {
+        if !dir.exists() {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                warn!("Failed to create data directory: {}", e);
+            }
+        }
+    }
+
+    let db_url = format!("sqlite:{}", db_path);
+    match Database::connect(&db_url).await {
+        Ok(db) => Some(db),
+        Err(e) => {
+            warn!("Failed to connect to database: {}", e);
+            None
+        }
+    }
+}
+
+/// Kimchi index broadcast interval.
+const KIMCHI_INDEX_INTERVAL: Duration = Duration::from_secs(5);
+/// Kimchi index persistence interval.
+const KIMCHI_INDEX_PERSIST_INTERVAL_MS: u64 = 60_000;
+/// Kimchi index history retention.
+const KIMCHI_INDEX_RETENTION_DAYS: u64 = 30;
+
+/// Periodically compute, broadcast and persist the market-wide kimchi premium index.
+async fn run_kimchi_index_reporter(
+    state: SharedState,
+    broadcast_tx: BroadcastSender,
+    db: Option<Database>,
+) {
+    debug!("Starting kimchi index reporter");
+    let mut last_persisted_ms = 0u64;
+
+    while state.is_running() {
+        tokio::time::sleep(KIMCHI_INDEX_INTERVAL).await;
+
+        let Some(index) = state.update_kimchi_index().await else {
+            continue;
+        };
+        ws_server::broadcast_kimchi_index(&broadcast_tx, &index);
+
+        let Some(db) = db.as_ref() else {
+            continue;
+        };
+        if index.timestamp_ms.saturating_sub(last_persisted_ms) < KIMCHI_INDEX_PERSIST_INTERVAL_MS {
+            continue;
+        }
+        last_persisted_ms = index.timestamp_ms;
+
+        let records: Vec<KimchiIndexRecord> = std::iter::once(KimchiIndexRecord {
+            exchange: "ALL".to_string(),
+            index_bps: index.index_bps,
+            markets: index.markets as i64,
+            weight_usd: index.exchanges.iter().map(|e| e.weight_usd).sum(),
+            timestamp_ms: index.timestamp_ms as i64,
+        })
+        .chain(index.exchanges.iter().map(|e| KimchiIndexRecord {
+            exchange: format!("{:?}", e.exchange),
+            index_bps: e.index_bps,
+            markets: e.markets as i64,
+            weight_usd: e.weight_usd,
+            timestamp_ms: index.timestamp_ms as i64,
+        }))
+        .collect();
+        if let Err(e) = db.record_kimchi_index(&records).await {
+            warn!("Failed to persist kimchi index: {}", e);
+            continue;
+        }
+
+        let retention_ms = KIMCHI_INDEX_RETENTION_DAYS * 24 * 60 * 60 * 1000;
+        let cutoff = index.timestamp_ms.saturating_sub(retention_ms) as i64;
+        if let Err(e) = db.cleanup_kimchi_index_history(cutoff).await {
+            warn!("Failed to clean up kimchi index history: {}", e);
+        }
+    }
+
+    info!("Kimchi index reporter stopped");
+}
+
+/// Opportunity lifecycle sweep interval (stale episodes, persistence).
+const OPPORTUNITY_LIFECYCLE_INTERVAL: Duration = Duration::from_secs(5);
+/// Opportunity episode history retention.
+const OPPORTUNITY_EPISODE_RETENTION_DAYS: u64 = 30;
+
+/// Close stale opportunity episodes and persist closed ones.
+async fn run_opportunity_lifecycle_reporter(
+    state: SharedState,
+    broadcast_tx: BroadcastSender,
+    db: Option<Database>,
+) {
+    debug!("Starting opportunity lifecycle reporter");
+
+    while state.is_running() {
+        tokio::time::sleep(OPPORTUNITY_LIFECYCLE_INTERVAL).await;
+
+        let now_ms = wallet_status::timestamp_ms();
+        let expired = state.opportunity_lifecycle().expire(now_ms);
+        ws_server::broadcast_opportunity_lifecycle(&broadcast_tx, &expired);
+        state.control().expire(&expired);
+
+        let Some(db) = db.as_ref() else {
+            // Nothing to persist to; keep the closed buffer from growing
+            state.opportunity_lifecycle().drain_closed();
+            continue;
+        };
+        persist_opportunity_episodes(&state, db).await;
+
+        let retention_ms = OPPORTUNITY_EPISODE_RETENTION_DAYS * 24 * 60 * 60 * 1000;
+        let cutoff = now_ms.saturating_sub(retention_ms) as i64;
+        if let Err(e) = db.cleanup_opportunity_episodes(cutoff).await {
+            warn!("Failed to clean up opportunity episodes: {}", e);
+        }
+    }
+
+    info!("Opportunity lifecycle reporter stopped");
+}
+
+/// Write episodes closed since the last call to the database.
+/// Episodes that fail to write are requeued for the next call.
+async fn persist_opportunity_episodes(state: &SharedState, db: &Database) {
+    let episodes = state.opportunity_lifecycle().drain_closed();
+    if episodes.is_empty() {
+        return;
+    }
+    let records: Vec<OpportunityEpisodeRecord> = episodes
+        .iter()
+        .map(|episode| OpportunityEpisodeRecord {
+            episode_id: episode.id as i64,
+            symbol: episode.symbol.clone(),
+            buy_exchange: format!("{:?}", episode.buy_exchange),
+            sell_exchange: format!("{:?}", episode.sell_exchange),
+            buy_quote: episode.buy_quote.as_str().to_string(),
+            sell_quote: episode.sell_quote.as_str().to_string(),
+            first_seen_ms: episode.first_seen_ms as i64,
+            closed_at_ms: episode.closed_at_ms.unwrap_or(episode.last_seen_ms) as i64,
+            duration_ms: episode.duration_ms() as i64,
+            peak_premium_bps: episode.peak_premium_bps as i64,
+            last_premium_bps: episode.last_premium_bps as i64,
+            time_above_threshold_ms: episode.time_above_threshold_ms as i64,
+            observations: episode.observations as i64,
+            close_reason: episode
+                .close_reason
+                .map(|reason| reason.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        })
+        .collect();
+    if let Err(e) = db.record_opportunity_episodes(&records).await {
+        warn!(
+            "Failed to persist {} opportunity episodes, retrying later: {}",
+            records.len(),
+            e
+        );
+        state.opportunity_lifecycle().requeue_closed(episodes);
+    }
+}
+
 /// Fetch initial orderbooks via REST API and populate state.
 /// Fetches from all exchanges with REST APIs and broadcasts to connected clients.
 async fn fetch_initial_orderbooks(
@@ -454,9 +805,11 @@ async fn fetch_initial_orderbooks(
                 &display_symbol,
                 Some(&quote),
                 &tick,
-                Some(mid_usd.to_f64()),
-                Some(bid_usd.to_f64()),
-                Some(ask_usd.to_f64()),
+                Some(UsdPrices {
+                    price: mid_usd.to_f64(),
+                    bid: bid_usd.to_f64(),
+                    ask: ask_usd.to_f64(),
+                }),
             );
             total_updated += 1;
         }
@@ -542,9 +895,11 @@ async fn fetch_initial_orderbooks(
                 &display_symbol,
                 Some(&quote),
                 &tick,
-                Some(mid_usd.to_f64()),
-                Some(bid_usd.to_f64()),
-                Some(ask_usd.to_f64()),
+                Some(UsdPrices {
+                    price: mid_usd.to_f64(),
+                    bid: bid_usd.to_f64(),
+                    ask: ask_usd.to_f64(),
+                }),
             );
             total_updated += 1;
         }
@@ -624,9 +979,11 @@ async fn fetch_initial_orderbooks(
                 &display_symbol,
                 Some(&quote),
                 &tick,
-                Some(mid_usd.to_f64()),
-                Some(bid_usd.to_f64()),
-                Some(ask_usd.to_f64()),
+                Some(UsdPrices {
+                    price: mid_usd.to_f64(),
+                    bid: bid_usd.to_f64(),
+                    ask: ask_usd.to_f64(),
+                }),
             );
             total_updated += 1;
         }
@@ -715,9 +1072,11 @@ async fn fetch_initial_orderbooks(
                     &display_symbol,
                     Some("KRW"),
                     &tick,
-                    Some(mid_price_usd),
-                    Some(bid_usd),
-                    Some(ask_usd),
+                    Some(UsdPrices {
+                        price: mid_price_usd,
+                        bid: bid_usd,
+                        ask: ask_usd,
+                    }),
                 );
             } else {
                 // No exchange rate available yet, store raw KRW prices
@@ -757,8 +1116,6 @@ async fn fetch_initial_orderbooks(
                     Some("KRW"),
                     &tick,
                     None,
-                    None,
-                    None,
                 );
                 debug!(
                     "  Upbit: {} stored as KRW (no exchange rate yet)",
@@ -846,9 +1203,11 @@ async fn fetch_initial_orderbooks(
                 &display_symbol,
                 Some("KRW"),
                 &tick,
-                Some(mid_price_usd),
-                Some(bid_usd),
-                Some(ask_usd),
+                Some(UsdPrices {
+                    price: mid_price_usd,
+                    bid: bid_usd,
+                    ask: ask_usd,
+                }),
             );
         }
         // Note: If USDT/KRW rate is not available, we skip this symbol.
@@ -928,9 +1287,11 @@ async fn fetch_initial_orderbooks(
                 base,
                 Some(quote),
                 &tick,
-                Some(mid_usd.to_f64()),
-                Some(bid_usd.to_f64()),
-                Some(ask_usd.to_f64()),
+                Some(UsdPrices {
+                    price: mid_usd.to_f64(),
+                    bid: bid_usd.to_f64(),
+                    ask: ask_usd.to_f64(),
+                }),
             );
             total_updated += 1;
             debug!(
@@ -964,9 +1325,10 @@ async fn spawn_live_feeds(
     let discovery = MarketDiscovery::new();
     let all_markets = discovery.fetch_all().await;
 
-    // Find common markets across exchanges we support for live feeds
+    // Find common markets across the enabled live feed exchanges
     // Apply symbol mappings to exclude mismatched symbols
-    let exchanges = ["Binance", "Coinbase", "Upbit", "Bithumb", "Bybit", "GateIO"];
+    let config = state.config.read().await.clone();
+    let exchanges = config.live_exchange_names();
     let common = MarketDiscovery::find_markets_on_n_exchanges_with_mappings(
         &all_markets,
         &exchanges,
@@ -990,15 +1352,23 @@ async fn spawn_live_feeds(
     let mut gateio_set: HashSet<String> = HashSet::new();
 
     // Always include USDT and USDC for exchange rate calculation
-    upbit_set.insert("KRW-USDT".to_string());
-    upbit_set.insert("KRW-USDC".to_string());
-    bithumb_set.insert("KRW-USDT".to_string());
-    bithumb_set.insert("KRW-USDC".to_string());
+    if config.is_exchange_enabled(Exchange::Upbit) {
+        upbit_set.insert("KRW-USDT".to_string());
+        upbit_set.insert("KRW-USDC".to_string());
+    }
+    if config.is_exchange_enabled(Exchange::Bithumb) {
+        bithumb_set.insert("KRW-USDT".to_string());
+        bithumb_set.insert("KRW-USDC".to_string());
+    }
 
     // Use by_quote to get all quote variants (USDT, USDC, KRW)
     // by_quote already filters to markets on 2+ exchanges
     for (key, exchange_markets) in &common.by_quote {
         for (exchange, market_info) in exchange_markets {
+            // Per-exchange pair lists restrict the subscribed markets
+            if !config.monitors_market(exchange, key) {
+                continue;
+            }
             match exchange.as_str() {
                 "Binance" => {
                     binance_set.insert(market_info.symbol.to_lowercase());
@@ -1065,23 +1435,31 @@ async fn spawn_live_feeds(
     // Fetch initial orderbooks via REST API before WebSocket feeds start
     // Only batch-capable exchanges: Binance, Bybit, GateIO, Upbit
     // Coinbase and Bithumb rely on WebSocket for initial data
-    // Include stablecoin symbols for initial price fetch
+    // Include stablecoin symbols for initial price fetch (exchanges with feeds only)
     let mut binance_symbols_with_stablecoins = binance_symbols.clone();
-    // Binance stablecoin pairs: USDT/USD, USDC/USDT, USDC/USD
-    binance_symbols_with_stablecoins.push("USDTUSD".to_string());
-    binance_symbols_with_stablecoins.push("USDCUSDT".to_string());
-    binance_symbols_with_stablecoins.push("USDCUSD".to_string());
+    if !binance_symbols.is_empty() {
+        // Binance stablecoin pairs: USDT/USD, USDC/USDT, USDC/USD
+        binance_symbols_with_stablecoins.push("USDTUSD".to_string());
+        binance_symbols_with_stablecoins.push("USDCUSDT".to_string());
+        binance_symbols_with_stablecoins.push("USDCUSD".to_string());
+    }
 
     let mut bybit_symbols_with_stablecoins = bybit_symbols.clone();
-    // Bybit stablecoin pairs: USDC/USDT
-    // Also add BTC/USD and BTC/USDC for deriving stablecoin rates (BTCUSD / BTCUSDT = USDT/USD)
-    bybit_symbols_with_stablecoins.push("USDCUSDT".to_string());
-    bybit_symbols_with_stablecoins.push("BTCUSD".to_string());
-    bybit_symbols_with_stablecoins.push("BTCUSDC".to_string());
+    if !bybit_symbols.is_empty() {
+        // Bybit stablecoin pairs: USDC/USDT
+        // Also add BTC/USD and BTC/USDC for deriving stablecoin rates (BTCUSD / BTCUSDT = USDT/USD)
+        bybit_symbols_with_stablecoins.push("USDCUSDT".to_string());
+        bybit_symbols_with_stablecoins.push("BTCUSD".to_string());
+        bybit_symbols_with_stablecoins.push("BTCUSDC".to_string());
+    }
 
     // GateIO stablecoin pairs: USDT/USD, USDC/USDT
     // Put stablecoins FIRST to ensure they're fetched before rate limiting kicks in
-    let mut gateio_symbols_with_stablecoins = vec!["USDC_USDT".to_string(), "USDT_USD".to_string()];
+    let mut gateio_symbols_with_stablecoins = Vec::new();
+    if !gateio_symbols.is_empty() {
+        gateio_symbols_with_stablecoins.push("USDC_USDT".to_string());
+        gateio_symbols_with_stablecoins.push("USDT_USD".to_string());
+    }
     gateio_symbols_with_stablecoins.extend(gateio_symbols.clone());
 
     // Bithumb uses base symbol format (e.g., "USDT", "BTC")
@@ -1103,20 +1481,13 @@ async fn spawn_live_feeds(
     )
     .await;
 
-    // Convert symbol_mappings to Arc for sharing across tasks
-    let symbol_mappings_arc = Arc::new(symbol_mappings.clone());
-
     // Create shared channel for all feed messages
     // All runners send FeedMessage to this channel, one handler processes them
     let (feed_tx, feed_rx) = mpsc::channel::<FeedMessage>(30000);
 
     // Start the common feed handler
-    let handler_ctx = FeedContext::new(
-        state.clone(),
-        broadcast_tx.clone(),
-        symbol_mappings_arc.clone(),
-        status_notifier.clone(),
-    );
+    let handler_ctx =
+        FeedContext::new(state.clone(), broadcast_tx.clone(), status_notifier.clone());
     handles.push(tokio::spawn(async move {
         feeds::run_feed_handler(feed_rx, handler_ctx).await;
     }));
@@ -1147,6 +1518,11 @@ async fn spawn_live_feeds(
         // Track initial subscriptions to prevent duplicate subscription on market discovery
         subscription_manager
             .track_initial_subscriptions(Exchange::Binance, all_binance_symbols.clone());
+        // Stablecoin rate symbols are not discovered markets; keep them across updates
+        subscription_manager.pin_subscriptions(
+            Exchange::Binance,
+            &all_binance_symbols[binance_symbols.len()..],
+        );
 
         // Register pool with subscription manager for dynamic subscriptions
         subscription_manager.register_exchange_pool(Exchange::Binance, pool_senders);
@@ -1224,6 +1600,10 @@ async fn spawn_live_feeds(
             // NOTE: Must be AFTER connect_all() to match Binance pattern and avoid race condition
             subscription_manager
                 .track_initial_subscriptions(Exchange::Coinbase, symbols_to_subscribe.clone());
+            subscription_manager.pin_subscriptions(
+                Exchange::Coinbase,
+                &["USDT-USD".to_string(), "USDT-USDC".to_string()],
+            );
 
             // Register pool with subscription manager for dynamic subscriptions
             subscription_manager.register_exchange_pool(Exchange::Coinbase, pool_senders);
@@ -1263,6 +1643,10 @@ async fn spawn_live_feeds(
 
         // Track initial subscriptions to prevent duplicate subscription on market discovery
         subscription_manager.track_initial_subscriptions(Exchange::Upbit, upbit_symbols.clone());
+        subscription_manager.pin_subscriptions(
+            Exchange::Upbit,
+            &["KRW-USDT".to_string(), "KRW-USDC".to_string()],
+        );
 
         let upbit_client = WsClient::new(upbit_config.clone(), ws_tx)
             .with_subscription_channel(sub_rx, Box::new(UpbitSubscriptionBuilder::new()));
@@ -1292,6 +1676,10 @@ async fn spawn_live_feeds(
 
         // Track initial subscriptions to prevent duplicate subscription on market discovery
         subscription_manager.track_initial_subscriptions(Exchange::Bithumb, bithumb_symbols.clone());
+        subscription_manager.pin_subscriptions(
+            Exchange::Bithumb,
+            &["KRW-USDT".to_string(), "KRW-USDC".to_string()],
+        );
 
         let bithumb_client = WsClient::new(bithumb_config.clone(), ws_tx)
             .with_subscription_channel(sub_rx, Box::new(BithumbSubscriptionBuilder::new()));
@@ -1328,6 +1716,8 @@ async fn spawn_live_feeds(
 
         // Track initial subscriptions to prevent duplicate subscription on market discovery
         subscription_manager.track_initial_subscriptions(Exchange::Bybit, all_bybit_symbols.clone());
+        subscription_manager
+            .pin_subscriptions(Exchange::Bybit, &all_bybit_symbols[bybit_symbols.len()..]);
 
         let bybit_client = WsClient::new(bybit_config.clone(), ws_tx)
             .with_subscription_channel(sub_rx, Box::new(BybitSubscriptionBuilder::new()));
@@ -1366,6 +1756,10 @@ async fn spawn_live_feeds(
 
         // Track initial subscriptions to prevent duplicate subscription on market discovery
         subscription_manager.track_initial_subscriptions(Exchange::GateIO, all_gateio_symbols.clone());
+        subscription_manager.pin_subscriptions(
+            Exchange::GateIO,
+            &all_gateio_symbols[gateio_symbols.len()..],
+        );
 
         let gateio_client = WsClient::new(gateio_config.clone(), ws_tx)
             .with_subscription_channel(sub_rx, Box::new(GateIOSubscriptionBuilder::new()));
@@ -1393,21 +1787,27 @@ async fn spawn_live_feeds(
 
 /// Run market discovery loop - periodically fetches markets from exchanges
 /// and broadcasts common markets to clients.
-/// Also triggers runtime subscription updates via SubscriptionManager.
+/// Also applies runtime subscription changes via SubscriptionManager: new
+/// common markets are subscribed, and markets that are no longer wanted
+/// (delisted, excluded, dropped from a pair list or on a disabled exchange)
+/// are unsubscribed. `reload` wakes the loop early after a configuration or
+/// symbol mapping change.
 async fn run_market_discovery(
     state: SharedState,
     broadcast_tx: BroadcastSender,
-    _symbol_mappings: Arc<SymbolMappings>,
     subscription_manager: Arc<SubscriptionManager>,
+    reload: Arc<Notify>,
 ) {
+    use std::collections::{HashMap, HashSet};
     debug!("Starting market discovery loop");
 
     let discovery = MarketDiscovery::new();
-    let exchanges = ["Binance", "Coinbase", "Upbit", "Bithumb", "Bybit", "GateIO"];
 
     loop {
-        // Reload symbol mappings on each iteration (in case they were updated)
-        let current_mappings = load_mappings();
+        // Configuration and symbol mappings may have been reloaded since the last pass
+        let config = state.config.read().await.clone();
+        let exchanges = config.live_exchange_names();
+        let current_mappings = state.symbol_mappings().await;
 
         let all_markets = discovery.fetch_all().await;
 
@@ -1430,100 +1830,44 @@ async fn run_market_discovery(
             // Broadcast to connected clients
             ws_server::broadcast_common_markets(&broadcast_tx, &common);
 
-            // Trigger runtime subscription updates for new markets
-            // Extract symbols per exchange from by_quote and update subscriptions
-            // This will calculate diff and only subscribe to new markets
-            use std::collections::HashSet;
-            let mut binance_markets: HashSet<String> = HashSet::new();
-            let mut coinbase_markets: HashSet<String> = HashSet::new();
-            let mut upbit_markets: HashSet<String> = HashSet::new();
-            let mut bithumb_markets: HashSet<String> = HashSet::new();
-            let mut bybit_markets: HashSet<String> = HashSet::new();
-            let mut gateio_markets: HashSet<String> = HashSet::new();
-
-            for (_key, exchange_markets) in &common.by_quote {
+            // Extract the wanted symbols per exchange from by_quote
+            let mut wanted: HashMap<&str, HashSet<String>> = HashMap::new();
+            for (key, exchange_markets) in &common.by_quote {
                 for (exchange, market_info) in exchange_markets {
-                    match exchange.as_str() {
-                        "Binance" => {
-                            binance_markets.insert(market_info.symbol.to_lowercase());
-                        }
-                        "Coinbase" => {
-                            coinbase_markets.insert(market_info.symbol.clone());
-                        }
-                        "Upbit" => {
-                            upbit_markets.insert(market_info.symbol.clone());
-                        }
-                        "Bithumb" => {
-                            bithumb_markets.insert(market_info.symbol.clone());
-                        }
-                        "Bybit" => {
-                            bybit_markets.insert(market_info.symbol.clone());
-                        }
-                        "GateIO" => {
-                            gateio_markets.insert(market_info.symbol.clone());
-                        }
-                        _ => {}
+                    if !config.monitors_market(exchange, key) {
+                        continue;
                     }
+                    let symbol = if exchange == "Binance" {
+                        market_info.symbol.to_lowercase()
+                    } else {
+                        market_info.symbol.clone()
+                    };
+                    wanted.entry(exchange.as_str()).or_default().insert(symbol);
                 }
             }
 
             // Update subscriptions for each exchange (diff is calculated internally)
-            // Note: Actual subscription messages won't be sent until Epic 2
-            // when SubscriptionBuilder implementations are added
-            let binance_vec: Vec<String> = binance_markets.into_iter().collect();
-            let coinbase_vec: Vec<String> = coinbase_markets.into_iter().collect();
-            let upbit_vec: Vec<String> = upbit_markets.into_iter().collect();
-            let bithumb_vec: Vec<String> = bithumb_markets.into_iter().collect();
-            let bybit_vec: Vec<String> = bybit_markets.into_iter().collect();
-            let gateio_vec: Vec<String> = gateio_markets.into_iter().collect();
-
-            // Only log if there are new subscriptions (update_subscriptions returns count)
-            if let Ok(count) = subscription_manager
-                .update_subscriptions(Exchange::Binance, &binance_vec)
-                .await
-            {
-                if count > 0 {
-                    info!("📡 Binance: {} new markets queued for subscription", count);
-                }
-            }
-            if let Ok(count) = subscription_manager
-                .update_subscriptions(Exchange::Coinbase, &coinbase_vec)
-                .await
-            {
-                if count > 0 {
-                    info!("📡 Coinbase: {} new markets queued for subscription", count);
-                }
-            }
-            if let Ok(count) = subscription_manager
-                .update_subscriptions(Exchange::Upbit, &upbit_vec)
-                .await
-            {
-                if count > 0 {
-                    info!("📡 Upbit: {} new markets queued for subscription", count);
+            for &(exchange, name) in LIVE_EXCHANGES.iter() {
+                if !subscription_manager.is_registered(exchange) {
+                    continue;
                 }
-            }
-            if let Ok(count) = subscription_manager
-                .update_subscriptions(Exchange::Bithumb, &bithumb_vec)
-                .await
-            {
-                if count > 0 {
-                    info!("📡 Bithumb: {} new markets queued for subscription", count);
+                // A failed market fetch must not unsubscribe the exchange's markets
+                if config.is_exchange_enabled(exchange) && !all_markets.contains_key(name) {
+                    continue;
                 }
-            }
-            if let Ok(count) = subscription_manager
-                .update_subscriptions(Exchange::Bybit, &bybit_vec)
-                .await
-            {
-                if count > 0 {
-                    info!("📡 Bybit: {} new markets queued for subscription", count);
-                }
-            }
-            if let Ok(count) = subscription_manager
-                .update_subscriptions(Exchange::GateIO, &gateio_vec)
-                .await
-            {
-                if count > 0 {
-                    info!("📡 GateIO: {} new markets queued for subscription", count);
+                let symbols: Vec<String> = wanted
+                    .remove(name)
+                    .map(|symbols| symbols.into_iter().collect())
+                    .unwrap_or_default();
+                match subscription_manager
+                    .update_subscriptions(exchange, &symbols)
+                    .await
+                {
+                    Ok(count) if count > 0 => {
+                        info!("📡 {}: {} new markets queued for subscription", name, count);
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!("📡 {}: subscription update failed: {}", name, e),
                 }
             }
         } else {
@@ -1533,8 +1877,11 @@ async fn run_market_discovery(
             );
         }
 
-        // Refresh every 5 minutes
-        tokio::time::sleep(Duration::from_secs(300)).await;
+        // Refresh every 5 minutes, or right away after a reload
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(300)) => {}
+            _ = reload.notified() => debug!("Market discovery woken by configuration reload"),
+        }
     }
 }
 
@@ -1545,15 +1892,35 @@ async fn main() {
 
     let args = Args::parse();
 
-    init_logging(&args.log_level);
+    let config = match load_config(&args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Configuration error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    if let Some(format) = args.dump_config {
+        println!("{}", config.dump(format));
+        return;
+    }
+
+    init_logging(&config.log_level);
 
     info!("🚀 Arbitrage Bot starting...");
-    info!("  Mode: {}", args.mode);
-    info!("  Min Premium: {} bps", args.min_premium);
+    info!("  Mode: {:?}", config.execution.mode);
+    info!("  Min Premium: {} bps", config.detector.min_premium_bps);
     info!("  Dry Run: {}", args.dry_run);
-    info!("  Live Feeds: {}", args.live);
-    info!("  WebSocket Port: {}", args.ws_port);
-    info!("  Telegram Alerts: {}", args.telegram);
+    info!("  Live Feeds: {}", config.server.live);
+    info!("  Exchanges: {}", config.live_exchange_names().join(", "));
+    info!("  WebSocket Port: {}", config.server.ws_port);
+    info!("  Telegram Alerts: {}", config.alerts.telegram);
+
+    let server = config.server.clone();
+    let alerts = config.alerts.clone();
+    let notifier_config = NotifierConfig::from(&config);
+
+    // Restore pair IDs before any symbol is registered
+    load_pair_registry(&server.pair_registry_path);
 
     // Load network name mapping for cross-exchange transfer path detection
     wallet_status::init_network_mapping();
@@ -1573,18 +1940,13 @@ async fn main() {
         info!("  Symbol Mappings: none configured");
     }
 
-    // Create config
-    let mut config = AppConfig::default();
-    config.detector.min_premium_bps = args.min_premium;
-    config.execution.mode = parse_mode(&args.mode);
-    config.log_level = args.log_level.clone();
-
     // Create shared state and price update receiver
     let (state, price_update_rx) = create_state(config);
+    state.set_symbol_mappings(symbol_mappings.clone()).await;
     state.start();
 
     // Start WebSocket server for clients (Tauri app) - must start first to get broadcast_tx
-    let broadcast_tx = match ws_server::start_ws_server(state.clone(), args.ws_port).await {
+    let broadcast_tx = match ws_server::start_ws_server(state.clone(), &server).await {
         Ok(tx) => tx,
         Err(e) => {
             tracing::error!("Failed to start WebSocket server: {}", e);
@@ -1616,32 +1978,23 @@ async fn main() {
                 statuses.len()
             );
             // Update cache first so new clients can get it
+            state.update_network_fees(&statuses).await;
             wallet_status::update_cache(statuses.clone());
             // Then broadcast to any already-connected clients
             ws_server::broadcast_wallet_status(&broadcast_tx, statuses);
         }
     }
 
+    // Open database (alert configuration, kimchi index and opportunity episode history)
+    let database = open_database(&alerts.db_path).await;
+
     // Initialize Telegram notifier if enabled
-    let notifier: Option<Arc<Notifier>> = if args.telegram {
+    let notifier: Option<Arc<Notifier>> = if alerts.telegram {
         match std::env::var("TELEGRAM_BOT_TOKEN") {
             Ok(token) => {
-                // Ensure data directory exists
-                let db_dir = std::path::Path::new(&args.db_path).parent();
-                if let Some(dir) = db_dir {
-                    if !dir.exists() {
-                        if let Err(e) = std::fs::create_dir_all(dir) {
-                            warn!("Failed to create data directory: {}", e);
-                        }
-                    }
-                }
-
-                // Connect to database
-                let db_url = format!("sqlite:{}", args.db_path);
-                match Database::connect(&db_url).await {
-                    Ok(db) => {
+                match database.clone() {
+                    Some(db) => {
                         let bot = Arc::new(TelegramBot::new(&token, db.clone()));
-                        let notifier_config = NotifierConfig::default();
 
                         // Create notifier with transfer path checker
                         let notifier = Notifier::new(db, bot.clone(), notifier_config)
@@ -1660,8 +2013,8 @@ async fn main() {
 
                         Some(notifier)
                     }
-                    Err(e) => {
-                        warn!("Failed to connect to alert database: {}", e);
+                    None => {
+                        warn!("Alert database unavailable, Telegram alerts disabled");
                         None
                     }
                 }
@@ -1715,8 +2068,52 @@ async fn main() {
 
     // Start wallet status updater for deposit/withdraw availability
     let wallet_broadcast = broadcast_tx.clone();
+    let wallet_state = state.clone();
+    tokio::spawn(async move {
+        wallet_status::run_wallet_status_updater(wallet_broadcast, wallet_state).await;
+    });
+
+    // Start account trading fee updater (VIP tier and per-market rates)
+    let fee_state = state.clone();
     tokio::spawn(async move {
-        wallet_status::run_wallet_status_updater(wallet_broadcast).await;
+        trading_fees::run_trading_fee_updater(fee_state).await;
+    });
+
+    // Start gas fee sampling for configured chain RPC endpoints
+    let gas_state = state.clone();
+    let gas_settings = state.config.read().await.gas.clone();
+    tokio::spawn(async move {
+        gas_oracle::run_gas_oracle_updater(gas_state, gas_settings).await;
+    });
+
+    // Start kimchi premium index reporter
+    let kimchi_state = state.clone();
+    let kimchi_broadcast = broadcast_tx.clone();
+    let kimchi_db = database.clone();
+    tokio::spawn(async move {
+        run_kimchi_index_reporter(kimchi_state, kimchi_broadcast, kimchi_db).await;
+    });
+
+    // Start opportunity lifecycle reporter (stale episodes, episode history)
+    let lifecycle_state = state.clone();
+    let lifecycle_broadcast = broadcast_tx.clone();
+    let lifecycle_db = database.clone();
+    tokio::spawn(async move {
+        run_opportunity_lifecycle_reporter(lifecycle_state, lifecycle_broadcast, lifecycle_db)
+            .await;
+    });
+
+    // Persist newly registered pair IDs (runs every 60 seconds)
+    let registry_path = server.pair_registry_path.clone();
+    tokio::spawn(async move {
+        let mut saved_len = PairRegistry::global().len();
+        loop {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            let len = PairRegistry::global().len();
+            if len != saved_len && save_pair_registry(&registry_path) {
+                saved_len = len;
+            }
+        }
     });
 
     // Start stale price cleanup task (runs every 10 seconds)
@@ -1736,7 +2133,7 @@ async fn main() {
     let (feed_handles, subscription_manager): (
         Vec<tokio::task::JoinHandle<()>>,
         Option<Arc<SubscriptionManager>>,
-    ) = if args.live {
+    ) = if server.live {
         info!("📡 Using LIVE WebSocket feeds");
         let (handles, sub_mgr) = spawn_live_feeds(
             state.clone(),
@@ -1758,31 +2155,43 @@ async fn main() {
 
     // Start market discovery loop
     // Pass SubscriptionManager for runtime subscription updates (live mode only)
+    let discovery_reload = state.market_discovery();
     let discovery_state = state.clone();
     let discovery_broadcast = broadcast_tx.clone();
-    let discovery_mappings = symbol_mappings.clone();
-    if let Some(sub_mgr) = subscription_manager {
-        tokio::spawn(async move {
-            run_market_discovery(
-                discovery_state,
-                discovery_broadcast,
-                discovery_mappings,
-                sub_mgr,
-            )
-            .await;
-        });
-    } else {
+    let discovery_sub_mgr = subscription_manager
+        .clone()
         // Simulated mode - no SubscriptionManager, use dummy version
-        tokio::spawn(async move {
-            run_market_discovery(
-                discovery_state,
-                discovery_broadcast,
-                discovery_mappings,
-                Arc::new(SubscriptionManager::new()),
-            )
-            .await;
-        });
-    }
+        .unwrap_or_else(|| Arc::new(SubscriptionManager::new()));
+    let discovery_notify = discovery_reload.clone();
+    tokio::spawn(async move {
+        run_market_discovery(
+            discovery_state,
+            discovery_broadcast,
+            discovery_sub_mgr,
+            discovery_notify,
+        )
+        .await;
+    });
+
+    // Watch the config file and symbol mappings and apply changes live
+    let config_paths = match args.config.clone() {
+        Some(path) => vec![path],
+        None => DEFAULT_CONFIG_PATHS.iter().map(PathBuf::from).collect(),
+    };
+    let watcher = config_reload::ConfigWatcher::new(config_paths, get_mappings_path());
+    let reload_state = state.clone();
+    let reload_broadcast = broadcast_tx.clone();
+    tokio::spawn(async move {
+        config_reload::run_config_reload(
+            reload_state,
+            reload_broadcast,
+            watcher,
+            move || load_config(&args),
+            subscription_manager,
+            discovery_reload,
+        )
+        .await;
+    });
 
     // Handle shutdown
     info!("Press Ctrl+C to stop...");
@@ -1799,6 +2208,16 @@ async fn main() {
     }
 
     state.stop();
+    state.detection_scheduler().close();
+    save_pair_registry(&server.pair_registry_path);
+
+    // Record episodes still open at shutdown
+    state
+        .opportunity_lifecycle()
+        .close_all(wallet_status::timestamp_ms());
+    if let Some(ref db) = database {
+        persist_opportunity_episodes(&state, db).await;
+    }
 
     // Wait for tasks with timeout, then abort
     let _ = tokio::time::timeout(Duration::from_secs(2), detector_handle).await;
@@ -1855,4 +2274,55 @@ mod tests {
         state.stop();
         assert!(!state.is_running());
     }
+
+    #[tokio::test]
+    async fn test_pairs_marked_while_paused_are_detected_after_resume() {
+        let mut config = AppConfig::default();
+        config.server.audit_log_path = String::new();
+        let (state, _price_rx) = create_state(config);
+        let (broadcast_tx, _) = tokio::sync::broadcast::channel(1024);
+        let operator = auth::Client {
+            name: "desktop".to_string(),
+            scope: config::ApiScope::Trade,
+        };
+        let command = |command| control::execute(&state, &broadcast_tx, &operator, command);
+        command(control::ControlCommand::Pause).await.unwrap();
+
+        let worker = tokio::spawn(run_detection_worker(
+            state.clone(),
+            broadcast_tx.clone(),
+            None,
+        ));
+        let scheduler = state.detection_scheduler();
+        for pair_id in [1, 2] {
+            scheduler.mark_dirty(PriceUpdateEvent {
+                exchange: Exchange::Binance,
+                pair_id,
+                symbol: format!("PAUSED{}", pair_id),
+            });
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let snapshot = scheduler.snapshot();
+        assert_eq!((snapshot.detections, snapshot.queue_depth), (0, 2));
+
+        command(control::ControlCommand::Resume).await.unwrap();
+        let detected = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                let snapshot = scheduler.snapshot();
+                if snapshot.detections == 2 && snapshot.queue_depth == 0 && snapshot.in_flight == 0
+                {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        assert!(
+            detected.is_ok(),
+            "paused pairs were not detected after resume"
+        );
+
+        scheduler.close();
+        worker.await.unwrap();
+    }
 }
@@ -0,0 +1,445 @@
+//! REST query API served next to the WebSocket endpoint.
+//!
+//! Read-only JSON snapshots of the same data the stream pushes, for scripts
+//! and notebooks that only need the current value. Responses reuse the
+//! `collect_*` functions and `Ws*Data` types of the WebSocket server.
+//! `POST /api/allocation` previews how posted balances would be spread over
+//! the current opportunities; it places no orders.
+//!
+//! Exchanges are named as in the stream ("Binance", "GateIO") and symbols are
+//! canonical base assets ("BTC"); both match case-insensitively.
+
+use crate::config::LIVE_EXCHANGES;
+use crate::ws_server::{
+    self, WsExchangeRateData, WsFeedHealthData, WsOpportunityData, WsOrderbookData,
+    WsPremiumMatrixData, WsPriceData, WsServerState, WsStatsData, WsWalletStatusData,
+};
+use arbitrage_core::{Exchange, FixedPoint, PairRegistry};
+use arbitrage_engine::{Balances, ExecutionPlan};
+use axum::{
+    extract::{rejection::JsonRejection, Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// REST endpoint error, returned as `{"error": "..."}`.
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("unknown exchange: {0}")]
+    UnknownExchange(String),
+    #[error("unknown symbol: {0}")]
+    UnknownSymbol(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0} not loaded yet")]
+    Unavailable(&'static str),
+    #[error("missing or invalid API key")]
+    Unauthorized,
+    #[error("{0}")]
+    Forbidden(String),
+    #[error("rate limit exceeded, retry in {0}s")]
+    RateLimited(u64),
+    #[error("{0}")]
+    Rejected(String),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            ApiError::UnknownExchange(_) | ApiError::Invalid(_) => StatusCode::BAD_REQUEST,
+            ApiError::UnknownSymbol(_) | ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Rejected(_) => StatusCode::CONFLICT,
+        };
+        let body = Json(serde_json::json!({ "error": self.to_string() }));
+        let mut response = (status, body).into_response();
+        if let ApiError::RateLimited(retry_after) = self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        response
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// REST routes, merged into the WebSocket server router.
+pub fn routes() -> Router<Arc<WsServerState>> {
+    Router::new()
+        .route("/api/prices", get(prices_handler))
+        .route("/api/premium-matrix/:symbol", get(premium_matrix_handler))
+        .route("/api/opportunities", get(opportunities_handler))
+        .route("/api/orderbook/:exchange/:symbol", get(orderbook_handler))
+        .route("/api/wallet-status", get(wallet_status_handler))
+        .route("/api/exchange-rate", get(exchange_rate_handler))
+        .route("/api/feeds", get(feeds_handler))
+        .route("/api/stats", get(stats_handler))
+        .route("/api/allocation", post(allocation_handler))
+}
+
+/// Live feed exchange by stream name.
+pub(crate) fn parse_exchange(name: &str) -> Result<Exchange, ApiError> {
+    LIVE_EXCHANGES
+        .iter()
+        .find(|(_, live_name)| live_name.eq_ignore_ascii_case(name))
+        .map(|&(exchange, _)| exchange)
+        .ok_or_else(|| ApiError::UnknownExchange(name.to_string()))
+}
+
+/// Registered pair id and canonical symbol, without registering new symbols.
+pub(crate) fn lookup_pair(symbol: &str) -> Result<(u32, String), ApiError> {
+    let symbol = symbol.to_uppercase();
+    match PairRegistry::global().get(&symbol) {
+        Some(pair_id) => Ok((pair_id, symbol)),
+        None => Err(ApiError::UnknownSymbol(symbol)),
+    }
+}
+
+fn matches(filter: &Option<String>, value: &str) -> bool {
+    filter
+        .as_deref()
+        .is_none_or(|filter| filter.eq_ignore_ascii_case(value))
+}
+
+/// `GET /api/prices` filters.
+#[derive(Debug, Default, Deserialize)]
+pub struct PriceQuery {
+    pub exchange: Option<String>,
+    pub symbol: Option<String>,
+    /// Quote currency (e.g. "USDT", "KRW")
+    pub quote: Option<String>,
+}
+
+/// Current prices, one per exchange market.
+async fn prices_handler(
+    State(state): State<Arc<WsServerState>>,
+    Query(query): Query<PriceQuery>,
+) -> Json<Vec<WsPriceData>> {
+    let prices = ws_server::collect_prices(&state.app_state)
+        .await
+        .into_iter()
+        .filter(|price| {
+            matches(&query.exchange, &price.exchange)
+                && matches(&query.symbol, &price.symbol)
+                && (query.quote.is_none()
+                    || price
+                        .quote
+                        .as_deref()
+                        .is_some_and(|quote| matches(&query.quote, quote)))
+        })
+        .collect();
+    Json(prices)
+}
+
+/// Premium matrix of a symbol across all exchange pairs.
+async fn premium_matrix_handler(
+    State(state): State<Arc<WsServerState>>,
+    Path(symbol): Path<String>,
+) -> ApiResult<WsPremiumMatrixData> {
+    let (pair_id, symbol) = lookup_pair(&symbol)?;
+    ws_server::collect_premium_matrix(&state.app_state, pair_id, &symbol)
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("no prices for {}", symbol)))
+}
+
+/// `GET /api/opportunities` filters.
+#[derive(Debug, Default, Deserialize)]
+pub struct OpportunityQuery {
+    pub symbol: Option<String>,
+    /// Either leg's exchange
+    pub exchange: Option<String>,
+    pub source_exchange: Option<String>,
+    pub target_exchange: Option<String>,
+    pub min_premium_bps: Option<i32>,
+    pub min_net_profit_bps: Option<i32>,
+    pub min_confidence: Option<u8>,
+    /// Only opportunities with (or without) a common transfer network
+    pub transferable: Option<bool>,
+    pub limit: Option<usize>,
+}
+
+impl OpportunityQuery {
+    fn accepts(&self, opp: &WsOpportunityData) -> bool {
+        matches(&self.symbol, &opp.symbol)
+            && (matches(&self.exchange, &opp.source_exchange)
+                || matches(&self.exchange, &opp.target_exchange))
+            && matches(&self.source_exchange, &opp.source_exchange)
+            && matches(&self.target_exchange, &opp.target_exchange)
+            && self
+                .min_premium_bps
+                .is_none_or(|min| opp.premium_bps >= min)
+            && self
+                .min_net_profit_bps
+                .is_none_or(|min| opp.net_profit_bps >= min)
+            && self
+                .min_confidence
+                .is_none_or(|min| opp.confidence_score >= min)
+            && self
+                .transferable
+                .is_none_or(|transferable| opp.has_transfer_path == transferable)
+    }
+}
+
+/// Active opportunities.
+async fn opportunities_handler(
+    State(state): State<Arc<WsServerState>>,
+    Query(query): Query<OpportunityQuery>,
+) -> Json<Vec<WsOpportunityData>> {
+    let opportunities = ws_server::collect_opportunities(&state.app_state)
+        .await
+        .into_iter()
+        .filter(|opp| query.accepts(opp))
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect();
+    Json(opportunities)
+}
+
+/// `GET /api/orderbook` options.
+#[derive(Debug, Default, Deserialize)]
+pub struct OrderbookQuery {
+    /// Levels per side (default: every cached level)
+    pub depth: Option<usize>,
+}
+
+/// Cached orderbook of an exchange market.
+async fn orderbook_handler(
+    State(state): State<Arc<WsServerState>>,
+    Path((exchange, symbol)): Path<(String, String)>,
+    Query(query): Query<OrderbookQuery>,
+) -> ApiResult<WsOrderbookData> {
+    let exchange = parse_exchange(&exchange)?;
+    let (pair_id, symbol) = lookup_pair(&symbol)?;
+    let depth = query.depth.unwrap_or(usize::MAX);
+    ws_server::collect_orderbook(&state.app_state, exchange, pair_id, &symbol, depth)
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("no orderbook for {:?} {}", exchange, symbol)))
+}
+
+/// Deposit/withdrawal status per exchange and network.
+async fn wallet_status_handler() -> ApiResult<WsWalletStatusData> {
+    ws_server::collect_wallet_status()
+        .map(Json)
+        .ok_or(ApiError::Unavailable("wallet status"))
+}
+
+/// KRW and stablecoin exchange rates.
+async fn exchange_rate_handler(
+    State(state): State<Arc<WsServerState>>,
+) -> ApiResult<WsExchangeRateData> {
+    ws_server::collect_exchange_rate(&state.app_state)
+        .map(Json)
+        .ok_or(ApiError::Unavailable("exchange rate"))
+}
+
+/// Connection state and message age per exchange feed.
+async fn feeds_handler(State(state): State<Arc<WsServerState>>) -> Json<WsFeedHealthData> {
+    Json(ws_server::collect_feed_health(&state.app_state))
+}
+
+/// Bot statistics.
+async fn stats_handler(State(state): State<Arc<WsServerState>>) -> Json<WsStatsData> {
+    Json(ws_server::collect_stats(&state.app_state))
+}
+
+/// `POST /api/allocation` body.
+#[derive(Debug, Default, Deserialize)]
+pub struct AllocationRequest {
+    pub balances: Vec<BalanceEntry>,
+}
+
+/// Available amount of a currency (quote or asset code) on an exchange.
+#[derive(Debug, Deserialize)]
+pub struct BalanceEntry {
+    pub exchange: String,
+    pub currency: String,
+    pub amount: f64,
+}
+
+/// Allocation of the posted balances across the current opportunities.
+async fn allocation_handler(
+    State(state): State<Arc<WsServerState>>,
+    body: Result<Json<AllocationRequest>, JsonRejection>,
+) -> ApiResult<ExecutionPlan> {
+    let Json(request) = body.map_err(|e| ApiError::Invalid(e.body_text()))?;
+    let mut balances = Balances::new();
+    for entry in &request.balances {
+        if !entry.amount.is_finite() || entry.amount < 0.0 {
+            return Err(ApiError::Invalid(format!(
+                "invalid {} balance: {}",
+                entry.currency, entry.amount
+            )));
+        }
+        balances.set(
+            parse_exchange(&entry.exchange)?,
+            &entry.currency,
+            FixedPoint::from_f64(entry.amount).0,
+        );
+    }
+    Ok(Json(state.app_state.plan_allocation(&balances).await))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::state::create_state;
+    use arbitrage_core::{symbol_to_pair_id, ArbitrageOpportunity, Asset, FixedPoint};
+    use tokio::sync::broadcast;
+
+    fn server_state() -> Arc<WsServerState> {
+        let (app_state, _rx) = create_state(AppConfig::default());
+        let (broadcast_tx, _) = broadcast::channel(1);
+        Arc::new(WsServerState {
+            app_state,
+            broadcast_tx,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_opportunities_filters() {
+        let state = server_state();
+        let opportunity = |id, source, target, symbol: &str, target_price| {
+            ArbitrageOpportunity::new(
+                id,
+                source,
+                target,
+                Asset::from_symbol(symbol),
+                FixedPoint::from_f64(100.0),
+                FixedPoint::from_f64(target_price),
+            )
+        };
+        *state.app_state.opportunities.write().await = vec![
+            opportunity(1, Exchange::Binance, Exchange::Upbit, "RESTA", 101.0),
+            opportunity(2, Exchange::Bybit, Exchange::Coinbase, "RESTA", 100.2),
+            opportunity(3, Exchange::Binance, Exchange::GateIO, "RESTB", 102.0),
+        ];
+
+        let query = |query: OpportunityQuery| {
+            let state = state.clone();
+            async move {
+                let Json(opps) = opportunities_handler(State(state), Query(query)).await;
+                opps.iter().map(|opp| opp.id).collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(query(OpportunityQuery::default()).await, vec![1, 2, 3]);
+        assert_eq!(
+            query(OpportunityQuery {
+                symbol: Some("resta".to_string()),
+                ..Default::default()
+            })
+            .await,
+            vec![1, 2]
+        );
+        assert_eq!(
+            query(OpportunityQuery {
+                exchange: Some("binance".to_string()),
+                min_premium_bps: Some(50),
+                ..Default::default()
+            })
+            .await,
+            vec![1, 3]
+        );
+        assert_eq!(
+            query(OpportunityQuery {
+                target_exchange: Some("GateIO".to_string()),
+                ..Default::default()
+            })
+            .await,
+            vec![3]
+        );
+        assert_eq!(
+            query(OpportunityQuery {
+                limit: Some(1),
+                ..Default::default()
+            })
+            .await,
+            vec![1]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_orderbook_snapshot() {
+        let state = server_state();
+        let pair_id = symbol_to_pair_id("RESTOB");
+        state.app_state.update_orderbook_snapshot(
+            Exchange::Binance,
+            pair_id,
+            &[(99.0, 1.0), (98.0, 2.0)],
+            &[(101.0, 1.5), (102.0, 3.0)],
+        );
+
+        let Json(book) = orderbook_handler(
+            State(state.clone()),
+            Path(("binance".to_string(), "restob".to_string())),
+            Query(OrderbookQuery { depth: Some(1) }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(book.exchange, "Binance");
+        assert_eq!(book.symbol, "RESTOB");
+        assert_eq!(book.bids, vec![(99.0, 1.0)]);
+        assert_eq!(book.asks, vec![(101.0, 1.5)]);
+
+        let missing = orderbook_handler(
+            State(state.clone()),
+            Path(("Upbit".to_string(), "RESTOB".to_string())),
+            Query(OrderbookQuery::default()),
+        )
+        .await;
+        assert!(matches!(missing, Err(ApiError::NotFound(_))));
+
+        let unknown = orderbook_handler(
+            State(state),
+            Path(("Kraken".to_string(), "RESTOB".to_string())),
+            Query(OrderbookQuery::default()),
+        )
+        .await;
+        assert!(matches!(unknown, Err(ApiError::UnknownExchange(_))));
+    }
+
+    #[tokio::test]
+    async fn test_premium_matrix_unknown_symbol() {
+        let result =
+            premium_matrix_handler(State(server_state()), Path("NOTLISTED".to_string())).await;
+        assert!(matches!(result, Err(ApiError::UnknownSymbol(_))));
+        assert_eq!(
+            result.unwrap_err().into_response().status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_allocation_validates_balances() {
+        let request = |exchange: &str, amount| {
+            Ok(Json(AllocationRequest {
+                balances: vec![BalanceEntry {
+                    exchange: exchange.to_string(),
+                    currency: "USDT".to_string(),
+                    amount,
+                }],
+            }))
+        };
+
+        let Json(plan) = allocation_handler(State(server_state()), request("binance", 1000.0))
+            .await
+            .unwrap();
+        assert!(plan.is_empty());
+
+        let unknown = allocation_handler(State(server_state()), request("Kraken", 1000.0)).await;
+        assert!(matches!(unknown, Err(ApiError::UnknownExchange(_))));
+        let negative = allocation_handler(State(server_state()), request("Binance", -1.0)).await;
+        assert!(matches!(negative, Err(ApiError::Invalid(_))));
+    }
+}
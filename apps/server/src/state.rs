@@ -1,18 +1,29 @@
 //! Application state management.
 
 use crate::config::AppConfig;
+use crate::control::Control;
+use crate::detection_scheduler::DetectionScheduler;
+use crate::feed_health::FeedHealth;
+use crate::trading_fees::AccountTradingFees;
 use arbitrage_core::{
-    symbol_to_pair_id, ArbitrageOpportunity, Exchange, FixedPoint, OptimalSizeReason, QuoteCurrency,
+    symbol_to_pair_id, ArbitrageOpportunity, Chain, Exchange, FixedPoint, MarketRules,
+    OptimalSizeReason, QuoteCurrency, RouteStep, TradeSide,
 };
 use arbitrage_engine::{
-    DetectorConfig, FeeManager, OpportunityDetector, OrderbookCache, PremiumMatrix,
+    default_max_levels, AllocationCandidate, AllocationLeg, AllocatorLimits, Balances,
+    BridgeQuoteRequest, BridgeQuotes, ConfidenceScorer, ConversionRates, DepegEvent,
+    DetectorConfig, ExecutionPlan, FeeConfig, FeeLeg, FeeManager, GasOracle, ImpactModelConfig,
+    KimchiIndex, KimchiIndexConfig, LifecycleConfig, LifecycleEvent, OpportunityDetector,
+    OpportunityTracker, OrderbookCache, PortfolioAllocator, PremiumMatrix, Side, SizingConstraints,
+    StablecoinUsd, TransferFee, TransferRiskInputs, TransferRiskModel,
 };
-use arbitrage_feeds::{CommonMarkets, PriceAggregator};
+use arbitrage_feeds::{CommonMarkets, PriceAggregator, SymbolMappings};
 use dashmap::DashMap;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::mpsc;
 use tokio::sync::RwLock; // Still needed for other fields
+use tokio::sync::{mpsc, Mutex, MutexGuard, Notify};
 
 /// Event sent when a price is updated.
 #[derive(Debug, Clone)]
@@ -178,6 +189,8 @@ pub struct AppState {
     /// Configuration.
     #[allow(dead_code)]
     pub config: RwLock<AppConfig>,
+    /// Serializes read-modify-write configuration updates.
+    config_update: Mutex<()>,
     /// Price aggregator.
     pub prices: PriceAggregator,
     /// Opportunity detector (internally lock-free via DashMap).
@@ -219,8 +232,38 @@ pub struct AppState {
     /// Full orderbook cache: (exchange, pair_id) -> OrderbookCache
     /// Used for optimal size calculation via depth walking algorithm.
     orderbook_cache: DashMap<(Exchange, u32), OrderbookCache>,
+    /// Orderbook levels cached per side, by exchange.
+    orderbook_levels: HashMap<Exchange, usize>,
+    /// Slippage model for sizing beyond the cached orderbook depth.
+    impact_model: ImpactModelConfig,
     /// Fee manager for all exchanges.
     fee_manager: RwLock<FeeManager>,
+    /// Latest account trading fees per exchange (re-applied when fee tables are rebuilt).
+    account_trading_fees: RwLock<HashMap<Exchange, AccountTradingFees>>,
+    /// Transfer-time price risk model (network confirmations, crediting, volatility).
+    transfer_risk: TransferRiskModel,
+    /// Per-chain gas prices for costing DEX and withdrawal steps.
+    gas_oracle: GasOracle,
+    /// Bridge quoters for routes whose legs sit on different chains.
+    bridge_quotes: BridgeQuotes,
+    /// Dirty-pair queue feeding the detection workers.
+    detection_scheduler: DetectionScheduler,
+    /// Per-exchange connection state and message timestamps.
+    feed_health: FeedHealth,
+    /// Remote control state (pause, kill switch, approval queue, audit log).
+    control: Control,
+    /// Wakes market discovery after exchange or symbol changes.
+    market_discovery: Arc<Notify>,
+    /// Confidence scorer for detected opportunities.
+    confidence: ConfidenceScorer,
+    /// Symbol mappings (canonical names, exclusions and confidence conflict flags).
+    symbol_mappings: RwLock<Arc<SymbolMappings>>,
+    /// Kimchi premium index settings.
+    kimchi_index_config: KimchiIndexConfig,
+    /// Latest kimchi premium index (for initial sync of new clients).
+    kimchi_index: RwLock<Option<KimchiIndex>>,
+    /// Opportunity episodes per route (stable ids, peak premium, closing reason).
+    lifecycle: OpportunityTracker,
     /// Channel to notify detector of price updates.
     price_update_tx: mpsc::Sender<PriceUpdateEvent>,
 }
@@ -230,11 +273,24 @@ impl AppState {
     /// Returns the state and a receiver for price update events.
     pub fn new(config: AppConfig) -> (Self, mpsc::Receiver<PriceUpdateEvent>) {
         let detector_config: DetectorConfig = (&config.detector).into();
+        let lifecycle = OpportunityTracker::new(LifecycleConfig {
+            threshold_bps: config.detector.min_premium_bps,
+            ..Default::default()
+        });
+        let fee_manager = fee_manager_from_config(&config);
+        let gas_oracle = GasOracle::new((&config.gas).into());
+        let orderbook_levels = config
+            .exchanges
+            .iter()
+            .filter_map(|settings| Some((settings.exchange, settings.orderbook_levels?)))
+            .collect();
         // Channel for price update notifications (bounded to prevent backpressure)
         let (price_update_tx, price_update_rx) = mpsc::channel(1024);
+        let control = Control::new(&config.server.audit_log_path);
 
         let state = Self {
             config: RwLock::new(config),
+            config_update: Mutex::new(()),
             prices: PriceAggregator::new(),
             detector: OpportunityDetector::new(detector_config),
             matrices: DashMap::new(),
@@ -251,7 +307,22 @@ impl AppState {
             depth_cache: DashMap::new(),
             stablecoin_prices: DashMap::new(),
             orderbook_cache: DashMap::new(),
-            fee_manager: RwLock::new(FeeManager::new()),
+            orderbook_levels,
+            impact_model: ImpactModelConfig::default(),
+            fee_manager: RwLock::new(fee_manager),
+            account_trading_fees: RwLock::new(HashMap::new()),
+            transfer_risk: TransferRiskModel::default(),
+            gas_oracle,
+            bridge_quotes: BridgeQuotes::with_defaults(),
+            detection_scheduler: DetectionScheduler::new(),
+            feed_health: FeedHealth::new(),
+            control,
+            market_discovery: Arc::new(Notify::new()),
+            confidence: ConfidenceScorer::default(),
+            symbol_mappings: RwLock::new(Arc::new(SymbolMappings::default())),
+            kimchi_index_config: KimchiIndexConfig::default(),
+            kimchi_index: RwLock::new(None),
+            lifecycle,
             price_update_tx,
         };
         (state, price_update_rx)
@@ -327,6 +398,7 @@ impl AppState {
     }
 
     /// Get USDC/KRW price for a specific exchange.
+    #[allow(dead_code)]
     pub fn get_usdc_krw_for_exchange(&self, exchange: Exchange) -> Option<FixedPoint> {
         match exchange {
             Exchange::Upbit => self.get_upbit_usdc_krw(),
@@ -365,23 +437,48 @@ impl AppState {
 
     /// Update exchange-specific stablecoin price.
     /// Call this for each stablecoin pair received from an exchange.
+    /// Returns a depeg event when the rate moves the peg into another level.
     pub fn update_exchange_stablecoin_price(
         &self,
         exchange: Exchange,
         base: &str,
         quote: &str,
         price: f64,
-    ) {
-        let mut entry = self.stablecoin_prices.entry(exchange).or_default();
-        let prices = entry.value_mut();
+    ) -> Option<DepegEvent> {
+        {
+            let mut entry = self.stablecoin_prices.entry(exchange).or_default();
+            let prices = entry.value_mut();
+
+            match (base, quote) {
+                ("USDT", "USD") => prices.usdt_usd = Some(price),
+                ("USDC", "USD") => prices.usdc_usd = Some(price),
+                ("USDC", "USDT") => prices.usdc_usdt = Some(price),
+                ("USDT", "USDC") => prices.usdt_usdc = Some(price),
+                _ => {}
+            }
+        } // stablecoin_prices shard lock released here
 
-        match (base, quote) {
-            ("USDT", "USD") => prices.usdt_usd = Some(price),
-            ("USDC", "USD") => prices.usdc_usd = Some(price),
-            ("USDC", "USDT") => prices.usdc_usdt = Some(price),
-            ("USDT", "USDC") => prices.usdt_usdc = Some(price),
-            _ => {}
-        }
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let event = self.detector.depeg_monitor().observe(
+            exchange,
+            QuoteCurrency::from_str(base)?,
+            QuoteCurrency::from_str(quote)?,
+            price,
+            now_ms,
+        )?;
+        tracing::warn!(
+            exchange = ?exchange,
+            stablecoin = base,
+            reference = quote,
+            rate = price,
+            deviation_bps = event.deviation_bps,
+            level = ?event.level,
+            "Stablecoin peg level changed"
+        );
+        Some(event)
     }
 
     /// Update reference crypto prices for deriving stablecoin rates.
@@ -438,6 +535,39 @@ impl AppState {
         }
     }
 
+    /// Snapshot of live conversion rates for one detection pass.
+    ///
+    /// Each Korean exchange keeps its own stablecoin/KRW rates and each
+    /// exchange its own stablecoin/USD prices, so a depeg on one venue
+    /// is not priced with another venue's quotes.
+    pub fn conversion_rates(&self) -> ConversionRates {
+        let rate = |price: Option<FixedPoint>| price.map(|p| p.to_f64()).unwrap_or(0.0);
+        let upbit_usdt_krw = rate(self.get_upbit_usdt_krw());
+        let usd_krw = crate::exchange_rate::get_api_rate().unwrap_or(upbit_usdt_krw);
+        let exchanges: Vec<Exchange> = self.stablecoin_prices.iter().map(|r| *r.key()).collect();
+        let exchange_stablecoin_usd = exchanges
+            .into_iter()
+            .map(|exchange| {
+                let prices = StablecoinUsd {
+                    usdt_usd: self.get_usdt_usd_for_exchange(exchange),
+                    usdc_usd: self.get_usdc_usd_for_exchange(exchange),
+                };
+                (exchange, prices)
+            })
+            .collect();
+
+        ConversionRates {
+            usdt_usd: self.get_usdt_usd_price().to_f64(),
+            usdc_usd: self.get_usdc_usd_price().to_f64(),
+            exchange_stablecoin_usd,
+            usd_krw,
+            upbit_usdt_krw,
+            upbit_usdc_krw: rate(self.get_upbit_usdc_krw()),
+            bithumb_usdt_krw: rate(self.get_bithumb_usdt_krw()),
+            bithumb_usdc_krw: rate(self.get_bithumb_usdc_krw()),
+        }
+    }
+
     /// Update common markets.
     pub async fn update_common_markets(&self, markets: CommonMarkets) {
         let mut stored = self.common_markets.write().await;
@@ -678,10 +808,18 @@ impl AppState {
         let mut entry = self
             .orderbook_cache
             .entry(key)
-            .or_insert_with(OrderbookCache::default);
+            .or_insert_with(|| OrderbookCache::new(self.orderbook_levels_for(exchange)));
         entry.update_snapshot_f64(bids, asks);
     }
 
+    /// Orderbook levels cached per side for an exchange.
+    pub fn orderbook_levels_for(&self, exchange: Exchange) -> usize {
+        self.orderbook_levels
+            .get(&exchange)
+            .copied()
+            .unwrap_or_else(|| default_max_levels(exchange))
+    }
+
     /// Apply delta updates to an existing orderbook.
     /// Delta updates only contain changed levels, not the full orderbook.
     pub fn apply_orderbook_delta(
@@ -691,8 +829,6 @@ impl AppState {
         bids: &[(f64, f64)],
         asks: &[(f64, f64)],
     ) {
-        use arbitrage_engine::Side;
-
         let key = (exchange, pair_id);
         if let Some(mut entry) = self.orderbook_cache.get_mut(&key) {
             for &(price, qty) in bids {
@@ -727,7 +863,6 @@ impl AppState {
     }
 
     /// Get orderbook for an exchange and pair.
-    #[allow(dead_code)]
     pub fn get_orderbook(&self, exchange: Exchange, pair_id: u32) -> Option<OrderbookCache> {
         self.orderbook_cache
             .get(&(exchange, pair_id))
@@ -782,6 +917,37 @@ impl AppState {
         self.fee_manager.read().await
     }
 
+    /// Apply account trading fees fetched from exchange APIs.
+    pub async fn apply_trading_fees(&self, updates: &[AccountTradingFees]) {
+        let mut account_fees = self.account_trading_fees.write().await;
+        let mut manager = self.fee_manager.write().await;
+        for update in updates {
+            update.apply(&mut manager);
+            account_fees.insert(update.exchange, update.clone());
+            tracing::info!(
+                "Applied {:?} account trading fees ({} market rates)",
+                update.exchange,
+                update.markets.len()
+            );
+        }
+    }
+
+    /// Update per-network withdrawal fees from a wallet status refresh.
+    pub async fn update_network_fees(
+        &self,
+        statuses: &[crate::wallet_status::ExchangeWalletStatus],
+    ) {
+        let mapping = crate::wallet_status::get_network_mapping();
+        let mut manager = self.fee_manager.write().await;
+        for status in statuses {
+            if let Some((exchange, assets)) =
+                crate::wallet_status::network_withdrawal_fees(status, mapping.as_ref())
+            {
+                manager.update_network_fees(exchange, assets);
+            }
+        }
+    }
+
     /// Update withdrawal fee for an asset (from exchange API).
     #[allow(dead_code)]
     pub async fn update_withdrawal_fee(
@@ -851,6 +1017,58 @@ impl AppState {
         self.stats.record_price_update();
     }
 
+    /// Replace the symbol mappings.
+    pub async fn set_symbol_mappings(&self, mappings: Arc<SymbolMappings>) {
+        *self.symbol_mappings.write().await = mappings;
+    }
+
+    /// Get the current symbol mappings.
+    pub async fn symbol_mappings(&self) -> Arc<SymbolMappings> {
+        self.symbol_mappings.read().await.clone()
+    }
+
+    /// Hold while deriving a new configuration from the current one and
+    /// applying it (remote control, hot reload), so concurrent updates cannot
+    /// overwrite each other.
+    pub async fn lock_config_update(&self) -> MutexGuard<'_, ()> {
+        self.config_update.lock().await
+    }
+
+    /// Apply a reloaded configuration to the running detector, lifecycle tracker
+    /// and fee tables. Exchanges that were disabled have their cached prices
+    /// cleared; the returned events close their open opportunities.
+    pub async fn apply_config(&self, config: AppConfig) -> Vec<LifecycleEvent> {
+        self.detector.set_config((&config.detector).into());
+        self.lifecycle.set_config(LifecycleConfig {
+            threshold_bps: config.detector.min_premium_bps,
+            ..self.lifecycle.config()
+        });
+        self.swap_fee_tables(&config).await;
+
+        let previous = std::mem::replace(&mut *self.config.write().await, config.clone());
+        let mut closed = Vec::new();
+        for settings in &previous.exchanges {
+            if settings.enabled && !config.is_exchange_enabled(settings.exchange) {
+                self.clear_exchange_caches(settings.exchange);
+                closed.extend(self.close_exchange_opportunities(settings.exchange));
+            }
+        }
+        closed
+    }
+
+    /// Replace the trading fee tables with ones built from configuration,
+    /// keeping live withdrawal/network fees and the last account trading fees.
+    async fn swap_fee_tables(&self, config: &AppConfig) {
+        let mut rebuilt = fee_manager_from_config(config);
+        let account_fees = self.account_trading_fees.read().await;
+        let mut manager = self.fee_manager.write().await;
+        rebuilt.carry_over_transfer_fees(std::mem::take(&mut *manager));
+        for update in account_fees.values() {
+            update.apply(&mut rebuilt);
+        }
+        *manager = rebuilt;
+    }
+
     /// Register symbols from common markets for opportunity detection.
     pub fn register_common_markets(&self, markets: &CommonMarkets) {
         for symbol in markets.common_bases() {
@@ -866,34 +1084,94 @@ impl AppState {
     /// Detect opportunities for a pair.
     /// Returns all detected opportunities (both new and updated).
     pub async fn detect_opportunities(&self, pair_id: u32) -> Vec<ArbitrageOpportunity> {
-        use arbitrage_engine::{calculate_optimal_size, DepthFeeConfig};
-
-        // Get exchange rates for multi-denomination premium calculation (lock-free atomic reads)
-        let usdt_krw = self.get_upbit_usdt_krw().map(|p| p.to_f64());
-        let usdc_krw = self.get_upbit_usdc_krw().map(|p| p.to_f64());
-        let usd_krw = crate::exchange_rate::get_api_rate().or(usdt_krw);
+        use arbitrage_engine::{calculate_optimal_size_with_impact, DepthFeeConfig};
+
+        // One rates snapshot prices detection and sizing alike (lock-free atomic reads)
+        let rates = self.conversion_rates();
+
+        // Sample prices for transfer-risk volatility
+        for tick in self.prices.get_all_prices_for_pair(pair_id) {
+            self.transfer_risk.volatility().record(
+                tick.exchange(),
+                pair_id,
+                tick.price().to_f64(),
+                tick.timestamp_ms(),
+            );
+        }
 
         // Detection is now lock-free (DashMap internally)
-        let mut opps = self
-            .detector
-            .detect_with_all_rates(pair_id, usd_krw, usdt_krw, usdc_krw);
+        let mut opps = self.detector.detect_with_conversion_rates(pair_id, &rates);
+
+        let (buy_role, sell_role, min_worst_case_profit, slippage_bps) = {
+            let config = self.config.read().await;
+            (
+                config.execution.buy_fee_role,
+                config.execution.sell_fee_role,
+                config.detector.min_worst_case_profit_usd,
+                config.execution.max_slippage_bps,
+            )
+        };
 
         // OPTIMIZATION: Collect all fee data upfront, then release lock immediately
         let fee_data: Vec<_> = {
             let fee_manager = self.fee_manager.read().await;
             opps.iter()
                 .map(|opp| {
-                    fee_manager.get_arbitrage_fees(
+                    let fees = fee_manager.get_arbitrage_fees_for_legs(
+                        FeeLeg {
+                            exchange: opp.source_exchange,
+                            quote: Some(opp.source_quote.as_str()),
+                            role: buy_role,
+                        },
+                        FeeLeg {
+                            exchange: opp.target_exchange,
+                            quote: Some(opp.target_quote.as_str()),
+                            role: sell_role,
+                        },
+                        &opp.asset.symbol,
+                    );
+                    let network = fee_manager
+                        .cheapest_withdrawal_network(
+                            opp.source_exchange,
+                            opp.target_exchange,
+                            &opp.asset.symbol,
+                        )
+                        .map(|n| n.network.clone());
+                    (fees, network)
+                })
+                .collect()
+        }; // fee_manager lock released here
+
+        // Venue order rules (min notional, lot size) from discovery metadata
+        let sizing_constraints: Vec<_> = {
+            let common = self.common_markets.read().await;
+            opps.iter()
+                .map(|opp| SizingConstraints {
+                    buy: market_rules(
+                        common.as_ref(),
                         opp.source_exchange,
+                        &opp.asset.symbol,
+                        opp.source_quote,
+                    ),
+                    sell: market_rules(
+                        common.as_ref(),
                         opp.target_exchange,
                         &opp.asset.symbol,
-                    )
+                        opp.target_quote,
+                    ),
                 })
                 .collect()
-        }; // fee_manager lock released here
+        };
 
         // Calculate optimal_size for each opportunity using orderbook depth (no locks held)
         for (i, opp) in opps.iter_mut().enumerate() {
+            // Live network data says the asset cannot be moved between the two
+            let ((_, _, transfer), _) = &fee_data[i];
+            if *transfer == TransferFee::NoOpenNetwork {
+                opp.optimal_size_reason = OptimalSizeReason::NoTransferNetwork;
+                continue;
+            }
+
             // Get orderbooks for both sides (DashMap - lock-free)
             let buy_ob = self.orderbook_cache.get(&(opp.source_exchange, pair_id));
             let sell_ob = self.orderbook_cache.get(&(opp.target_exchange, pair_id));
@@ -907,6 +1185,7 @@ impl AppState {
             if let (Some(buy_ob), Some(sell_ob)) = (buy_ob, sell_ob) {
                 let mut buy_asks = buy_ob.asks_vec();
                 let mut sell_bids = sell_ob.bids_vec();
+                let mut constraints = sizing_constraints[i];
 
                 // Normalize prices to the overseas exchange's quote currency
                 let source_is_krw = opp.source_quote == QuoteCurrency::KRW;
@@ -924,15 +1203,11 @@ impl AppState {
                 // Get the appropriate KRW rate based on overseas quote
                 let get_krw_rate_for_quote =
                     |exchange: Exchange, quote: QuoteCurrency| -> Option<u64> {
-                        match quote {
-                            QuoteCurrency::USDT => {
-                                self.get_usdt_krw_for_exchange(exchange).map(|p| p.0)
-                            }
-                            QuoteCurrency::USDC => {
-                                self.get_usdc_krw_for_exchange(exchange).map(|p| p.0)
-                            }
-                            _ => self.get_usdt_krw_for_exchange(exchange).map(|p| p.0),
-                        }
+                        let rate = match quote {
+                            QuoteCurrency::USDC => rates.usdc_krw_for(exchange),
+                            _ => rates.usdt_krw_for(exchange),
+                        };
+                        Some(FixedPoint::from_f64(rate).0)
                     };
 
                 // OPTIMIZATION: In-place KRW conversion to avoid extra heap allocation
@@ -943,6 +1218,9 @@ impl AppState {
                                 *price = (*price as u128 * FixedPoint::SCALE as u128 / rate as u128)
                                     as u64;
                             }
+                            constraints.buy.min_notional =
+                                (constraints.buy.min_notional as u128 * FixedPoint::SCALE as u128
+                                    / rate as u128) as u64;
                         }
                         _ => {
                             opp.optimal_size_reason = OptimalSizeReason::NoConversionRate;
@@ -958,6 +1236,9 @@ impl AppState {
                                 *price = (*price as u128 * FixedPoint::SCALE as u128 / rate as u128)
                                     as u64;
                             }
+                            constraints.sell.min_notional =
+                                (constraints.sell.min_notional as u128 * FixedPoint::SCALE as u128
+                                    / rate as u128) as u64;
                         }
                         _ => {
                             opp.optimal_size_reason = OptimalSizeReason::NoConversionRate;
@@ -967,28 +1248,71 @@ impl AppState {
                 }
 
                 // Use pre-fetched fee data
-                let (buy_fee, sell_fee, withdrawal_fee) = fee_data[i];
+                let ((buy_fee, sell_fee, transfer), ref network) = fee_data[i];
 
                 let fees = DepthFeeConfig {
                     buy_fee_bps: buy_fee,
                     sell_fee_bps: sell_fee,
-                    withdrawal_fee,
+                    withdrawal_fee: transfer.fee().unwrap_or(0),
                 };
-
-                // Calculate optimal size using depth walking algorithm
-                let result = calculate_optimal_size(&buy_asks, &sell_bids, fees);
+                // The bought amount must clear the network's minimum withdrawal
+                constraints.buy.min_qty = constraints.buy.min_qty.max(transfer.min_withdrawal());
+
+                // Calculate optimal size using depth walking, extrapolating
+                // slippage past the cached depth and applying venue order rules
+                let result = calculate_optimal_size_with_impact(
+                    &buy_asks,
+                    &sell_bids,
+                    fees,
+                    Some(&self.impact_model),
+                    &constraints,
+                );
 
                 opp.optimal_size = result.amount;
                 opp.optimal_profit = result.profit;
 
-                if result.amount > 0 {
-                    opp.optimal_size_reason = OptimalSizeReason::Ok;
+                if result.below_min_order {
+                    opp.optimal_size_reason = OptimalSizeReason::BelowMinOrder;
+                } else if result.amount > 0 {
+                    opp.optimal_size_reason = if result.depth_exhausted {
+                        OptimalSizeReason::DepthExhausted
+                    } else {
+                        OptimalSizeReason::Ok
+                    };
+                    let notional = result.amount as u128 * result.avg_buy_price as u128
+                        / FixedPoint::SCALE as u128;
+                    opp.transfer_risk = Some(self.transfer_risk.assess(&TransferRiskInputs {
+                        pair_id,
+                        buy_exchange: opp.source_exchange,
+                        sell_exchange: opp.target_exchange,
+                        network: network.as_deref(),
+                        notional: notional.min(i64::MAX as u128) as i64,
+                        profit: result.profit,
+                    }));
                 } else {
                     opp.optimal_size_reason = OptimalSizeReason::NotProfitable;
                 }
             }
         }
 
+        // Gas for DEX legs and the withdrawal, at live fees and native token prices
+        let now_ms = crate::wallet_status::timestamp_ms();
+        for (opp, (_, network)) in opps.iter_mut().zip(&fee_data) {
+            self.cost_route(opp, network.as_deref(), slippage_bps, &rates, now_ms);
+        }
+
+        // Drop trades whose edge would not survive the transfer
+        if let Some(min_profit) = min_worst_case_profit {
+            let min_profit = (min_profit * FixedPoint::SCALE as f64) as i64;
+            opps.retain(|opp| {
+                opp.transfer_risk
+                    .as_ref()
+                    .is_none_or(|risk| risk.worst_case_profit >= min_profit)
+            });
+        }
+
+        self.score_confidence(&mut opps).await;
+
         // Record stats for new opportunities (lock-free)
         for opp in &opps {
             // Use a simple heuristic: count unique opportunities
@@ -1000,12 +1324,369 @@ impl AppState {
         opps
     }
 
+    /// Build the execution route (buy, transfer, bridge if the legs sit on
+    /// different chains, sell), then fill its gas and bridge costs and the net
+    /// profit estimate (profit at optimal size minus both).
+    fn cost_route(
+        &self,
+        opp: &mut ArbitrageOpportunity,
+        network: Option<&str>,
+        slippage_bps: u16,
+        rates: &ConversionRates,
+        now_ms: u64,
+    ) {
+        let (source, target) = (opp.source_exchange, opp.target_exchange);
+
+        // Profit is in the non-KRW leg's quote currency
+        let (exchange, quote, leg_price) = if opp.source_quote == QuoteCurrency::KRW {
+            (target, opp.target_quote, opp.target_price)
+        } else {
+            (source, opp.source_quote, opp.source_price)
+        };
+        let quote_usd = rates.quote_usd_for(exchange, quote);
+        let token_price_usd = FixedPoint(leg_price).to_f64() * quote_usd;
+
+        opp.route.clear();
+        opp.add_step(RouteStep::trade(
+            source,
+            opp.pair_id,
+            TradeSide::Buy,
+            FixedPoint(opp.source_price),
+            slippage_bps,
+        ));
+        // No transfer between DEX legs on the same chain
+        let same_chain = source.chain().is_some() && source.chain() == target.chain();
+        let transfer_chain = network
+            .and_then(Chain::from_network)
+            .or(source.chain())
+            .or(target.chain());
+        let mut bridge_fee = 0;
+        if let Some(chain) = transfer_chain.filter(|_| !same_chain) {
+            // Funds leave on the source DEX's chain (else the withdrawal network)
+            // and must arrive on the target DEX's chain
+            let from = source.chain().unwrap_or(chain);
+            let to = target.chain().unwrap_or(chain);
+            opp.add_step(RouteStep::withdraw(source, from));
+            if from != to {
+                let request = BridgeQuoteRequest {
+                    asset: opp.asset.symbol.to_string(),
+                    source_chain: from,
+                    dest_chain: to,
+                    amount: opp.optimal_size,
+                    token_price_usd,
+                };
+                if let Some(quote) = self.bridge_quotes.best_quote(&request) {
+                    opp.add_step(RouteStep::bridge(quote.protocol, from, to));
+                    bridge_fee = quote.fee;
+                }
+            }
+            opp.add_step(RouteStep::deposit(target, to));
+        }
+        opp.add_step(RouteStep::trade(
+            target,
+            opp.pair_id,
+            TradeSide::Sell,
+            FixedPoint(opp.target_price),
+            slippage_bps,
+        ));
+
+        let cost = self.gas_oracle.route_cost(
+            &opp.route,
+            |chain| self.native_token_usd(chain, rates),
+            now_ms,
+        );
+        let gas_cost = if quote_usd > 0.0 {
+            cost.usd / quote_usd
+        } else {
+            cost.usd
+        };
+        opp.estimated_gas_cost = FixedPoint::from_f64(gas_cost).0;
+        // Bridge fee is in tokens; price it at the leg's quote price
+        opp.estimated_bridge_fee =
+            (bridge_fee as u128 * leg_price as u128 / FixedPoint::SCALE as u128) as u64;
+        let costs = opp
+            .estimated_gas_cost
+            .saturating_add(opp.estimated_bridge_fee)
+            .min(i64::MAX as u64) as i64;
+        opp.net_profit_estimate = opp.optimal_profit.saturating_sub(costs);
+    }
+
+    /// USD price of a chain's native token: median across exchanges, each
+    /// converted from its quote currency.
+    pub fn native_token_usd(&self, chain: Chain, rates: &ConversionRates) -> Option<f64> {
+        let pair_id = symbol_to_pair_id(chain.native_symbol());
+        let mut prices: Vec<f64> = self
+            .prices
+            .get_all_prices_for_pair(pair_id)
+            .iter()
+            .map(|tick| {
+                tick.price().to_f64() * rates.quote_usd_for(tick.exchange(), tick.quote_currency())
+            })
+            .filter(|price| price.is_finite() && *price > 0.0)
+            .collect();
+        if prices.is_empty() {
+            return None;
+        }
+        prices.sort_by(|a, b| a.total_cmp(b));
+        Some(prices[prices.len() / 2])
+    }
+
+    /// Get the gas oracle.
+    pub fn gas_oracle(&self) -> &GasOracle {
+        &self.gas_oracle
+    }
+
+    /// Get the detection scheduler.
+    pub fn detection_scheduler(&self) -> &DetectionScheduler {
+        &self.detection_scheduler
+    }
+
+    /// Get the feed health tracker.
+    pub fn feed_health(&self) -> &FeedHealth {
+        &self.feed_health
+    }
+
+    /// Get the remote control state.
+    pub fn control(&self) -> &Control {
+        &self.control
+    }
+
+    /// Notifier that wakes market discovery early.
+    pub fn market_discovery(&self) -> Arc<Notify> {
+        self.market_discovery.clone()
+    }
+
+    /// Follow detected opportunities across passes as episodes.
+    /// Gives each route a stable id and returns open/new-peak/close events.
+    pub fn track_opportunities(
+        &self,
+        pair_id: u32,
+        opps: &mut [ArbitrageOpportunity],
+    ) -> Vec<LifecycleEvent> {
+        self.lifecycle
+            .observe(pair_id, opps, crate::wallet_status::timestamp_ms())
+    }
+
+    /// Close open episodes with a leg on a disconnected exchange.
+    pub fn close_exchange_opportunities(&self, exchange: Exchange) -> Vec<LifecycleEvent> {
+        self.lifecycle
+            .close_exchange(exchange, crate::wallet_status::timestamp_ms())
+    }
+
+    /// Get the opportunity lifecycle tracker.
+    pub fn opportunity_lifecycle(&self) -> &OpportunityTracker {
+        &self.lifecycle
+    }
+
+    /// Score confidence for each opportunity from quote age, depth, premium persistence,
+    /// transfer path, 24h volume and symbol mapping conflicts.
+    async fn score_confidence(&self, opps: &mut [ArbitrageOpportunity]) {
+        use arbitrage_engine::{volume_usd, ConfidenceInputs};
+
+        let mappings = self.symbol_mappings().await;
+        let rates = self.conversion_rates();
+
+        for opp in opps.iter_mut() {
+            let source_ex = format!("{:?}", opp.source_exchange);
+            let target_ex = format!("{:?}", opp.target_exchange);
+            let symbol = opp.asset.symbol.as_str();
+
+            let has_transfer_path =
+                crate::wallet_status::is_wallet_status_known(&source_ex, &target_ex).then(|| {
+                    crate::wallet_status::has_transfer_path(symbol, &source_ex, &target_ex)
+                });
+
+            let volume_for = |exchange: Exchange| {
+                self.prices
+                    .get_price(exchange, opp.pair_id)
+                    .and_then(|tick| volume_usd(&tick, &rates))
+            };
+
+            let inputs = ConfidenceInputs {
+                source_timestamp_ms: opp.source_price_timestamp_ms,
+                target_timestamp_ms: opp.target_price_timestamp_ms,
+                source_depth: opp.source_depth,
+                target_depth: opp.target_depth,
+                optimal_size: opp.optimal_size,
+                optimal_size_reason: opp.optimal_size_reason,
+                persistence_ratio: self.detector.persistence_ratio(
+                    opp.pair_id,
+                    opp.source_exchange,
+                    opp.target_exchange,
+                ),
+                has_transfer_path,
+                source_volume_usd: volume_for(opp.source_exchange),
+                target_volume_usd: volume_for(opp.target_exchange),
+                symbol_conflict: mappings.has_conflict(symbol),
+            };
+
+            let (score, breakdown) = self.confidence.score(&inputs);
+            opp.confidence_score = score;
+            opp.confidence_breakdown = Some(breakdown);
+        }
+    }
+
+    /// Recompute the market-wide kimchi premium index from the current premium matrices.
+    /// Uses the forex USD/KRW rate; returns None until the rate and enough markets are available.
+    pub async fn update_kimchi_index(&self) -> Option<KimchiIndex> {
+        use arbitrage_engine::{compute_kimchi_index, volume_usd};
+
+        let usd_krw =
+            crate::exchange_rate::get_api_rate().or_else(crate::exchange_rate::get_usd_krw_rate)?;
+
+        let rates = self.conversion_rates();
+
+        // Korean exchanges mostly don't report 24h volume; fall back to overseas volume
+        let markets = self.detector.kimchi_markets(usd_krw, |pair_id, exchange| {
+            let ticks = self.prices.get_all_prices_for_pair(pair_id);
+            ticks
+                .iter()
+                .find(|tick| tick.exchange() == exchange)
+                .and_then(|tick| volume_usd(tick, &rates))
+                .unwrap_or_else(|| {
+                    ticks
+                        .iter()
+                        .filter(|tick| tick.quote_currency() != QuoteCurrency::KRW)
+                        .filter_map(|tick| volume_usd(tick, &rates))
+                        .sum()
+                })
+        });
+
+        let timestamp_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let index = compute_kimchi_index(&markets, &self.kimchi_index_config, timestamp_ms)?;
+        *self.kimchi_index.write().await = Some(index.clone());
+        Some(index)
+    }
+
+    /// Get the latest kimchi premium index.
+    pub async fn kimchi_index(&self) -> Option<KimchiIndex> {
+        self.kimchi_index.read().await.clone()
+    }
+
     /// Get recent opportunities.
     #[allow(dead_code)]
     pub async fn recent_opportunities(&self) -> Vec<ArbitrageOpportunity> {
         self.opportunities.read().await.clone()
     }
 
+    /// Allocate balances across the current sized opportunities.
+    ///
+    /// Opportunities that share an orderbook or a balance are sized together,
+    /// so the plan never counts the same liquidity or funds twice.
+    pub async fn plan_allocation(&self, balances: &Balances) -> ExecutionPlan {
+        let opps: Vec<_> = self
+            .opportunities
+            .read()
+            .await
+            .iter()
+            .filter(|opp| opp.optimal_size > 0)
+            .cloned()
+            .collect();
+        let (limits, buy_role, sell_role) = {
+            let config = self.config.read().await;
+            (
+                AllocatorLimits::from(&config.execution),
+                config.execution.buy_fee_role,
+                config.execution.sell_fee_role,
+            )
+        };
+
+        let rates = self.conversion_rates();
+        let quote_usd = |exchange: Exchange, quote: QuoteCurrency| {
+            Some(rates.quote_usd_for(exchange, quote)).filter(|usd| *usd > 0.0)
+        };
+        let mut books = HashMap::new();
+        let mut candidates = Vec::with_capacity(opps.len());
+        {
+            let fee_manager = self.fee_manager.read().await;
+            let common = self.common_markets.read().await;
+            for opp in &opps {
+                let (Some(buy_usd), Some(sell_usd)) = (
+                    quote_usd(opp.source_exchange, opp.source_quote),
+                    quote_usd(opp.target_exchange, opp.target_quote),
+                ) else {
+                    continue;
+                };
+                let (Some(buy_ob), Some(sell_ob)) = (
+                    self.orderbook_cache
+                        .get(&(opp.source_exchange, opp.pair_id)),
+                    self.orderbook_cache
+                        .get(&(opp.target_exchange, opp.pair_id)),
+                ) else {
+                    continue;
+                };
+                let (buy_fee_bps, sell_fee_bps, transfer) = fee_manager
+                    .get_arbitrage_fees_for_legs(
+                        FeeLeg {
+                            exchange: opp.source_exchange,
+                            quote: Some(opp.source_quote.as_str()),
+                            role: buy_role,
+                        },
+                        FeeLeg {
+                            exchange: opp.target_exchange,
+                            quote: Some(opp.target_quote.as_str()),
+                            role: sell_role,
+                        },
+                        &opp.asset.symbol,
+                    );
+                // Routes with every network closed cannot be funded
+                let Some(withdrawal_fee) = transfer.fee() else {
+                    continue;
+                };
+                let mut rules = SizingConstraints {
+                    buy: market_rules(
+                        common.as_ref(),
+                        opp.source_exchange,
+                        &opp.asset.symbol,
+                        opp.source_quote,
+                    ),
+                    sell: market_rules(
+                        common.as_ref(),
+                        opp.target_exchange,
+                        &opp.asset.symbol,
+                        opp.target_quote,
+                    ),
+                };
+                rules.buy.min_qty = rules.buy.min_qty.max(transfer.min_withdrawal());
+
+                let best_ask = buy_ob.best_ask().map(|(price, _)| price.to_f64());
+                books
+                    .entry((opp.source_exchange, opp.pair_id, Side::Ask))
+                    .or_insert_with(|| buy_ob.asks_vec());
+                books
+                    .entry((opp.target_exchange, opp.pair_id, Side::Bid))
+                    .or_insert_with(|| sell_ob.bids_vec());
+
+                candidates.push(AllocationCandidate {
+                    opportunity_id: opp.id,
+                    pair_id: opp.pair_id,
+                    asset: opp.asset.symbol.to_string(),
+                    buy: AllocationLeg {
+                        exchange: opp.source_exchange,
+                        quote: opp.source_quote,
+                        fee_bps: buy_fee_bps,
+                        quote_usd: buy_usd,
+                    },
+                    sell: AllocationLeg {
+                        exchange: opp.target_exchange,
+                        quote: opp.target_quote,
+                        fee_bps: sell_fee_bps,
+                        quote_usd: sell_usd,
+                    },
+                    fixed_cost_usd: FixedPoint(withdrawal_fee).to_f64()
+                        * best_ask.unwrap_or(0.0)
+                        * buy_usd,
+                    rules,
+                });
+            }
+        }
+
+        PortfolioAllocator::new(limits).allocate(&candidates, &books, balances)
+    }
+
     /// Get statistics summary.
     pub fn stats_summary(&self) -> StatsSummary {
         self.stats.summary()
@@ -1018,6 +1699,55 @@ pub type SharedState = Arc<AppState>;
 /// Receiver for price update events.
 pub type PriceUpdateReceiver = mpsc::Receiver<PriceUpdateEvent>;
 
+/// Build the fee manager with per-exchange VIP tier, fee-token and fee
+/// override settings.
+fn fee_manager_from_config(config: &AppConfig) -> FeeManager {
+    let mut manager = FeeManager::new();
+    for settings in &config.exchanges {
+        if let Some(tier) = settings.vip_tier {
+            if !manager.set_vip_tier(settings.exchange, tier) {
+                tracing::warn!(
+                    "Unknown VIP tier {} for {:?}, using default fees",
+                    tier,
+                    settings.exchange
+                );
+            }
+        }
+        manager.set_fee_token_enabled(settings.exchange, settings.use_fee_token);
+        if settings.maker_fee_bps.is_some() || settings.taker_fee_bps.is_some() {
+            let fees = manager.get_trading_fees(settings.exchange);
+            manager.update_trading_fees(
+                settings.exchange,
+                FeeConfig {
+                    maker_fee_bps: settings.maker_fee_bps.unwrap_or(fees.maker_fee_bps),
+                    taker_fee_bps: settings.taker_fee_bps.unwrap_or(fees.taker_fee_bps),
+                    ..fees
+                },
+            );
+        }
+    }
+    manager
+}
+
+/// Order rules for a market from discovery metadata (unrestricted if unknown).
+fn market_rules(
+    common: Option<&CommonMarkets>,
+    exchange: Exchange,
+    base: &str,
+    quote: QuoteCurrency,
+) -> MarketRules {
+    let exchange_name = format!("{:?}", exchange);
+    common
+        .and_then(|common| common.common.get(base))
+        .and_then(|markets| {
+            markets
+                .iter()
+                .find(|(ex, info)| *ex == exchange_name && info.quote == quote.as_str())
+        })
+        .map(|(_, info)| info.rules)
+        .unwrap_or_default()
+}
+
 /// Create shared state and price update receiver.
 pub fn create_state(config: AppConfig) -> (SharedState, PriceUpdateReceiver) {
     let (state, rx) = AppState::new(config);
@@ -1093,6 +1823,132 @@ mod tests {
         assert_eq!(summary.price_updates, 1);
     }
 
+    #[tokio::test]
+    async fn test_cost_route_fills_gas_and_net_profit() {
+        let (state, _rx) = AppState::new(AppConfig::default());
+        let eth = symbol_to_pair_id("ETH");
+        for (exchange, price) in [
+            (Exchange::Binance, 2_000.0),
+            (Exchange::Coinbase, 2_100.0),
+            (Exchange::Kraken, 2_050.0),
+        ] {
+            state
+                .update_price(exchange, eth, FixedPoint::from_f64(price))
+                .await;
+        }
+        let rates = ConversionRates::uniform_krw(None, None, None);
+        assert_eq!(
+            state.native_token_usd(Chain::Ethereum, &rates),
+            Some(2_050.0)
+        );
+        assert_eq!(state.native_token_usd(Chain::Solana, &rates), None);
+
+        state
+            .gas_oracle()
+            .record(Chain::Ethereum, 20_000_000_000, 2_000_000_000, 1_000);
+        let mut opp = ArbitrageOpportunity::new(
+            1,
+            Exchange::Binance,
+            Exchange::UniswapV3,
+            arbitrage_core::Asset::eth(),
+            FixedPoint::from_f64(2_000.0),
+            FixedPoint::from_f64(2_040.0),
+        )
+        .with_optimal_size(
+            FixedPoint::from_f64(1.0).0,
+            FixedPoint::from_f64(30.0).0 as i64,
+        );
+        state.cost_route(&mut opp, Some("ERC20"), 50, &rates, 2_000);
+
+        // Buy, withdraw, deposit, swap: (65k + 150k) gas at 22 gwei and $2050/ETH
+        assert_eq!(opp.total_hops, 4);
+        let gas = FixedPoint(opp.estimated_gas_cost).to_f64();
+        assert!((gas - 9.6965).abs() < 1e-6);
+        assert_eq!(
+            opp.net_profit_estimate,
+            opp.optimal_profit - opp.estimated_gas_cost as i64
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cost_route_bridges_dex_chains() {
+        let (state, _rx) = AppState::new(AppConfig::default());
+        let rates = ConversionRates::uniform_krw(None, None, None);
+        let mut opp = ArbitrageOpportunity::new(
+            1,
+            Exchange::UniswapV3,
+            Exchange::Raydium,
+            arbitrage_core::Asset::eth(),
+            FixedPoint::from_f64(2_000.0),
+            FixedPoint::from_f64(2_040.0),
+        )
+        .with_optimal_size(
+            FixedPoint::from_f64(1.0).0,
+            FixedPoint::from_f64(30.0).0 as i64,
+        );
+        state.cost_route(&mut opp, None, 50, &rates, 0);
+
+        // Swap, withdraw, bridge, deposit, swap
+        assert_eq!(opp.total_hops, 5);
+        assert!(matches!(
+            opp.route[2],
+            RouteStep::Bridge {
+                source_chain: Chain::Ethereum,
+                dest_chain: Chain::Solana,
+                ..
+            }
+        ));
+        assert!(opp.estimated_bridge_fee > 0);
+        assert_eq!(
+            opp.net_profit_estimate,
+            opp.optimal_profit - (opp.estimated_gas_cost + opp.estimated_bridge_fee) as i64
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_swaps_thresholds_and_fee_tables() {
+        let (state, _rx) = AppState::new(AppConfig::default());
+        state
+            .update_withdrawal_fee(Exchange::Upbit, "XRP", 1_000, 0)
+            .await;
+        state
+            .apply_trading_fees(&[AccountTradingFees {
+                exchange: Exchange::Bybit,
+                account: Some(FeeConfig::with_maker_taker(2, 4)),
+                markets: Vec::new(),
+                fee_token_applied: false,
+            }])
+            .await;
+
+        let mut config = AppConfig::default();
+        config.detector.min_premium_bps = 80;
+        for settings in &mut config.exchanges {
+            match settings.exchange {
+                Exchange::Binance => settings.taker_fee_bps = Some(7),
+                Exchange::Upbit => settings.enabled = false,
+                _ => {}
+            }
+        }
+        state.apply_config(config).await;
+
+        assert_eq!(state.detector.config().min_premium_bps, 80);
+        assert_eq!(state.opportunity_lifecycle().config().threshold_bps, 80);
+        assert!(!state
+            .config
+            .read()
+            .await
+            .is_exchange_enabled(Exchange::Upbit));
+
+        let fees = state.get_fee_manager().await;
+        assert_eq!(fees.get_trading_fees(Exchange::Binance).taker_fee_bps, 7);
+        // Account rates and live withdrawal fees survive the swap
+        assert_eq!(fees.get_trading_fees(Exchange::Bybit).taker_fee_bps, 4);
+        assert_eq!(
+            fees.get_withdrawal_fee_amount(Exchange::Upbit, "XRP"),
+            1_000
+        );
+    }
+
     #[tokio::test]
     async fn test_shared_state() {
         let config = AppConfig::default();
@@ -4,8 +4,10 @@
 //! - WebSocket disconnections
 //! - WebSocket reconnections
 //! - Circuit breaker events
+//! - Stablecoin depeg level changes
 
 use arbitrage_core::Exchange;
+use arbitrage_engine::{DepegEvent, DepegLevel};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -22,6 +24,8 @@ pub enum StatusEvent {
     Reconnected(Exchange),
     /// Circuit breaker opened - connection attempts blocked
     CircuitBreakerOpen(Exchange, Duration),
+    /// Stablecoin peg moved into another depeg level
+    Depeg(DepegEvent),
     /// Server started
     ServerStarted,
     /// Server stopping
@@ -43,6 +47,8 @@ pub struct StatusNotifierConfig {
     pub notify_on_reconnect: bool,
     /// Whether to send notifications on circuit breaker events
     pub notify_on_circuit_breaker: bool,
+    /// Whether to send notifications on stablecoin depeg level changes
+    pub notify_on_depeg: bool,
 }
 
 impl StatusNotifierConfig {
@@ -63,6 +69,7 @@ impl StatusNotifierConfig {
             notify_on_disconnect: true,
             notify_on_reconnect: true,
             notify_on_circuit_breaker: true,
+            notify_on_depeg: true,
         })
     }
 }
@@ -118,6 +125,27 @@ impl StatusNotifier {
                     exchange, wait_time
                 )
             }
+            StatusEvent::Depeg(event) => {
+                if !self.config.notify_on_depeg {
+                    return;
+                }
+                let icon = match event.level {
+                    DepegLevel::Critical => "🚨",
+                    DepegLevel::Warning => "⚠️",
+                    DepegLevel::Normal => "✅",
+                };
+                format!(
+                    "{} <b>{:?}</b> {}/{} {:?} → {:?}\nRate {:.4} ({:+} bps from parity)",
+                    icon,
+                    event.exchange,
+                    event.stablecoin.as_str(),
+                    event.reference.as_str(),
+                    event.previous_level,
+                    event.level,
+                    event.rate,
+                    event.deviation_bps
+                )
+            }
             StatusEvent::ServerStarted | StatusEvent::ServerStopping => {
                 // Don't send notifications for server start/stop
                 return;
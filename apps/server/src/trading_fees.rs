@@ -0,0 +1,440 @@
+//! Account trading fee sync from authenticated exchange endpoints.
+//!
+//! Fetches the maker/taker rates actually charged to the account (VIP tier,
+//! promotions, per-market rates) and applies them to the fee manager so net
+//! profit reflects what we pay. Exchanges without API keys keep the schedule
+//! defaults.
+
+use crate::state::SharedState;
+use crate::wallet_status::{sign_binance, sign_bybit, sign_gateio, timestamp_ms};
+use arbitrage_core::Exchange;
+use arbitrage_engine::{FeeConfig, FeeManager};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Quote currencies used to split concatenated symbols, longest first so a
+/// shorter quote never matches the tail of a longer one (BTCTUSD is BTC/TUSD).
+const SYMBOL_QUOTES: &[&str] = &[
+    "FDUSD", "USDT", "USDC", "BUSD", "TUSD", "USDP", "USDE", "EURI", "USD", "DAI", "EUR", "TRY",
+    "BRL", "JPY", "BTC", "ETH", "BNB",
+];
+
+/// Account trading fees reported by an exchange.
+#[derive(Debug, Clone)]
+pub struct AccountTradingFees {
+    pub exchange: Exchange,
+    /// Account-wide rates (None = keep current rates).
+    pub account: Option<FeeConfig>,
+    /// Per-market rates: (base, quote, fees).
+    pub markets: Vec<(String, String, FeeConfig)>,
+    /// Whether the reported rates already include the fee-token discount.
+    pub fee_token_applied: bool,
+}
+
+impl AccountTradingFees {
+    fn new(exchange: Exchange) -> Self {
+        Self {
+            exchange,
+            account: None,
+            markets: Vec::new(),
+            fee_token_applied: false,
+        }
+    }
+
+    /// Apply the fetched rates to a fee manager.
+    pub fn apply(&self, manager: &mut FeeManager) {
+        let exchange = self.exchange;
+        if let Some(account) = self.account {
+            let current = manager.get_trading_fees(exchange);
+            manager.update_trading_fees(
+                exchange,
+                FeeConfig {
+                    maker_fee_bps: account.maker_fee_bps,
+                    taker_fee_bps: account.taker_fee_bps,
+                    ..current
+                },
+            );
+        }
+        if !self.markets.is_empty() {
+            manager.clear_market_fees(exchange);
+            for (base, quote, fees) in &self.markets {
+                manager.set_market_fees(exchange, Some(base), Some(quote), *fees);
+            }
+        }
+        if self.fee_token_applied {
+            // Avoid discounting twice
+            manager.set_fee_token_enabled(exchange, false);
+        }
+    }
+}
+
+/// Convert a decimal fee rate (e.g. "0.00075") to basis points, rounded up.
+fn rate_to_bps(rate: &str) -> Option<i32> {
+    let rate: f64 = rate.trim().parse().ok()?;
+    if !rate.is_finite() {
+        return None;
+    }
+    // Round away tiny float error before ceiling (0.001 * 10000 = 10.000000000000002)
+    let bps = (rate * 10_000.0 * 1e6).round() / 1e6;
+    Some(bps.ceil() as i32)
+}
+
+/// Split a concatenated symbol (e.g. BTCFDUSD) into base and quote.
+fn split_symbol(symbol: &str) -> Option<(String, String)> {
+    let s = symbol.to_uppercase();
+    SYMBOL_QUOTES.iter().find_map(|quote| {
+        let base = s.strip_suffix(quote)?;
+        (!base.is_empty()).then(|| (base.to_string(), quote.to_string()))
+    })
+}
+
+fn build_client() -> Result<Client, String> {
+    Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to build client: {}", e))
+}
+
+// ============================================================================
+// Binance (GET /sapi/v1/asset/tradeFee)
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct BinanceTradeFee {
+    symbol: String,
+    #[serde(rename = "makerCommission")]
+    maker_commission: String,
+    #[serde(rename = "takerCommission")]
+    taker_commission: String,
+}
+
+fn parse_binance_trade_fees(fees: Vec<BinanceTradeFee>) -> AccountTradingFees {
+    let mut result = AccountTradingFees::new(Exchange::Binance);
+    for fee in fees {
+        let (Some((base, quote)), Some(maker), Some(taker)) = (
+            split_symbol(&fee.symbol),
+            rate_to_bps(&fee.maker_commission),
+            rate_to_bps(&fee.taker_commission),
+        ) else {
+            continue;
+        };
+        result
+            .markets
+            .push((base, quote, FeeConfig::with_maker_taker(maker, taker)));
+    }
+    result
+}
+
+/// Fetch Binance per-symbol account fees (requires BINANCE_API_KEY, BINANCE_SECRET_KEY).
+async fn fetch_binance_trading_fees() -> Result<Option<AccountTradingFees>, String> {
+    let api_key = std::env::var("BINANCE_API_KEY").unwrap_or_default();
+    let secret_key = std::env::var("BINANCE_SECRET_KEY").unwrap_or_default();
+
+    if api_key.is_empty() || secret_key.is_empty() {
+        info!("Binance API keys not configured, using fee schedule defaults");
+        return Ok(None);
+    }
+
+    let client = build_client()?;
+    let query = format!("timestamp={}", timestamp_ms());
+    let signature = sign_binance(&query, &secret_key);
+    let url = format!(
+        "https://api.binance.com/sapi/v1/asset/tradeFee?{}&signature={}",
+        query, signature
+    );
+
+    let resp = client
+        .get(&url)
+        .header("X-MBX-APIKEY", &api_key)
+        .send()
+        .await
+        .map_err(|e| format!("Binance trade fee request failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let error_text = resp.text().await.unwrap_or_default();
+        return Err(format!("Binance trade fee API error: {}", error_text));
+    }
+
+    let fees: Vec<BinanceTradeFee> = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Binance trade fees: {}", e))?;
+
+    Ok(Some(parse_binance_trade_fees(fees)))
+}
+
+// ============================================================================
+// Bybit (GET /v5/account/fee-rate)
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct BybitFeeRate {
+    symbol: String,
+    #[serde(rename = "makerFeeRate")]
+    maker_fee_rate: String,
+    #[serde(rename = "takerFeeRate")]
+    taker_fee_rate: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitFeeRateResult {
+    list: Vec<BybitFeeRate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BybitFeeRateResponse {
+    #[serde(rename = "retCode")]
+    ret_code: i32,
+    #[serde(rename = "retMsg")]
+    ret_msg: String,
+    result: Option<BybitFeeRateResult>,
+}
+
+fn parse_bybit_fee_rates(rates: Vec<BybitFeeRate>) -> AccountTradingFees {
+    let mut result = AccountTradingFees::new(Exchange::Bybit);
+    for rate in rates {
+        let (Some((base, quote)), Some(maker), Some(taker)) = (
+            split_symbol(&rate.symbol),
+            rate_to_bps(&rate.maker_fee_rate),
+            rate_to_bps(&rate.taker_fee_rate),
+        ) else {
+            continue;
+        };
+        result
+            .markets
+            .push((base, quote, FeeConfig::with_maker_taker(maker, taker)));
+    }
+    result
+}
+
+/// Fetch Bybit spot account fee rates (requires BYBIT_API_KEY, BYBIT_SECRET_KEY).
+async fn fetch_bybit_trading_fees() -> Result<Option<AccountTradingFees>, String> {
+    let api_key = std::env::var("BYBIT_API_KEY").unwrap_or_default();
+    let secret_key = std::env::var("BYBIT_SECRET_KEY").unwrap_or_default();
+
+    if api_key.is_empty() || secret_key.is_empty() {
+        info!("Bybit API keys not configured, using fee schedule defaults");
+        return Ok(None);
+    }
+
+    let client = build_client()?;
+    let timestamp = timestamp_ms();
+    let recv_window = "5000";
+    let query = "category=spot";
+    let signature = sign_bybit(timestamp, &api_key, recv_window, query, &secret_key);
+
+    let resp = client
+        .get(format!(
+            "https://api.bybit.com/v5/account/fee-rate?{}",
+            query
+        ))
+        .header("X-BAPI-API-KEY", &api_key)
+        .header("X-BAPI-SIGN", signature)
+        .header("X-BAPI-TIMESTAMP", timestamp.to_string())
+        .header("X-BAPI-RECV-WINDOW", recv_window)
+        .send()
+        .await
+        .map_err(|e| format!("Bybit fee rate request failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let error_text = resp.text().await.unwrap_or_default();
+        return Err(format!("Bybit fee rate API error: {}", error_text));
+    }
+
+    let response: BybitFeeRateResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Bybit fee rates: {}", e))?;
+
+    if response.ret_code != 0 {
+        return Err(format!(
+            "Bybit API error: {} - {}",
+            response.ret_code, response.ret_msg
+        ));
+    }
+
+    let rates = response.result.map(|r| r.list).unwrap_or_default();
+    Ok(Some(parse_bybit_fee_rates(rates)))
+}
+
+// ============================================================================
+// Gate.io (GET /api/v4/wallet/fee, HMAC-SHA512 signing)
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct GateIOFee {
+    maker_fee: String,
+    taker_fee: String,
+    #[serde(default)]
+    gt_discount: bool,
+    #[serde(default)]
+    gt_maker_fee: String,
+    #[serde(default)]
+    gt_taker_fee: String,
+}
+
+fn parse_gateio_fee(fee: GateIOFee) -> AccountTradingFees {
+    let mut result = AccountTradingFees::new(Exchange::GateIO);
+    let gt_rates = (
+        rate_to_bps(&fee.gt_maker_fee),
+        rate_to_bps(&fee.gt_taker_fee),
+    );
+    let (maker, taker) = match gt_rates {
+        (Some(maker), Some(taker)) if fee.gt_discount => {
+            result.fee_token_applied = true;
+            (Some(maker), Some(taker))
+        }
+        _ => (rate_to_bps(&fee.maker_fee), rate_to_bps(&fee.taker_fee)),
+    };
+    if let (Some(maker), Some(taker)) = (maker, taker) {
+        result.account = Some(FeeConfig::with_maker_taker(maker, taker));
+    }
+    result
+}
+
+/// Fetch Gate.io account fee rates (requires GATEIO_API_KEY, GATEIO_SECRET_KEY).
+async fn fetch_gateio_trading_fees() -> Result<Option<AccountTradingFees>, String> {
+    let api_key = std::env::var("GATEIO_API_KEY").unwrap_or_default();
+    let secret_key = std::env::var("GATEIO_SECRET_KEY").unwrap_or_default();
+
+    if api_key.is_empty() || secret_key.is_empty() {
+        info!("Gate.io API keys not configured, using fee schedule defaults");
+        return Ok(None);
+    }
+
+    let client = build_client()?;
+    let path = "/api/v4/wallet/fee";
+    let timestamp = timestamp_ms() / 1000;
+    let signature = sign_gateio("GET", path, "", "", timestamp, &secret_key);
+
+    let resp = client
+        .get(format!("https://api.gateio.ws{}", path))
+        .header("KEY", &api_key)
+        .header("Timestamp", timestamp.to_string())
+        .header("SIGN", signature)
+        .send()
+        .await
+        .map_err(|e| format!("Gate.io fee request failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let error_text = resp.text().await.unwrap_or_default();
+        return Err(format!("Gate.io fee API error: {}", error_text));
+    }
+
+    let fee: GateIOFee = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Gate.io fees: {}", e))?;
+
+    Ok(Some(parse_gateio_fee(fee)))
+}
+
+// ============================================================================
+// Aggregate and Update Functions
+// ============================================================================
+
+/// Fetch account trading fees from all exchanges with API keys.
+pub async fn fetch_all_trading_fees() -> Vec<AccountTradingFees> {
+    let (binance, bybit, gateio) = tokio::join!(
+        fetch_binance_trading_fees(),
+        fetch_bybit_trading_fees(),
+        fetch_gateio_trading_fees()
+    );
+
+    let mut results = Vec::new();
+    for (name, result) in [("Binance", binance), ("Bybit", bybit), ("Gate.io", gateio)] {
+        match result {
+            Ok(Some(fees)) => results.push(fees),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to fetch {} trading fees: {}", name, e),
+        }
+    }
+    results
+}
+
+/// Run account trading fee updater loop.
+/// Updates every hour (fee tiers change at most daily).
+pub async fn run_trading_fee_updater(state: SharedState) {
+    loop {
+        let updates = fetch_all_trading_fees().await;
+        if !updates.is_empty() {
+            state.apply_trading_fees(&updates).await;
+        }
+
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_to_bps() {
+        assert_eq!(rate_to_bps("0.001"), Some(10));
+        assert_eq!(rate_to_bps("0.00075"), Some(8));
+        assert_eq!(rate_to_bps("0"), Some(0));
+        assert_eq!(rate_to_bps("-0.0001"), Some(-1));
+        assert_eq!(rate_to_bps("abc"), None);
+    }
+
+    #[test]
+    fn test_split_symbol() {
+        assert_eq!(
+            split_symbol("BTCFDUSD"),
+            Some(("BTC".to_string(), "FDUSD".to_string()))
+        );
+        assert_eq!(
+            split_symbol("ethbtc"),
+            Some(("ETH".to_string(), "BTC".to_string()))
+        );
+        assert_eq!(
+            split_symbol("BTCTUSD"),
+            Some(("BTC".to_string(), "TUSD".to_string()))
+        );
+        assert_eq!(
+            split_symbol("ETHUSDE"),
+            Some(("ETH".to_string(), "USDE".to_string()))
+        );
+        assert_eq!(split_symbol("USDT"), None);
+
+        assert!(SYMBOL_QUOTES
+            .windows(2)
+            .all(|pair| pair[0].len() >= pair[1].len()));
+    }
+
+    #[test]
+    fn test_binance_fees_become_market_overrides() {
+        let fees: Vec<BinanceTradeFee> = serde_json::from_str(
+            r#"[
+                {"symbol":"BTCFDUSD","makerCommission":"0","takerCommission":"0"},
+                {"symbol":"ETHUSDT","makerCommission":"0.0009","takerCommission":"0.001"}
+            ]"#,
+        )
+        .unwrap();
+        let mut manager = FeeManager::new();
+        parse_binance_trade_fees(fees).apply(&mut manager);
+
+        let fees = manager.get_market_trading_fees(Exchange::Binance, "ETH", Some("USDT"));
+        assert_eq!((fees.maker_fee_bps, fees.taker_fee_bps), (9, 10));
+        let fees = manager.get_market_trading_fees(Exchange::Binance, "BTC", Some("FDUSD"));
+        assert_eq!(fees.taker_fee_bps, 0);
+    }
+
+    #[test]
+    fn test_gateio_gt_rates_disable_token_discount() {
+        let fee: GateIOFee = serde_json::from_str(
+            r#"{"maker_fee":"0.002","taker_fee":"0.002","gt_discount":true,
+                "gt_maker_fee":"0.0015","gt_taker_fee":"0.0015"}"#,
+        )
+        .unwrap();
+        let mut manager = FeeManager::new();
+        manager.set_fee_token_enabled(Exchange::GateIO, true);
+        parse_gateio_fee(fee).apply(&mut manager);
+
+        assert_eq!(manager.fee_token_discount_pct(Exchange::GateIO), 0);
+        assert_eq!(manager.get_trading_fees(Exchange::GateIO).taker_fee_bps, 15);
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! Fetches wallet status from exchanges periodically and broadcasts via WebSocket.
 
+use crate::state::SharedState;
 use crate::ws_server::{self, BroadcastSender};
+use arbitrage_core::{Exchange, FixedPoint};
+use arbitrage_engine::NetworkWithdrawalFee;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -209,7 +212,7 @@ pub fn find_common_networks_for_asset(
 
     // Debug logging for troubleshooting
     if tracing::enabled!(tracing::Level::DEBUG)
-        && (source_networks.len() > 0 || target_networks.len() > 0)
+        && (!source_networks.is_empty() || !target_networks.is_empty())
     {
         tracing::debug!(
             "Network check for {} ({} -> {}): source_withdraw={:?}, target_deposit={:?}, common={:?}",
@@ -235,7 +238,8 @@ pub struct NetworkStatus {
     pub deposit_enabled: bool,
     pub withdraw_enabled: bool,
     pub min_withdraw: f64,
-    pub withdraw_fee: f64,
+    /// Withdrawal fee in asset units (None = not reported by the exchange).
+    pub withdraw_fee: Option<f64>,
     pub confirms_required: u32,
 }
 
@@ -257,7 +261,7 @@ pub struct ExchangeWalletStatus {
     pub last_updated: u64,
 }
 
-fn timestamp_ms() -> u64 {
+pub(crate) fn timestamp_ms() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
@@ -329,7 +333,7 @@ async fn fetch_upbit_wallet_status() -> Result<ExchangeWalletStatus, String> {
                         deposit_enabled,
                         withdraw_enabled,
                         min_withdraw: 0.0,
-                        withdraw_fee: 0.0,
+                        withdraw_fee: None,
                         confirms_required: 0,
                     }
                 })
@@ -437,7 +441,7 @@ async fn fetch_coinbase_wallet_status() -> Result<ExchangeWalletStatus, String>
                         .as_ref()
                         .and_then(|d| d.min_withdrawal_amount)
                         .unwrap_or(0.0),
-                    withdraw_fee: 0.0,
+                    withdraw_fee: None,
                     confirms_required: 0,
                 }]
             } else {
@@ -451,7 +455,7 @@ async fn fetch_coinbase_wallet_status() -> Result<ExchangeWalletStatus, String>
                             deposit_enabled: is_online,
                             withdraw_enabled: is_online,
                             min_withdraw: n.min_withdrawal_amount.unwrap_or(0.0),
-                            withdraw_fee: 0.0,
+                            withdraw_fee: None,
                             confirms_required: n.network_confirmations.unwrap_or(0),
                         }
                     })
@@ -598,7 +602,7 @@ async fn fetch_bithumb_wallet_status() -> Result<ExchangeWalletStatus, String> {
                         deposit_enabled,
                         withdraw_enabled,
                         min_withdraw: 0.0,
-                        withdraw_fee: 0.0,
+                        withdraw_fee: None,
                         confirms_required: 0,
                     }
                 })
@@ -656,7 +660,7 @@ struct BinanceNetwork {
     min_confirm: u32,
 }
 
-fn sign_binance(query: &str, secret: &str) -> String {
+pub(crate) fn sign_binance(query: &str, secret: &str) -> String {
     use hmac::{Hmac, Mac};
     use sha2::Sha256;
 
@@ -726,7 +730,7 @@ async fn fetch_binance_wallet_status() -> Result<ExchangeWalletStatus, String> {
                     deposit_enabled: n.deposit_enable,
                     withdraw_enabled: n.withdraw_enable,
                     min_withdraw: n.withdraw_min.parse().unwrap_or(0.0),
-                    withdraw_fee: n.withdraw_fee.parse().unwrap_or(0.0),
+                    withdraw_fee: n.withdraw_fee.parse().ok(),
                     confirms_required: n.min_confirm,
                 })
                 .collect();
@@ -789,7 +793,7 @@ struct BybitCoinInfoResult {
     rows: Vec<BybitCoinInfo>,
 }
 
-fn sign_bybit(
+pub(crate) fn sign_bybit(
     timestamp: u64,
     api_key: &str,
     recv_window: &str,
@@ -876,7 +880,7 @@ async fn fetch_bybit_wallet_status() -> Result<ExchangeWalletStatus, String> {
                         deposit_enabled,
                         withdraw_enabled,
                         min_withdraw: chain.min_withdraw.parse().unwrap_or(0.0),
-                        withdraw_fee: chain.withdraw_fee.parse().unwrap_or(0.0),
+                        withdraw_fee: chain.withdraw_fee.parse().ok(),
                         confirms_required: chain.confirmation.parse().unwrap_or(0),
                     }
                 })
@@ -907,9 +911,86 @@ async fn fetch_bybit_wallet_status() -> Result<ExchangeWalletStatus, String> {
 }
 
 // ============================================================================
-// Gate.io Client (Public API)
+// Gate.io Client (Public API, withdrawal fees with API key)
 // ============================================================================
 
+pub(crate) fn sign_gateio(
+    method: &str,
+    path: &str,
+    query: &str,
+    body: &str,
+    timestamp: u64,
+    secret: &str,
+) -> String {
+    use hmac::{Hmac, Mac};
+    use sha2::{Digest, Sha512};
+
+    type HmacSha512 = Hmac<Sha512>;
+    let body_hash = hex::encode(Sha512::digest(body.as_bytes()));
+    let sign_str = format!(
+        "{}\n{}\n{}\n{}\n{}",
+        method, path, query, body_hash, timestamp
+    );
+    let mut mac = HmacSha512::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(sign_str.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Gate.io withdrawal fees per currency (authenticated)
+#[derive(Debug, Deserialize)]
+struct GateIOWithdrawStatus {
+    currency: String,
+    #[serde(default)]
+    withdraw_amount_mini: String,
+    /// chain -> fixed withdrawal fee
+    #[serde(default)]
+    withdraw_fix_on_chains: HashMap<String, String>,
+}
+
+/// Fetch Gate.io withdrawal fees (requires GATEIO_API_KEY, GATEIO_SECRET_KEY).
+/// Returns None if no API keys are configured.
+async fn fetch_gateio_withdraw_fees(
+    client: &Client,
+) -> Result<Option<HashMap<String, GateIOWithdrawStatus>>, String> {
+    let api_key = std::env::var("GATEIO_API_KEY").unwrap_or_default();
+    let secret_key = std::env::var("GATEIO_SECRET_KEY").unwrap_or_default();
+
+    if api_key.is_empty() || secret_key.is_empty() {
+        info!("Gate.io API keys not configured (GATEIO_API_KEY, GATEIO_SECRET_KEY)");
+        return Ok(None);
+    }
+
+    let path = "/api/v4/wallet/withdraw_status";
+    let timestamp = timestamp_ms() / 1000;
+    let signature = sign_gateio("GET", path, "", "", timestamp, &secret_key);
+
+    let resp = client
+        .get(format!("https://api.gateio.ws{}", path))
+        .header("KEY", &api_key)
+        .header("Timestamp", timestamp.to_string())
+        .header("SIGN", signature)
+        .send()
+        .await
+        .map_err(|e| format!("Gate.io withdraw status request failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let error_text = resp.text().await.unwrap_or_default();
+        return Err(format!("Gate.io withdraw status API error: {}", error_text));
+    }
+
+    let statuses: Vec<GateIOWithdrawStatus> = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Gate.io withdraw status: {}", e))?;
+
+    Ok(Some(
+        statuses
+            .into_iter()
+            .map(|s| (s.currency.clone(), s))
+            .collect(),
+    ))
+}
+
 /// Gate.io chain info
 #[derive(Debug, Deserialize)]
 struct GateIOChain {
@@ -938,6 +1019,25 @@ struct GateIOCurrency {
     chains: Vec<GateIOChain>,
 }
 
+/// Minimum withdrawal and fee of a Gate.io currency on one chain.
+/// The fee is None when the withdraw status has no entry for the currency or chain.
+fn gateio_chain_fees(
+    fees: Option<&HashMap<String, GateIOWithdrawStatus>>,
+    currency: &str,
+    chain: &str,
+) -> (f64, Option<f64>) {
+    let Some(status) = fees.and_then(|fees| fees.get(currency)) else {
+        return (0.0, None);
+    };
+    (
+        status.withdraw_amount_mini.parse().unwrap_or(0.0),
+        status
+            .withdraw_fix_on_chains
+            .get(chain)
+            .and_then(|fee| fee.parse().ok()),
+    )
+}
+
 /// Fetch Gate.io wallet status (public API - no auth required)
 async fn fetch_gateio_wallet_status() -> Result<ExchangeWalletStatus, String> {
     let client = Client::builder()
@@ -961,6 +1061,13 @@ async fn fetch_gateio_wallet_status() -> Result<ExchangeWalletStatus, String> {
         .await
         .map_err(|e| format!("Failed to parse Gate.io currencies: {}", e))?;
 
+    let withdraw_fees = fetch_gateio_withdraw_fees(&client)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to fetch Gate.io withdrawal fees: {}", e);
+            None
+        });
+
     let wallet_status: Vec<AssetWalletStatus> = currencies
         .into_iter()
         .filter(|c| !c.delisted && !c.trade_disabled)
@@ -973,20 +1080,24 @@ async fn fetch_gateio_wallet_status() -> Result<ExchangeWalletStatus, String> {
                     deposit_enabled: !c.deposit_disabled,
                     withdraw_enabled: !c.withdraw_disabled,
                     min_withdraw: 0.0,
-                    withdraw_fee: 0.0,
+                    withdraw_fee: None,
                     confirms_required: 0,
                 }]
             } else {
                 c.chains
                     .iter()
-                    .map(|chain| NetworkStatus {
-                        network: chain.name.clone(),
-                        name: chain.name.clone(),
-                        deposit_enabled: !chain.deposit_disabled,
-                        withdraw_enabled: !chain.withdraw_disabled,
-                        min_withdraw: 0.0,
-                        withdraw_fee: 0.0,
-                        confirms_required: 0,
+                    .map(|chain| {
+                        let (min_withdraw, withdraw_fee) =
+                            gateio_chain_fees(withdraw_fees.as_ref(), &c.currency, &chain.name);
+                        NetworkStatus {
+                            network: chain.name.clone(),
+                            name: chain.name.clone(),
+                            deposit_enabled: !chain.deposit_disabled,
+                            withdraw_enabled: !chain.withdraw_disabled,
+                            min_withdraw,
+                            withdraw_fee,
+                            confirms_required: 0,
+                        }
                     })
                     .collect()
             };
@@ -1084,6 +1195,55 @@ pub async fn fetch_all_wallet_status() -> Vec<ExchangeWalletStatus> {
     results
 }
 
+/// Map a wallet status exchange name to an exchange.
+fn exchange_from_name(name: &str) -> Option<Exchange> {
+    match name {
+        "Upbit" => Some(Exchange::Upbit),
+        "Bithumb" => Some(Exchange::Bithumb),
+        "Coinbase" => Some(Exchange::Coinbase),
+        "Binance" => Some(Exchange::Binance),
+        "Bybit" => Some(Exchange::Bybit),
+        "GateIO" => Some(Exchange::GateIO),
+        _ => None,
+    }
+}
+
+/// Live network fees of an exchange, per asset.
+pub type ExchangeNetworkFees = (Exchange, Vec<(String, Vec<NetworkWithdrawalFee>)>);
+
+/// Convert wallet status into per-network withdrawal fees for the fee manager.
+/// Network IDs are mapped to canonical names so they can be matched across exchanges.
+pub fn network_withdrawal_fees(
+    status: &ExchangeWalletStatus,
+    mapping: Option<&NetworkNameMapping>,
+) -> Option<ExchangeNetworkFees> {
+    let exchange = exchange_from_name(&status.exchange)?;
+    let to_fixed = |amount: f64| FixedPoint::from_f64(amount.max(0.0)).0;
+
+    let assets = status
+        .wallet_status
+        .iter()
+        .map(|asset| {
+            let networks = asset
+                .networks
+                .iter()
+                .map(|n| NetworkWithdrawalFee {
+                    network: mapping
+                        .and_then(|m| m.get_canonical(&status.exchange, &n.network))
+                        .unwrap_or_else(|| n.network.to_uppercase()),
+                    fee: n.withdraw_fee.map(to_fixed),
+                    min_withdrawal: to_fixed(n.min_withdraw),
+                    withdraw_enabled: n.withdraw_enabled,
+                    deposit_enabled: n.deposit_enabled,
+                })
+                .collect();
+            (asset.asset.clone(), networks)
+        })
+        .collect();
+
+    Some((exchange, assets))
+}
+
 /// Get cached wallet status for initial sync.
 pub fn get_cached_wallet_status() -> Vec<ExchangeWalletStatus> {
     CACHED_WALLET_STATUS.read().unwrap().clone()
@@ -1249,8 +1409,8 @@ pub fn save_network_mappings(statuses: &[ExchangeWalletStatus]) {
 }
 
 /// Run wallet status updater loop.
-/// Updates every 5 minutes.
-pub async fn run_wallet_status_updater(broadcast_tx: BroadcastSender) {
+/// Updates every 5 minutes and pushes withdrawal fees into the fee manager.
+pub async fn run_wallet_status_updater(broadcast_tx: BroadcastSender, state: SharedState) {
     let mut first_run = true;
 
     loop {
@@ -1263,7 +1423,8 @@ pub async fn run_wallet_status_updater(broadcast_tx: BroadcastSender) {
                 first_run = false;
             }
 
-            // Update cache first
+            // Update cache and withdrawal fees first
+            state.update_network_fees(&statuses).await;
             update_cache(statuses.clone());
             // Then broadcast
             ws_server::broadcast_wallet_status(&broadcast_tx, statuses);
@@ -1295,4 +1456,63 @@ mod tests {
             assert_eq!(status.exchange, "Coinbase");
         }
     }
+
+    #[test]
+    fn test_network_withdrawal_fees_only_when_reported() {
+        let network = |id: &str, fee: Option<f64>| NetworkStatus {
+            network: id.to_string(),
+            name: id.to_string(),
+            deposit_enabled: true,
+            withdraw_enabled: true,
+            min_withdraw: 10.0,
+            withdraw_fee: fee,
+            confirms_required: 0,
+        };
+        let status = ExchangeWalletStatus {
+            exchange: "Binance".to_string(),
+            wallet_status: vec![AssetWalletStatus {
+                asset: "USDT".to_string(),
+                name: "Tether".to_string(),
+                networks: vec![
+                    network("trx", Some(1.0)),
+                    network("ETH", Some(0.0)),
+                    network("SOL", None),
+                ],
+                can_deposit: true,
+                can_withdraw: true,
+            }],
+            last_updated: 0,
+        };
+
+        let (exchange, assets) = network_withdrawal_fees(&status, None).unwrap();
+        assert_eq!(exchange, Exchange::Binance);
+        let networks = &assets[0].1;
+        assert_eq!(networks[0].network, "TRX");
+        assert_eq!(networks[0].fee, Some(FixedPoint::from_f64(1.0).0));
+        assert_eq!(networks[1].fee, Some(0));
+        assert_eq!(networks[2].fee, None);
+        assert_eq!(networks[0].min_withdrawal, FixedPoint::from_f64(10.0).0);
+    }
+
+    #[test]
+    fn test_gateio_missing_fee_is_unknown() {
+        let fees: HashMap<String, GateIOWithdrawStatus> = [(
+            "USDT".to_string(),
+            GateIOWithdrawStatus {
+                currency: "USDT".to_string(),
+                withdraw_amount_mini: "1.5".to_string(),
+                withdraw_fix_on_chains: [("TRX".to_string(), "1".to_string())].into(),
+            },
+        )]
+        .into();
+
+        assert_eq!(
+            gateio_chain_fees(Some(&fees), "USDT", "TRX"),
+            (1.5, Some(1.0))
+        );
+        // Chain or currency missing from the withdraw status: fee unknown, not free
+        assert_eq!(gateio_chain_fees(Some(&fees), "USDT", "ETH"), (1.5, None));
+        assert_eq!(gateio_chain_fees(Some(&fees), "BTC", "BTC"), (0.0, None));
+        assert_eq!(gateio_chain_fees(None, "USDT", "TRX"), (0.0, None));
+    }
 }
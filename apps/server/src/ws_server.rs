@@ -2,24 +2,36 @@
 //!
 //! Event-driven: broadcasts data when new prices/stats/opportunities arrive.
 
+use crate::auth::{self, Client, ClientLimit, ServerAuth};
+use crate::config::ServerSettings;
+use crate::control::{self, PendingExecution};
 use crate::exchange_rate;
+use crate::rest_api;
 use crate::state::SharedState;
 use crate::wallet_status;
-use arbitrage_core::{Exchange, FixedPoint, PriceTick};
+use crate::ws_subscription::{ClientMessage, Subscription, SNAPSHOT_CHANNELS};
+use arbitrage_core::{
+    ConfidenceBreakdown, Exchange, FixedPoint, PairRegistry, PremiumStats, PriceTick, TransferRisk,
+};
+use arbitrage_engine::{DepegEvent, DepegLevel, KimchiIndex, LifecycleEvent};
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
         State,
     },
+    middleware,
     response::IntoResponse,
     routing::get,
-    Router,
+    Extension, Router,
 };
+use axum_server::tls_rustls::RustlsConfig;
+use futures_util::stream::SplitSink;
 use futures_util::{SinkExt, StreamExt};
 use serde::Serialize;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::broadcast;
-use tower_http::cors::{Any, CorsLayer};
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::{Interval, MissedTickBehavior};
 use tracing::{debug, info, warn};
 
 /// Price data for WebSocket broadcast.
@@ -58,6 +70,10 @@ pub struct WsStatsData {
     pub opportunities_detected: u64,
     pub trades_executed: u64,
     pub is_running: bool,
+    /// Pairs waiting for detection
+    pub detection_queue_depth: usize,
+    /// Moving average of price-update-to-detection latency (ms)
+    pub detection_latency_ms: f64,
 }
 
 /// USD-like premium for WebSocket broadcast.
@@ -69,6 +85,35 @@ pub struct WsUsdlikePremium {
     pub quote: String,
 }
 
+/// Transfer-time price risk for WebSocket broadcast.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsTransferRisk {
+    /// Transfer network (canonical name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    /// Expected confirmation plus deposit crediting time (ms)
+    pub transfer_time_ms: u64,
+    /// Standard deviation of the price move over the transfer time (bps)
+    pub volatility_bps: f64,
+    /// Profit after a volatility penalty (quote currency)
+    pub expected_profit: f64,
+    /// Profit after a worst-case adverse price move (quote currency)
+    pub worst_case_profit: f64,
+}
+
+impl From<&TransferRisk> for WsTransferRisk {
+    fn from(risk: &TransferRisk) -> Self {
+        let to_f64 = |value: i64| value as f64 / FixedPoint::SCALE as f64;
+        Self {
+            network: risk.network.clone(),
+            transfer_time_ms: risk.transfer_time_ms,
+            volatility_bps: risk.volatility_bps,
+            expected_profit: to_f64(risk.expected_profit),
+            worst_case_profit: to_f64(risk.worst_case_profit),
+        }
+    }
+}
+
 /// Opportunity data for WebSocket broadcast.
 #[derive(Debug, Clone, Serialize)]
 pub struct WsOpportunityData {
@@ -93,6 +138,12 @@ pub struct WsOpportunityData {
     pub target_price: f64,
     pub net_profit_bps: i32,
     pub confidence_score: u8,
+    /// Per-factor breakdown of confidence_score (why an opportunity is rated low)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence_breakdown: Option<ConfidenceBreakdown>,
+    /// Rolling premium statistics for this route (z-score, percentile, half-life)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub premium_stats: Option<PremiumStats>,
     pub timestamp: u64,
     /// Common networks available for transfer between source and target exchanges.
     /// Empty if no common network is available (opportunity not executable).
@@ -120,9 +171,15 @@ pub struct WsOpportunityData {
     /// Expected profit at optimal_size (in quote currency, e.g., USDT)
     #[serde(default)]
     pub optimal_profit: f64,
+    /// Gas for DEX legs and the withdrawal (in the same quote currency as optimal_profit)
+    #[serde(default)]
+    pub estimated_gas_cost: f64,
     /// Reason for optimal_size value: "ok" | "no_orderbook" | "not_profitable"
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub optimal_size_reason: Option<String>,
+    /// Transfer-time price risk at optimal_size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_risk: Option<WsTransferRisk>,
     /// Raw price from source exchange in original quote currency (e.g., KRW for Korean exchanges, USDT for Binance)
     #[serde(default)]
     pub source_raw_price: f64,
@@ -182,6 +239,7 @@ pub struct WsNetworkStatus {
     pub deposit_enabled: bool,
     pub withdraw_enabled: bool,
     pub min_withdraw: f64,
+    /// Withdrawal fee (0 = not reported)
     pub withdraw_fee: f64,
     pub confirms_required: u32,
 }
@@ -233,6 +291,290 @@ pub struct WsPremiumMatrixData {
     pub timestamp: u64,
 }
 
+/// Kimchi premium index for a single Korean exchange.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsKimchiExchangeIndex {
+    pub exchange: String,
+    pub index_bps: f64,
+    pub markets: u32,
+    pub weight_usd: f64,
+}
+
+/// Market deviating from its exchange's kimchi index.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsKimchiOutlier {
+    pub symbol: String,
+    pub exchange: String,
+    pub premium_bps: i32,
+    pub deviation_bps: f64,
+}
+
+/// Market-wide kimchi premium index (volume-weighted).
+#[derive(Debug, Clone, Serialize)]
+pub struct WsKimchiIndexData {
+    /// Combined index across Korean exchanges (bps)
+    pub index_bps: f64,
+    pub markets: u32,
+    pub exchanges: Vec<WsKimchiExchangeIndex>,
+    pub outliers: Vec<WsKimchiOutlier>,
+    pub timestamp: u64,
+}
+
+impl From<&KimchiIndex> for WsKimchiIndexData {
+    fn from(index: &KimchiIndex) -> Self {
+        Self {
+            index_bps: index.index_bps,
+            markets: index.markets,
+            exchanges: index
+                .exchanges
+                .iter()
+                .map(|e| WsKimchiExchangeIndex {
+                    exchange: format!("{:?}", e.exchange),
+                    index_bps: e.index_bps,
+                    markets: e.markets,
+                    weight_usd: e.weight_usd,
+                })
+                .collect(),
+            outliers: index
+                .outliers
+                .iter()
+                .map(|o| WsKimchiOutlier {
+                    symbol: o.symbol.clone(),
+                    exchange: format!("{:?}", o.exchange),
+                    premium_bps: o.premium_bps,
+                    deviation_bps: o.deviation_bps,
+                })
+                .collect(),
+            timestamp: index.timestamp_ms,
+        }
+    }
+}
+
+/// Stablecoin peg level change.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsDepegData {
+    pub exchange: String,
+    pub stablecoin: String,
+    /// Currency the stablecoin is priced in (USD or another stablecoin)
+    pub reference: String,
+    pub rate: f64,
+    /// Signed deviation from parity (bps)
+    pub deviation_bps: i32,
+    /// "normal", "warning" or "critical"
+    pub level: DepegLevel,
+    pub previous_level: DepegLevel,
+    pub timestamp: u64,
+}
+
+impl From<&DepegEvent> for WsDepegData {
+    fn from(event: &DepegEvent) -> Self {
+        Self {
+            exchange: format!("{:?}", event.exchange),
+            stablecoin: event.stablecoin.as_str().to_string(),
+            reference: event.reference.as_str().to_string(),
+            rate: event.rate,
+            deviation_bps: event.deviation_bps,
+            level: event.level,
+            previous_level: event.previous_level,
+            timestamp: event.timestamp_ms,
+        }
+    }
+}
+
+/// Opportunity episode state change.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsOpportunityLifecycleData {
+    /// "opened", "updated" (new peak) or "closed"
+    pub event: &'static str,
+    /// Stable opportunity id for the route while the episode is open
+    pub id: u64,
+    pub symbol: String,
+    pub source_exchange: String,
+    pub target_exchange: String,
+    pub source_quote: String,
+    pub target_quote: String,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub duration_ms: u64,
+    pub peak_premium_bps: i32,
+    pub last_premium_bps: i32,
+    pub time_above_threshold_ms: u64,
+    /// Set when closed: "below_threshold", "stale", "exchange_disconnected", "shutdown"
+    pub close_reason: Option<&'static str>,
+}
+
+impl From<&LifecycleEvent> for WsOpportunityLifecycleData {
+    fn from(event: &LifecycleEvent) -> Self {
+        let kind = match event {
+            LifecycleEvent::Opened(_) => "opened",
+            LifecycleEvent::Updated(_) => "updated",
+            LifecycleEvent::Closed(_) => "closed",
+        };
+        let episode = event.episode();
+        Self {
+            event: kind,
+            id: episode.id,
+            symbol: episode.symbol.clone(),
+            source_exchange: format!("{:?}", episode.buy_exchange),
+            target_exchange: format!("{:?}", episode.sell_exchange),
+            source_quote: episode.buy_quote.as_str().to_string(),
+            target_quote: episode.sell_quote.as_str().to_string(),
+            first_seen: episode.first_seen_ms,
+            last_seen: episode.last_seen_ms,
+            duration_ms: episode.duration_ms(),
+            peak_premium_bps: episode.peak_premium_bps,
+            last_premium_bps: episode.last_premium_bps,
+            time_above_threshold_ms: episode.time_above_threshold_ms,
+            close_reason: episode.close_reason.map(|reason| reason.as_str()),
+        }
+    }
+}
+
+/// Orderbook snapshot for one exchange market.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsOrderbookData {
+    pub exchange: String,
+    pub symbol: String,
+    pub pair_id: u32,
+    /// Bids as [price, quantity], best first
+    pub bids: Vec<(f64, f64)>,
+    /// Asks as [price, quantity], best first
+    pub asks: Vec<(f64, f64)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spread_bps: Option<i32>,
+    /// Last snapshot or delta applied
+    pub timestamp: u64,
+}
+
+/// Feed health of a single exchange.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsFeedStatus {
+    pub exchange: String,
+    pub connected: bool,
+    /// Open WebSocket connections (pooled exchanges use several)
+    pub connections: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_message: Option<u64>,
+    /// Time since the last message (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_age_ms: Option<u64>,
+    pub messages: u64,
+    pub disconnects: u64,
+    pub reconnects: u64,
+    /// Set while the circuit breaker blocks reconnecting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_open_until: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Feed health of all exchanges.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsFeedHealthData {
+    pub feeds: Vec<WsFeedStatus>,
+    pub timestamp: u64,
+}
+
+/// Configuration applied by a live reload.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsConfigChangedData {
+    /// Changed settings as dotted paths (e.g. "detector.min_premium_bps",
+    /// "exchanges.Upbit.enabled", "symbol_mappings")
+    pub changed: Vec<String>,
+    /// Changed settings that only take effect after a restart
+    pub restart_required: Vec<String>,
+    pub min_premium_bps: i32,
+    pub enabled_exchanges: Vec<String>,
+    pub timestamp: u64,
+}
+
+/// Remote control command outcome, also the audit log entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsControlData {
+    /// API key name of the client that sent the command
+    pub client: String,
+    /// Command name (e.g. "pause", "approve")
+    pub command: String,
+    /// Full command as sent
+    pub params: serde_json::Value,
+    pub ok: bool,
+    /// What was done, or why the command was rejected
+    pub message: String,
+    pub timestamp: u64,
+}
+
+/// Execution waiting for manual approval.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsPendingExecutionData {
+    pub opportunity_id: u64,
+    pub symbol: String,
+    pub buy_exchange: String,
+    pub sell_exchange: String,
+    pub premium_bps: i32,
+    pub net_profit_bps: i32,
+    pub queued_at: u64,
+}
+
+impl From<&PendingExecution> for WsPendingExecutionData {
+    fn from(pending: &PendingExecution) -> Self {
+        Self {
+            opportunity_id: pending.opportunity_id,
+            symbol: pending.symbol.clone(),
+            buy_exchange: format!("{:?}", pending.buy_exchange),
+            sell_exchange: format!("{:?}", pending.sell_exchange),
+            premium_bps: pending.premium_bps,
+            net_profit_bps: pending.net_profit_bps,
+            queued_at: pending.queued_at_ms,
+        }
+    }
+}
+
+/// Remote control state.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsControlStatusData {
+    /// Detection paused
+    pub paused: bool,
+    /// Kill switch engaged (no approvals, no resuming)
+    pub kill_switch: bool,
+    /// Symbols excluded from detection
+    pub disabled_symbols: Vec<String>,
+    pub pending: Vec<WsPendingExecutionData>,
+    /// Recent commands, oldest first
+    pub audit: Vec<WsControlData>,
+}
+
+/// Acknowledgement of a client subscription command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WsSubscriptionData {
+    /// Subscribed channels (message types)
+    pub channels: Vec<String>,
+    /// Subscribed symbols (None = all)
+    pub symbols: Option<Vec<String>>,
+    pub excluded_symbols: Vec<String>,
+    /// Subscribed exchanges (None = all)
+    pub exchanges: Option<Vec<String>>,
+    pub excluded_exchanges: Vec<String>,
+    /// Price and premium matrix conflation interval
+    pub throttle_ms: Option<u64>,
+    /// Messages dropped so far because this client fell behind
+    pub dropped: u64,
+}
+
+/// Broadcast messages dropped because the client fell behind.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsLaggedData {
+    pub dropped: u64,
+    /// Dropped since the connection opened
+    pub total_dropped: u64,
+    pub timestamp: u64,
+}
+
+/// Rejected client command.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsErrorData {
+    pub message: String,
+}
+
 /// WebSocket message types.
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", content = "data")]
@@ -246,7 +588,7 @@ pub enum WsServerMessage {
     #[serde(rename = "stats")]
     Stats(WsStatsData),
     #[serde(rename = "opportunity")]
-    Opportunity(WsOpportunityData),
+    Opportunity(Box<WsOpportunityData>),
     /// Batch of opportunities (for initial sync)
     #[serde(rename = "opportunities")]
     Opportunities(Vec<WsOpportunityData>),
@@ -261,6 +603,58 @@ pub enum WsServerMessage {
     /// Premium matrix for a symbol (all exchange pairs)
     #[serde(rename = "premium_matrix")]
     PremiumMatrix(WsPremiumMatrixData),
+    /// Market-wide kimchi premium index
+    #[serde(rename = "kimchi_index")]
+    KimchiIndex(WsKimchiIndexData),
+    /// Stablecoin depeg level change
+    #[serde(rename = "depeg")]
+    Depeg(WsDepegData),
+    /// Opportunity opened, reached a new peak or closed
+    #[serde(rename = "opportunity_lifecycle")]
+    OpportunityLifecycle(WsOpportunityLifecycleData),
+    /// Configuration reloaded without restart
+    #[serde(rename = "config_changed")]
+    ConfigChanged(WsConfigChangedData),
+    /// Subscription command acknowledged (this client only)
+    #[serde(rename = "subscription")]
+    Subscription(WsSubscriptionData),
+    /// Messages dropped because this client fell behind
+    #[serde(rename = "lagged")]
+    Lagged(WsLaggedData),
+    /// Client command rejected
+    #[serde(rename = "error")]
+    Error(WsErrorData),
+    /// Remote control command applied or rejected
+    #[serde(rename = "control")]
+    Control(WsControlData),
+    /// Execution queued for manual approval
+    #[serde(rename = "pending_execution")]
+    PendingExecution(WsPendingExecutionData),
+}
+
+impl WsServerMessage {
+    /// Subscription channel of a broadcast message (None for replies to one client).
+    pub fn channel(&self) -> Option<&'static str> {
+        match self {
+            WsServerMessage::Price(_) | WsServerMessage::Prices(_) => Some("price"),
+            WsServerMessage::Stats(_) => Some("stats"),
+            WsServerMessage::Opportunity(_) | WsServerMessage::Opportunities(_) => {
+                Some("opportunity")
+            }
+            WsServerMessage::ExchangeRate(_) => Some("exchange_rate"),
+            WsServerMessage::CommonMarkets(_) => Some("common_markets"),
+            WsServerMessage::WalletStatus(_) => Some("wallet_status"),
+            WsServerMessage::PremiumMatrix(_) => Some("premium_matrix"),
+            WsServerMessage::KimchiIndex(_) => Some("kimchi_index"),
+            WsServerMessage::Depeg(_) => Some("depeg"),
+            WsServerMessage::OpportunityLifecycle(_) => Some("opportunity_lifecycle"),
+            WsServerMessage::ConfigChanged(_) => Some("config_changed"),
+            WsServerMessage::Control(_) | WsServerMessage::PendingExecution(_) => Some("control"),
+            WsServerMessage::Subscription(_)
+            | WsServerMessage::Lagged(_)
+            | WsServerMessage::Error(_) => None,
+        }
+    }
 }
 
 /// Broadcast channel sender.
@@ -273,14 +667,15 @@ pub struct WsServerState {
 }
 
 /// Create WebSocket server router.
-pub fn create_ws_router(state: Arc<WsServerState>) -> Router {
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
+pub fn create_ws_router(state: Arc<WsServerState>, auth: Arc<ServerAuth>) -> Router {
+    let cors = auth.cors();
 
     Router::new()
         .route("/ws", get(ws_handler))
+        .merge(rest_api::routes())
+        .merge(control::routes())
+        .route_layer(middleware::from_fn_with_state(auth, auth::authorize))
+        // Load balancer probes need no credentials
         .route("/health", get(health_handler))
         .layer(cors)
         .with_state(state)
@@ -295,12 +690,39 @@ async fn health_handler() -> &'static str {
 async fn ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<WsServerState>>,
+    Extension(client): Extension<Client>,
+    Extension(limit): Extension<ClientLimit>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    debug!("WebSocket upgrade from client {}", client.name);
+    ws.on_upgrade(move |socket| handle_socket(socket, state, limit))
+}
+
+/// Client commands queued per connection before it is closed.
+const MAX_QUEUED_COMMANDS: usize = 32;
+
+/// Queue a command for the send task; false when the connection should close.
+fn queue_command(
+    command_tx: &mpsc::Sender<Result<ClientMessage, String>>,
+    command: Result<ClientMessage, String>,
+) -> bool {
+    match command_tx.try_send(command) {
+        Ok(()) => true,
+        Err(mpsc::error::TrySendError::Full(_)) => {
+            warn!("WebSocket client is not reading replies, closing connection");
+            false
+        }
+        Err(mpsc::error::TrySendError::Closed(_)) => false,
+    }
 }
 
 /// Handle individual WebSocket connection.
-async fn handle_socket(socket: WebSocket, state: Arc<WsServerState>) {
+///
+/// Client commands (see `ws_subscription`) are forwarded to the send task,
+/// which owns the connection's subscription and filters every broadcast.
+/// Inbound messages are charged to the client's rate limit like requests.
+/// A client that lets more than `MAX_QUEUED_COMMANDS` replies pile up
+/// without reading them is disconnected.
+async fn handle_socket(socket: WebSocket, state: Arc<WsServerState>, limit: ClientLimit) {
     let (mut sender, mut receiver) = socket.split();
 
     // Subscribe to broadcast channel
@@ -309,83 +731,92 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsServerState>) {
     debug!("WebSocket client connected");
 
     // Send initial data
-    let initial_prices = collect_prices(&state.app_state).await;
-    let initial_stats = collect_stats(&state.app_state);
-    let initial_rate = collect_exchange_rate(&state.app_state);
-    let initial_opportunities = collect_opportunities(&state.app_state).await;
-    let initial_common_markets = collect_common_markets(&state.app_state).await;
-    let initial_wallet_status = collect_wallet_status();
-
-    if let Ok(json) = serde_json::to_string(&WsServerMessage::Prices(initial_prices)) {
-        let _ = sender.send(Message::Text(json)).await;
-    }
-    if let Ok(json) = serde_json::to_string(&WsServerMessage::Stats(initial_stats)) {
-        let _ = sender.send(Message::Text(json)).await;
-    }
-    if let Some(rate) = initial_rate {
-        if let Ok(json) = serde_json::to_string(&WsServerMessage::ExchangeRate(rate)) {
-            let _ = sender.send(Message::Text(json)).await;
-        }
-    }
-    if !initial_opportunities.is_empty() {
-        if let Ok(json) =
-            serde_json::to_string(&WsServerMessage::Opportunities(initial_opportunities))
-        {
-            let _ = sender.send(Message::Text(json)).await;
-        }
-    }
-    if let Some(common_markets) = initial_common_markets {
-        if let Ok(json) = serde_json::to_string(&WsServerMessage::CommonMarkets(common_markets)) {
-            let _ = sender.send(Message::Text(json)).await;
-        }
-    }
-    if let Some(wallet_status) = initial_wallet_status {
-        if let Ok(json) = serde_json::to_string(&WsServerMessage::WalletStatus(wallet_status)) {
-            let _ = sender.send(Message::Text(json)).await;
-        }
+    let mut subscription = Subscription::default();
+    for msg in collect_snapshot(&state.app_state, &subscription, &SNAPSHOT_CHANNELS).await {
+        let _ = send_message(&mut sender, &msg).await;
     }
 
-    // Spawn task to send broadcast messages to this client
+    let (command_tx, mut command_rx) =
+        mpsc::channel::<Result<ClientMessage, String>>(MAX_QUEUED_COMMANDS);
+
+    // Spawn task to send broadcast messages and command replies to this client
+    let app_state = state.app_state.clone();
     let send_task = tokio::spawn(async move {
+        let mut flush: Option<Interval> = None;
         loop {
-            tokio::select! {
+            let outgoing = tokio::select! {
                 msg = broadcast_rx.recv() => {
                     match msg {
-                        Ok(ws_msg) => {
-                            if let Ok(json) = serde_json::to_string(&ws_msg) {
-                                if sender.send(Message::Text(json)).await.is_err() {
-                                    break;
-                                }
-                            }
-                        }
-                        Err(broadcast::error::RecvError::Lagged(_)) => {
-                            // Skip lagged messages
-                            continue;
+                        Ok(ws_msg) => subscription
+                            .filter(ws_msg)
+                            .and_then(|ws_msg| subscription.conflate(ws_msg))
+                            .into_iter()
+                            .collect(),
+                        Err(broadcast::error::RecvError::Lagged(dropped)) => {
+                            let total_dropped = subscription.record_dropped(dropped);
+                            debug!("WebSocket client lagged, {} messages dropped", dropped);
+                            vec![WsServerMessage::Lagged(WsLaggedData {
+                                dropped,
+                                total_dropped,
+                                timestamp: wallet_status::timestamp_ms(),
+                            })]
                         }
                         Err(broadcast::error::RecvError::Closed) => {
                             break;
                         }
                     }
                 }
+                command = command_rx.recv() => {
+                    let Some(command) = command else {
+                        break;
+                    };
+                    let throttle = subscription.throttle();
+                    let mut replies = handle_command(&app_state, &mut subscription, command).await;
+                    if subscription.throttle() != throttle {
+                        // Send what was held for the old interval
+                        replies.extend(subscription.flush());
+                        flush = subscription.throttle().map(|period| {
+                            let mut interval = tokio::time::interval(period);
+                            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                            interval
+                        });
+                    }
+                    replies
+                }
+                _ = next_flush(&mut flush) => subscription.flush(),
+            };
+            for msg in outgoing {
+                if send_message(&mut sender, &msg).await.is_err() {
+                    return;
+                }
             }
         }
     });
 
-    // Handle incoming messages (ping/pong, close)
+    // Handle incoming messages (commands, ping/pong, close)
     while let Some(msg) = receiver.next().await {
-        match msg {
-            Ok(Message::Ping(data)) => {
-                // Pong is handled automatically by axum
-                let _ = data;
+        let msg = match msg {
+            Ok(Message::Close(_)) => break,
+            Ok(msg) => msg,
+            Err(e) => {
+                warn!("WebSocket error: {}", e);
+                break;
             }
-            Ok(Message::Close(_)) => {
+        };
+        // Over the limit: answer with the error instead of handling the message
+        if let Err(e) = limit.check() {
+            if !queue_command(&command_tx, Err(e.to_string())) {
                 break;
             }
-            Err(e) => {
-                warn!("WebSocket error: {}", e);
+            continue;
+        }
+        // Pong is handled automatically by axum; binary frames are ignored
+        if let Message::Text(text) = msg {
+            let command = serde_json::from_str::<ClientMessage>(&text)
+                .map_err(|e| format!("invalid command: {}", e));
+            if !queue_command(&command_tx, command) {
                 break;
             }
-            _ => {}
         }
     }
 
@@ -394,8 +825,109 @@ async fn handle_socket(socket: WebSocket, state: Arc<WsServerState>) {
     debug!("WebSocket client disconnected");
 }
 
+/// Serialize and send one message.
+async fn send_message(
+    sender: &mut SplitSink<WebSocket, Message>,
+    msg: &WsServerMessage,
+) -> Result<(), axum::Error> {
+    match serde_json::to_string(msg) {
+        Ok(json) => sender.send(Message::Text(json)).await,
+        Err(_) => Ok(()),
+    }
+}
+
+/// Wait for the next throttle flush (forever when not throttled).
+async fn next_flush(flush: &mut Option<Interval>) {
+    match flush {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Apply a client command and build the replies.
+async fn handle_command(
+    state: &SharedState,
+    subscription: &mut Subscription,
+    command: Result<ClientMessage, String>,
+) -> Vec<WsServerMessage> {
+    let result = match command {
+        Ok(ClientMessage::Snapshot { channels }) => {
+            match subscription.snapshot_channels(channels) {
+                Ok(channels) => Ok(collect_snapshot(state, subscription, &channels).await),
+                Err(e) => Err(e),
+            }
+        }
+        Ok(command) => subscription
+            .apply(command)
+            .map(|()| vec![WsServerMessage::Subscription(subscription.status())]),
+        Err(e) => Err(e),
+    };
+    result.unwrap_or_else(|message| vec![WsServerMessage::Error(WsErrorData { message })])
+}
+
+/// Current state of the given channels, limited to the client's subscription.
+///
+/// Premium matrices are only included for symbols subscribed by name.
+pub(crate) async fn collect_snapshot(
+    state: &SharedState,
+    subscription: &Subscription,
+    channels: &[&str],
+) -> Vec<WsServerMessage> {
+    let mut messages = Vec::new();
+    for &channel in channels {
+        match channel {
+            "price" => messages.push(WsServerMessage::Prices(collect_prices(state).await)),
+            "stats" => messages.push(WsServerMessage::Stats(collect_stats(state))),
+            "exchange_rate" => {
+                messages.extend(collect_exchange_rate(state).map(WsServerMessage::ExchangeRate))
+            }
+            "opportunity" => {
+                let opportunities = collect_opportunities(state).await;
+                if !opportunities.is_empty() {
+                    messages.push(WsServerMessage::Opportunities(opportunities));
+                }
+            }
+            "common_markets" => messages.extend(
+                collect_common_markets(state)
+                    .await
+                    .map(WsServerMessage::CommonMarkets),
+            ),
+            "wallet_status" => {
+                messages.extend(collect_wallet_status().map(WsServerMessage::WalletStatus))
+            }
+            "kimchi_index" => messages.extend(
+                state
+                    .kimchi_index()
+                    .await
+                    .map(|index| WsServerMessage::KimchiIndex(WsKimchiIndexData::from(&index))),
+            ),
+            "premium_matrix" => {
+                let Some(symbols) = subscription.symbols() else {
+                    continue;
+                };
+                for symbol in symbols {
+                    let Some(pair_id) = PairRegistry::global().get(symbol) else {
+                        continue;
+                    };
+                    messages.extend(
+                        collect_premium_matrix(state, pair_id, symbol)
+                            .map(WsServerMessage::PremiumMatrix),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    messages
+        .into_iter()
+        .filter_map(|message| subscription.select(message))
+        .collect()
+}
+
 /// Collect current prices from state.
-async fn collect_prices(state: &SharedState) -> Vec<WsPriceData> {
+pub(crate) async fn collect_prices(state: &SharedState) -> Vec<WsPriceData> {
     let mut prices = Vec::new();
 
     // Get all prices from aggregator
@@ -435,19 +967,22 @@ async fn collect_prices(state: &SharedState) -> Vec<WsPriceData> {
 }
 
 /// Collect current stats from state.
-fn collect_stats(state: &SharedState) -> WsStatsData {
+pub(crate) fn collect_stats(state: &SharedState) -> WsStatsData {
     let summary = state.stats_summary();
+    let scheduler = state.detection_scheduler().snapshot();
     WsStatsData {
         uptime_secs: summary.uptime_secs,
         price_updates: summary.price_updates,
         opportunities_detected: summary.opportunities_detected,
         trades_executed: summary.trades_executed,
         is_running: state.is_running(),
+        detection_queue_depth: scheduler.queue_depth,
+        detection_latency_ms: scheduler.avg_latency_ms,
     }
 }
 
 /// Collect current exchange rate if loaded.
-fn collect_exchange_rate(state: &SharedState) -> Option<WsExchangeRateData> {
+pub(crate) fn collect_exchange_rate(state: &SharedState) -> Option<WsExchangeRateData> {
     let upbit_usdt_krw = state
         .get_upbit_usdt_krw()
         .map(|p| p.to_f64())
@@ -482,7 +1017,7 @@ fn collect_exchange_rate(state: &SharedState) -> Option<WsExchangeRateData> {
 }
 
 /// Collect cached wallet status for initial sync.
-fn collect_wallet_status() -> Option<WsWalletStatusData> {
+pub(crate) fn collect_wallet_status() -> Option<WsWalletStatusData> {
     let cached = wallet_status::get_cached_wallet_status();
     if cached.is_empty() {
         return None;
@@ -507,7 +1042,7 @@ fn collect_wallet_status() -> Option<WsWalletStatusData> {
                             deposit_enabled: n.deposit_enabled,
                             withdraw_enabled: n.withdraw_enabled,
                             min_withdraw: n.min_withdraw,
-                            withdraw_fee: n.withdraw_fee,
+                            withdraw_fee: n.withdraw_fee.unwrap_or(0.0),
                             confirms_required: n.confirms_required,
                         })
                         .collect(),
@@ -529,7 +1064,7 @@ fn collect_wallet_status() -> Option<WsWalletStatusData> {
 }
 
 /// Collect current opportunities from state.
-async fn collect_opportunities(state: &SharedState) -> Vec<WsOpportunityData> {
+pub(crate) async fn collect_opportunities(state: &SharedState) -> Vec<WsOpportunityData> {
     let opps = state.opportunities.read().await;
 
     let result: Vec<WsOpportunityData> = opps
@@ -576,8 +1111,10 @@ async fn collect_opportunities(state: &SharedState) -> Vec<WsOpportunityData> {
                 kimchi_premium_bps: opp.kimchi_premium_bps,
                 source_price: FixedPoint(opp.source_price).to_f64(),
                 target_price: FixedPoint(opp.target_price).to_f64(),
-                net_profit_bps: (opp.net_profit_estimate / 100) as i32,
+                net_profit_bps: opp.net_profit_bps().unwrap_or(0),
                 confidence_score: opp.confidence_score,
+                confidence_breakdown: opp.confidence_breakdown,
+                premium_stats: opp.premium_stats,
                 timestamp: opp.discovered_at_ms,
                 common_networks,
                 has_transfer_path,
@@ -586,6 +1123,7 @@ async fn collect_opportunities(state: &SharedState) -> Vec<WsOpportunityData> {
                 target_depth,
                 optimal_size: FixedPoint(opp.optimal_size).to_f64(),
                 optimal_profit: FixedPoint(opp.optimal_profit as u64).to_f64(),
+                estimated_gas_cost: FixedPoint(opp.estimated_gas_cost).to_f64(),
                 optimal_size_reason: Some(match opp.optimal_size_reason {
                     arbitrage_core::OptimalSizeReason::Ok => "ok".to_string(),
                     arbitrage_core::OptimalSizeReason::NoOrderbook => "no_orderbook".to_string(),
@@ -595,7 +1133,17 @@ async fn collect_opportunities(state: &SharedState) -> Vec<WsOpportunityData> {
                     arbitrage_core::OptimalSizeReason::NoConversionRate => {
                         "no_conversion_rate".to_string()
                     }
+                    arbitrage_core::OptimalSizeReason::DepthExhausted => {
+                        "depth_exhausted".to_string()
+                    }
+                    arbitrage_core::OptimalSizeReason::BelowMinOrder => {
+                        "below_min_order".to_string()
+                    }
+                    arbitrage_core::OptimalSizeReason::NoTransferNetwork => {
+                        "no_transfer_network".to_string()
+                    }
                 }),
+                transfer_risk: opp.transfer_risk.as_ref().map(WsTransferRisk::from),
                 source_raw_price: FixedPoint(opp.source_raw_price).to_f64(),
                 target_raw_price: FixedPoint(opp.target_raw_price).to_f64(),
             }
@@ -606,7 +1154,7 @@ async fn collect_opportunities(state: &SharedState) -> Vec<WsOpportunityData> {
 }
 
 /// Collect current common markets from state.
-async fn collect_common_markets(state: &SharedState) -> Option<WsCommonMarketsData> {
+pub(crate) async fn collect_common_markets(state: &SharedState) -> Option<WsCommonMarketsData> {
     let common = state.get_common_markets().await?;
 
     let mut markets_map = std::collections::HashMap::new();
@@ -634,6 +1182,105 @@ async fn collect_common_markets(state: &SharedState) -> Option<WsCommonMarketsDa
     })
 }
 
+/// Collect the premium matrix of a pair (all exchange pairs).
+pub(crate) fn collect_premium_matrix(
+    state: &SharedState,
+    pair_id: u32,
+    symbol: &str,
+) -> Option<WsPremiumMatrixData> {
+    // Same live rates snapshot the detector uses
+    let rates = state.conversion_rates();
+    let matrix = state.detector.get_matrix(pair_id)?;
+
+    // Use Display format for quote currency (e.g., "KRW", "USDT")
+    // Use Debug format for exchange (e.g., "Binance", "Upbit")
+    let entries = matrix
+        .all_premiums_multi_denomination(&rates)
+        .iter()
+        .map(|p| WsPremiumEntry {
+            buy_exchange: format!("{:?}", p.0),
+            sell_exchange: format!("{:?}", p.1),
+            buy_quote: p.2.as_str().to_string(),
+            sell_quote: p.3.as_str().to_string(),
+            tether_premium_bps: p.10, // usdlike_premium_bps
+            kimchi_premium_bps: p.12, // kimchi_premium
+        })
+        .collect();
+
+    Some(WsPremiumMatrixData {
+        symbol: symbol.to_string(),
+        pair_id,
+        entries,
+        timestamp: wallet_status::timestamp_ms(),
+    })
+}
+
+/// Collect the cached orderbook of an exchange market, up to `depth` levels per side.
+pub(crate) fn collect_orderbook(
+    state: &SharedState,
+    exchange: Exchange,
+    pair_id: u32,
+    symbol: &str,
+    depth: usize,
+) -> Option<WsOrderbookData> {
+    let book = state.get_orderbook(exchange, pair_id)?;
+    let levels = |side: Vec<(u64, u64)>| -> Vec<(f64, f64)> {
+        side.into_iter()
+            .take(depth)
+            .map(|(price, qty)| (FixedPoint(price).to_f64(), FixedPoint(qty).to_f64()))
+            .collect()
+    };
+
+    Some(WsOrderbookData {
+        exchange: format!("{:?}", exchange),
+        symbol: symbol.to_string(),
+        pair_id,
+        bids: levels(book.bids_vec()),
+        asks: levels(book.asks_vec()),
+        spread_bps: book.spread_bps(),
+        timestamp: book.timestamp_ms(),
+    })
+}
+
+/// Collect feed health of every exchange seen so far.
+pub(crate) fn collect_feed_health(state: &SharedState) -> WsFeedHealthData {
+    let now = wallet_status::timestamp_ms();
+    let feeds = state
+        .feed_health()
+        .snapshot()
+        .into_iter()
+        .map(|(exchange, status)| WsFeedStatus {
+            exchange: format!("{:?}", exchange),
+            connected: status.is_connected(),
+            connections: status.connections,
+            last_message: status.last_message_ms,
+            message_age_ms: status.last_message_ms.map(|ms| now.saturating_sub(ms)),
+            messages: status.messages,
+            disconnects: status.disconnects,
+            reconnects: status.reconnects,
+            circuit_open_until: status.circuit_open_until_ms,
+            last_error: status.last_error,
+        })
+        .collect();
+
+    WsFeedHealthData {
+        feeds,
+        timestamp: now,
+    }
+}
+
+/// Remote control state and recent audit entries.
+pub(crate) fn collect_control_status(state: &SharedState) -> WsControlStatusData {
+    let control = state.control();
+    WsControlStatusData {
+        paused: control.is_paused(),
+        kill_switch: control.is_kill_switch_engaged(),
+        disabled_symbols: control.disabled_symbols(),
+        pending: control.pending().iter().map(Into::into).collect(),
+        audit: control.recent_audit(),
+    }
+}
+
 /// Broadcast a single price update (event-driven).
 pub fn broadcast_price(
     tx: &BroadcastSender,
@@ -642,7 +1289,7 @@ pub fn broadcast_price(
     symbol: &str,
     tick: &PriceTick,
 ) {
-    broadcast_price_with_quote_and_usd(tx, exchange, pair_id, symbol, None, tick, None, None, None);
+    broadcast_price_with_quote_and_usd(tx, exchange, pair_id, symbol, None, tick, None);
 }
 
 /// Broadcast a single price update with quote currency (event-driven).
@@ -654,9 +1301,15 @@ pub fn broadcast_price_with_quote(
     quote: Option<&str>,
     tick: &PriceTick,
 ) {
-    broadcast_price_with_quote_and_usd(
-        tx, exchange, pair_id, symbol, quote, tick, None, None, None,
-    );
+    broadcast_price_with_quote_and_usd(tx, exchange, pair_id, symbol, quote, tick, None);
+}
+
+/// USD-converted prices of a tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsdPrices {
+    pub price: f64,
+    pub bid: f64,
+    pub ask: f64,
 }
 
 /// Broadcast a single price update with quote currency and USD-converted prices (event-driven).
@@ -667,9 +1320,7 @@ pub fn broadcast_price_with_quote_and_usd(
     symbol: &str,
     quote: Option<&str>,
     tick: &PriceTick,
-    price_usd: Option<f64>,
-    bid_usd: Option<f64>,
-    ask_usd: Option<f64>,
+    usd: Option<UsdPrices>,
 ) {
     let price_data = WsPriceData {
         exchange: format!("{:?}", exchange),
@@ -681,9 +1332,9 @@ pub fn broadcast_price_with_quote_and_usd(
         volume_24h: tick.volume_24h().to_f64(),
         timestamp: tick.timestamp_ms(),
         quote: quote.map(|q| q.to_string()),
-        price_usd,
-        bid_usd,
-        ask_usd,
+        price_usd: usd.map(|usd| usd.price),
+        bid_usd: usd.map(|usd| usd.bid),
+        ask_usd: usd.map(|usd| usd.ask),
     };
 
     let _ = tx.send(WsServerMessage::Price(price_data));
@@ -746,8 +1397,10 @@ pub fn broadcast_opportunity(
         kimchi_premium_bps: opp.kimchi_premium_bps,
         source_price: FixedPoint(opp.source_price).to_f64(),
         target_price: FixedPoint(opp.target_price).to_f64(),
-        net_profit_bps: (opp.net_profit_estimate / 100) as i32, // Convert to bps approximation
+        net_profit_bps: opp.net_profit_bps().unwrap_or(0),
         confidence_score: opp.confidence_score,
+        confidence_breakdown: opp.confidence_breakdown,
+        premium_stats: opp.premium_stats,
         timestamp: std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -759,17 +1412,24 @@ pub fn broadcast_opportunity(
         target_depth,
         optimal_size: optimal_size_f64,
         optimal_profit: optimal_profit_f64,
+        estimated_gas_cost: FixedPoint(opp.estimated_gas_cost).to_f64(),
         optimal_size_reason: Some(match opp.optimal_size_reason {
             arbitrage_core::OptimalSizeReason::Ok => "ok".to_string(),
             arbitrage_core::OptimalSizeReason::NoOrderbook => "no_orderbook".to_string(),
             arbitrage_core::OptimalSizeReason::NotProfitable => "not_profitable".to_string(),
             arbitrage_core::OptimalSizeReason::NoConversionRate => "no_conversion_rate".to_string(),
+            arbitrage_core::OptimalSizeReason::DepthExhausted => "depth_exhausted".to_string(),
+            arbitrage_core::OptimalSizeReason::BelowMinOrder => "below_min_order".to_string(),
+            arbitrage_core::OptimalSizeReason::NoTransferNetwork => {
+                "no_transfer_network".to_string()
+            }
         }),
+        transfer_risk: opp.transfer_risk.as_ref().map(WsTransferRisk::from),
         source_raw_price: FixedPoint(opp.source_raw_price).to_f64(),
         target_raw_price: FixedPoint(opp.target_raw_price).to_f64(),
     };
 
-    let _ = tx.send(WsServerMessage::Opportunity(ws_opp));
+    let _ = tx.send(WsServerMessage::Opportunity(Box::new(ws_opp)));
 }
 
 /// Broadcast exchange rate update to all clients.
@@ -862,7 +1522,7 @@ pub fn broadcast_wallet_status(
                             deposit_enabled: n.deposit_enabled,
                             withdraw_enabled: n.withdraw_enabled,
                             min_withdraw: n.min_withdraw,
-                            withdraw_fee: n.withdraw_fee,
+                            withdraw_fee: n.withdraw_fee.unwrap_or(0.0),
                             confirms_required: n.confirms_required,
                         })
                         .collect(),
@@ -885,43 +1545,6 @@ pub fn broadcast_wallet_status(
     let _ = tx.send(WsServerMessage::WalletStatus(data));
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::config::AppConfig;
-    use crate::state::create_state;
-    use arbitrage_core::{ArbitrageOpportunity, Asset};
-    use tokio::sync::broadcast;
-
-    #[tokio::test]
-    async fn test_broadcast_opportunity_includes_pair_id() {
-        let (state, _rx) = create_state(AppConfig::default());
-        let (tx, mut rx) = broadcast::channel(1);
-
-        let asset = Asset::from_symbol("DOGE");
-        let opp = ArbitrageOpportunity::new(
-            1,
-            Exchange::Binance,
-            Exchange::Coinbase,
-            asset,
-            FixedPoint::from_f64(0.10),
-            FixedPoint::from_f64(0.12),
-        )
-        .with_pair_id(42);
-
-        broadcast_opportunity(&tx, &state, &opp);
-
-        let msg = rx.recv().await.expect("opportunity broadcast");
-        match msg {
-            WsServerMessage::Opportunity(data) => {
-                assert_eq!(data.pair_id, 42);
-                assert_eq!(data.symbol, "DOGE");
-            }
-            _ => panic!("unexpected message type"),
-        }
-    }
-}
-
 /// Broadcast premium matrix for a symbol to all clients.
 /// This sends all exchange pair premiums for the given symbol.
 pub fn broadcast_premium_matrix(
@@ -943,9 +1566,43 @@ pub fn broadcast_premium_matrix(
     let _ = tx.send(WsServerMessage::PremiumMatrix(data));
 }
 
+/// Broadcast the market-wide kimchi premium index.
+pub fn broadcast_kimchi_index(tx: &BroadcastSender, index: &KimchiIndex) {
+    let _ = tx.send(WsServerMessage::KimchiIndex(WsKimchiIndexData::from(index)));
+}
+
+/// Broadcast a stablecoin depeg level change.
+pub fn broadcast_depeg(tx: &BroadcastSender, event: &DepegEvent) {
+    let _ = tx.send(WsServerMessage::Depeg(WsDepegData::from(event)));
+}
+
+/// Broadcast opportunity lifecycle events.
+pub fn broadcast_opportunity_lifecycle(tx: &BroadcastSender, events: &[LifecycleEvent]) {
+    for event in events {
+        let _ = tx.send(WsServerMessage::OpportunityLifecycle(
+            WsOpportunityLifecycleData::from(event),
+        ));
+    }
+}
+
+/// Broadcast a configuration reload.
+pub fn broadcast_config_changed(tx: &BroadcastSender, data: WsConfigChangedData) {
+    let _ = tx.send(WsServerMessage::ConfigChanged(data));
+}
+
+/// Broadcast a remote control command outcome.
+pub fn broadcast_control(tx: &BroadcastSender, data: WsControlData) {
+    let _ = tx.send(WsServerMessage::Control(data));
+}
+
+/// Broadcast an execution waiting for manual approval.
+pub fn broadcast_pending_execution(tx: &BroadcastSender, pending: &PendingExecution) {
+    let _ = tx.send(WsServerMessage::PendingExecution(pending.into()));
+}
+
 /// Create WebSocket server and return the broadcast sender for event-driven updates.
 /// The caller should use the returned sender to broadcast price/stats/opportunity updates.
-pub fn create_ws_server(state: SharedState) -> (Router, BroadcastSender) {
+pub fn create_ws_server(state: SharedState, auth: Arc<ServerAuth>) -> (Router, BroadcastSender) {
     let (broadcast_tx, _) = broadcast::channel::<WsServerMessage>(1000);
 
     let ws_state = Arc::new(WsServerState {
@@ -953,29 +1610,115 @@ pub fn create_ws_server(state: SharedState) -> (Router, BroadcastSender) {
         broadcast_tx: broadcast_tx.clone(),
     });
 
-    let app = create_ws_router(ws_state);
+    let app = create_ws_router(ws_state, auth);
     (app, broadcast_tx)
 }
 
 /// Start the WebSocket server and return the broadcast sender.
 pub async fn start_ws_server(
     state: SharedState,
-    port: u16,
+    settings: &ServerSettings,
 ) -> Result<BroadcastSender, Box<dyn std::error::Error + Send + Sync>> {
-    let (app, broadcast_tx) = create_ws_server(state);
-
-    let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
-    info!("WebSocket server listening on ws://0.0.0.0:{}", port);
-
-    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let auth = Arc::new(ServerAuth::new(settings));
+    if !auth.requires_key() {
+        warn!("No API keys configured: clients connect without authentication (read-only)");
+    }
+    let (app, broadcast_tx) = create_ws_server(state, auth);
+    // Client addresses key the rate limit for anonymous clients
+    let service = app.into_make_service_with_connect_info::<SocketAddr>();
+
+    let port = settings.ws_port;
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    let (ws_scheme, http_scheme) = if settings.tls.is_some() {
+        ("wss", "https")
+    } else {
+        ("ws", "http")
+    };
+    info!(
+        "WebSocket server listening on {}://0.0.0.0:{}",
+        ws_scheme, port
+    );
+    info!(
+        "REST API listening on {}://0.0.0.0:{}/api",
+        http_scheme, port
+    );
 
     // Spawn server in background and return the broadcast sender
     let tx_clone = broadcast_tx.clone();
-    tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, app).await {
-            tracing::error!("WebSocket server error: {}", e);
+    match &settings.tls {
+        Some(tls) => {
+            // Ignore the error if a provider was already installed
+            let _ = rustls::crypto::ring::default_provider().install_default();
+            let tls_config = RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path).await?;
+            let server = axum_server::bind_rustls(addr, tls_config);
+            tokio::spawn(async move {
+                if let Err(e) = server.serve(service).await {
+                    tracing::error!("WebSocket server error: {}", e);
+                }
+            });
         }
-    });
+        None => {
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            tokio::spawn(async move {
+                if let Err(e) = axum::serve(listener, service).await {
+                    tracing::error!("WebSocket server error: {}", e);
+                }
+            });
+        }
+    }
 
     Ok(tx_clone)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::state::create_state;
+    use arbitrage_core::{ArbitrageOpportunity, Asset};
+    use tokio::sync::broadcast;
+
+    #[tokio::test]
+    async fn test_broadcast_opportunity_includes_pair_id() {
+        let (state, _rx) = create_state(AppConfig::default());
+        let (tx, mut rx) = broadcast::channel(1);
+
+        let asset = Asset::from_symbol("DOGE");
+        let opp = ArbitrageOpportunity::new(
+            1,
+            Exchange::Binance,
+            Exchange::Coinbase,
+            asset,
+            FixedPoint::from_f64(0.10),
+            FixedPoint::from_f64(0.12),
+        )
+        .with_pair_id(42);
+
+        broadcast_opportunity(&tx, &state, &opp);
+
+        let msg = rx.recv().await.expect("opportunity broadcast");
+        match msg {
+            WsServerMessage::Opportunity(data) => {
+                assert_eq!(data.pair_id, 42);
+                assert_eq!(data.symbol, "DOGE");
+            }
+            _ => panic!("unexpected message type"),
+        }
+    }
+
+    #[test]
+    fn test_queue_command_closes_when_client_stops_reading() {
+        let (tx, mut rx) = mpsc::channel(MAX_QUEUED_COMMANDS);
+        for _ in 0..MAX_QUEUED_COMMANDS {
+            assert!(queue_command(&tx, Err("rate limited".to_string())));
+        }
+        assert!(!queue_command(&tx, Err("rate limited".to_string())));
+
+        // Draining makes room again
+        assert!(rx.try_recv().is_ok());
+        assert!(queue_command(&tx, Err("rate limited".to_string())));
+
+        drop(rx);
+        assert!(!queue_command(&tx, Err("rate limited".to_string())));
+    }
+}
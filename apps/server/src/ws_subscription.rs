@@ -0,0 +1,508 @@
+//! Per-connection subscriptions on the `/ws` endpoint.
+//!
+//! A new connection receives every message type for every market, as before
+//! the protocol existed. Clients narrow the stream with JSON commands:
+//!
+//! ```text
+//! {"type":"subscribe","channels":["price","opportunity"],"symbols":["BTC","ETH"],"exchanges":["Upbit","Binance"]}
+//! {"type":"unsubscribe","symbols":["ETH"]}
+//! {"type":"reset"}
+//! {"type":"throttle","interval_ms":500}
+//! {"type":"snapshot","channels":["opportunity"]}
+//! ```
+//!
+//! The first `subscribe` naming symbols (or exchanges, channels) replaces
+//! "all" with the named ones; later ones add to them. `unsubscribe` removes.
+//! A message for a route passes the exchange filter when either leg is
+//! subscribed. Throttling conflates price and premium matrix updates to the
+//! latest one per market every interval. Each command is acknowledged with a
+//! `subscription` message, or an `error` message if it is rejected.
+
+use crate::config::LIVE_EXCHANGES;
+use crate::ws_server::{WsServerMessage, WsSubscriptionData};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+/// Channels a client can subscribe to, by message type. Batch messages
+/// (`prices`, `opportunities`) belong to their single-item channel.
+pub const CHANNELS: [&str; 12] = [
+    "price",
+    "opportunity",
+    "opportunity_lifecycle",
+    "premium_matrix",
+    "stats",
+    "exchange_rate",
+    "common_markets",
+    "wallet_status",
+    "kimchi_index",
+    "depeg",
+    "config_changed",
+    "control",
+];
+
+/// Channels with a current state to snapshot, in initial sync order.
+pub const SNAPSHOT_CHANNELS: [&str; 8] = [
+    "price",
+    "stats",
+    "exchange_rate",
+    "opportunity",
+    "common_markets",
+    "wallet_status",
+    "kimchi_index",
+    "premium_matrix",
+];
+
+/// Shortest accepted throttle interval.
+pub const MIN_THROTTLE_MS: u64 = 50;
+
+/// Command sent by a client.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Subscribe(SelectionUpdate),
+    Unsubscribe(SelectionUpdate),
+    /// Back to every channel, symbol and exchange, unthrottled.
+    Reset,
+    /// Conflate price and premium matrix updates; 0 turns throttling off.
+    Throttle {
+        interval_ms: u64,
+    },
+    /// Send the current state of the given channels (default: all), filtered
+    /// by the subscription.
+    Snapshot {
+        #[serde(default)]
+        channels: Option<Vec<String>>,
+    },
+}
+
+/// Channels, symbols and exchanges named by a subscribe/unsubscribe command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct SelectionUpdate {
+    #[serde(default)]
+    pub channels: Vec<String>,
+    #[serde(default)]
+    pub symbols: Vec<String>,
+    #[serde(default)]
+    pub exchanges: Vec<String>,
+}
+
+/// Subscribed values of one dimension: all (minus exclusions) or only some.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Selection {
+    only: Option<BTreeSet<String>>,
+    except: BTreeSet<String>,
+}
+
+impl Selection {
+    fn contains(&self, value: &str) -> bool {
+        self.only.as_ref().is_none_or(|only| only.contains(value)) && !self.except.contains(value)
+    }
+
+    fn subscribe(&mut self, values: Vec<String>) {
+        if values.is_empty() {
+            return;
+        }
+        for value in &values {
+            self.except.remove(value);
+        }
+        self.only.get_or_insert_with(BTreeSet::new).extend(values);
+    }
+
+    fn unsubscribe(&mut self, values: Vec<String>) {
+        match self.only {
+            Some(ref mut only) => values.iter().for_each(|value| {
+                only.remove(value);
+            }),
+            None => self.except.extend(values),
+        }
+    }
+}
+
+/// Filter and throttle state of one client connection.
+#[derive(Debug, Default)]
+pub struct Subscription {
+    channels: Selection,
+    symbols: Selection,
+    exchanges: Selection,
+    throttle: Option<Duration>,
+    /// Latest throttled message per market, sent on the next flush.
+    pending: HashMap<String, WsServerMessage>,
+    /// Broadcast messages missed because the client fell behind.
+    dropped: u64,
+}
+
+impl Subscription {
+    /// Apply a subscribe, unsubscribe, reset or throttle command.
+    pub fn apply(&mut self, message: ClientMessage) -> Result<(), String> {
+        match message {
+            ClientMessage::Subscribe(update) => {
+                let (channels, symbols, exchanges) = normalize(update)?;
+                self.channels.subscribe(channels);
+                self.symbols.subscribe(symbols);
+                self.exchanges.subscribe(exchanges);
+            }
+            ClientMessage::Unsubscribe(update) => {
+                let (channels, symbols, exchanges) = normalize(update)?;
+                self.channels.unsubscribe(channels);
+                self.symbols.unsubscribe(symbols);
+                self.exchanges.unsubscribe(exchanges);
+            }
+            ClientMessage::Reset => {
+                *self = Self {
+                    dropped: self.dropped,
+                    ..Self::default()
+                };
+            }
+            ClientMessage::Throttle { interval_ms: 0 } => {
+                self.throttle = None;
+            }
+            ClientMessage::Throttle { interval_ms } => {
+                self.throttle = Some(Duration::from_millis(interval_ms.max(MIN_THROTTLE_MS)));
+            }
+            ClientMessage::Snapshot { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Throttle interval, if conflating.
+    pub fn throttle(&self) -> Option<Duration> {
+        self.throttle
+    }
+
+    /// Symbols subscribed by name (None when subscribed to all).
+    pub fn symbols(&self) -> Option<impl Iterator<Item = &str>> {
+        let only = self.symbols.only.as_ref()?;
+        Some(
+            only.iter()
+                .map(String::as_str)
+                .filter(|symbol| self.symbols.contains(symbol)),
+        )
+    }
+
+    pub fn wants_channel(&self, channel: &str) -> bool {
+        self.channels.contains(channel)
+    }
+
+    /// Channels to snapshot for a request: the named ones, or every
+    /// subscribed channel. Event-only channels have no snapshot.
+    pub fn snapshot_channels(
+        &self,
+        requested: Option<Vec<String>>,
+    ) -> Result<Vec<&'static str>, String> {
+        let Some(requested) = requested else {
+            return Ok(SNAPSHOT_CHANNELS
+                .into_iter()
+                .filter(|channel| self.wants_channel(channel))
+                .collect());
+        };
+        let (requested, _, _) = normalize(SelectionUpdate {
+            channels: requested,
+            ..Default::default()
+        })?;
+        Ok(SNAPSHOT_CHANNELS
+            .into_iter()
+            .filter(|channel| requested.iter().any(|name| name == channel))
+            .collect())
+    }
+
+    /// The part of a broadcast message this client subscribed to, if any.
+    pub fn filter(&self, message: WsServerMessage) -> Option<WsServerMessage> {
+        match message.channel() {
+            Some(channel) if !self.wants_channel(channel) => None,
+            Some(_) => self.select(message),
+            // Replies to this client
+            None => Some(message),
+        }
+    }
+
+    /// The part of a message on subscribed symbols and exchanges, if any.
+    pub fn select(&self, message: WsServerMessage) -> Option<WsServerMessage> {
+        let route = |symbol: &str, source: &str, target: &str| {
+            self.symbols.contains(symbol)
+                && (self.exchanges.contains(source) || self.exchanges.contains(target))
+        };
+        let admitted = match &message {
+            WsServerMessage::Price(price) => {
+                self.symbols.contains(&price.symbol) && self.exchanges.contains(&price.exchange)
+            }
+            WsServerMessage::Prices(prices) => {
+                let prices = prices
+                    .iter()
+                    .filter(|price| {
+                        self.symbols.contains(&price.symbol)
+                            && self.exchanges.contains(&price.exchange)
+                    })
+                    .cloned()
+                    .collect();
+                return Some(WsServerMessage::Prices(prices));
+            }
+            WsServerMessage::Opportunity(opp) => {
+                route(&opp.symbol, &opp.source_exchange, &opp.target_exchange)
+            }
+            WsServerMessage::Opportunities(opps) => {
+                let opps = opps
+                    .iter()
+                    .filter(|opp| route(&opp.symbol, &opp.source_exchange, &opp.target_exchange))
+                    .cloned()
+                    .collect();
+                return Some(WsServerMessage::Opportunities(opps));
+            }
+            WsServerMessage::OpportunityLifecycle(event) => route(
+                &event.symbol,
+                &event.source_exchange,
+                &event.target_exchange,
+            ),
+            WsServerMessage::PremiumMatrix(matrix) => {
+                if !self.symbols.contains(&matrix.symbol) {
+                    return None;
+                }
+                let mut matrix = matrix.clone();
+                matrix.entries.retain(|entry| {
+                    self.exchanges.contains(&entry.buy_exchange)
+                        || self.exchanges.contains(&entry.sell_exchange)
+                });
+                return Some(WsServerMessage::PremiumMatrix(matrix));
+            }
+            WsServerMessage::Depeg(depeg) => self.exchanges.contains(&depeg.exchange),
+            _ => true,
+        };
+        admitted.then_some(message)
+    }
+
+    /// Hold a message for the next flush when throttled. Returns the message
+    /// back if it should be sent right away.
+    pub fn conflate(&mut self, message: WsServerMessage) -> Option<WsServerMessage> {
+        if self.throttle.is_none() {
+            return Some(message);
+        }
+        let key = match &message {
+            WsServerMessage::Price(price) => format!(
+                "price:{}:{}:{}",
+                price.exchange,
+                price.symbol,
+                price.quote.as_deref().unwrap_or_default()
+            ),
+            WsServerMessage::PremiumMatrix(matrix) => format!("premium_matrix:{}", matrix.symbol),
+            _ => return Some(message),
+        };
+        self.pending.insert(key, message);
+        None
+    }
+
+    /// Throttled messages due on this flush.
+    pub fn flush(&mut self) -> Vec<WsServerMessage> {
+        self.pending.drain().map(|(_, message)| message).collect()
+    }
+
+    /// Record broadcast messages missed by the client; returns the total.
+    pub fn record_dropped(&mut self, count: u64) -> u64 {
+        self.dropped += count;
+        self.dropped
+    }
+
+    /// Current subscription, for the acknowledgement.
+    pub fn status(&self) -> WsSubscriptionData {
+        let listed = |selection: &Selection| {
+            selection
+                .only
+                .as_ref()
+                .map(|only| only.difference(&selection.except).cloned().collect())
+        };
+        WsSubscriptionData {
+            channels: CHANNELS
+                .iter()
+                .filter(|channel| self.channels.contains(channel))
+                .map(|channel| channel.to_string())
+                .collect(),
+            symbols: listed(&self.symbols),
+            excluded_symbols: self.symbols.except.iter().cloned().collect(),
+            exchanges: listed(&self.exchanges),
+            excluded_exchanges: self.exchanges.except.iter().cloned().collect(),
+            throttle_ms: self.throttle.map(|interval| interval.as_millis() as u64),
+            dropped: self.dropped,
+        }
+    }
+}
+
+/// Normalized (channels, symbols, exchanges) of a selection update.
+type NormalizedUpdate = (Vec<String>, Vec<String>, Vec<String>);
+
+/// Validate channel and exchange names and canonicalize case
+/// (symbols upper case, exchanges as in the stream).
+fn normalize(update: SelectionUpdate) -> Result<NormalizedUpdate, String> {
+    let channels = update
+        .channels
+        .iter()
+        .map(|name| {
+            CHANNELS
+                .iter()
+                .find(|channel| channel.eq_ignore_ascii_case(name))
+                .map(|channel| channel.to_string())
+                .ok_or_else(|| format!("unknown channel: {}", name))
+        })
+        .collect::<Result<_, _>>()?;
+    let exchanges = update
+        .exchanges
+        .iter()
+        .map(|name| {
+            LIVE_EXCHANGES
+                .iter()
+                .find(|(_, live_name)| live_name.eq_ignore_ascii_case(name))
+                .map(|(_, live_name)| live_name.to_string())
+                .ok_or_else(|| format!("unknown exchange: {}", name))
+        })
+        .collect::<Result<_, _>>()?;
+    let symbols = update
+        .symbols
+        .iter()
+        .map(|symbol| symbol.to_uppercase())
+        .collect();
+    Ok((channels, symbols, exchanges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws_server::{WsPremiumEntry, WsPremiumMatrixData, WsPriceData};
+
+    fn price(exchange: &str, symbol: &str, value: f64) -> WsServerMessage {
+        WsServerMessage::Price(WsPriceData {
+            exchange: exchange.to_string(),
+            symbol: symbol.to_string(),
+            pair_id: 1,
+            price: value,
+            bid: value,
+            ask: value,
+            volume_24h: 0.0,
+            timestamp: 0,
+            quote: Some("USDT".to_string()),
+            price_usd: None,
+            bid_usd: None,
+            ask_usd: None,
+        })
+    }
+
+    fn parse(json: &str) -> ClientMessage {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_client_message_parsing() {
+        assert_eq!(
+            parse(r#"{"type":"subscribe","symbols":["btc"]}"#),
+            ClientMessage::Subscribe(SelectionUpdate {
+                symbols: vec!["btc".to_string()],
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            parse(r#"{"type":"throttle","interval_ms":250}"#),
+            ClientMessage::Throttle { interval_ms: 250 }
+        );
+        assert_eq!(
+            parse(r#"{"type":"snapshot"}"#),
+            ClientMessage::Snapshot { channels: None }
+        );
+        assert!(serde_json::from_str::<ClientMessage>(r#"{"type":"trade"}"#).is_err());
+    }
+
+    #[test]
+    fn test_subscription_filters_symbols_exchanges_and_channels() {
+        let mut subscription = Subscription::default();
+        assert!(subscription.filter(price("Binance", "ETH", 1.0)).is_some());
+
+        subscription
+            .apply(parse(
+                r#"{"type":"subscribe","symbols":["btc"],"exchanges":["upbit"]}"#,
+            ))
+            .unwrap();
+        assert!(subscription.filter(price("Upbit", "BTC", 1.0)).is_some());
+        assert!(subscription.filter(price("Binance", "BTC", 1.0)).is_none());
+        assert!(subscription.filter(price("Upbit", "ETH", 1.0)).is_none());
+
+        let matrix = WsServerMessage::PremiumMatrix(WsPremiumMatrixData {
+            symbol: "BTC".to_string(),
+            pair_id: 1,
+            entries: ["Upbit", "Bybit"]
+                .iter()
+                .map(|buy| WsPremiumEntry {
+                    buy_exchange: buy.to_string(),
+                    sell_exchange: "Binance".to_string(),
+                    buy_quote: "KRW".to_string(),
+                    sell_quote: "USDT".to_string(),
+                    tether_premium_bps: 0,
+                    kimchi_premium_bps: 0,
+                })
+                .collect(),
+            timestamp: 0,
+        });
+        match subscription.filter(matrix) {
+            Some(WsServerMessage::PremiumMatrix(matrix)) => {
+                assert_eq!(matrix.entries.len(), 1);
+                assert_eq!(matrix.entries[0].buy_exchange, "Upbit");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        subscription
+            .apply(parse(r#"{"type":"unsubscribe","channels":["price"]}"#))
+            .unwrap();
+        assert!(subscription.filter(price("Upbit", "BTC", 1.0)).is_none());
+        assert!(!subscription
+            .status()
+            .channels
+            .contains(&"price".to_string()));
+
+        subscription.apply(ClientMessage::Reset).unwrap();
+        assert!(subscription.filter(price("Binance", "ETH", 1.0)).is_some());
+    }
+
+    #[test]
+    fn test_subscription_rejects_unknown_names() {
+        let mut subscription = Subscription::default();
+        let err = subscription
+            .apply(parse(r#"{"type":"subscribe","channels":["orders"]}"#))
+            .unwrap_err();
+        assert_eq!(err, "unknown channel: orders");
+        assert!(subscription
+            .apply(parse(r#"{"type":"subscribe","exchanges":["Kraken"]}"#))
+            .is_err());
+        assert_eq!(subscription.status().symbols, None);
+    }
+
+    #[test]
+    fn test_throttle_conflates_to_latest_per_market() {
+        let mut subscription = Subscription::default();
+        subscription
+            .apply(ClientMessage::Throttle { interval_ms: 10 })
+            .unwrap();
+        assert_eq!(
+            subscription.throttle(),
+            Some(Duration::from_millis(MIN_THROTTLE_MS))
+        );
+
+        assert!(subscription.conflate(price("Upbit", "BTC", 1.0)).is_none());
+        assert!(subscription.conflate(price("Upbit", "BTC", 2.0)).is_none());
+        assert!(subscription
+            .conflate(price("Binance", "BTC", 3.0))
+            .is_none());
+
+        let mut flushed: Vec<f64> = subscription
+            .flush()
+            .into_iter()
+            .map(|message| match message {
+                WsServerMessage::Price(price) => price.price,
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+        flushed.sort_by(|a, b| a.total_cmp(b));
+        assert_eq!(flushed, vec![2.0, 3.0]);
+        assert!(subscription.flush().is_empty());
+
+        subscription
+            .apply(ClientMessage::Throttle { interval_ms: 0 })
+            .unwrap();
+        assert!(subscription.conflate(price("Upbit", "BTC", 4.0)).is_some());
+    }
+}
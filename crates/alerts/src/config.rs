@@ -85,3 +85,37 @@ pub struct AlertHistory {
     pub target_price: f64,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// Persisted kimchi premium index point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KimchiIndexRecord {
+    /// Korean exchange name, or "ALL" for the combined index.
+    pub exchange: String,
+    pub index_bps: f64,
+    pub markets: i64,
+    pub weight_usd: f64,
+    /// Calculation time (ms since epoch).
+    pub timestamp_ms: i64,
+}
+
+/// Persisted opportunity episode (one continuous period a route was open).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpportunityEpisodeRecord {
+    pub episode_id: i64,
+    pub symbol: String,
+    pub buy_exchange: String,
+    pub sell_exchange: String,
+    pub buy_quote: String,
+    pub sell_quote: String,
+    /// First sighting (ms since epoch).
+    pub first_seen_ms: i64,
+    /// Close time (ms since epoch).
+    pub closed_at_ms: i64,
+    pub duration_ms: i64,
+    pub peak_premium_bps: i64,
+    pub last_premium_bps: i64,
+    pub time_above_threshold_ms: i64,
+    pub observations: i64,
+    /// e.g. "below_threshold", "stale", "exchange_disconnected", "shutdown"
+    pub close_reason: String,
+}
@@ -1,6 +1,6 @@
 //! SQLite database for alert configuration and history.
 
-use crate::config::{AlertConfig, AlertHistory};
+use crate::config::{AlertConfig, AlertHistory, KimchiIndexRecord, OpportunityEpisodeRecord};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
 use std::str::FromStr;
 use thiserror::Error;
@@ -113,6 +113,63 @@ impl Database {
         .execute(&self.pool)
         .await?;
 
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS kimchi_index_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                exchange TEXT NOT NULL,
+                index_bps REAL NOT NULL,
+                markets INTEGER NOT NULL,
+                weight_usd REAL NOT NULL,
+                timestamp_ms INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_kimchi_index_time
+            ON kimchi_index_history(exchange, timestamp_ms)
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS opportunity_episodes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                episode_id INTEGER NOT NULL,
+                symbol TEXT NOT NULL,
+                buy_exchange TEXT NOT NULL,
+                sell_exchange TEXT NOT NULL,
+                buy_quote TEXT NOT NULL,
+                sell_quote TEXT NOT NULL,
+                first_seen_ms INTEGER NOT NULL,
+                closed_at_ms INTEGER NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                peak_premium_bps INTEGER NOT NULL,
+                last_premium_bps INTEGER NOT NULL,
+                time_above_threshold_ms INTEGER NOT NULL,
+                observations INTEGER NOT NULL,
+                close_reason TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_opportunity_episodes_time
+            ON opportunity_episodes(closed_at_ms)
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
         Ok(())
     }
 
@@ -364,6 +421,191 @@ impl Database {
 
         Ok(result.rows_affected())
     }
+
+    /// Record kimchi index points (combined and per exchange) in one transaction.
+    pub async fn record_kimchi_index(&self, records: &[KimchiIndexRecord]) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+        for record in records {
+            sqlx::query(
+                r#"
+                INSERT INTO kimchi_index_history (exchange, index_bps, markets, weight_usd, timestamp_ms)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&record.exchange)
+            .bind(record.index_bps)
+            .bind(record.markets)
+            .bind(record.weight_usd)
+            .bind(record.timestamp_ms)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Get kimchi index history for an exchange ("ALL" = combined) since a timestamp,
+    /// oldest first.
+    pub async fn get_kimchi_index_history(
+        &self,
+        exchange: &str,
+        since_ms: i64,
+        limit: i64,
+    ) -> Result<Vec<KimchiIndexRecord>, DbError> {
+        let rows = sqlx::query_as::<_, (String, f64, i64, f64, i64)>(
+            r#"
+            SELECT exchange, index_bps, markets, weight_usd, timestamp_ms FROM (
+                SELECT * FROM kimchi_index_history
+                WHERE exchange = ? AND timestamp_ms >= ?
+                ORDER BY timestamp_ms DESC
+                LIMIT ?
+            ) ORDER BY timestamp_ms ASC
+            "#,
+        )
+        .bind(exchange)
+        .bind(since_ms)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(exchange, index_bps, markets, weight_usd, timestamp_ms)| KimchiIndexRecord {
+                    exchange,
+                    index_bps,
+                    markets,
+                    weight_usd,
+                    timestamp_ms,
+                },
+            )
+            .collect())
+    }
+
+    /// Clean up kimchi index history older than the given timestamp.
+    pub async fn cleanup_kimchi_index_history(&self, before_ms: i64) -> Result<u64, DbError> {
+        let result = sqlx::query("DELETE FROM kimchi_index_history WHERE timestamp_ms < ?")
+            .bind(before_ms)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Record closed opportunity episodes in one transaction.
+    pub async fn record_opportunity_episodes(
+        &self,
+        records: &[OpportunityEpisodeRecord],
+    ) -> Result<(), DbError> {
+        let mut tx = self.pool.begin().await?;
+        for record in records {
+            sqlx::query(
+                r#"
+                INSERT INTO opportunity_episodes (
+                    episode_id, symbol, buy_exchange, sell_exchange, buy_quote, sell_quote,
+                    first_seen_ms, closed_at_ms, duration_ms, peak_premium_bps, last_premium_bps,
+                    time_above_threshold_ms, observations, close_reason
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(record.episode_id)
+            .bind(&record.symbol)
+            .bind(&record.buy_exchange)
+            .bind(&record.sell_exchange)
+            .bind(&record.buy_quote)
+            .bind(&record.sell_quote)
+            .bind(record.first_seen_ms)
+            .bind(record.closed_at_ms)
+            .bind(record.duration_ms)
+            .bind(record.peak_premium_bps)
+            .bind(record.last_premium_bps)
+            .bind(record.time_above_threshold_ms)
+            .bind(record.observations)
+            .bind(&record.close_reason)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Get opportunity episodes closed since a timestamp (optionally for one symbol),
+    /// oldest first.
+    pub async fn get_opportunity_episodes(
+        &self,
+        symbol: Option<&str>,
+        since_ms: i64,
+        limit: i64,
+    ) -> Result<Vec<OpportunityEpisodeRecord>, DbError> {
+        type Row = (
+            i64,
+            String,
+            String,
+            String,
+            String,
+            String,
+            i64,
+            i64,
+            i64,
+            i64,
+            i64,
+            i64,
+            i64,
+            String,
+        );
+        let rows = sqlx::query_as::<_, Row>(
+            r#"
+            SELECT episode_id, symbol, buy_exchange, sell_exchange, buy_quote, sell_quote,
+                   first_seen_ms, closed_at_ms, duration_ms, peak_premium_bps, last_premium_bps,
+                   time_above_threshold_ms, observations, close_reason
+            FROM (
+                SELECT * FROM opportunity_episodes
+                WHERE (? IS NULL OR symbol = ?) AND closed_at_ms >= ?
+                ORDER BY closed_at_ms DESC
+                LIMIT ?
+            ) ORDER BY closed_at_ms ASC
+            "#,
+        )
+        .bind(symbol)
+        .bind(symbol)
+        .bind(since_ms)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| OpportunityEpisodeRecord {
+                episode_id: row.0,
+                symbol: row.1,
+                buy_exchange: row.2,
+                sell_exchange: row.3,
+                buy_quote: row.4,
+                sell_quote: row.5,
+                first_seen_ms: row.6,
+                closed_at_ms: row.7,
+                duration_ms: row.8,
+                peak_premium_bps: row.9,
+                last_premium_bps: row.10,
+                time_above_threshold_ms: row.11,
+                observations: row.12,
+                close_reason: row.13,
+            })
+            .collect())
+    }
+
+    /// Clean up opportunity episodes closed before the given timestamp.
+    pub async fn cleanup_opportunity_episodes(&self, before_ms: i64) -> Result<u64, DbError> {
+        let result = sqlx::query("DELETE FROM opportunity_episodes WHERE closed_at_ms < ?")
+            .bind(before_ms)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
 }
 
 #[cfg(test)]
@@ -434,4 +676,94 @@ mod tests {
             .unwrap();
         assert!(!is_active);
     }
+
+    #[tokio::test]
+    async fn test_kimchi_index_history() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let record = |exchange: &str, index_bps: f64, timestamp_ms: i64| KimchiIndexRecord {
+            exchange: exchange.to_string(),
+            index_bps,
+            markets: 10,
+            weight_usd: 1_000_000.0,
+            timestamp_ms,
+        };
+
+        db.record_kimchi_index(&[record("ALL", 200.0, 1_000), record("Upbit", 210.0, 1_000)])
+            .await
+            .unwrap();
+        db.record_kimchi_index(&[record("ALL", 250.0, 2_000), record("Upbit", 260.0, 2_000)])
+            .await
+            .unwrap();
+
+        let history = db.get_kimchi_index_history("ALL", 0, 100).await.unwrap();
+        assert_eq!(
+            history,
+            vec![record("ALL", 200.0, 1_000), record("ALL", 250.0, 2_000)]
+        );
+
+        // Limit keeps the most recent points
+        let latest = db.get_kimchi_index_history("Upbit", 0, 1).await.unwrap();
+        assert_eq!(latest, vec![record("Upbit", 260.0, 2_000)]);
+
+        let removed = db.cleanup_kimchi_index_history(2_000).await.unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(
+            db.get_kimchi_index_history("ALL", 0, 100)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_opportunity_episode_history() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        let record = |episode_id: i64, symbol: &str, closed_at_ms: i64| OpportunityEpisodeRecord {
+            episode_id,
+            symbol: symbol.to_string(),
+            buy_exchange: "Binance".to_string(),
+            sell_exchange: "Upbit".to_string(),
+            buy_quote: "USDT".to_string(),
+            sell_quote: "KRW".to_string(),
+            first_seen_ms: closed_at_ms - 60_000,
+            closed_at_ms,
+            duration_ms: 60_000,
+            peak_premium_bps: 250,
+            last_premium_bps: 120,
+            time_above_threshold_ms: 55_000,
+            observations: 42,
+            close_reason: "below_threshold".to_string(),
+        };
+
+        db.record_opportunity_episodes(&[
+            record(1, "BTC", 100_000),
+            record(2, "ETH", 200_000),
+            record(3, "BTC", 300_000),
+        ])
+        .await
+        .unwrap();
+
+        let all = db.get_opportunity_episodes(None, 0, 100).await.unwrap();
+        assert_eq!(all.len(), 3);
+        let btc = db
+            .get_opportunity_episodes(Some("BTC"), 0, 100)
+            .await
+            .unwrap();
+        assert_eq!(
+            btc,
+            vec![record(1, "BTC", 100_000), record(3, "BTC", 300_000)]
+        );
+        let latest = db.get_opportunity_episodes(None, 0, 1).await.unwrap();
+        assert_eq!(latest[0].episode_id, 3);
+
+        assert_eq!(db.cleanup_opportunity_episodes(200_000).await.unwrap(), 1);
+        assert_eq!(
+            db.get_opportunity_episodes(None, 0, 100)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
 }
@@ -4,13 +4,15 @@
 //! - SQLite-based configuration storage
 //! - Telegram bot integration for notifications
 //! - Alert filtering and deduplication
+//! - Kimchi premium index history
+//! - Opportunity episode history
 
 pub mod config;
 pub mod db;
 pub mod notifier;
 pub mod telegram;
 
-pub use config::AlertConfig;
+pub use config::{AlertConfig, KimchiIndexRecord, OpportunityEpisodeRecord};
 pub use db::Database;
 pub use notifier::{Notifier, NotifierConfig, TransferPathChecker};
 pub use telegram::TelegramBot;
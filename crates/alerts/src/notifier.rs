@@ -28,6 +28,9 @@ pub struct NotifierConfig {
     pub history_retention_days: i64,
     /// Only send alerts for opportunities with valid transfer paths.
     pub require_transfer_path: bool,
+    /// Also alert when the premium z-score reaches this value, even if the
+    /// premium is below the configured minimum (None = disabled).
+    pub min_premium_zscore: Option<f64>,
 }
 
 impl std::fmt::Debug for NotifierConfig {
@@ -36,6 +39,7 @@ impl std::fmt::Debug for NotifierConfig {
             .field("cooldown_minutes", &self.cooldown_minutes)
             .field("history_retention_days", &self.history_retention_days)
             .field("require_transfer_path", &self.require_transfer_path)
+            .field("min_premium_zscore", &self.min_premium_zscore)
             .finish()
     }
 }
@@ -46,6 +50,7 @@ impl Default for NotifierConfig {
             cooldown_minutes: 5,
             history_retention_days: 30,
             require_transfer_path: true,
+            min_premium_zscore: None,
         }
     }
 }
@@ -138,6 +143,10 @@ impl Notifier {
 
         // Get all enabled configs that match this opportunity
         let configs = self.db.get_all_enabled_configs().await?;
+        let meets_zscore = matches!(
+            (self.config.min_premium_zscore, opportunity.premium_stats),
+            (Some(min_z), Some(stats)) if stats.zscore >= min_z
+        );
         let mut sent_count = 0u32;
 
         for config in configs {
@@ -157,7 +166,8 @@ impl Notifier {
                 0.0
             };
 
-            let meets_premium = premium_bps >= config.min_premium_bps;
+            let meets_premium =
+                premium_bps >= config.min_premium_bps || (premium_bps > 0 && meets_zscore);
             let meets_profit =
                 config.min_profit_usd == 0.0 || optimal_profit_usd >= config.min_profit_usd;
 
@@ -1,6 +1,6 @@
 //! Asset and trading pair definitions.
 
-use crate::{Chain, Exchange};
+use crate::{symbol_to_pair_id, Chain, Exchange};
 use compact_str::CompactString;
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
@@ -73,15 +73,6 @@ impl Asset {
     }
 }
 
-/// Generate a consistent pair_id from a symbol string.
-/// This allows dynamic symbols to work with the pair_id based system.
-pub fn symbol_to_pair_id(symbol: &str) -> u32 {
-    use std::hash::{Hash, Hasher};
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    symbol.hash(&mut hasher);
-    hasher.finish() as u32
-}
-
 /// Trading pair representing base/quote assets on an exchange.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingPair {
@@ -135,6 +126,12 @@ impl TradingPair {
         self.exchange.hash(&mut hasher);
         hasher.finish() as u32
     }
+
+    /// Pair ID of this pair's base asset in the global pair registry.
+    /// Matches the `pair_id` of price ticks for the same market on any exchange.
+    pub fn pair_id(&self) -> u32 {
+        symbol_to_pair_id(&self.base.symbol)
+    }
 }
 
 #[cfg(test)]
@@ -242,4 +239,29 @@ mod tests {
         );
         assert_ne!(pair.id(), pair3.id());
     }
+
+    #[test]
+    fn test_trading_pair_registry_id() {
+        let pair = TradingPair::new(
+            Asset::eth(),
+            Asset::native("USDT", Chain::Ethereum, 6),
+            Exchange::Binance,
+        );
+        assert_eq!(pair.pair_id(), symbol_to_pair_id("ETH"));
+
+        // Same market on another exchange shares the registry ID
+        let pair3 = TradingPair::new(
+            Asset::eth(),
+            Asset::native("USDT", Chain::Ethereum, 6),
+            Exchange::Coinbase,
+        );
+        assert_eq!(pair.pair_id(), pair3.pair_id());
+
+        let btc = TradingPair::new(
+            Asset::btc(),
+            Asset::native("USDT", Chain::Ethereum, 6),
+            Exchange::Binance,
+        );
+        assert_ne!(pair.pair_id(), btc.pair_id());
+    }
 }
@@ -79,6 +79,49 @@ impl Chain {
         }
     }
 
+    /// Symbol of the native token that pays for gas.
+    pub fn native_symbol(self) -> &'static str {
+        match self {
+            Chain::Ethereum | Chain::Arbitrum | Chain::Optimism | Chain::Base => "ETH",
+            Chain::Polygon => "POL",
+            Chain::Avalanche => "AVAX",
+            Chain::Bsc => "BNB",
+            Chain::Solana => "SOL",
+            Chain::Cosmos => "ATOM",
+            Chain::Osmosis => "OSMO",
+        }
+    }
+
+    /// Decimal places of the native token's smallest unit (wei, lamports, uatom).
+    pub fn native_decimals(self) -> u8 {
+        match self {
+            Chain::Solana => 9,
+            Chain::Cosmos | Chain::Osmosis => 6,
+            _ => 18,
+        }
+    }
+
+    /// Chain for an exchange withdrawal network name or ID (e.g. "ERC20", "Arbitrum One", "BEP20").
+    /// Returns None for networks that are not one of our chains (Bitcoin, Tron, ...).
+    pub fn from_network(network: &str) -> Option<Self> {
+        let chain = match network.trim().to_lowercase().as_str() {
+            "ethereum" | "eth" | "erc20" => Chain::Ethereum,
+            "arbitrum" | "arbitrum one" | "arbone" | "arbi" | "arb" => Chain::Arbitrum,
+            "optimism" | "op" | "opeth" => Chain::Optimism,
+            "base" => Chain::Base,
+            "polygon" | "matic" | "pol" | "polygon pos" => Chain::Polygon,
+            "avalanche" | "avalanche c-chain" | "avaxc" | "avax c-chain" | "avax-c" => {
+                Chain::Avalanche
+            }
+            "bsc" | "bep20" | "bsc (bnb chain)" | "bnb smart chain" => Chain::Bsc,
+            "solana" | "sol" | "spl" => Chain::Solana,
+            "cosmos" | "atom" | "cosmos hub" => Chain::Cosmos,
+            "osmosis" | "osmo" => Chain::Osmosis,
+            _ => return None,
+        };
+        Some(chain)
+    }
+
     /// Get all chain variants.
     pub fn all() -> &'static [Chain] {
         &[
@@ -141,6 +184,24 @@ mod tests {
         assert_eq!(Chain::Solana.as_str(), "Solana");
     }
 
+    #[test]
+    fn test_chain_native_token() {
+        assert_eq!(Chain::Arbitrum.native_symbol(), "ETH");
+        assert_eq!(Chain::Bsc.native_symbol(), "BNB");
+        assert_eq!(Chain::Ethereum.native_decimals(), 18);
+        assert_eq!(Chain::Solana.native_decimals(), 9);
+    }
+
+    #[test]
+    fn test_chain_from_network() {
+        assert_eq!(Chain::from_network("ERC20"), Some(Chain::Ethereum));
+        assert_eq!(Chain::from_network("Arbitrum One"), Some(Chain::Arbitrum));
+        assert_eq!(Chain::from_network("BEP20"), Some(Chain::Bsc));
+        assert_eq!(Chain::from_network("SOL"), Some(Chain::Solana));
+        assert_eq!(Chain::from_network("BTC"), None);
+        assert_eq!(Chain::from_network("TRC20"), None);
+    }
+
     #[test]
     fn test_chain_all_variants() {
         // Ensure all variants are covered
@@ -1,17 +1,19 @@
 //! Exchange identifiers and types.
 
-use crate::Chain;
+use crate::{Chain, FixedPoint};
 use serde::{Deserialize, Serialize};
 
 /// Type of exchange.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum ExchangeType {
-    Cex = 1,       // Centralized exchange (Binance, Coinbase)
-    CpmmDex = 2,   // Constant Product AMM (Uniswap V2)
-    ClmmDex = 3,   // Concentrated Liquidity AMM (Uniswap V3)
-    PerpDex = 4,   // Perpetual DEX (dYdX, GMX)
-    Orderbook = 5, // On-chain orderbook (Serum)
+    Cex = 1,           // Centralized exchange (Binance, Coinbase)
+    CpmmDex = 2,       // Constant Product AMM (Uniswap V2)
+    ClmmDex = 3,       // Concentrated Liquidity AMM (Uniswap V3)
+    PerpDex = 4,       // Perpetual DEX (dYdX, GMX)
+    Orderbook = 5,     // On-chain orderbook (Serum)
+    StableSwapDex = 6, // StableSwap AMM (Curve)
+    WeightedDex = 7,   // Weighted-product AMM (Balancer)
 }
 
 impl ExchangeType {
@@ -22,6 +24,8 @@ impl ExchangeType {
             3 => Some(ExchangeType::ClmmDex),
             4 => Some(ExchangeType::PerpDex),
             5 => Some(ExchangeType::Orderbook),
+            6 => Some(ExchangeType::StableSwapDex),
+            7 => Some(ExchangeType::WeightedDex),
             _ => None,
         }
     }
@@ -115,7 +119,9 @@ impl Exchange {
 
             Exchange::UniswapV2 | Exchange::SushiSwap => ExchangeType::CpmmDex,
 
-            Exchange::UniswapV3 | Exchange::Curve | Exchange::Balancer => ExchangeType::ClmmDex,
+            Exchange::UniswapV3 => ExchangeType::ClmmDex,
+            Exchange::Curve => ExchangeType::StableSwapDex,
+            Exchange::Balancer => ExchangeType::WeightedDex,
 
             Exchange::Raydium | Exchange::Orca | Exchange::Jupiter => ExchangeType::CpmmDex,
 
@@ -207,6 +213,46 @@ impl Exchange {
     }
 }
 
+/// Order size rules for a single market, as published by the exchange.
+///
+/// All values are FixedPoint; 0 means the exchange does not enforce (or
+/// did not report) the rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct MarketRules {
+    /// Minimum order value in quote currency.
+    pub min_notional: u64,
+    /// Minimum order quantity in base asset.
+    pub min_qty: u64,
+    /// Quantity step (lot size) in base asset.
+    pub lot_size: u64,
+}
+
+impl MarketRules {
+    /// Round a quantity down to the lot size.
+    pub fn round_down(&self, qty: u64) -> u64 {
+        if self.lot_size == 0 {
+            qty
+        } else {
+            qty - qty % self.lot_size
+        }
+    }
+
+    /// Round a quantity up to the lot size.
+    pub fn round_up(&self, qty: u64) -> u64 {
+        if self.lot_size == 0 || qty.is_multiple_of(self.lot_size) {
+            qty
+        } else {
+            qty.saturating_add(self.lot_size - qty % self.lot_size)
+        }
+    }
+
+    /// Check whether an order of `qty` at `price` satisfies the minimums.
+    pub fn accepts(&self, qty: u64, price: u64) -> bool {
+        let notional = (qty as u128 * price as u128 / FixedPoint::SCALE as u128) as u64;
+        qty > 0 && qty >= self.min_qty && notional >= self.min_notional
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,6 +266,8 @@ mod tests {
         assert_eq!(ExchangeType::from_id(3), Some(ExchangeType::ClmmDex));
         assert_eq!(ExchangeType::from_id(4), Some(ExchangeType::PerpDex));
         assert_eq!(ExchangeType::from_id(5), Some(ExchangeType::Orderbook));
+        assert_eq!(ExchangeType::from_id(6), Some(ExchangeType::StableSwapDex));
+        assert_eq!(ExchangeType::from_id(7), Some(ExchangeType::WeightedDex));
         assert_eq!(ExchangeType::from_id(255), None);
     }
 
@@ -256,6 +304,11 @@ mod tests {
         assert_eq!(Exchange::Coinbase.exchange_type(), ExchangeType::Cex);
         assert_eq!(Exchange::UniswapV2.exchange_type(), ExchangeType::CpmmDex);
         assert_eq!(Exchange::UniswapV3.exchange_type(), ExchangeType::ClmmDex);
+        assert_eq!(Exchange::Curve.exchange_type(), ExchangeType::StableSwapDex);
+        assert_eq!(
+            Exchange::Balancer.exchange_type(),
+            ExchangeType::WeightedDex
+        );
         assert_eq!(Exchange::Gmx.exchange_type(), ExchangeType::PerpDex);
     }
 
@@ -296,4 +349,27 @@ mod tests {
         assert!(dexes.contains(&Exchange::Raydium));
         assert!(!dexes.contains(&Exchange::Binance));
     }
+
+    // === MarketRules tests ===
+
+    #[test]
+    fn test_market_rules_rounding() {
+        let rules = MarketRules {
+            min_notional: FixedPoint::from_f64(5.0).0,
+            min_qty: FixedPoint::from_f64(0.01).0,
+            lot_size: FixedPoint::from_f64(0.01).0,
+        };
+        let qty = FixedPoint::from_f64(1.234).0;
+        assert_eq!(rules.round_down(qty), FixedPoint::from_f64(1.23).0);
+        assert_eq!(rules.round_up(qty), FixedPoint::from_f64(1.24).0);
+        assert_eq!(MarketRules::default().round_down(qty), qty);
+
+        let price = FixedPoint::from_f64(100.0).0;
+        assert!(rules.accepts(FixedPoint::from_f64(0.05).0, price));
+        assert!(!rules.accepts(FixedPoint::from_f64(0.04).0, price));
+        assert!(!rules.accepts(
+            FixedPoint::from_f64(0.005).0,
+            FixedPoint::from_f64(10_000.0).0
+        ));
+    }
 }
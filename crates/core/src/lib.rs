@@ -6,6 +6,7 @@ pub mod chain;
 pub mod exchange;
 pub mod execution;
 pub mod opportunity;
+pub mod pair_registry;
 pub mod price;
 pub mod quote_currency;
 
@@ -15,5 +16,6 @@ pub use chain::*;
 pub use exchange::*;
 pub use execution::*;
 pub use opportunity::*;
+pub use pair_registry::*;
 pub use price::*;
 pub use quote_currency::*;
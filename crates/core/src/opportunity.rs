@@ -16,6 +16,65 @@ pub enum OptimalSizeReason {
     NotProfitable,
     /// Missing KRW conversion rate for cross-currency calculation.
     NoConversionRate,
+    /// Profitable through the whole cached orderbook; size includes extrapolated depth.
+    DepthExhausted,
+    /// Profitable size is below an exchange's minimum order (notional or quantity).
+    BelowMinOrder,
+    /// No network is open for withdrawal on the buy exchange and deposit on the sell exchange.
+    NoTransferNetwork,
+}
+
+/// Per-factor breakdown of an opportunity's confidence score.
+///
+/// Each factor is scored 0-100 (50 = no data). `ArbitrageOpportunity::confidence_score`
+/// is the weighted combination of these factors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ConfidenceBreakdown {
+    /// Agreement between source and target quote timestamps.
+    pub quote_age: u8,
+    /// Top-of-book depth relative to the optimal size.
+    pub depth: u8,
+    /// How consistently the premium stayed above threshold recently.
+    pub persistence: u8,
+    /// Availability of a deposit/withdraw network shared by both exchanges.
+    pub transfer_path: u8,
+    /// Recent trading activity (24h volume) on both exchanges.
+    pub activity: u8,
+    /// Symbol mapping agreement (0 when the symbol maps to different coins).
+    pub symbol_mapping: u8,
+}
+
+/// Statistics of a route's current premium relative to its recent history.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct PremiumStats {
+    /// Number of samples in the window, including the latest.
+    pub samples: u32,
+    /// Mean premium over the window before the latest sample (bps).
+    pub mean_bps: f64,
+    /// Sample standard deviation over the window before the latest sample (bps).
+    pub stddev_bps: f64,
+    /// Z-score of the latest premium: (latest - mean) / stddev (0 if stddev is 0).
+    pub zscore: f64,
+    /// Percentage of earlier samples at or below the latest premium (0-100).
+    pub percentile: f64,
+    /// Mean-reversion half-life in ms (None if the series is not mean-reverting).
+    pub half_life_ms: Option<f64>,
+}
+
+/// Price risk while the asset is in transit between exchanges.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TransferRisk {
+    /// Transfer network (canonical name, None if unknown).
+    pub network: Option<String>,
+    /// Expected confirmation plus deposit crediting time (ms).
+    pub transfer_time_ms: u64,
+    /// Standard deviation of the price move over the transfer time (bps).
+    pub volatility_bps: f64,
+    /// Profit after a volatility penalty (quote currency, FixedPoint scale).
+    pub expected_profit: i64,
+    /// Profit after an adverse price move at the worst-case quantile
+    /// (quote currency, FixedPoint scale).
+    pub worst_case_profit: i64,
 }
 
 /// USD-like stablecoin type for premium calculation.
@@ -231,6 +290,15 @@ pub struct ArbitrageOpportunity {
     pub max_amount: u64,
     /// Confidence score 0-100
     pub confidence_score: u8,
+    /// Per-factor breakdown of `confidence_score` (None until scored).
+    #[serde(default)]
+    pub confidence_breakdown: Option<ConfidenceBreakdown>,
+    /// Premium statistics over the detector's history window (None until enough history).
+    #[serde(default)]
+    pub premium_stats: Option<PremiumStats>,
+    /// Transfer-time price risk at optimal_size (None until sized).
+    #[serde(default)]
+    pub transfer_risk: Option<TransferRisk>,
 
     // Optimal execution sizing (from orderbook depth analysis)
     /// Optimal trade size calculated from orderbook depth walking.
@@ -395,6 +463,9 @@ impl ArbitrageOpportunity {
             min_amount: 0,
             max_amount: u64::MAX,
             confidence_score: 50,
+            confidence_breakdown: None,
+            premium_stats: None,
+            transfer_risk: None,
             optimal_size: 0,
             optimal_profit: 0,
             optimal_size_reason: OptimalSizeReason::default(),
@@ -448,6 +519,19 @@ impl ArbitrageOpportunity {
         self
     }
 
+    /// Set confidence score and its breakdown (builder pattern).
+    pub fn with_confidence(mut self, score: u8, breakdown: ConfidenceBreakdown) -> Self {
+        self.confidence_score = score;
+        self.confidence_breakdown = Some(breakdown);
+        self
+    }
+
+    /// Set premium statistics (builder pattern).
+    pub fn with_premium_stats(mut self, stats: Option<PremiumStats>) -> Self {
+        self.premium_stats = stats;
+        self
+    }
+
     /// Calculate multi-denomination premiums for opportunities.
     /// Returns (usdlike_premium, kimchi_premium_bps).
     ///
@@ -593,6 +677,18 @@ impl ArbitrageOpportunity {
     pub fn is_profitable(&self) -> bool {
         self.net_profit_estimate > 0
     }
+
+    /// Net profit estimate relative to the buy notional at optimal size (bps).
+    /// None until sized.
+    pub fn net_profit_bps(&self) -> Option<i32> {
+        let notional =
+            self.optimal_size as i128 * self.source_price as i128 / FixedPoint::SCALE as i128;
+        if notional <= 0 {
+            return None;
+        }
+        let bps = self.net_profit_estimate as i128 * 10_000 / notional;
+        Some(bps.clamp(i32::MIN as i128, i32::MAX as i128) as i32)
+    }
 }
 
 #[cfg(test)]
@@ -747,4 +843,22 @@ mod tests {
         opp.net_profit_estimate = -100; // negative
         assert!(!opp.is_profitable());
     }
+
+    #[test]
+    fn test_arbitrage_opportunity_net_profit_bps() {
+        let mut opp = ArbitrageOpportunity::new(
+            1,
+            Exchange::Binance,
+            Exchange::Coinbase,
+            Asset::eth(),
+            FixedPoint::from_f64(2000.0),
+            FixedPoint::from_f64(2020.0),
+        );
+        assert_eq!(opp.net_profit_bps(), None);
+
+        // $3 net on 0.5 ETH at $2000 = 30 bps
+        opp.optimal_size = FixedPoint::from_f64(0.5).0;
+        opp.net_profit_estimate = FixedPoint::from_f64(3.0).0 as i64;
+        assert_eq!(opp.net_profit_bps(), Some(30));
+    }
 }
@@ -0,0 +1,411 @@
+//! Interning registry mapping market symbols to dense pair IDs.
+//!
+//! IDs are assigned sequentially from 1 in registration order, so two symbols can
+//! never share an ID. A snapshot of the registry can be saved and restored so IDs
+//! stay the same across restarts and recorded data remains valid.
+
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use thiserror::Error;
+
+/// Pair registry errors.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PairRegistryError {
+    #[error("pair_id {pair_id} is already assigned to {existing}, cannot assign it to {symbol}")]
+    IdConflict {
+        pair_id: u32,
+        existing: String,
+        symbol: String,
+    },
+    #[error("symbol {symbol} already has pair_id {existing}, cannot reassign it to {pair_id}")]
+    SymbolConflict {
+        symbol: String,
+        existing: u32,
+        pair_id: u32,
+    },
+    #[error("pair_id 0 is reserved")]
+    ReservedId,
+}
+
+/// Single registry entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairRegistryEntry {
+    pub symbol: String,
+    pub pair_id: u32,
+}
+
+/// Serializable registry contents, ordered by pair_id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PairRegistrySnapshot {
+    pub pairs: Vec<PairRegistryEntry>,
+}
+
+/// Outcome of restoring a snapshot entry by entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PairRegistryRestore {
+    /// Entries added to the registry.
+    pub restored: usize,
+    /// Entries skipped because they conflict with the registry or an earlier entry.
+    pub conflicts: Vec<PairRegistryError>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    ids: HashMap<CompactString, u32>,
+    symbols: HashMap<u32, CompactString>,
+    next_id: u32,
+}
+
+impl Default for Inner {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            symbols: HashMap::new(),
+            next_id: 1, // 0 is reserved for "no pair"
+        }
+    }
+}
+
+impl Inner {
+    fn insert(&mut self, symbol: &str, pair_id: u32) {
+        self.ids.insert(CompactString::new(symbol), pair_id);
+        self.symbols.insert(pair_id, CompactString::new(symbol));
+        if pair_id >= self.next_id {
+            self.next_id = pair_id.checked_add(1).expect("pair_id space exhausted");
+        }
+    }
+
+    fn check(&self, symbol: &str, pair_id: u32) -> Result<bool, PairRegistryError> {
+        if pair_id == 0 {
+            return Err(PairRegistryError::ReservedId);
+        }
+        if let Some(&existing) = self.ids.get(symbol) {
+            if existing == pair_id {
+                return Ok(false);
+            }
+            return Err(PairRegistryError::SymbolConflict {
+                symbol: symbol.to_string(),
+                existing,
+                pair_id,
+            });
+        }
+        if let Some(existing) = self.symbols.get(&pair_id) {
+            return Err(PairRegistryError::IdConflict {
+                pair_id,
+                existing: existing.to_string(),
+                symbol: symbol.to_string(),
+            });
+        }
+        Ok(true)
+    }
+}
+
+/// Symbol to pair_id interning registry (thread-safe).
+#[derive(Debug, Default)]
+pub struct PairRegistry {
+    inner: RwLock<Inner>,
+}
+
+impl PairRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process-wide registry used by `symbol_to_pair_id`.
+    pub fn global() -> &'static PairRegistry {
+        static GLOBAL: OnceLock<PairRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(PairRegistry::new)
+    }
+
+    /// Get the pair_id for a symbol, assigning the next free ID if it is new.
+    pub fn get_or_register(&self, symbol: &str) -> u32 {
+        if let Some(pair_id) = self.get(symbol) {
+            return pair_id;
+        }
+
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        // Another thread may have registered it between the read and write lock
+        if let Some(&pair_id) = inner.ids.get(symbol) {
+            return pair_id;
+        }
+        let pair_id = inner.next_id;
+        inner.insert(symbol, pair_id);
+        pair_id
+    }
+
+    /// Get the pair_id for a registered symbol.
+    pub fn get(&self, symbol: &str) -> Option<u32> {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        inner.ids.get(symbol).copied()
+    }
+
+    /// Get the symbol for a pair_id.
+    pub fn symbol(&self, pair_id: u32) -> Option<String> {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        inner.symbols.get(&pair_id).map(|s| s.to_string())
+    }
+
+    /// Register a symbol with a specific pair_id.
+    /// Succeeds if the mapping already exists; fails if either side is taken.
+    pub fn register_with_id(&self, symbol: &str, pair_id: u32) -> Result<(), PairRegistryError> {
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        if inner.check(symbol, pair_id)? {
+            inner.insert(symbol, pair_id);
+        }
+        Ok(())
+    }
+
+    /// Number of registered symbols.
+    pub fn len(&self) -> usize {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        inner.ids.len()
+    }
+
+    /// Check if no symbols are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Snapshot the registry for persistence.
+    pub fn snapshot(&self) -> PairRegistrySnapshot {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        let mut pairs: Vec<PairRegistryEntry> = inner
+            .symbols
+            .iter()
+            .map(|(&pair_id, symbol)| PairRegistryEntry {
+                symbol: symbol.to_string(),
+                pair_id,
+            })
+            .collect();
+        pairs.sort_by_key(|e| e.pair_id);
+        PairRegistrySnapshot { pairs }
+    }
+
+    /// Restore a snapshot, merging it with already registered symbols.
+    /// All entries are validated before any are applied. Returns the number added.
+    pub fn restore(&self, snapshot: &PairRegistrySnapshot) -> Result<usize, PairRegistryError> {
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+
+        let mut seen_ids: HashMap<u32, &str> = HashMap::new();
+        let mut seen_symbols: HashMap<&str, u32> = HashMap::new();
+        let mut new_entries = Vec::new();
+        for entry in &snapshot.pairs {
+            let symbol = entry.symbol.as_str();
+            // Conflicts within the snapshot itself
+            if let Some(&existing) = seen_ids.get(&entry.pair_id) {
+                if existing != symbol {
+                    return Err(PairRegistryError::IdConflict {
+                        pair_id: entry.pair_id,
+                        existing: existing.to_string(),
+                        symbol: symbol.to_string(),
+                    });
+                }
+            }
+            if let Some(&existing) = seen_symbols.get(symbol) {
+                if existing != entry.pair_id {
+                    return Err(PairRegistryError::SymbolConflict {
+                        symbol: symbol.to_string(),
+                        existing,
+                        pair_id: entry.pair_id,
+                    });
+                }
+            }
+            if seen_ids.insert(entry.pair_id, symbol).is_some() {
+                continue; // Duplicate of an earlier entry
+            }
+            seen_symbols.insert(symbol, entry.pair_id);
+
+            if inner.check(symbol, entry.pair_id)? {
+                new_entries.push(entry);
+            }
+        }
+
+        for entry in &new_entries {
+            inner.insert(&entry.symbol, entry.pair_id);
+        }
+        Ok(new_entries.len())
+    }
+
+    /// Restore every snapshot entry that does not conflict, in snapshot order.
+    /// Conflicting entries are skipped and returned so the caller can report them.
+    pub fn restore_partial(&self, snapshot: &PairRegistrySnapshot) -> PairRegistryRestore {
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+
+        let mut result = PairRegistryRestore::default();
+        for entry in &snapshot.pairs {
+            match inner.check(&entry.symbol, entry.pair_id) {
+                Ok(true) => {
+                    inner.insert(&entry.symbol, entry.pair_id);
+                    result.restored += 1;
+                }
+                Ok(false) => {}
+                Err(e) => result.conflicts.push(e),
+            }
+        }
+        result
+    }
+}
+
+/// Get the pair_id for a symbol from the global registry, registering it if needed.
+/// This allows dynamic symbols to work with the pair_id based system.
+pub fn symbol_to_pair_id(symbol: &str) -> u32 {
+    PairRegistry::global().get_or_register(symbol)
+}
+
+/// Get the symbol for a pair_id from the global registry.
+pub fn pair_id_to_symbol(pair_id: u32) -> Option<String> {
+    PairRegistry::global().symbol(pair_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_dense_and_stable() {
+        let registry = PairRegistry::new();
+        assert_eq!(registry.get_or_register("BTC"), 1);
+        assert_eq!(registry.get_or_register("ETH"), 2);
+        assert_eq!(registry.get_or_register("BTC"), 1);
+        assert_eq!(registry.symbol(2).as_deref(), Some("ETH"));
+        assert_eq!(registry.get("SOL"), None);
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn test_many_symbols_never_collide() {
+        let registry = PairRegistry::new();
+        let ids: std::collections::HashSet<u32> = (0..10_000)
+            .map(|i| registry.get_or_register(&format!("SYM{}", i)))
+            .collect();
+        assert_eq!(ids.len(), 10_000);
+        assert!(!ids.contains(&0));
+    }
+
+    #[test]
+    fn test_register_with_id_conflicts() {
+        let registry = PairRegistry::new();
+        registry.register_with_id("BTC", 5).unwrap();
+        // Idempotent
+        registry.register_with_id("BTC", 5).unwrap();
+
+        assert_eq!(
+            registry.register_with_id("ETH", 5),
+            Err(PairRegistryError::IdConflict {
+                pair_id: 5,
+                existing: "BTC".to_string(),
+                symbol: "ETH".to_string(),
+            })
+        );
+        assert_eq!(
+            registry.register_with_id("BTC", 6),
+            Err(PairRegistryError::SymbolConflict {
+                symbol: "BTC".to_string(),
+                existing: 5,
+                pair_id: 6,
+            })
+        );
+        assert_eq!(
+            registry.register_with_id("ETH", 0),
+            Err(PairRegistryError::ReservedId)
+        );
+
+        // New symbols continue after the highest assigned ID
+        assert_eq!(registry.get_or_register("ETH"), 6);
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let registry = PairRegistry::new();
+        registry.get_or_register("BTC");
+        registry.get_or_register("ETH");
+        registry.get_or_register("XRP");
+
+        let snapshot = registry.snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let decoded: PairRegistrySnapshot = serde_json::from_str(&json).unwrap();
+
+        let restored = PairRegistry::new();
+        assert_eq!(restored.restore(&decoded), Ok(3));
+        assert_eq!(restored.get("ETH"), Some(2));
+        assert_eq!(restored.get_or_register("SOL"), 4);
+        // Restoring again adds nothing
+        assert_eq!(restored.restore(&decoded), Ok(0));
+    }
+
+    #[test]
+    fn test_restore_rejects_conflicts_atomically() {
+        let registry = PairRegistry::new();
+        registry.get_or_register("BTC"); // 1
+
+        let snapshot = PairRegistrySnapshot {
+            pairs: vec![
+                PairRegistryEntry {
+                    symbol: "ETH".to_string(),
+                    pair_id: 2,
+                },
+                PairRegistryEntry {
+                    symbol: "SOL".to_string(),
+                    pair_id: 1,
+                },
+            ],
+        };
+        assert!(matches!(
+            registry.restore(&snapshot),
+            Err(PairRegistryError::IdConflict { pair_id: 1, .. })
+        ));
+        // Nothing from the failed snapshot was applied
+        assert_eq!(registry.get("ETH"), None);
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_restore_partial_skips_conflicts() {
+        let registry = PairRegistry::new();
+        registry.get_or_register("BTC"); // 1
+
+        let snapshot = PairRegistrySnapshot {
+            pairs: vec![
+                PairRegistryEntry {
+                    symbol: "ETH".to_string(),
+                    pair_id: 2,
+                },
+                PairRegistryEntry {
+                    symbol: "SOL".to_string(),
+                    pair_id: 1,
+                },
+                PairRegistryEntry {
+                    symbol: "XRP".to_string(),
+                    pair_id: 2,
+                },
+                PairRegistryEntry {
+                    symbol: "BTC".to_string(),
+                    pair_id: 1,
+                },
+            ],
+        };
+        let result = registry.restore_partial(&snapshot);
+        assert_eq!(result.restored, 1);
+        assert_eq!(
+            result.conflicts,
+            vec![
+                PairRegistryError::IdConflict {
+                    pair_id: 1,
+                    existing: "BTC".to_string(),
+                    symbol: "SOL".to_string(),
+                },
+                PairRegistryError::IdConflict {
+                    pair_id: 2,
+                    existing: "ETH".to_string(),
+                    symbol: "XRP".to_string(),
+                },
+            ]
+        );
+        assert_eq!(registry.get("ETH"), Some(2));
+        assert_eq!(registry.get("SOL"), None);
+        assert_eq!(registry.get_or_register("SOL"), 3);
+    }
+}
@@ -62,7 +62,7 @@ pub struct PriceTick {
     quote_currency: u8, // 1 byte
     /// Current price (fixed-point 18 decimals)
     price: u64, // 8 bytes
-    /// 24h trading volume
+    /// 24h trading volume in quote currency (turnover, 0 = not reported)
     pub volume_24h: u64, // 8 bytes
     /// Best bid price
     bid: u64, // 8 bytes
@@ -0,0 +1,150 @@
+//! Engine hot-path benchmarks on synthetic load.
+//!
+//! Run with `cargo bench -p arbitrage-engine`. Per-operation budgets that fail
+//! the build live in `benches/budgets.json` (checked by `tests/latency_budget.rs`).
+
+use arbitrage_core::{Exchange, FixedPoint, QuoteCurrency};
+use arbitrage_engine::{
+    apply_to_detector, apply_to_matrix, calculate_optimal_size, DepthFeeConfig, DetectorConfig,
+    OpportunityDetector, OrderbookCache, Side, SyntheticMarket,
+};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+const SEED: u64 = 0x5eed;
+
+fn premium_matrix(c: &mut Criterion) {
+    let mut market = SyntheticMarket::new(1, SEED);
+    let rates = market.rates();
+    let mut matrix = market.matrix(1);
+
+    c.bench_function("premium_matrix/update", |b| {
+        b.iter_batched(
+            || market.next_tick(),
+            |tick| apply_to_matrix(&mut matrix, black_box(&tick)),
+            BatchSize::SmallInput,
+        )
+    });
+    c.bench_function("premium_matrix/all_premiums_multi_denomination", |b| {
+        b.iter(|| black_box(&matrix).all_premiums_multi_denomination(black_box(&rates)))
+    });
+}
+
+fn detector(c: &mut Criterion) {
+    for pairs in [100u32, 500] {
+        let mut market = SyntheticMarket::new(pairs, SEED);
+        let detector = OpportunityDetector::new(DetectorConfig::default());
+        market.populate(&detector);
+        let rates = market.rates();
+
+        c.bench_function(&format!("detector/update/{}_pairs", pairs), |b| {
+            b.iter_batched(
+                || market.next_tick(),
+                |tick| apply_to_detector(&detector, black_box(&tick)),
+                BatchSize::SmallInput,
+            )
+        });
+        let mut pair_id = 0;
+        c.bench_function(
+            &format!("detector/detect_with_conversion_rates/{}_pairs", pairs),
+            |b| {
+                b.iter(|| {
+                    pair_id = pair_id % pairs + 1;
+                    detector.detect_with_conversion_rates(black_box(pair_id), &rates)
+                })
+            },
+        );
+    }
+}
+
+fn optimal_size(c: &mut Criterion) {
+    let mut market = SyntheticMarket::new(1, SEED);
+    // Sell side 50 bps above the buy side: profitable for the first levels
+    let asks = market.book_side(50_000.0, 20, 2.0, true);
+    let bids = market.book_side(50_250.0, 20, 2.0, false);
+
+    c.bench_function("depth/calculate_optimal_size/20_levels", |b| {
+        b.iter(|| {
+            calculate_optimal_size(
+                black_box(&asks),
+                black_box(&bids),
+                DepthFeeConfig::default(),
+            )
+        })
+    });
+}
+
+fn orderbook(c: &mut Criterion) {
+    let mut market = SyntheticMarket::new(1, SEED);
+    let bids = market.book_side(50_000.0, 50, 1.0, false);
+    let asks = market.book_side(50_005.0, 50, 1.0, true);
+    let mut book = OrderbookCache::new(50);
+    book.update_snapshot(&bids, &asks);
+
+    c.bench_function("orderbook/apply_delta", |b| {
+        b.iter_batched(
+            || {
+                let side = if market.next_unit() < 0.5 {
+                    Side::Bid
+                } else {
+                    Side::Ask
+                };
+                let offset = (market.next_unit() - 0.5) * 0.01;
+                let qty = if market.next_unit() < 0.2 {
+                    0.0
+                } else {
+                    market.next_unit()
+                };
+                (
+                    side,
+                    FixedPoint::from_f64(50_000.0 * (1.0 + offset)).0,
+                    FixedPoint::from_f64(qty).0,
+                )
+            },
+            |(side, price, qty)| book.apply_delta(side, price, qty),
+            BatchSize::SmallInput,
+        )
+    });
+    c.bench_function("orderbook/update_snapshot/50_levels", |b| {
+        b.iter(|| book.update_snapshot(black_box(&bids), black_box(&asks)))
+    });
+}
+
+/// Burst of updates for hundreds of pairs, each followed by detection on its
+/// pair: the event-driven detector's per-update work.
+fn synthetic_load(c: &mut Criterion) {
+    let mut market = SyntheticMarket::new(300, SEED);
+    let detector = OpportunityDetector::new(DetectorConfig::default());
+    market.populate(&detector);
+    let rates = market.rates();
+    let burst: Vec<_> = (0..1_000).map(|_| market.next_tick()).collect();
+
+    c.bench_function("load/update_and_detect/1000_ticks_300_pairs", |b| {
+        b.iter(|| {
+            for tick in &burst {
+                apply_to_detector(&detector, tick);
+                black_box(detector.detect_with_conversion_rates(tick.pair_id, &rates));
+            }
+        })
+    });
+
+    // Kimchi routes: one KRW market against the USDT markets
+    let upbit = burst
+        .iter()
+        .find(|tick| tick.exchange == Exchange::Upbit && tick.quote == QuoteCurrency::KRW)
+        .copied();
+    if let Some(tick) = upbit {
+        c.bench_function("load/detect_krw_pair", |b| {
+            b.iter(|| detector.detect_with_conversion_rates(black_box(tick.pair_id), &rates))
+        });
+    }
+}
+
+criterion_group!(
+    benches,
+    premium_matrix,
+    detector,
+    optimal_size,
+    orderbook,
+    synthetic_load
+);
+criterion_main!(benches);
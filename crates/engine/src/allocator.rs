@@ -0,0 +1,656 @@
+//! Portfolio-level allocation across concurrent opportunities.
+//!
+//! Opportunities detected at the same time compete for the same quote
+//! balances, and often for the same orderbook (several routes buying from one
+//! Upbit ask ladder). Sizing each opportunity independently over-commits both.
+//! The allocator fills all candidates together, always taking the segment with
+//! the best marginal profit next, while tracking shared book consumption,
+//! balances and risk limits. The result is an execution plan.
+
+use crate::{Side, SizingConstraints};
+use arbitrage_core::{Exchange, FixedPoint, QuoteCurrency};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+const SCALE: f64 = FixedPoint::SCALE as f64;
+
+/// Orderbook ladder shared between candidates: (exchange, pair_id, side).
+pub type BookKey = (Exchange, u32, Side);
+
+/// Available balances per exchange and currency (FixedPoint).
+#[derive(Debug, Clone, Default)]
+pub struct Balances {
+    amounts: HashMap<(Exchange, String), u64>,
+}
+
+impl Balances {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the available amount of `currency` (quote or asset code) on an exchange.
+    pub fn set(&mut self, exchange: Exchange, currency: &str, amount: u64) {
+        self.amounts
+            .insert((exchange, currency.to_uppercase()), amount);
+    }
+
+    /// Available amount (0 when unknown).
+    pub fn get(&self, exchange: Exchange, currency: &str) -> u64 {
+        self.amounts
+            .get(&(exchange, currency.to_uppercase()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn spend(&mut self, exchange: Exchange, currency: &str, amount: u64) {
+        if let Some(balance) = self.amounts.get_mut(&(exchange, currency.to_uppercase())) {
+            *balance = balance.saturating_sub(amount);
+        }
+    }
+}
+
+/// One side of a candidate trade.
+#[derive(Debug, Clone, Copy)]
+pub struct AllocationLeg {
+    pub exchange: Exchange,
+    /// Quote currency of the book (prices are in this currency).
+    pub quote: QuoteCurrency,
+    /// Trading fee in basis points.
+    pub fee_bps: u32,
+    /// USD value of one unit of the quote currency.
+    pub quote_usd: f64,
+}
+
+/// An opportunity offered to the allocator.
+#[derive(Debug, Clone)]
+pub struct AllocationCandidate {
+    pub opportunity_id: u64,
+    pub pair_id: u32,
+    /// Base asset symbol (used for inventory and per-asset limits).
+    pub asset: String,
+    /// Leg that buys from the ask ladder.
+    pub buy: AllocationLeg,
+    /// Leg that sells into the bid ladder.
+    pub sell: AllocationLeg,
+    /// Fixed cost of executing the trade at all (e.g. withdrawal fee), in USD.
+    pub fixed_cost_usd: f64,
+    /// Venue order rules, with minimums in each leg's own quote currency.
+    pub rules: SizingConstraints,
+}
+
+/// Risk limits for a plan. USD values of 0 mean unlimited.
+#[derive(Debug, Clone, Default)]
+pub struct AllocatorLimits {
+    /// Total buy notional across all trades.
+    pub max_total_notional_usd: f64,
+    /// Notional per exchange (counted on both the buy and the sell exchange).
+    pub max_exchange_notional_usd: f64,
+    /// Notional per base asset.
+    pub max_asset_notional_usd: f64,
+    /// Maximum number of trades in the plan (0 = unlimited).
+    pub max_trades: usize,
+    /// Sell legs need base asset inventory on the sell exchange
+    /// (simultaneous execution instead of buy-transfer-sell).
+    pub require_sell_inventory: bool,
+}
+
+/// A sized trade in the execution plan.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedTrade {
+    pub opportunity_id: u64,
+    pub pair_id: u32,
+    pub asset: String,
+    pub buy_exchange: Exchange,
+    pub sell_exchange: Exchange,
+    pub buy_quote: QuoteCurrency,
+    pub sell_quote: QuoteCurrency,
+    /// Quantity in base asset (FixedPoint).
+    pub amount: u64,
+    /// Average buy price in the buy quote currency (FixedPoint).
+    pub avg_buy_price: u64,
+    /// Average sell price in the sell quote currency (FixedPoint).
+    pub avg_sell_price: u64,
+    /// Buy cost including fees, in the buy quote currency (FixedPoint).
+    pub buy_cost: u64,
+    /// Sell proceeds after fees, in the sell quote currency (FixedPoint).
+    pub sell_proceeds: u64,
+    /// Buy notional in USD.
+    pub notional_usd: f64,
+    /// Expected profit after fees and fixed costs in USD.
+    pub expected_profit_usd: f64,
+}
+
+/// Amount of a balance the plan uses.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BalanceUsage {
+    pub exchange: Exchange,
+    pub currency: String,
+    pub amount: u64,
+}
+
+/// Allocator output: trades ordered by expected profit, best first.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExecutionPlan {
+    pub trades: Vec<PlannedTrade>,
+    pub balance_usage: Vec<BalanceUsage>,
+    pub total_notional_usd: f64,
+    pub expected_profit_usd: f64,
+}
+
+impl ExecutionPlan {
+    pub fn is_empty(&self) -> bool {
+        self.trades.is_empty()
+    }
+}
+
+/// A filled segment: `qty` bought at `buy_price` and sold at `sell_price`.
+#[derive(Debug, Clone, Copy)]
+struct Fill {
+    buy_price: u64,
+    sell_price: u64,
+    qty: u64,
+}
+
+/// Greedy portfolio allocator.
+#[derive(Debug, Clone, Default)]
+pub struct PortfolioAllocator {
+    limits: AllocatorLimits,
+}
+
+impl PortfolioAllocator {
+    pub fn new(limits: AllocatorLimits) -> Self {
+        Self { limits }
+    }
+
+    pub fn limits(&self) -> &AllocatorLimits {
+        &self.limits
+    }
+
+    /// Pick a subset of candidates and sizes that maximizes expected profit.
+    ///
+    /// `books` holds the ask and bid ladders referenced by the candidates,
+    /// in each leg's quote currency. Candidates whose final size is not
+    /// profitable after fixed costs, or is below a venue minimum, are dropped
+    /// and the remaining candidates are re-allocated.
+    pub fn allocate(
+        &self,
+        candidates: &[AllocationCandidate],
+        books: &HashMap<BookKey, Vec<(u64, u64)>>,
+        balances: &Balances,
+    ) -> ExecutionPlan {
+        let mut excluded = HashSet::new();
+        loop {
+            let fills = self.fill(candidates, books, balances, &excluded);
+            let mut trades = Vec::new();
+            let mut changed = false;
+            for (idx, candidate) in candidates.iter().enumerate() {
+                if excluded.contains(&idx) || fills[idx].is_empty() {
+                    continue;
+                }
+                match finalize(candidate, &fills[idx]) {
+                    Some(trade) => trades.push(trade),
+                    None => {
+                        excluded.insert(idx);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                return self.plan(trades);
+            }
+        }
+    }
+
+    /// Fill candidates segment by segment, best marginal profit first.
+    fn fill(
+        &self,
+        candidates: &[AllocationCandidate],
+        books: &HashMap<BookKey, Vec<(u64, u64)>>,
+        balances: &Balances,
+        excluded: &HashSet<usize>,
+    ) -> Vec<Vec<Fill>> {
+        let limits = &self.limits;
+        let mut fills: Vec<Vec<Fill>> = vec![Vec::new(); candidates.len()];
+        let mut consumed: HashMap<BookKey, u64> = HashMap::new();
+        let mut remaining = balances.clone();
+        let mut total_notional = 0.0;
+        let mut exchange_notional: HashMap<Exchange, f64> = HashMap::new();
+        let mut asset_notional: HashMap<&str, f64> = HashMap::new();
+        let mut trades = 0usize;
+
+        loop {
+            let mut best: Option<(usize, f64, Fill)> = None;
+
+            for (idx, c) in candidates.iter().enumerate() {
+                if excluded.contains(&idx) {
+                    continue;
+                }
+                if fills[idx].is_empty() && limits.max_trades > 0 && trades >= limits.max_trades {
+                    continue;
+                }
+                let buy_key = (c.buy.exchange, c.pair_id, Side::Ask);
+                let sell_key = (c.sell.exchange, c.pair_id, Side::Bid);
+                let (Some(asks), Some(bids)) = (books.get(&buy_key), books.get(&sell_key)) else {
+                    continue;
+                };
+                let consumed_of = |key: &BookKey| consumed.get(key).copied().unwrap_or(0);
+                let (Some((buy_price, buy_qty)), Some((sell_price, sell_qty))) = (
+                    level_at(asks, consumed_of(&buy_key)),
+                    level_at(bids, consumed_of(&sell_key)),
+                ) else {
+                    continue;
+                };
+
+                let margin = margin_usd(c, buy_price, sell_price);
+                let unit_usd = buy_price as f64 * c.buy.quote_usd / SCALE;
+                if margin <= 0.0 || unit_usd <= 0.0 {
+                    continue;
+                }
+
+                let mut qty = buy_qty.min(sell_qty);
+
+                // Quote balance on the buy exchange
+                let unit_cost = with_fee(buy_price, c.buy.fee_bps, true);
+                let balance = remaining.get(c.buy.exchange, c.buy.quote.as_str());
+                if let Some(affordable) =
+                    (balance as u128 * FixedPoint::SCALE as u128).checked_div(unit_cost)
+                {
+                    qty = qty.min(u64::try_from(affordable).unwrap_or(u64::MAX));
+                }
+                if limits.require_sell_inventory {
+                    qty = qty.min(remaining.get(c.sell.exchange, &c.asset));
+                }
+
+                // Notional limits
+                let mut cap_usd = f64::INFINITY;
+                if limits.max_total_notional_usd > 0.0 {
+                    cap_usd = cap_usd.min(limits.max_total_notional_usd - total_notional);
+                }
+                if limits.max_exchange_notional_usd > 0.0 {
+                    for exchange in [c.buy.exchange, c.sell.exchange] {
+                        let used = exchange_notional.get(&exchange).copied().unwrap_or(0.0);
+                        cap_usd = cap_usd.min(limits.max_exchange_notional_usd - used);
+                    }
+                }
+                if limits.max_asset_notional_usd > 0.0 {
+                    let used = asset_notional.get(c.asset.as_str()).copied().unwrap_or(0.0);
+                    cap_usd = cap_usd.min(limits.max_asset_notional_usd - used);
+                }
+                if cap_usd.is_finite() {
+                    qty = qty.min((cap_usd.max(0.0) / unit_usd * SCALE) as u64);
+                }
+
+                if qty == 0 {
+                    continue;
+                }
+                if best.is_none_or(|(_, best_margin, _)| margin > best_margin) {
+                    let fill = Fill {
+                        buy_price,
+                        sell_price,
+                        qty,
+                    };
+                    best = Some((idx, margin, fill));
+                }
+            }
+
+            let Some((idx, _, fill)) = best else {
+                break;
+            };
+            let c = &candidates[idx];
+
+            *consumed
+                .entry((c.buy.exchange, c.pair_id, Side::Ask))
+                .or_insert(0) += fill.qty;
+            *consumed
+                .entry((c.sell.exchange, c.pair_id, Side::Bid))
+                .or_insert(0) += fill.qty;
+            let cost = (fill.qty as u128)
+                .checked_mul(with_fee(fill.buy_price, c.buy.fee_bps, true))
+                .map_or(u128::MAX, |cost| cost / FixedPoint::SCALE as u128);
+            remaining.spend(
+                c.buy.exchange,
+                c.buy.quote.as_str(),
+                u64::try_from(cost).unwrap_or(u64::MAX),
+            );
+            if limits.require_sell_inventory {
+                remaining.spend(c.sell.exchange, &c.asset, fill.qty);
+            }
+
+            let notional =
+                fill.qty as f64 * fill.buy_price as f64 * c.buy.quote_usd / SCALE / SCALE;
+            total_notional += notional;
+            *exchange_notional.entry(c.buy.exchange).or_insert(0.0) += notional;
+            if c.sell.exchange != c.buy.exchange {
+                *exchange_notional.entry(c.sell.exchange).or_insert(0.0) += notional;
+            }
+            *asset_notional.entry(c.asset.as_str()).or_insert(0.0) += notional;
+
+            if fills[idx].is_empty() {
+                trades += 1;
+            }
+            match fills[idx].last_mut() {
+                Some(last)
+                    if last.buy_price == fill.buy_price && last.sell_price == fill.sell_price =>
+                {
+                    last.qty += fill.qty;
+                }
+                _ => fills[idx].push(fill),
+            }
+        }
+
+        fills
+    }
+
+    fn plan(&self, mut trades: Vec<PlannedTrade>) -> ExecutionPlan {
+        trades.sort_by(|a, b| b.expected_profit_usd.total_cmp(&a.expected_profit_usd));
+
+        let mut usage: HashMap<(Exchange, String), u64> = HashMap::new();
+        for trade in &trades {
+            *usage
+                .entry((trade.buy_exchange, trade.buy_quote.as_str().to_string()))
+                .or_insert(0) += trade.buy_cost;
+            if self.limits.require_sell_inventory {
+                *usage
+                    .entry((trade.sell_exchange, trade.asset.to_uppercase()))
+                    .or_insert(0) += trade.amount;
+            }
+        }
+        let mut balance_usage: Vec<_> = usage
+            .into_iter()
+            .map(|((exchange, currency), amount)| BalanceUsage {
+                exchange,
+                currency,
+                amount,
+            })
+            .collect();
+        balance_usage
+            .sort_by(|a, b| (a.exchange.id(), &a.currency).cmp(&(b.exchange.id(), &b.currency)));
+
+        ExecutionPlan {
+            total_notional_usd: trades.iter().map(|t| t.notional_usd).sum(),
+            expected_profit_usd: trades.iter().map(|t| t.expected_profit_usd).sum(),
+            trades,
+            balance_usage,
+        }
+    }
+}
+
+/// Price and remaining quantity of the first level not yet consumed.
+fn level_at(levels: &[(u64, u64)], consumed: u64) -> Option<(u64, u64)> {
+    let mut skipped = 0u64;
+    for &(price, qty) in levels {
+        if skipped + qty > consumed {
+            return Some((price, skipped + qty - consumed));
+        }
+        skipped += qty;
+    }
+    None
+}
+
+/// Price adjusted for a fee: increased for buys, reduced for sells.
+fn with_fee(price: u64, fee_bps: u32, buy: bool) -> u128 {
+    let factor = if buy {
+        10000 + fee_bps as u128
+    } else {
+        10000u128.saturating_sub(fee_bps as u128)
+    };
+    price as u128 * factor / 10000
+}
+
+/// Profit per unit of base asset in USD after trading fees.
+fn margin_usd(c: &AllocationCandidate, buy_price: u64, sell_price: u64) -> f64 {
+    let buy = with_fee(buy_price, c.buy.fee_bps, true) as f64 * c.buy.quote_usd;
+    let sell = with_fee(sell_price, c.sell.fee_bps, false) as f64 * c.sell.quote_usd;
+    (sell - buy) / SCALE
+}
+
+/// Round to the lot size, apply fixed costs and venue minimums.
+/// Fills are in fill order, so trimming from the end drops the worst segments.
+fn finalize(c: &AllocationCandidate, fills: &[Fill]) -> Option<PlannedTrade> {
+    let total: u64 = fills.iter().map(|f| f.qty).sum();
+    let mut left = c.rules.lot().round_down(total);
+    let amount = left;
+
+    let mut buy_value = 0u128;
+    let mut sell_value = 0u128;
+    let mut buy_cost = 0u128;
+    let mut sell_proceeds = 0u128;
+    let mut profit = 0.0;
+    for fill in fills {
+        let qty = fill.qty.min(left);
+        if qty == 0 {
+            break;
+        }
+        left -= qty;
+        let scale = FixedPoint::SCALE as u128;
+        buy_value += qty as u128 * fill.buy_price as u128 / scale;
+        sell_value += qty as u128 * fill.sell_price as u128 / scale;
+        buy_cost += qty as u128 * with_fee(fill.buy_price, c.buy.fee_bps, true) / scale;
+        sell_proceeds += qty as u128 * with_fee(fill.sell_price, c.sell.fee_bps, false) / scale;
+        profit += qty as f64 * margin_usd(c, fill.buy_price, fill.sell_price) / SCALE;
+    }
+    if amount == 0 {
+        return None;
+    }
+
+    let avg = |value: u128| (value * FixedPoint::SCALE as u128 / amount as u128) as u64;
+    let (avg_buy_price, avg_sell_price) = (avg(buy_value), avg(sell_value));
+    let expected_profit_usd = profit - c.fixed_cost_usd;
+    if expected_profit_usd <= 0.0
+        || !c.rules.buy.accepts(amount, avg_buy_price)
+        || !c.rules.sell.accepts(amount, avg_sell_price)
+    {
+        return None;
+    }
+
+    Some(PlannedTrade {
+        opportunity_id: c.opportunity_id,
+        pair_id: c.pair_id,
+        asset: c.asset.clone(),
+        buy_exchange: c.buy.exchange,
+        sell_exchange: c.sell.exchange,
+        buy_quote: c.buy.quote,
+        sell_quote: c.sell.quote,
+        amount,
+        avg_buy_price,
+        avg_sell_price,
+        buy_cost: buy_cost as u64,
+        sell_proceeds: sell_proceeds as u64,
+        notional_usd: buy_value as f64 * c.buy.quote_usd / SCALE,
+        expected_profit_usd,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrage_core::MarketRules;
+
+    fn fp(v: f64) -> u64 {
+        FixedPoint::from_f64(v).0
+    }
+
+    fn leg(exchange: Exchange, quote: QuoteCurrency) -> AllocationLeg {
+        AllocationLeg {
+            exchange,
+            quote,
+            fee_bps: 0,
+            quote_usd: 1.0,
+        }
+    }
+
+    fn candidate(id: u64, buy: Exchange, sell: Exchange) -> AllocationCandidate {
+        AllocationCandidate {
+            opportunity_id: id,
+            pair_id: 1,
+            asset: "BTC".to_string(),
+            buy: leg(buy, QuoteCurrency::USDT),
+            sell: leg(sell, QuoteCurrency::USDT),
+            fixed_cost_usd: 0.0,
+            rules: SizingConstraints::default(),
+        }
+    }
+
+    /// Two routes buying from the same Upbit ask ladder.
+    fn shared_books() -> HashMap<BookKey, Vec<(u64, u64)>> {
+        HashMap::from([
+            (
+                (Exchange::Upbit, 1, Side::Ask),
+                vec![(fp(100.0), fp(1.0)), (fp(101.0), fp(1.0))],
+            ),
+            (
+                (Exchange::Binance, 1, Side::Bid),
+                vec![(fp(105.0), fp(2.0))],
+            ),
+            ((Exchange::Bybit, 1, Side::Bid), vec![(fp(103.0), fp(2.0))]),
+        ])
+    }
+
+    fn usdt_balance(amount: f64) -> Balances {
+        let mut balances = Balances::new();
+        balances.set(Exchange::Upbit, "USDT", fp(amount));
+        balances
+    }
+
+    #[test]
+    fn test_shared_book_is_not_double_counted() {
+        let candidates = [
+            candidate(1, Exchange::Upbit, Exchange::Binance),
+            candidate(2, Exchange::Upbit, Exchange::Bybit),
+        ];
+        let plan = PortfolioAllocator::default().allocate(
+            &candidates,
+            &shared_books(),
+            &usdt_balance(1_000.0),
+        );
+
+        // The better route takes the whole ladder; the other gets nothing
+        assert_eq!(plan.trades.len(), 1);
+        assert_eq!(plan.trades[0].opportunity_id, 1);
+        assert_eq!(plan.trades[0].amount, fp(2.0));
+        assert_eq!(plan.trades[0].avg_buy_price, fp(100.5));
+        assert!((plan.expected_profit_usd - 9.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_balance_limits_allocation() {
+        let candidates = [candidate(1, Exchange::Upbit, Exchange::Binance)];
+        let plan = PortfolioAllocator::default().allocate(
+            &candidates,
+            &shared_books(),
+            &usdt_balance(150.0),
+        );
+
+        let trade = &plan.trades[0];
+        assert!(trade.amount > fp(1.0) && trade.amount < fp(1.5));
+        assert!(trade.buy_cost <= fp(150.0));
+        assert_eq!(
+            plan.balance_usage,
+            vec![BalanceUsage {
+                exchange: Exchange::Upbit,
+                currency: "USDT".to_string(),
+                amount: trade.buy_cost,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_large_balance_does_not_wrap() {
+        // 2^56 * SCALE is a multiple of 2^64: truncating it to u64 would leave nothing affordable
+        let mut balances = Balances::new();
+        balances.set(Exchange::Upbit, "USDT", 1 << 56);
+        let books = HashMap::from([
+            ((Exchange::Upbit, 1, Side::Ask), vec![(1, fp(1.0))]),
+            ((Exchange::Binance, 1, Side::Bid), vec![(2, fp(1.0))]),
+        ]);
+        let candidates = [candidate(1, Exchange::Upbit, Exchange::Binance)];
+
+        let plan = PortfolioAllocator::default().allocate(&candidates, &books, &balances);
+
+        assert_eq!(plan.trades.len(), 1);
+        assert_eq!(plan.trades[0].amount, fp(1.0));
+    }
+
+    #[test]
+    fn test_fixed_cost_drops_candidate_and_frees_book() {
+        // Route 1 has the better spread but a fixed cost larger than its profit
+        let mut expensive = candidate(1, Exchange::Upbit, Exchange::Binance);
+        expensive.fixed_cost_usd = 50.0;
+        let candidates = [expensive, candidate(2, Exchange::Upbit, Exchange::Bybit)];
+
+        let plan = PortfolioAllocator::default().allocate(
+            &candidates,
+            &shared_books(),
+            &usdt_balance(1_000.0),
+        );
+
+        assert_eq!(plan.trades.len(), 1);
+        assert_eq!(plan.trades[0].opportunity_id, 2);
+        assert_eq!(plan.trades[0].amount, fp(2.0));
+    }
+
+    #[test]
+    fn test_risk_limits() {
+        let candidates = [
+            candidate(1, Exchange::Upbit, Exchange::Binance),
+            candidate(2, Exchange::Binance, Exchange::Bybit),
+        ];
+        let mut books = shared_books();
+        books.insert(
+            (Exchange::Binance, 1, Side::Ask),
+            vec![(fp(100.0), fp(2.0))],
+        );
+        let mut balances = usdt_balance(1_000.0);
+        balances.set(Exchange::Binance, "USDT", fp(1_000.0));
+
+        let allocator = PortfolioAllocator::new(AllocatorLimits {
+            max_trades: 1,
+            ..AllocatorLimits::default()
+        });
+        assert_eq!(
+            allocator
+                .allocate(&candidates, &books, &balances)
+                .trades
+                .len(),
+            1
+        );
+
+        let allocator = PortfolioAllocator::new(AllocatorLimits {
+            max_total_notional_usd: 150.0,
+            ..AllocatorLimits::default()
+        });
+        let plan = allocator.allocate(&candidates, &books, &balances);
+        assert!(plan.total_notional_usd <= 150.0 + 1e-6);
+
+        let allocator = PortfolioAllocator::new(AllocatorLimits {
+            require_sell_inventory: true,
+            ..AllocatorLimits::default()
+        });
+        assert!(allocator
+            .allocate(&candidates, &books, &balances)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_lot_size_and_minimums() {
+        let mut c = candidate(1, Exchange::Upbit, Exchange::Binance);
+        c.rules = SizingConstraints {
+            buy: MarketRules {
+                lot_size: fp(0.1),
+                ..MarketRules::default()
+            },
+            sell: MarketRules::default(),
+        };
+        let plan = PortfolioAllocator::default().allocate(
+            &[c.clone()],
+            &shared_books(),
+            &usdt_balance(150.0),
+        );
+        assert_eq!(plan.trades[0].amount, fp(1.4));
+
+        c.rules.sell.min_notional = fp(1_000.0);
+        let plan =
+            PortfolioAllocator::default().allocate(&[c], &shared_books(), &usdt_balance(150.0));
+        assert!(plan.is_empty());
+    }
+}
@@ -0,0 +1,1562 @@
+//! AMM swap math for pricing DEX pools.
+//!
+//! Pools are quoted offline from their state (reserves, or sqrt price, liquidity
+//! and initialized ticks), so no RPC is needed. Amounts are raw token units;
+//! prices are quote per base in whole tokens.
+//!
+//! - `CpmmPool`: constant product (x*y=k) with the fee taken from the input,
+//!   matching Uniswap V2 `getAmountOut`.
+//! - `ClmmPool`: concentrated liquidity with Uniswap V3 tick traversal, using the
+//!   same Q64.96 fixed-point math and rounding as the pool contract.
+//! - `StableSwapPool`: Curve StableSwap, solving the invariant for D and y by
+//!   Newton iteration as the pool contract does.
+//! - `WeightedPool`: Balancer weighted pools (constant weighted product).
+//!
+//! `AmmPool::marginal_prices` gives bid/ask/mid for `PremiumMatrix`, and
+//! `AmmPool::depth_ladder` turns a pool into orderbook-style levels that
+//! `calculate_optimal_size` can walk against a CEX book. The pool fee is already
+//! in ladder prices, so the DEX leg's fee in `DepthFeeConfig` should be 0.
+
+use arbitrage_core::{FixedPoint, TradeSide};
+use std::cmp::Ordering;
+use thiserror::Error;
+
+/// AMM pricing errors.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmmError {
+    #[error("swap amount is zero")]
+    ZeroAmount,
+    #[error("pool has no liquidity")]
+    NoLiquidity,
+    #[error("arithmetic overflow in pool math")]
+    Overflow,
+    #[error("pool invariant did not converge")]
+    NoConvergence,
+}
+
+/// Result of an exact-input swap quote.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapQuote {
+    /// Input consumed, including the fee (raw units). Less than requested when the
+    /// pool runs out of liquidity in the swap direction.
+    pub amount_in: u128,
+    /// Output received (raw units).
+    pub amount_out: u128,
+    /// Fee paid to liquidity providers, in raw units of the token it is charged
+    /// in: the input for Uniswap and Balancer pools, the output for Curve pools.
+    pub fee_amount: u128,
+    /// Marginal price after the swap, before fees (quote per base).
+    pub price_after: f64,
+    /// Initialized ticks crossed (always 0 for constant-product pools).
+    pub ticks_crossed: u32,
+}
+
+/// Top-of-book view of a pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarginalPrices {
+    /// Marginal price before fees.
+    pub mid: FixedPoint,
+    /// Price received for an infinitesimal sell, after the pool fee.
+    pub bid: FixedPoint,
+    /// Price paid for an infinitesimal buy, after the pool fee.
+    pub ask: FixedPoint,
+}
+
+/// A pool that can be quoted for exact-input swaps between a base and quote token.
+pub trait AmmPool {
+    /// Base and quote token decimals.
+    fn decimals(&self) -> (u8, u8);
+
+    /// Pool fee as a fraction of the input amount.
+    fn fee_rate(&self) -> f64;
+
+    /// Marginal price before fees (quote per base).
+    fn spot_price(&self) -> f64;
+
+    /// Quote an exact-input swap against the current pool state.
+    ///
+    /// `Buy` spends `amount_in` quote tokens for base; `Sell` spends base for quote.
+    fn quote_exact_in(&self, side: TradeSide, amount_in: u128) -> Result<SwapQuote, AmmError>;
+
+    /// Marginal bid/ask/mid, for feeding DEX quotes into a `PremiumMatrix`.
+    fn marginal_prices(&self) -> MarginalPrices {
+        let mid = self.spot_price();
+        let keep = 1.0 - self.fee_rate();
+        MarginalPrices {
+            mid: FixedPoint::from_f64(mid),
+            bid: FixedPoint::from_f64(mid * keep),
+            ask: FixedPoint::from_f64(if keep > 0.0 { mid / keep } else { 0.0 }),
+        }
+    }
+
+    /// Depth-equivalent ladder of `(price, quantity)` levels in FixedPoint.
+    ///
+    /// Splits `max_amount_in` (quote tokens for `Buy`, base tokens for `Sell`) into
+    /// `levels` equal chunks executed one after another, so the result is asks
+    /// (ascending) for `Buy` and bids (descending) for `Sell`. Quantities are in
+    /// base; prices are average fill prices including the pool fee. The ladder
+    /// stops early when the pool runs out of liquidity.
+    fn depth_ladder(&self, side: TradeSide, max_amount_in: u128, levels: usize) -> Vec<(u64, u64)> {
+        let (base_decimals, quote_decimals) = self.decimals();
+        let mut ladder = Vec::with_capacity(levels);
+        let (mut prev_in, mut prev_out) = (0u128, 0u128);
+
+        for level in 1..=levels {
+            let target = if level == levels {
+                max_amount_in
+            } else {
+                max_amount_in / levels as u128 * level as u128
+            };
+            let Ok(quote) = self.quote_exact_in(side, target) else {
+                break;
+            };
+            let (amount_in, amount_out) = (quote.amount_in - prev_in, quote.amount_out - prev_out);
+            (prev_in, prev_out) = (quote.amount_in, quote.amount_out);
+
+            let (base, quote_amount) = match side {
+                TradeSide::Buy => (amount_out, amount_in),
+                TradeSide::Sell => (amount_in, amount_out),
+            };
+            let qty = raw_to_fixed(base, base_decimals);
+            if qty > 0 && quote_amount > 0 {
+                let price =
+                    raw_to_f64(quote_amount, quote_decimals) / raw_to_f64(base, base_decimals);
+                ladder.push((FixedPoint::from_f64(price).0, qty));
+            }
+            if quote.amount_in < target {
+                break;
+            }
+        }
+        ladder
+    }
+}
+
+/// Constant-product pool (Uniswap V2, SushiSwap, Raydium AMM).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpmmPool {
+    /// Base token reserve (raw units).
+    pub base_reserve: u128,
+    /// Quote token reserve (raw units).
+    pub quote_reserve: u128,
+    /// Swap fee in basis points (30 = 0.3%).
+    pub fee_bps: u32,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+}
+
+impl CpmmPool {
+    /// Create a pool from its reserves.
+    pub fn new(
+        base_reserve: u128,
+        quote_reserve: u128,
+        fee_bps: u32,
+        base_decimals: u8,
+        quote_decimals: u8,
+    ) -> Self {
+        Self {
+            base_reserve,
+            quote_reserve,
+            fee_bps,
+            base_decimals,
+            quote_decimals,
+        }
+    }
+}
+
+impl AmmPool for CpmmPool {
+    fn decimals(&self) -> (u8, u8) {
+        (self.base_decimals, self.quote_decimals)
+    }
+
+    fn fee_rate(&self) -> f64 {
+        self.fee_bps as f64 / 10_000.0
+    }
+
+    fn spot_price(&self) -> f64 {
+        if self.base_reserve == 0 {
+            return 0.0;
+        }
+        raw_to_f64(self.quote_reserve, self.quote_decimals)
+            / raw_to_f64(self.base_reserve, self.base_decimals)
+    }
+
+    fn quote_exact_in(&self, side: TradeSide, amount_in: u128) -> Result<SwapQuote, AmmError> {
+        if amount_in == 0 {
+            return Err(AmmError::ZeroAmount);
+        }
+        if self.base_reserve == 0 || self.quote_reserve == 0 {
+            return Err(AmmError::NoLiquidity);
+        }
+        let (reserve_in, reserve_out) = match side {
+            TradeSide::Buy => (self.quote_reserve, self.base_reserve),
+            TradeSide::Sell => (self.base_reserve, self.quote_reserve),
+        };
+
+        // out = in * (1 - fee) * reserve_out / (reserve_in + in * (1 - fee))
+        let in_with_fee = U256::from(amount_in)
+            .checked_mul(U256::from(10_000 - self.fee_bps.min(10_000) as u128))
+            .ok_or(AmmError::Overflow)?;
+        let denominator = U256::from(reserve_in)
+            .checked_mul(U256::from(10_000))
+            .and_then(|d| d.checked_add(in_with_fee))
+            .ok_or(AmmError::Overflow)?;
+        let amount_out = U256::mul_div(in_with_fee, U256::from(reserve_out), denominator)
+            .and_then(U256::to_u128)
+            .ok_or(AmmError::Overflow)?;
+        let fee_amount = U256::mul_div(
+            U256::from(amount_in),
+            U256::from(self.fee_bps as u128),
+            U256::from(10_000),
+        )
+        .and_then(U256::to_u128)
+        .ok_or(AmmError::Overflow)?;
+
+        let after = match side {
+            TradeSide::Buy => CpmmPool {
+                base_reserve: self.base_reserve - amount_out,
+                quote_reserve: self.quote_reserve.saturating_add(amount_in),
+                ..self.clone()
+            },
+            TradeSide::Sell => CpmmPool {
+                base_reserve: self.base_reserve.saturating_add(amount_in),
+                quote_reserve: self.quote_reserve - amount_out,
+                ..self.clone()
+            },
+        };
+        Ok(SwapQuote {
+            amount_in,
+            amount_out,
+            fee_amount,
+            price_after: after.spot_price(),
+            ticks_crossed: 0,
+        })
+    }
+}
+
+/// Lowest tick of a concentrated-liquidity pool.
+pub const MIN_TICK: i32 = -887_272;
+/// Highest tick of a concentrated-liquidity pool.
+pub const MAX_TICK: i32 = 887_272;
+
+/// sqrt price at `MIN_TICK` (Q64.96).
+const MIN_SQRT_RATIO: U256 = U256([4_295_128_739, 0, 0, 0]);
+/// sqrt price at `MAX_TICK` (Q64.96).
+const MAX_SQRT_RATIO: U256 = U256([0x5d951d5263988d26, 0xefd1fc6a50648849, 0xfffd8963, 0]);
+/// 2^96, the Q64.96 unit.
+const Q96: U256 = U256([0, 1 << 32, 0, 0]);
+/// Fee denominator for concentrated-liquidity pools (fees are in hundredths of a bp).
+const FEE_PIPS_DENOMINATOR: u32 = 1_000_000;
+
+/// An initialized tick of a concentrated-liquidity pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClmmTick {
+    pub index: i32,
+    /// Liquidity added when the price crosses this tick upwards (removed downwards).
+    pub liquidity_net: i128,
+}
+
+/// Concentrated-liquidity pool (Uniswap V3 and forks).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClmmPool {
+    /// Current sqrt(token1/token0) price (Q64.96), as in `slot0`.
+    pub sqrt_price_x96: U256,
+    /// Current tick, as in `slot0`.
+    pub tick: i32,
+    /// In-range liquidity.
+    pub liquidity: u128,
+    /// Swap fee in hundredths of a basis point (3000 = 0.3%).
+    pub fee_pips: u32,
+    pub tick_spacing: i32,
+    /// Initialized ticks, ascending by index.
+    pub ticks: Vec<ClmmTick>,
+    /// Whether token0 is the base asset (otherwise token1 is).
+    pub base_is_token0: bool,
+    pub token0_decimals: u8,
+    pub token1_decimals: u8,
+}
+
+impl ClmmPool {
+    /// Create a pool from its `slot0` state and initialized ticks.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sqrt_price_x96: U256,
+        tick: i32,
+        liquidity: u128,
+        fee_pips: u32,
+        tick_spacing: i32,
+        mut ticks: Vec<ClmmTick>,
+        base_is_token0: bool,
+        token0_decimals: u8,
+        token1_decimals: u8,
+    ) -> Self {
+        ticks.sort_by_key(|t| t.index);
+        Self {
+            sqrt_price_x96,
+            tick,
+            liquidity,
+            fee_pips,
+            tick_spacing: tick_spacing.max(1),
+            ticks,
+            base_is_token0,
+            token0_decimals,
+            token1_decimals,
+        }
+    }
+
+    /// Quote price for a sqrt price (quote per base).
+    fn price_at(&self, sqrt_price_x96: U256) -> f64 {
+        let sqrt = sqrt_price_x96.to_f64() / Q96.to_f64();
+        let token1_per_token0 =
+            sqrt * sqrt * 10f64.powi(self.token0_decimals as i32 - self.token1_decimals as i32);
+        if self.base_is_token0 {
+            token1_per_token0
+        } else if token1_per_token0 > 0.0 {
+            1.0 / token1_per_token0
+        } else {
+            0.0
+        }
+    }
+
+    /// Next tick to step to, mirroring `TickBitmap.nextInitializedTickWithinOneWord`.
+    ///
+    /// Steps never leave a 256-tick bitmap word, so rounding matches the contract.
+    /// Returns the tick and its liquidity delta if it is initialized.
+    fn next_tick_within_word(&self, tick: i32, lte: bool) -> (i32, Option<i128>) {
+        let spacing = self.tick_spacing;
+        let compressed = tick.div_euclid(spacing);
+        if lte {
+            let word_start = (compressed - compressed.rem_euclid(256)) * spacing;
+            let upper = compressed * spacing;
+            let idx = self.ticks.partition_point(|t| t.index <= upper);
+            match idx.checked_sub(1).map(|i| self.ticks[i]) {
+                Some(t) if t.index >= word_start => (t.index, Some(t.liquidity_net)),
+                _ => (word_start, None),
+            }
+        } else {
+            let compressed = compressed + 1;
+            let lower = compressed * spacing;
+            let word_end = (compressed + 255 - compressed.rem_euclid(256)) * spacing;
+            let idx = self.ticks.partition_point(|t| t.index < lower);
+            match self.ticks.get(idx) {
+                Some(t) if t.index <= word_end => (t.index, Some(t.liquidity_net)),
+                _ => (word_end, None),
+            }
+        }
+    }
+}
+
+impl AmmPool for ClmmPool {
+    fn decimals(&self) -> (u8, u8) {
+        if self.base_is_token0 {
+            (self.token0_decimals, self.token1_decimals)
+        } else {
+            (self.token1_decimals, self.token0_decimals)
+        }
+    }
+
+    fn fee_rate(&self) -> f64 {
+        self.fee_pips as f64 / FEE_PIPS_DENOMINATOR as f64
+    }
+
+    fn spot_price(&self) -> f64 {
+        self.price_at(self.sqrt_price_x96)
+    }
+
+    fn quote_exact_in(&self, side: TradeSide, amount_in: u128) -> Result<SwapQuote, AmmError> {
+        if amount_in == 0 {
+            return Err(AmmError::ZeroAmount);
+        }
+        if self.liquidity == 0 && self.ticks.is_empty() {
+            return Err(AmmError::NoLiquidity);
+        }
+        // Selling token0 moves the price down
+        let zero_for_one = (side == TradeSide::Sell) == self.base_is_token0;
+        let limit = if zero_for_one {
+            MIN_SQRT_RATIO.checked_add(U256::ONE)
+        } else {
+            MAX_SQRT_RATIO.checked_sub(U256::ONE)
+        }
+        .ok_or(AmmError::Overflow)?;
+
+        let mut sqrt_price = self.sqrt_price_x96;
+        let mut tick = self.tick;
+        let mut liquidity = self.liquidity;
+        let mut remaining = U256::from(amount_in);
+        let mut amount_out = U256::ZERO;
+        let mut fee_amount = U256::ZERO;
+        let mut ticks_crossed = 0;
+
+        while !remaining.is_zero() && sqrt_price != limit {
+            let (next, liquidity_net) = self.next_tick_within_word(tick, zero_for_one);
+            let next = next.clamp(MIN_TICK, MAX_TICK);
+            let sqrt_next = sqrt_ratio_at_tick(next);
+            let target = if zero_for_one {
+                sqrt_next.max(limit)
+            } else {
+                sqrt_next.min(limit)
+            };
+
+            let step = compute_swap_step(sqrt_price, target, liquidity, remaining, self.fee_pips)
+                .ok_or(AmmError::Overflow)?;
+            remaining = step
+                .amount_in
+                .checked_add(step.fee_amount)
+                .and_then(|spent| remaining.checked_sub(spent))
+                .ok_or(AmmError::Overflow)?;
+            amount_out = amount_out
+                .checked_add(step.amount_out)
+                .ok_or(AmmError::Overflow)?;
+            fee_amount = fee_amount
+                .checked_add(step.fee_amount)
+                .ok_or(AmmError::Overflow)?;
+            sqrt_price = step.sqrt_next;
+
+            if sqrt_price == sqrt_next {
+                if let Some(net) = liquidity_net {
+                    let net = if zero_for_one { -net } else { net };
+                    liquidity = liquidity
+                        .checked_add_signed(net)
+                        .ok_or(AmmError::Overflow)?;
+                    ticks_crossed += 1;
+                }
+                tick = if zero_for_one { next - 1 } else { next };
+            }
+        }
+
+        let remaining = remaining.to_u128().ok_or(AmmError::Overflow)?;
+        Ok(SwapQuote {
+            amount_in: amount_in - remaining,
+            amount_out: amount_out.to_u128().ok_or(AmmError::Overflow)?,
+            fee_amount: fee_amount.to_u128().ok_or(AmmError::Overflow)?,
+            price_after: self.price_at(sqrt_price),
+            ticks_crossed,
+        })
+    }
+}
+
+/// sqrt(1.0001^tick) in Q64.96, as `TickMath.getSqrtRatioAtTick`.
+pub fn sqrt_ratio_at_tick(tick: i32) -> U256 {
+    const RATIOS: [u128; 19] = [
+        0xfff97272373d413259a46990580e213a,
+        0xfff2e50f5f656932ef12357cf3c7fdcc,
+        0xffe5caca7e10e4e61c3624eaa0941cd0,
+        0xffcb9843d60f6159c9db58835c926644,
+        0xff973b41fa98c081472e6896dfb254c0,
+        0xff2ea16466c96a3843ec78b326b52861,
+        0xfe5dee046a99a2a811c461f1969c3053,
+        0xfcbe86c7900a88aedcffc83b479aa3a4,
+        0xf987a7253ac413176f2b074cf7815e54,
+        0xf3392b0822b70005940c7a398e4b70f3,
+        0xe7159475a2c29b7443b29c7fa6e889d9,
+        0xd097f3bdfd2022b8845ad8f792aa5825,
+        0xa9f746462d870fdf8a65dc1f90e061e5,
+        0x70d869a156d2a1b890bb3df62baf32f7,
+        0x31be135f97d08fd981231505542fcfa6,
+        0x9aa508b5b7a84e1c677de54f3e99bc9,
+        0x5d6af8dedb81196699c329225ee604,
+        0x2216e584f5fa1ea926041bedfe98,
+        0x48a170391f7dc42444e8fa2,
+    ];
+    let abs_tick = tick.clamp(MIN_TICK, MAX_TICK).unsigned_abs();
+
+    let mut ratio = if abs_tick & 1 != 0 {
+        U256::from(0xfffcb933bd6fad37aa2d162d1a594001u128)
+    } else {
+        U256([0, 0, 1, 0])
+    };
+    for (bit, factor) in RATIOS.iter().enumerate() {
+        if abs_tick & (2 << bit) != 0 {
+            let product = ratio.full_mul(U256::from(*factor));
+            ratio = U256([product[2], product[3], product[4], product[5]]);
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX.div_rem(ratio).0;
+    }
+
+    // Round up so the result is never below the true ratio
+    let round_up = ratio.0[0] & 0xffff_ffff != 0;
+    let sqrt = ratio.shr(32);
+    if round_up {
+        sqrt.checked_add(U256::ONE).unwrap_or(sqrt)
+    } else {
+        sqrt
+    }
+}
+
+/// One swap step within a tick range (`SwapMath.computeSwapStep`, exact input).
+struct SwapStep {
+    sqrt_next: U256,
+    amount_in: U256,
+    amount_out: U256,
+    fee_amount: U256,
+}
+
+fn compute_swap_step(
+    current: U256,
+    target: U256,
+    liquidity: u128,
+    remaining: U256,
+    fee_pips: u32,
+) -> Option<SwapStep> {
+    let zero_for_one = current >= target;
+    let fee_pips = fee_pips.min(FEE_PIPS_DENOMINATOR - 1);
+    let fee = U256::from(fee_pips as u128);
+    let fee_complement = U256::from((FEE_PIPS_DENOMINATOR - fee_pips) as u128);
+
+    let remaining_less_fee = U256::mul_div(
+        remaining,
+        fee_complement,
+        U256::from(FEE_PIPS_DENOMINATOR as u128),
+    )?;
+    let max_in = if zero_for_one {
+        amount0_delta(target, current, liquidity, true)?
+    } else {
+        amount1_delta(current, target, liquidity, true)?
+    };
+    let sqrt_next = if remaining_less_fee >= max_in {
+        target
+    } else {
+        next_sqrt_price_from_input(current, liquidity, remaining_less_fee, zero_for_one)?
+    };
+    let reached_target = sqrt_next == target;
+
+    let (amount_in, amount_out) = if zero_for_one {
+        let amount_in = if reached_target {
+            max_in
+        } else {
+            amount0_delta(sqrt_next, current, liquidity, true)?
+        };
+        (
+            amount_in,
+            amount1_delta(sqrt_next, current, liquidity, false)?,
+        )
+    } else {
+        let amount_in = if reached_target {
+            max_in
+        } else {
+            amount1_delta(current, sqrt_next, liquidity, true)?
+        };
+        (
+            amount_in,
+            amount0_delta(current, sqrt_next, liquidity, false)?,
+        )
+    };
+    let fee_amount = if reached_target {
+        U256::mul_div_rounding_up(amount_in, fee, fee_complement)?
+    } else {
+        // The rest of the input is taken as fee
+        remaining.checked_sub(amount_in)?
+    };
+
+    Some(SwapStep {
+        sqrt_next,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+/// token0 needed to move between two sqrt prices (`SqrtPriceMath.getAmount0Delta`).
+fn amount0_delta(a: U256, b: U256, liquidity: u128, round_up: bool) -> Option<U256> {
+    let (lower, upper) = if a <= b { (a, b) } else { (b, a) };
+    if lower.is_zero() {
+        return None;
+    }
+    let numerator1 = U256::from(liquidity).shl(96);
+    let numerator2 = upper.checked_sub(lower)?;
+    if round_up {
+        U256::mul_div_rounding_up(numerator1, numerator2, upper)?.div_rounding_up(lower)
+    } else {
+        Some(
+            U256::mul_div(numerator1, numerator2, upper)?
+                .div_rem(lower)
+                .0,
+        )
+    }
+}
+
+/// token1 needed to move between two sqrt prices (`SqrtPriceMath.getAmount1Delta`).
+fn amount1_delta(a: U256, b: U256, liquidity: u128, round_up: bool) -> Option<U256> {
+    let (lower, upper) = if a <= b { (a, b) } else { (b, a) };
+    let diff = upper.checked_sub(lower)?;
+    if round_up {
+        U256::mul_div_rounding_up(U256::from(liquidity), diff, Q96)
+    } else {
+        U256::mul_div(U256::from(liquidity), diff, Q96)
+    }
+}
+
+/// sqrt price after adding `amount_in` (`SqrtPriceMath.getNextSqrtPriceFromInput`).
+fn next_sqrt_price_from_input(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount_in: U256,
+    zero_for_one: bool,
+) -> Option<U256> {
+    if sqrt_price.is_zero() || liquidity == 0 {
+        return None;
+    }
+    if amount_in.is_zero() {
+        return Some(sqrt_price);
+    }
+    if zero_for_one {
+        // Rounds up: L * sqrtP / (L + amount * sqrtP)
+        let numerator1 = U256::from(liquidity).shl(96);
+        let product = amount_in.full_mul(sqrt_price);
+        if product[4..].iter().all(|&limb| limb == 0) {
+            let product = U256([product[0], product[1], product[2], product[3]]);
+            if let Some(denominator) = numerator1.checked_add(product) {
+                return U256::mul_div_rounding_up(numerator1, sqrt_price, denominator);
+            }
+        }
+        numerator1.div_rounding_up(numerator1.div_rem(sqrt_price).0.checked_add(amount_in)?)
+    } else {
+        // Rounds down: sqrtP + amount / L
+        let quotient = U256::mul_div(amount_in, Q96, U256::from(liquidity))?;
+        sqrt_price.checked_add(quotient)
+    }
+}
+
+/// Curve fee denominator (fees are in units of 1e-10).
+const CURVE_FEE_DENOMINATOR: u128 = 10_000_000_000;
+/// Precision of the amplification coefficient in StableSwap math.
+const A_PRECISION: u128 = 100;
+/// 18-decimal fixed-point unit used by Curve rates and Balancer weights and fees.
+const WAD: u128 = 1_000_000_000_000_000_000;
+/// Newton iteration limit, as in the Curve contracts.
+const MAX_NEWTON_ITERATIONS: usize = 255;
+
+/// Curve StableSwap pool (plain pools of 2-8 coins).
+///
+/// Balances are normalized to 18 decimals and multiplied by per-coin rates
+/// before solving the invariant, so LST pools can be quoted at their oracle rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StableSwapPool {
+    /// Coin balances (raw units).
+    pub balances: Vec<u128>,
+    pub decimals: Vec<u8>,
+    /// Per-coin rate in 1e18 (1e18 for plain coins; the oracle rate for LSTs).
+    pub rates: Vec<u128>,
+    /// Amplification coefficient as returned by the pool's `A()`.
+    pub amplification: u128,
+    /// Swap fee in units of 1e-10 (4_000_000 = 0.04%), charged on the output.
+    pub fee: u128,
+    pub base_index: usize,
+    pub quote_index: usize,
+}
+
+impl StableSwapPool {
+    /// Create a pool from its balances. `base_index`/`quote_index` pick the coins to quote.
+    pub fn new(
+        balances: Vec<u128>,
+        decimals: Vec<u8>,
+        amplification: u128,
+        fee: u128,
+        base_index: usize,
+        quote_index: usize,
+    ) -> Self {
+        assert_eq!(
+            balances.len(),
+            decimals.len(),
+            "one decimals entry per coin"
+        );
+        assert!(
+            base_index < balances.len()
+                && quote_index < balances.len()
+                && base_index != quote_index,
+            "base and quote must be different coins of the pool"
+        );
+        let rates = vec![WAD; balances.len()];
+        Self {
+            balances,
+            decimals,
+            rates,
+            amplification,
+            fee,
+            base_index,
+            quote_index,
+        }
+    }
+
+    /// Set per-coin rates (1e18 = par), e.g. the wstETH/stETH exchange rate.
+    pub fn with_rates(mut self, rates: Vec<u128>) -> Self {
+        assert_eq!(rates.len(), self.balances.len(), "one rate per coin");
+        self.rates = rates;
+        self
+    }
+
+    /// Rate multiplier from raw units to 18-decimal value units, in 1e18.
+    fn rate_multiplier(&self, coin: usize) -> Option<U256> {
+        let scale = 10u128.checked_pow(18u32.checked_sub(self.decimals[coin] as u32)?)?;
+        U256::from(self.rates[coin]).checked_mul(U256::from(scale))
+    }
+
+    /// Balances in value units (`xp` in the contracts).
+    fn xp(&self, balances: &[u128]) -> Option<Vec<U256>> {
+        balances
+            .iter()
+            .enumerate()
+            .map(|(coin, &balance)| {
+                U256::mul_div(
+                    U256::from(balance),
+                    self.rate_multiplier(coin)?,
+                    U256::from(WAD),
+                )
+            })
+            .collect()
+    }
+
+    /// `A * A_PRECISION`, the form used by the invariant math.
+    fn amp(&self) -> U256 {
+        U256::from(self.amplification.saturating_mul(A_PRECISION))
+    }
+
+    /// Marginal price of coin `i` in coin `j` for value balances `xp`, before fees.
+    fn price_for(&self, xp: &[U256]) -> f64 {
+        let Ok(d) = get_d(xp, self.amp()) else {
+            return 0.0;
+        };
+        let n = xp.len() as f64;
+        let d = d.to_f64();
+        // D^(n+1) / (n^n * prod(x)), accumulated term by term to stay in range
+        let d_p = xp.iter().fold(d, |acc, x| acc * d / (x.to_f64() * n));
+        let ann = self.amplification as f64 * n;
+        let (x_i, x_j) = (xp[self.base_index].to_f64(), xp[self.quote_index].to_f64());
+        let value_price = (ann + d_p / x_i) / (ann + d_p / x_j);
+        value_price * self.rates[self.base_index] as f64 / self.rates[self.quote_index] as f64
+    }
+}
+
+impl AmmPool for StableSwapPool {
+    fn decimals(&self) -> (u8, u8) {
+        (
+            self.decimals[self.base_index],
+            self.decimals[self.quote_index],
+        )
+    }
+
+    fn fee_rate(&self) -> f64 {
+        self.fee as f64 / CURVE_FEE_DENOMINATOR as f64
+    }
+
+    fn spot_price(&self) -> f64 {
+        self.xp(&self.balances)
+            .map_or(0.0, |xp| self.price_for(&xp))
+    }
+
+    fn quote_exact_in(&self, side: TradeSide, amount_in: u128) -> Result<SwapQuote, AmmError> {
+        if amount_in == 0 {
+            return Err(AmmError::ZeroAmount);
+        }
+        if self.balances.contains(&0) {
+            return Err(AmmError::NoLiquidity);
+        }
+        let (i, j) = match side {
+            TradeSide::Buy => (self.quote_index, self.base_index),
+            TradeSide::Sell => (self.base_index, self.quote_index),
+        };
+
+        // get_dy: solve for the new balance of j, take the fee from the output
+        let xp = self.xp(&self.balances).ok_or(AmmError::Overflow)?;
+        let rate_i = self.rate_multiplier(i).ok_or(AmmError::Overflow)?;
+        let rate_j = self.rate_multiplier(j).ok_or(AmmError::Overflow)?;
+        let dx = U256::mul_div(U256::from(amount_in), rate_i, U256::from(WAD))
+            .ok_or(AmmError::Overflow)?;
+        let x = xp[i].checked_add(dx).ok_or(AmmError::Overflow)?;
+        let d = get_d(&xp, self.amp())?;
+        let y = get_y(i, j, x, &xp, self.amp(), d)?;
+        let dy = xp[j]
+            .checked_sub(y)
+            .and_then(|dy| dy.checked_sub(U256::ONE))
+            .ok_or(AmmError::NoLiquidity)?;
+        let fee = U256::mul_div(dy, U256::from(self.fee), U256::from(CURVE_FEE_DENOMINATOR))
+            .ok_or(AmmError::Overflow)?;
+        let to_raw = |value: U256| {
+            U256::mul_div(value, U256::from(WAD), rate_j)
+                .and_then(U256::to_u128)
+                .ok_or(AmmError::Overflow)
+        };
+        let amount_out = to_raw(dy.checked_sub(fee).ok_or(AmmError::Overflow)?)?;
+        let fee_amount = to_raw(fee)?;
+
+        let mut balances = self.balances.clone();
+        balances[i] = balances[i].saturating_add(amount_in);
+        balances[j] -= amount_out;
+        let price_after = self.xp(&balances).map_or(0.0, |xp| self.price_for(&xp));
+
+        Ok(SwapQuote {
+            amount_in,
+            amount_out,
+            fee_amount,
+            price_after,
+            ticks_crossed: 0,
+        })
+    }
+}
+
+/// StableSwap invariant D for value balances (`get_D`).
+fn get_d(xp: &[U256], amp: U256) -> Result<U256, AmmError> {
+    let overflow = AmmError::Overflow;
+    let n = U256::from(xp.len() as u128);
+    let sum = xp
+        .iter()
+        .try_fold(U256::ZERO, |acc, &x| acc.checked_add(x))
+        .ok_or(overflow)?;
+    if sum.is_zero() {
+        return Ok(U256::ZERO);
+    }
+    let ann = amp.checked_mul(n).ok_or(overflow)?;
+    let a_precision = U256::from(A_PRECISION);
+
+    let mut d = sum;
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        let mut d_p = d;
+        for &x in xp {
+            d_p = U256::mul_div(d_p, d, x.checked_mul(n).ok_or(overflow)?).ok_or(overflow)?;
+        }
+        let prev = d;
+        // D = (Ann * S / A_PRECISION + D_P * N) * D / ((Ann - A_PRECISION) * D / A_PRECISION + (N + 1) * D_P)
+        let numerator = U256::mul_div(ann, sum, a_precision)
+            .zip(d_p.checked_mul(n))
+            .and_then(|(a, b)| a.checked_add(b))
+            .ok_or(overflow)?;
+        let denominator = ann
+            .checked_sub(a_precision)
+            .and_then(|ann| U256::mul_div(ann, d, a_precision))
+            .zip(n.checked_add(U256::ONE).and_then(|n1| n1.checked_mul(d_p)))
+            .and_then(|(a, b)| a.checked_add(b))
+            .ok_or(overflow)?;
+        d = U256::mul_div(numerator, d, denominator).ok_or(overflow)?;
+        if d.abs_diff(prev) <= U256::ONE {
+            return Ok(d);
+        }
+    }
+    Err(AmmError::NoConvergence)
+}
+
+/// New value balance of coin `j` when coin `i` is set to `x`, keeping D (`get_y`).
+fn get_y(i: usize, j: usize, x: U256, xp: &[U256], amp: U256, d: U256) -> Result<U256, AmmError> {
+    let overflow = AmmError::Overflow;
+    let n = U256::from(xp.len() as u128);
+    let ann = amp.checked_mul(n).ok_or(overflow)?;
+
+    let mut c = d;
+    let mut sum = U256::ZERO;
+    for (k, &balance) in xp.iter().enumerate() {
+        let x_k = match k {
+            k if k == i => x,
+            k if k == j => continue,
+            _ => balance,
+        };
+        sum = sum.checked_add(x_k).ok_or(overflow)?;
+        c = U256::mul_div(c, d, x_k.checked_mul(n).ok_or(overflow)?).ok_or(overflow)?;
+    }
+    // c = c * D * A_PRECISION / (Ann * N); b = S + D * A_PRECISION / Ann
+    let a_precision = U256::from(A_PRECISION);
+    c = d
+        .checked_mul(a_precision)
+        .zip(ann.checked_mul(n))
+        .and_then(|(num, den)| U256::mul_div(c, num, den))
+        .ok_or(overflow)?;
+    let b = U256::mul_div(d, a_precision, ann)
+        .and_then(|b| b.checked_add(sum))
+        .ok_or(overflow)?;
+
+    let mut y = d;
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        let prev = y;
+        // y = (y^2 + c) / (2y + b - D)
+        let numerator = y
+            .checked_mul(y)
+            .and_then(|y2| y2.checked_add(c))
+            .ok_or(overflow)?;
+        let denominator = y
+            .checked_add(y)
+            .and_then(|y2| y2.checked_add(b))
+            .and_then(|v| v.checked_sub(d))
+            .ok_or(overflow)?;
+        y = numerator.checked_div(denominator).ok_or(overflow)?;
+        if y.abs_diff(prev) <= U256::ONE {
+            return Ok(y);
+        }
+    }
+    Err(AmmError::NoConvergence)
+}
+
+/// Balancer weighted pool (constant weighted product).
+///
+/// The power in the swap formula is evaluated in f64, so outputs can differ
+/// from the vault's 18-decimal fixed-point result in the last few significant
+/// digits. Outputs are rounded down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedPool {
+    /// Token balances (raw units).
+    pub balances: Vec<u128>,
+    /// Normalized weights in 1e18 (0.8e18 = 80%).
+    pub weights: Vec<u128>,
+    pub decimals: Vec<u8>,
+    /// Swap fee in 1e18 (3e15 = 0.3%), charged on the input.
+    pub swap_fee: u128,
+    pub base_index: usize,
+    pub quote_index: usize,
+}
+
+impl WeightedPool {
+    /// The vault rejects swaps larger than this fraction of the input balance.
+    pub const MAX_IN_RATIO: f64 = 0.3;
+
+    /// Create a pool from its balances and normalized weights.
+    pub fn new(
+        balances: Vec<u128>,
+        weights: Vec<u128>,
+        decimals: Vec<u8>,
+        swap_fee: u128,
+        base_index: usize,
+        quote_index: usize,
+    ) -> Self {
+        assert!(
+            balances.len() == weights.len() && balances.len() == decimals.len(),
+            "one weight and decimals entry per token"
+        );
+        assert!(
+            base_index < balances.len()
+                && quote_index < balances.len()
+                && base_index != quote_index,
+            "base and quote must be different tokens of the pool"
+        );
+        Self {
+            balances,
+            weights,
+            decimals,
+            swap_fee,
+            base_index,
+            quote_index,
+        }
+    }
+
+    /// Spot price of `base` in `quote` for the given balances, before fees.
+    fn price_for(&self, base_balance: u128, quote_balance: u128) -> f64 {
+        let (base, quote) = (self.base_index, self.quote_index);
+        let base_value = raw_to_f64(base_balance, self.decimals[base]) / self.weights[base] as f64;
+        if base_value <= 0.0 {
+            return 0.0;
+        }
+        raw_to_f64(quote_balance, self.decimals[quote]) / self.weights[quote] as f64 / base_value
+    }
+}
+
+impl AmmPool for WeightedPool {
+    fn decimals(&self) -> (u8, u8) {
+        (
+            self.decimals[self.base_index],
+            self.decimals[self.quote_index],
+        )
+    }
+
+    fn fee_rate(&self) -> f64 {
+        self.swap_fee as f64 / WAD as f64
+    }
+
+    fn spot_price(&self) -> f64 {
+        self.price_for(
+            self.balances[self.base_index],
+            self.balances[self.quote_index],
+        )
+    }
+
+    fn quote_exact_in(&self, side: TradeSide, amount_in: u128) -> Result<SwapQuote, AmmError> {
+        if amount_in == 0 {
+            return Err(AmmError::ZeroAmount);
+        }
+        let (i, j) = match side {
+            TradeSide::Buy => (self.quote_index, self.base_index),
+            TradeSide::Sell => (self.base_index, self.quote_index),
+        };
+        let (balance_in, balance_out) = (self.balances[i], self.balances[j]);
+        if balance_in == 0 || balance_out == 0 || self.weights[j] == 0 {
+            return Err(AmmError::NoLiquidity);
+        }
+
+        // Swaps above the max in ratio revert, so only that much can be filled
+        let amount_in = amount_in.min((balance_in as f64 * Self::MAX_IN_RATIO) as u128);
+        let fee_amount = U256::mul_div_rounding_up(
+            U256::from(amount_in),
+            U256::from(self.swap_fee),
+            U256::from(WAD),
+        )
+        .and_then(U256::to_u128)
+        .ok_or(AmmError::Overflow)?;
+        let in_after_fee = amount_in.saturating_sub(fee_amount);
+
+        // out = B_out * (1 - (B_in / (B_in + in)) ^ (w_in / w_out))
+        let exponent = self.weights[i] as f64 / self.weights[j] as f64;
+        let ratio = in_after_fee as f64 / (balance_in as f64 + in_after_fee as f64);
+        let fraction_out = -(exponent * (-ratio).ln_1p()).exp_m1();
+        let amount_out = ((balance_out as f64 * fraction_out).floor() as u128).min(balance_out);
+
+        let mut balances = self.balances.clone();
+        balances[i] = balances[i].saturating_add(amount_in);
+        balances[j] -= amount_out;
+
+        Ok(SwapQuote {
+            amount_in,
+            amount_out,
+            fee_amount,
+            price_after: self.price_for(balances[self.base_index], balances[self.quote_index]),
+            ticks_crossed: 0,
+        })
+    }
+}
+
+/// Raw token amount as whole tokens.
+fn raw_to_f64(raw: u128, decimals: u8) -> f64 {
+    raw as f64 / 10f64.powi(decimals as i32)
+}
+
+/// Raw token amount in FixedPoint scale (saturating).
+fn raw_to_fixed(raw: u128, decimals: u8) -> u64 {
+    let decimals = decimals as u32;
+    let scaled = match decimals.cmp(&FixedPoint::DECIMALS) {
+        Ordering::Greater => raw / 10u128.pow(decimals - FixedPoint::DECIMALS),
+        Ordering::Less => raw.saturating_mul(10u128.pow(FixedPoint::DECIMALS - decimals)),
+        Ordering::Equal => raw,
+    };
+    u64::try_from(scaled).unwrap_or(u64::MAX)
+}
+
+/// 256-bit unsigned integer for pool state and Q64.96 math.
+///
+/// Little-endian 64-bit limbs. Only the operations the pool math needs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct U256([u64; 4]);
+
+impl U256 {
+    pub const ZERO: Self = Self([0; 4]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+    pub const MAX: Self = Self([u64::MAX; 4]);
+
+    /// Parse a big-endian 32-byte word (e.g. an ABI-encoded `uint256`).
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (i, chunk) in bytes.chunks_exact(8).enumerate() {
+            limbs[3 - i] = u64::from_be_bytes(chunk.try_into().expect("8-byte chunk"));
+        }
+        Self(limbs)
+    }
+
+    /// Encode as a big-endian 32-byte word.
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            bytes[(3 - i) * 8..(4 - i) * 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Value as u128, if it fits.
+    pub fn to_u128(self) -> Option<u128> {
+        (self.0[2] == 0 && self.0[3] == 0).then_some(self.0[0] as u128 | (self.0[1] as u128) << 64)
+    }
+
+    /// Nearest f64 (for display and price conversion).
+    pub fn to_f64(self) -> f64 {
+        self.0.iter().rev().fold(0.0, |acc, &limb| {
+            acc * 18_446_744_073_709_551_616.0 + limb as f64
+        })
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let mut out = [0u64; 4];
+        let mut carry = false;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        (!carry).then_some(Self(out))
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let (out, borrow) = self.overflowing_sub(rhs);
+        (!borrow).then_some(out)
+    }
+
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let mut out = [0u64; 4];
+        let mut borrow = false;
+        for (i, limb) in out.iter_mut().enumerate() {
+            let (diff, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+        (Self(out), borrow)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let product = self.full_mul(rhs);
+        product[4..]
+            .iter()
+            .all(|&limb| limb == 0)
+            .then_some(Self([product[0], product[1], product[2], product[3]]))
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        (!rhs.is_zero()).then(|| self.div_rem(rhs).0)
+    }
+
+    pub fn abs_diff(self, rhs: Self) -> Self {
+        if self >= rhs {
+            self.overflowing_sub(rhs).0
+        } else {
+            rhs.overflowing_sub(self).0
+        }
+    }
+
+    /// Shift left by fewer than 256 bits (bits shifted out are dropped).
+    fn shl(self, bits: u32) -> Self {
+        let (limbs, bits) = ((bits / 64) as usize, bits % 64);
+        let mut out = [0u64; 4];
+        for (i, limb) in out.iter_mut().enumerate().skip(limbs) {
+            *limb = self.0[i - limbs] << bits;
+            if bits > 0 && i > limbs {
+                *limb |= self.0[i - limbs - 1] >> (64 - bits);
+            }
+        }
+        Self(out)
+    }
+
+    /// Shift right by fewer than 256 bits.
+    fn shr(self, bits: u32) -> Self {
+        let (limbs, bits) = ((bits / 64) as usize, bits % 64);
+        let mut out = [0u64; 4];
+        for (i, limb) in out.iter_mut().enumerate().take(4 - limbs) {
+            *limb = self.0[i + limbs] >> bits;
+            if bits > 0 && i + limbs + 1 < 4 {
+                *limb |= self.0[i + limbs + 1] << (64 - bits);
+            }
+        }
+        Self(out)
+    }
+
+    /// Full 512-bit product as little-endian limbs.
+    fn full_mul(self, rhs: Self) -> [u64; 8] {
+        let mut out = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let t = self.0[i] as u128 * rhs.0[j] as u128 + out[i + j] as u128 + carry;
+                out[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            out[i + 4] = carry as u64;
+        }
+        out
+    }
+
+    /// Quotient and remainder. Panics on a zero divisor.
+    fn div_rem(self, divisor: Self) -> (Self, Self) {
+        let (quotient, rem) = div_rem_limbs(&self.0, divisor);
+        (Self(quotient), rem)
+    }
+
+    /// Division rounding up (`UnsafeMath.divRoundingUp`); None on a zero divisor.
+    fn div_rounding_up(self, divisor: Self) -> Option<Self> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (quotient, rem) = self.div_rem(divisor);
+        if rem.is_zero() {
+            Some(quotient)
+        } else {
+            quotient.checked_add(Self::ONE)
+        }
+    }
+
+    /// floor(a * b / denominator) with a 512-bit intermediate (`FullMath.mulDiv`).
+    fn mul_div(a: Self, b: Self, denominator: Self) -> Option<Self> {
+        Self::mul_div_rem(a, b, denominator).map(|(quotient, _)| quotient)
+    }
+
+    /// ceil(a * b / denominator) (`FullMath.mulDivRoundingUp`).
+    fn mul_div_rounding_up(a: Self, b: Self, denominator: Self) -> Option<Self> {
+        let (quotient, rem) = Self::mul_div_rem(a, b, denominator)?;
+        if rem.is_zero() {
+            Some(quotient)
+        } else {
+            quotient.checked_add(Self::ONE)
+        }
+    }
+
+    fn mul_div_rem(a: Self, b: Self, denominator: Self) -> Option<(Self, Self)> {
+        if denominator.is_zero() {
+            return None;
+        }
+        let (quotient, rem) = div_rem_limbs(&a.full_mul(b), denominator);
+        quotient[4..].iter().all(|&limb| limb == 0).then_some((
+            Self([quotient[0], quotient[1], quotient[2], quotient[3]]),
+            rem,
+        ))
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        Self([value as u64, (value >> 64) as u64, 0, 0])
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Binary long division of a little-endian limb number by a 256-bit divisor.
+fn div_rem_limbs<const N: usize>(numerator: &[u64; N], divisor: U256) -> ([u64; N], U256) {
+    assert!(!divisor.is_zero(), "division by zero");
+    let mut quotient = [0u64; N];
+    let mut rem = U256::ZERO;
+    let top = numerator
+        .iter()
+        .rposition(|&limb| limb != 0)
+        .map_or(0, |i| i * 64 + 64 - numerator[i].leading_zeros() as usize);
+
+    for bit in (0..top).rev() {
+        let carry = rem.0[3] >> 63 == 1;
+        rem = rem.shl(1);
+        rem.0[0] |= (numerator[bit / 64] >> (bit % 64)) & 1;
+        if carry || rem >= divisor {
+            rem = rem.overflowing_sub(divisor).0;
+            quotient[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+    (quotient, rem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_optimal_size, DepthFeeConfig};
+
+    const ETH: u128 = 1_000_000_000_000_000_000;
+    const USDC: u128 = 1_000_000;
+
+    #[test]
+    fn test_cpmm_matches_uniswap_v2_amount_out() {
+        // 1000 WETH / 2,000,000 USDC at 30 bps
+        let pool = CpmmPool::new(1_000 * ETH, 2_000_000 * USDC, 30, 18, 6);
+        assert!((pool.spot_price() - 2000.0).abs() < 1e-9);
+
+        let sell = pool.quote_exact_in(TradeSide::Sell, ETH).unwrap();
+        // getAmountOut: in * 997 * reserve_out / (reserve_in * 1000 + in * 997)
+        assert_eq!(sell.amount_out, 1_992_013_962);
+        assert_eq!(sell.fee_amount, 3 * ETH / 1000);
+        assert!(sell.price_after < 2000.0);
+
+        let buy = pool.quote_exact_in(TradeSide::Buy, 2_000 * USDC).unwrap();
+        assert_eq!(buy.amount_out, 996_006_981_039_903_216);
+        assert!(buy.price_after > 2000.0);
+
+        let prices = pool.marginal_prices();
+        assert_eq!(prices.mid, FixedPoint::from_f64(2000.0));
+        assert!(prices.bid < prices.mid && prices.mid < prices.ask);
+        assert_eq!(
+            pool.quote_exact_in(TradeSide::Buy, 0),
+            Err(AmmError::ZeroAmount)
+        );
+    }
+
+    #[test]
+    fn test_sqrt_ratio_at_tick_bounds() {
+        assert_eq!(sqrt_ratio_at_tick(0), Q96);
+        assert_eq!(sqrt_ratio_at_tick(MIN_TICK), MIN_SQRT_RATIO);
+        assert_eq!(sqrt_ratio_at_tick(MAX_TICK), MAX_SQRT_RATIO);
+        assert!(sqrt_ratio_at_tick(-1) < Q96 && Q96 < sqrt_ratio_at_tick(1));
+        assert_eq!(
+            U256::from_be_bytes(MAX_SQRT_RATIO.to_be_bytes()),
+            MAX_SQRT_RATIO
+        );
+    }
+
+    #[test]
+    fn test_u256_mul_div_at_max() {
+        let two = U256::from(2);
+        assert_eq!(
+            U256::mul_div(U256::MAX, U256::MAX, U256::MAX),
+            Some(U256::MAX)
+        );
+        assert_eq!(U256::mul_div(U256::MAX, two, two), Some(U256::MAX));
+        assert_eq!(
+            U256::mul_div_rounding_up(U256::MAX, U256::MAX, U256::MAX),
+            Some(U256::MAX)
+        );
+        // Quotients above 256 bits and zero denominators are rejected
+        assert_eq!(U256::mul_div(U256::MAX, two, U256::ONE), None);
+        assert_eq!(U256::mul_div(U256::MAX, U256::MAX, U256::ONE), None);
+        assert_eq!(U256::mul_div(U256::ONE, U256::ONE, U256::ZERO), None);
+        assert_eq!(U256::MAX.checked_mul(two), None);
+        assert_eq!(U256::MAX.checked_add(U256::ONE), None);
+        assert_eq!(U256::ZERO.checked_sub(U256::ONE), None);
+    }
+
+    #[test]
+    fn test_u256_rounding_up_boundaries() {
+        let n = |v: u128| U256::from(v);
+        assert_eq!(U256::mul_div_rounding_up(n(7), n(3), n(7)), Some(n(3)));
+        assert_eq!(U256::mul_div_rounding_up(n(7), n(3), n(6)), Some(n(4)));
+        assert_eq!(U256::mul_div(n(7), n(3), n(6)), Some(n(3)));
+        assert_eq!(n(10).div_rounding_up(n(5)), Some(n(2)));
+        assert_eq!(n(11).div_rounding_up(n(5)), Some(n(3)));
+        assert_eq!(U256::MAX.div_rounding_up(U256::MAX), Some(U256::ONE));
+        assert_eq!(n(1).div_rounding_up(U256::ZERO), None);
+
+        // (2^192 - 1)(2^192 + 1) / 2^128 = 2^256 - 1 remainder 2^128 - 1:
+        // the floor is MAX, so rounding up overflows
+        let a = U256([u64::MAX, u64::MAX, u64::MAX, 0]);
+        let b = U256([1, 0, 0, 1]);
+        let d = U256([0, 0, 1, 0]);
+        assert_eq!(U256::mul_div(a, b, d), Some(U256::MAX));
+        assert_eq!(U256::mul_div_rounding_up(a, b, d), None);
+    }
+
+    #[test]
+    fn test_u256_shifts() {
+        let x = U256([1, 2, 3, 4]);
+        assert_eq!(x.shl(0), x);
+        assert_eq!(x.shr(0), x);
+        assert_eq!(x.shl(64), U256([0, 1, 2, 3]));
+        assert_eq!(x.shr(64), U256([2, 3, 4, 0]));
+        assert_eq!(U256::ONE.shl(255), U256([0, 0, 0, 1 << 63]));
+        assert_eq!(U256::from(2).shl(255), U256::ZERO);
+        assert_eq!(U256::MAX.shr(255), U256::ONE);
+        assert_eq!(U256::ONE.shl(255).shr(255), U256::ONE);
+        assert_eq!(
+            U256::from(u64::MAX as u128).shl(1),
+            U256([u64::MAX - 1, 1, 0, 0])
+        );
+    }
+
+    #[test]
+    fn test_u256_division_by_one_and_max() {
+        let x = U256([5, 6, 7, 8]);
+        assert_eq!(x.checked_div(U256::ONE), Some(x));
+        assert_eq!(U256::MAX.checked_div(U256::ONE), Some(U256::MAX));
+        assert_eq!(U256::MAX.div_rem(U256::MAX), (U256::ONE, U256::ZERO));
+        assert_eq!(x.div_rem(U256::MAX), (U256::ZERO, x));
+        assert_eq!(x.checked_div(U256::ZERO), None);
+        assert_eq!(U256::mul_div(x, U256::MAX, U256::MAX), Some(x));
+        assert_eq!(U256::mul_div(x, U256::ONE, U256::ONE), Some(x));
+    }
+
+    /// USDC/WETH 0.05% pool around 2000 USDC per WETH (token0 = USDC, token1 = WETH).
+    ///
+    /// Two positions of 1e18 liquidity: ticks 199600..201000 and 200200..200400.
+    fn usdc_weth_pool() -> ClmmPool {
+        let tick = 200_311;
+        let ticks = [
+            (199_600, 1_000_000_000_000_000_000),
+            (200_200, 1_000_000_000_000_000_000),
+            (200_400, -1_000_000_000_000_000_000),
+            (201_000, -1_000_000_000_000_000_000),
+        ]
+        .map(|(index, liquidity_net)| ClmmTick {
+            index,
+            liquidity_net,
+        });
+        let sqrt_price = sqrt_ratio_at_tick(tick)
+            .checked_add(U256::from(1_000_000_000_000_000_000_000u128))
+            .unwrap();
+        ClmmPool::new(
+            sqrt_price,
+            tick,
+            2_000_000_000_000_000_000,
+            500,
+            10,
+            ticks.to_vec(),
+            false,
+            6,
+            18,
+        )
+    }
+
+    #[test]
+    fn test_clmm_swap_traverses_ticks() {
+        let pool = usdc_weth_pool();
+        assert!((pool.spot_price() - 2000.04).abs() < 0.01);
+        assert_eq!(pool.decimals(), (18, 6));
+
+        // Small sell stays in range
+        let small = pool.quote_exact_in(TradeSide::Sell, ETH / 10).unwrap();
+        assert_eq!(small.amount_out, 199_903_580);
+        assert_eq!(small.fee_amount, 50_000_000_000_000);
+        assert_eq!(small.ticks_crossed, 0);
+
+        // Large sell leaves the narrow position and continues on the wide one
+        let large = pool.quote_exact_in(TradeSide::Sell, 300 * ETH).unwrap();
+        assert_eq!(large.amount_out, 595_275_900_581);
+        assert_eq!(large.ticks_crossed, 1);
+        assert!((large.price_after - 1964.71).abs() < 0.01);
+
+        let buy = pool.quote_exact_in(TradeSide::Buy, 10_000 * USDC).unwrap();
+        assert_eq!(buy.amount_out, 4_996_840_949_429_805_565);
+        assert!(buy.price_after > pool.spot_price());
+
+        // Past the last position the input is only partly filled
+        let drain = pool
+            .quote_exact_in(TradeSide::Buy, 1_000_000_000 * USDC)
+            .unwrap();
+        assert_eq!(drain.amount_in, 1_868_193_336_088);
+        assert_eq!(drain.amount_out, 904_662_773_079_212_407_167);
+        assert_eq!(drain.ticks_crossed, 2);
+    }
+
+    #[test]
+    fn test_stableswap_prices_depeg_and_lst_rates() {
+        // USDC/USDT at A = 2000 and 1 bp fee
+        let balanced = StableSwapPool::new(
+            vec![50_000_000 * USDC, 50_000_000 * USDC],
+            vec![6, 6],
+            2000,
+            1_000_000,
+            1,
+            0,
+        );
+        assert!((balanced.spot_price() - 1.0).abs() < 1e-12);
+        let quote = balanced
+            .quote_exact_in(TradeSide::Buy, 1_000_000 * USDC)
+            .unwrap();
+        assert_eq!(quote.amount_out, 999_890_002_101);
+        assert_eq!(quote.fee_amount, 99_999_000);
+
+        // USDT-heavy pool: USDT trades below par and selling it costs more
+        let depegged = StableSwapPool::new(
+            vec![20_000_000 * USDC, 80_000_000 * USDC],
+            vec![6, 6],
+            2000,
+            1_000_000,
+            1,
+            0,
+        );
+        assert!(depegged.spot_price() < 0.999);
+        let quote = depegged
+            .quote_exact_in(TradeSide::Sell, 1_000_000 * USDC)
+            .unwrap();
+        assert_eq!(quote.amount_out, 998_355_819_915);
+        assert!(quote.price_after < depegged.spot_price());
+
+        // 3pool with an 18-decimal coin: DAI -> USDC
+        let three_pool = StableSwapPool::new(
+            vec![100_000_000 * ETH, 100_000_000 * USDC, 100_000_000 * USDC],
+            vec![18, 6, 6],
+            2000,
+            1_000_000,
+            0,
+            1,
+        );
+        let quote = three_pool
+            .quote_exact_in(TradeSide::Sell, 10_000_000 * ETH)
+            .unwrap();
+        assert_eq!(quote.amount_out, 9_998_495_283_228);
+
+        // wstETH/ETH quoted at the 1.15 oracle rate
+        let lst = StableSwapPool::new(
+            vec![11_500 * ETH, 10_000 * ETH],
+            vec![18, 18],
+            100,
+            4_000_000,
+            1,
+            0,
+        )
+        .with_rates(vec![ETH, 1_150_000_000_000_000_000]);
+        assert!((lst.spot_price() - 1.15).abs() < 1e-9);
+        let quote = lst.quote_exact_in(TradeSide::Sell, 100 * ETH).unwrap();
+        assert_eq!(quote.amount_out, 114_942_618_427_110_457_050);
+
+        // Depth-equivalent ladder for comparison with CEX books
+        let bids = depegged.depth_ladder(TradeSide::Sell, 5_000_000 * USDC, 5);
+        assert_eq!(bids.len(), 5);
+        assert!(bids.windows(2).all(|w| w[0].0 >= w[1].0));
+        assert_eq!(bids[0].1, FixedPoint::from_f64(1_000_000.0).0);
+    }
+
+    #[test]
+    fn test_weighted_pool_swaps() {
+        // 80/20 BAL/WETH at 0.3%: spot = (2000 / 0.2) / (8M / 0.8) = 0.001 WETH per BAL
+        let pool = WeightedPool::new(
+            vec![8_000_000 * ETH, 2_000 * ETH],
+            vec![800_000_000_000_000_000, 200_000_000_000_000_000],
+            vec![18, 18],
+            3_000_000_000_000_000,
+            0,
+            1,
+        );
+        assert!((pool.spot_price() - 0.001).abs() < 1e-15);
+
+        let sell = pool.quote_exact_in(TradeSide::Sell, 1_000 * ETH).unwrap();
+        assert_eq!(sell.fee_amount, 3 * ETH);
+        let expected = 996_689_449_594_599_945f64;
+        assert!((sell.amount_out as f64 - expected).abs() / expected < 1e-12);
+
+        let buy = pool.quote_exact_in(TradeSide::Buy, ETH).unwrap();
+        let expected = 996_689_488_276_454_622_744f64;
+        assert!((buy.amount_out as f64 - expected).abs() / expected < 1e-12);
+
+        // A 50/50 pool is a constant-product pool
+        let even = WeightedPool::new(
+            vec![1_000 * ETH, 2_000_000 * USDC],
+            vec![ETH / 2, ETH / 2],
+            vec![18, 6],
+            3_000_000_000_000_000,
+            0,
+            1,
+        );
+        let cpmm = CpmmPool::new(1_000 * ETH, 2_000_000 * USDC, 30, 18, 6);
+        let (a, b) = (
+            even.quote_exact_in(TradeSide::Sell, 10 * ETH).unwrap(),
+            cpmm.quote_exact_in(TradeSide::Sell, 10 * ETH).unwrap(),
+        );
+        assert!(a.amount_out.abs_diff(b.amount_out) <= 1);
+
+        // Input above 30% of the balance is only partly filled
+        let capped = even.quote_exact_in(TradeSide::Sell, 500 * ETH).unwrap();
+        assert_eq!(capped.amount_in, 300 * ETH);
+    }
+
+    #[test]
+    fn test_ladder_sizes_cex_vs_dex() {
+        let pool = CpmmPool::new(1_000 * ETH, 2_000_000 * USDC, 30, 18, 6);
+        let asks = pool.depth_ladder(TradeSide::Buy, 100_000 * USDC, 20);
+        assert_eq!(asks.len(), 20);
+        assert!(asks.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert!(asks[0].0 > pool.marginal_prices().mid.0);
+
+        let bids = pool.depth_ladder(TradeSide::Sell, 50 * ETH, 10);
+        assert!(bids.windows(2).all(|w| w[0].0 >= w[1].0));
+
+        // CEX bids 1% above the pool: buy on the DEX (fee already in the ladder)
+        let cex_bids = vec![(FixedPoint::from_f64(2020.0).0, FixedPoint::from_f64(50.0).0)];
+        let fees = DepthFeeConfig {
+            buy_fee_bps: 0,
+            sell_fee_bps: 10,
+            withdrawal_fee: 0,
+        };
+        let result = calculate_optimal_size(&asks, &cex_bids, fees);
+        assert!(result.is_profitable());
+        // Stops before the pool price passes the CEX bid net of fees
+        let amount = result.amount as f64 / FixedPoint::SCALE as f64;
+        assert!(amount > 1.0 && amount < 10.0, "{amount}");
+    }
+}
@@ -0,0 +1,511 @@
+//! Bridge quotes for costing cross-chain transfers.
+//!
+//! A [`BridgeQuoter`] prices moving an amount of a token between two chains
+//! over one protocol: the fee, the expected transfer time and the largest
+//! amount the lane takes. [`FeeScheduleQuoter`] models a protocol as per-lane
+//! fee schedules (a proportional fee plus a fixed fee in USD); the Across,
+//! Stargate and Wormhole constructors carry typical published schedules, and
+//! lanes can be replaced at runtime as live quotes come in. [`BridgeQuotes`]
+//! collects quoters and picks the cheapest quote for a transfer.
+
+use arbitrage_core::{BridgeProtocol, BridgeRoute, Chain, FixedPoint};
+use dashmap::DashMap;
+
+/// A transfer to quote.
+#[derive(Debug, Clone)]
+pub struct BridgeQuoteRequest {
+    /// Token symbol (e.g., "ETH", "USDC").
+    pub asset: String,
+    pub source_chain: Chain,
+    pub dest_chain: Chain,
+    /// Amount to bridge (token units, FixedPoint scale).
+    pub amount: u64,
+    /// Token price in USD, to convert fixed USD fees and limits.
+    pub token_price_usd: f64,
+}
+
+/// Quote for one transfer over one protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeQuote {
+    pub protocol: BridgeProtocol,
+    pub source_chain: Chain,
+    pub dest_chain: Chain,
+    /// Total fee (token units, FixedPoint scale).
+    pub fee: u64,
+    /// Expected time until funds arrive (seconds).
+    pub estimated_time_secs: u32,
+    /// Largest amount the lane takes (token units, FixedPoint scale).
+    pub max_amount: u64,
+}
+
+impl BridgeQuote {
+    /// Amount arriving on the destination chain.
+    pub fn amount_received(&self, amount: u64) -> u64 {
+        amount.saturating_sub(self.fee)
+    }
+
+    /// Fee relative to the amount sent (bps, 0 for a zero amount).
+    pub fn fee_bps(&self, amount: u64) -> i32 {
+        if amount == 0 {
+            return 0;
+        }
+        (self.fee as u128 * 10_000 / amount as u128).min(i32::MAX as u128) as i32
+    }
+
+    /// Whether the lane takes `amount`.
+    pub fn covers(&self, amount: u64) -> bool {
+        amount <= self.max_amount
+    }
+}
+
+/// Prices transfers over one bridge protocol.
+pub trait BridgeQuoter: Send + Sync + std::fmt::Debug {
+    /// Protocol quoted.
+    fn protocol(&self) -> BridgeProtocol;
+
+    /// Whether the protocol bridges from `source` to `dest`.
+    fn supports(&self, source: Chain, dest: Chain) -> bool;
+
+    /// Quote a transfer. None if the lane or asset is not served, or the quote
+    /// needs a token price that is missing.
+    fn quote(&self, request: &BridgeQuoteRequest) -> Option<BridgeQuote>;
+}
+
+/// Fee schedule of one bridge lane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BridgeLane {
+    pub source_chain: Chain,
+    pub dest_chain: Chain,
+    /// Proportional fee (LP and protocol fees) in basis points.
+    pub fee_bps: u16,
+    /// Fixed fee in USD (relayer gas, messaging fee).
+    pub fixed_fee_usd: f64,
+    /// Expected transfer time in seconds.
+    pub estimated_time_secs: u32,
+    /// Largest transfer in USD (None = unlimited).
+    pub max_amount_usd: Option<f64>,
+}
+
+impl BridgeLane {
+    pub fn new(
+        source_chain: Chain,
+        dest_chain: Chain,
+        fee_bps: u16,
+        fixed_fee_usd: f64,
+        estimated_time_secs: u32,
+    ) -> Self {
+        Self {
+            source_chain,
+            dest_chain,
+            fee_bps,
+            fixed_fee_usd,
+            estimated_time_secs,
+            max_amount_usd: None,
+        }
+    }
+
+    /// Set the largest transfer in USD (builder pattern).
+    pub fn with_max_amount_usd(mut self, max_amount_usd: f64) -> Self {
+        self.max_amount_usd = Some(max_amount_usd);
+        self
+    }
+}
+
+impl From<&BridgeRoute> for BridgeLane {
+    fn from(route: &BridgeRoute) -> Self {
+        Self::new(
+            route.source_chain,
+            route.dest_chain,
+            route.fee_bps,
+            0.0,
+            route.estimated_time_secs,
+        )
+    }
+}
+
+/// Quoter pricing each lane from a fee schedule.
+#[derive(Debug)]
+pub struct FeeScheduleQuoter {
+    protocol: BridgeProtocol,
+    /// Token symbols served (None = any token).
+    assets: Option<Vec<String>>,
+    lanes: DashMap<(Chain, Chain), BridgeLane>,
+}
+
+impl FeeScheduleQuoter {
+    /// Create a quoter with the given lanes, serving any token.
+    pub fn new(protocol: BridgeProtocol, lanes: impl IntoIterator<Item = BridgeLane>) -> Self {
+        let quoter = Self {
+            protocol,
+            assets: None,
+            lanes: DashMap::new(),
+        };
+        for lane in lanes {
+            quoter.set_lane(lane);
+        }
+        quoter
+    }
+
+    /// Quoter built from bridge route configs of this protocol (inactive
+    /// routes skipped). Serves the routes' supported assets, or any token if
+    /// none are listed.
+    pub fn from_routes(protocol: BridgeProtocol, routes: &[BridgeRoute]) -> Self {
+        let routes: Vec<&BridgeRoute> = routes
+            .iter()
+            .filter(|route| route.protocol == protocol && route.is_active)
+            .collect();
+        let mut quoter = Self::new(
+            protocol,
+            routes.iter().map(|route| BridgeLane::from(*route)),
+        );
+        let assets: Vec<String> = routes
+            .iter()
+            .flat_map(|route| &route.supported_assets)
+            .map(|asset| asset.symbol.to_string())
+            .collect();
+        if !assets.is_empty() {
+            quoter.assets = Some(assets);
+        }
+        quoter
+    }
+
+    /// Restrict the quoter to the given token symbols (builder pattern).
+    pub fn with_assets(mut self, assets: &[&str]) -> Self {
+        self.assets = Some(assets.iter().map(|asset| asset.to_string()).collect());
+        self
+    }
+
+    /// Add or replace a lane's schedule (e.g., from a live quote).
+    pub fn set_lane(&self, lane: BridgeLane) {
+        self.lanes
+            .insert((lane.source_chain, lane.dest_chain), lane);
+    }
+
+    /// Stop quoting a lane.
+    pub fn remove_lane(&self, source: Chain, dest: Chain) {
+        self.lanes.remove(&(source, dest));
+    }
+
+    /// Across: relayers fill on the destination chain within seconds and are
+    /// repaid later, so the fee is an LP plus capital fee and the destination
+    /// fill gas; instant fills are capped by relayer capital.
+    pub fn across() -> Self {
+        const CHAINS: [Chain; 5] = [
+            Chain::Ethereum,
+            Chain::Arbitrum,
+            Chain::Optimism,
+            Chain::Base,
+            Chain::Polygon,
+        ];
+        let lanes = lanes_between(&CHAINS, |source, dest| {
+            let (fill_gas_usd, fill_secs) = match dest {
+                Chain::Ethereum => (3.0, 60),
+                _ => (0.1, 10),
+            };
+            BridgeLane::new(source, dest, 4, fill_gas_usd, fill_secs)
+                .with_max_amount_usd(1_000_000.0)
+        });
+        Self::new(BridgeProtocol::Across, lanes)
+            .with_assets(&["ETH", "WETH", "USDC", "USDT", "DAI", "WBTC"])
+    }
+
+    /// Stargate: pooled liquidity with a flat protocol fee; the LayerZero
+    /// message is paid on the source chain and waits for its confirmations.
+    pub fn stargate() -> Self {
+        const CHAINS: [Chain; 7] = [
+            Chain::Ethereum,
+            Chain::Arbitrum,
+            Chain::Optimism,
+            Chain::Base,
+            Chain::Polygon,
+            Chain::Avalanche,
+            Chain::Bsc,
+        ];
+        let lanes = lanes_between(&CHAINS, |source, dest| {
+            let (message_fee_usd, confirm_secs) = match source {
+                Chain::Ethereum => (2.0, 180),
+                Chain::Polygon => (0.1, 120),
+                _ => (0.3, 60),
+            };
+            BridgeLane::new(source, dest, 6, message_fee_usd, confirm_secs)
+                .with_max_amount_usd(5_000_000.0)
+        });
+        Self::new(BridgeProtocol::Stargate, lanes).with_assets(&["ETH", "USDC", "USDT"])
+    }
+
+    /// Wormhole token bridge with automatic relaying: lock-and-mint, so no
+    /// liquidity cap and no proportional fee, but the guardians wait for source
+    /// chain finality (L1 finality for rollups).
+    pub fn wormhole() -> Self {
+        const CHAINS: [Chain; 8] = [
+            Chain::Ethereum,
+            Chain::Arbitrum,
+            Chain::Optimism,
+            Chain::Base,
+            Chain::Polygon,
+            Chain::Avalanche,
+            Chain::Bsc,
+            Chain::Solana,
+        ];
+        let lanes = lanes_between(&CHAINS, |source, dest| {
+            let finality_secs = match source {
+                Chain::Ethereum | Chain::Arbitrum | Chain::Optimism | Chain::Base => 1_200,
+                Chain::Polygon => 60,
+                Chain::Bsc => 45,
+                _ => 15,
+            };
+            let relayer_fee_usd = if dest == Chain::Ethereum { 5.0 } else { 0.5 };
+            BridgeLane::new(source, dest, 0, relayer_fee_usd, finality_secs)
+        });
+        Self::new(BridgeProtocol::Wormhole, lanes)
+    }
+
+    fn serves(&self, asset: &str) -> bool {
+        self.assets
+            .as_ref()
+            .is_none_or(|assets| assets.iter().any(|a| a.eq_ignore_ascii_case(asset)))
+    }
+}
+
+/// Lanes between every ordered pair of distinct chains.
+fn lanes_between(
+    chains: &[Chain],
+    schedule: impl Fn(Chain, Chain) -> BridgeLane,
+) -> Vec<BridgeLane> {
+    let mut lanes = Vec::new();
+    for &source in chains {
+        for &dest in chains.iter().filter(|&&dest| dest != source) {
+            lanes.push(schedule(source, dest));
+        }
+    }
+    lanes
+}
+
+impl BridgeQuoter for FeeScheduleQuoter {
+    fn protocol(&self) -> BridgeProtocol {
+        self.protocol
+    }
+
+    fn supports(&self, source: Chain, dest: Chain) -> bool {
+        self.lanes.contains_key(&(source, dest))
+    }
+
+    fn quote(&self, request: &BridgeQuoteRequest) -> Option<BridgeQuote> {
+        if !self.serves(&request.asset) {
+            return None;
+        }
+        let lane = self
+            .lanes
+            .get(&(request.source_chain, request.dest_chain))?;
+        let price = request.token_price_usd;
+        let needs_price = lane.fixed_fee_usd > 0.0 || lane.max_amount_usd.is_some();
+        if needs_price && !(price.is_finite() && price > 0.0) {
+            return None;
+        }
+
+        let proportional = request.amount as u128 * lane.fee_bps as u128 / 10_000;
+        let fixed = if lane.fixed_fee_usd > 0.0 {
+            FixedPoint::from_f64(lane.fixed_fee_usd / price).0
+        } else {
+            0
+        };
+        let max_amount = lane
+            .max_amount_usd
+            .map_or(u64::MAX, |max_usd| FixedPoint::from_f64(max_usd / price).0);
+        Some(BridgeQuote {
+            protocol: self.protocol,
+            source_chain: lane.source_chain,
+            dest_chain: lane.dest_chain,
+            fee: (proportional as u64).saturating_add(fixed),
+            estimated_time_secs: lane.estimated_time_secs,
+            max_amount,
+        })
+    }
+}
+
+/// Quoters across protocols.
+#[derive(Debug, Default)]
+pub struct BridgeQuotes {
+    quoters: Vec<Box<dyn BridgeQuoter>>,
+}
+
+impl BridgeQuotes {
+    /// No quoters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Across, Stargate and Wormhole at their typical fee schedules.
+    pub fn with_defaults() -> Self {
+        let mut quotes = Self::new();
+        quotes.add(FeeScheduleQuoter::across());
+        quotes.add(FeeScheduleQuoter::stargate());
+        quotes.add(FeeScheduleQuoter::wormhole());
+        quotes
+    }
+
+    /// Add a quoter.
+    pub fn add(&mut self, quoter: impl BridgeQuoter + 'static) {
+        self.quoters.push(Box::new(quoter));
+    }
+
+    /// Protocols bridging from `source` to `dest`.
+    pub fn protocols(&self, source: Chain, dest: Chain) -> Vec<BridgeProtocol> {
+        self.quoters
+            .iter()
+            .filter(|quoter| quoter.supports(source, dest))
+            .map(|quoter| quoter.protocol())
+            .collect()
+    }
+
+    /// All quotes for a transfer, cheapest first (faster first on equal fees).
+    pub fn quotes(&self, request: &BridgeQuoteRequest) -> Vec<BridgeQuote> {
+        let mut quotes: Vec<BridgeQuote> = self
+            .quoters
+            .iter()
+            .filter_map(|quoter| quoter.quote(request))
+            .collect();
+        quotes.sort_by_key(|quote| (quote.fee, quote.estimated_time_secs));
+        quotes
+    }
+
+    /// Cheapest quote whose lane takes the whole amount.
+    pub fn best_quote(&self, request: &BridgeQuoteRequest) -> Option<BridgeQuote> {
+        self.quotes(request)
+            .into_iter()
+            .find(|quote| quote.covers(request.amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrage_core::Asset;
+
+    fn request(asset: &str, source: Chain, dest: Chain, amount: f64) -> BridgeQuoteRequest {
+        BridgeQuoteRequest {
+            asset: asset.to_string(),
+            source_chain: source,
+            dest_chain: dest,
+            amount: FixedPoint::from_f64(amount).0,
+            token_price_usd: 2_000.0,
+        }
+    }
+
+    #[test]
+    fn test_fee_schedule_quote() {
+        let quoter = FeeScheduleQuoter::new(
+            BridgeProtocol::Across,
+            [
+                BridgeLane::new(Chain::Ethereum, Chain::Arbitrum, 5, 2.0, 30)
+                    .with_max_amount_usd(20_000.0),
+            ],
+        );
+        assert!(quoter.supports(Chain::Ethereum, Chain::Arbitrum));
+        assert!(!quoter.supports(Chain::Arbitrum, Chain::Ethereum));
+
+        // 0.05% of 2 ETH + $2 at $2000 = 0.001 + 0.001 ETH
+        let req = request("ETH", Chain::Ethereum, Chain::Arbitrum, 2.0);
+        let quote = quoter.quote(&req).unwrap();
+        assert_eq!(quote.fee, FixedPoint::from_f64(0.002).0);
+        assert_eq!(quote.fee_bps(req.amount), 10);
+        assert_eq!(
+            quote.amount_received(req.amount),
+            FixedPoint::from_f64(1.998).0
+        );
+        assert_eq!(quote.max_amount, FixedPoint::from_f64(10.0).0);
+        assert_eq!(quote.estimated_time_secs, 30);
+
+        // Fixed USD fees need a token price
+        let no_price = BridgeQuoteRequest {
+            token_price_usd: 0.0,
+            ..req
+        };
+        assert_eq!(quoter.quote(&no_price), None);
+    }
+
+    #[test]
+    fn test_quoter_from_bridge_routes() {
+        let mut route = BridgeRoute::new(
+            BridgeProtocol::Stargate,
+            Chain::Ethereum,
+            Chain::Arbitrum,
+            50,
+            300,
+        );
+        route.add_supported_asset(Asset::eth());
+        let mut inactive = BridgeRoute::new(
+            BridgeProtocol::Stargate,
+            Chain::Arbitrum,
+            Chain::Ethereum,
+            50,
+            300,
+        );
+        inactive.is_active = false;
+
+        let quoter =
+            FeeScheduleQuoter::from_routes(BridgeProtocol::Stargate, &[route.clone(), inactive]);
+        assert!(!quoter.supports(Chain::Arbitrum, Chain::Ethereum));
+        let req = request("ETH", Chain::Ethereum, Chain::Arbitrum, 100.0);
+        assert_eq!(
+            quoter.quote(&req).unwrap().fee,
+            route.calculate_fee(req.amount)
+        );
+        assert_eq!(
+            quoter.quote(&request("SOL", Chain::Ethereum, Chain::Arbitrum, 1.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_best_quote_picks_cheapest_covering_lane() {
+        let mut quotes = BridgeQuotes::new();
+        quotes.add(FeeScheduleQuoter::new(
+            BridgeProtocol::Across,
+            [BridgeLane::new(Chain::Ethereum, Chain::Base, 2, 0.0, 10)
+                .with_max_amount_usd(10_000.0)],
+        ));
+        quotes.add(FeeScheduleQuoter::new(
+            BridgeProtocol::Wormhole,
+            [BridgeLane::new(Chain::Ethereum, Chain::Base, 0, 4.0, 1_200)],
+        ));
+        assert_eq!(
+            quotes.protocols(Chain::Ethereum, Chain::Base),
+            vec![BridgeProtocol::Across, BridgeProtocol::Wormhole]
+        );
+
+        // 1 ETH: 2 bps (0.0002) beats $4 (0.002)
+        let small = request("ETH", Chain::Ethereum, Chain::Base, 1.0);
+        assert_eq!(quotes.quotes(&small).len(), 2);
+        assert_eq!(
+            quotes.best_quote(&small).unwrap().protocol,
+            BridgeProtocol::Across
+        );
+
+        // 50 ETH exceeds Across's $10k cap
+        let large = request("ETH", Chain::Ethereum, Chain::Base, 50.0);
+        assert_eq!(
+            quotes.best_quote(&large).unwrap().protocol,
+            BridgeProtocol::Wormhole
+        );
+
+        assert_eq!(
+            quotes.best_quote(&request("ETH", Chain::Base, Chain::Ethereum, 1.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_default_quoters() {
+        let quotes = BridgeQuotes::with_defaults();
+        assert!(quotes
+            .protocols(Chain::Ethereum, Chain::Solana)
+            .contains(&BridgeProtocol::Wormhole));
+        let usdc = BridgeQuoteRequest {
+            token_price_usd: 1.0,
+            ..request("USDC", Chain::Ethereum, Chain::Arbitrum, 10_000.0)
+        };
+        let best = quotes.best_quote(&usdc).unwrap();
+        assert!(best.fee_bps(usdc.amount) < 10);
+    }
+}
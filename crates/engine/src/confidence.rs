@@ -0,0 +1,359 @@
+//! Confidence scoring for arbitrage opportunities.
+//!
+//! Combines quote freshness, orderbook depth, premium persistence, transfer
+//! path availability, trading activity and symbol mapping conflicts into a
+//! single 0-100 score with a per-factor breakdown.
+
+use crate::ConversionRates;
+use arbitrage_core::{ConfidenceBreakdown, OptimalSizeReason, PriceTick};
+
+/// Score used when a factor has no data.
+const NEUTRAL_SCORE: u8 = 50;
+
+/// Relative weight of each confidence factor.
+#[derive(Debug, Clone, Copy)]
+pub struct ConfidenceWeights {
+    pub quote_age: u32,
+    pub depth: u32,
+    pub persistence: u32,
+    pub transfer_path: u32,
+    pub activity: u32,
+    pub symbol_mapping: u32,
+}
+
+impl Default for ConfidenceWeights {
+    fn default() -> Self {
+        Self {
+            quote_age: 20,
+            depth: 20,
+            persistence: 20,
+            transfer_path: 20,
+            activity: 10,
+            symbol_mapping: 10,
+        }
+    }
+}
+
+/// Confidence scoring configuration.
+#[derive(Debug, Clone)]
+pub struct ConfidenceConfig {
+    /// Factor weights.
+    pub weights: ConfidenceWeights,
+    /// Timestamp skew (ms) between the two quotes at which the quote age score reaches 0.
+    pub max_quote_skew_ms: u64,
+    /// 24h volume (USD) at which the activity score reaches 100.
+    pub target_volume_usd: f64,
+    /// Maximum total score when the symbol has conflicting mappings.
+    pub conflict_score_cap: u8,
+}
+
+impl Default for ConfidenceConfig {
+    fn default() -> Self {
+        Self {
+            weights: ConfidenceWeights::default(),
+            max_quote_skew_ms: 5_000,
+            target_volume_usd: 1_000_000.0,
+            conflict_score_cap: 10,
+        }
+    }
+}
+
+/// Inputs for scoring a single opportunity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfidenceInputs {
+    /// Source quote timestamp (ms since epoch, 0 = unknown).
+    pub source_timestamp_ms: u64,
+    /// Target quote timestamp (ms since epoch, 0 = unknown).
+    pub target_timestamp_ms: u64,
+    /// Best ask size at source (FixedPoint scale).
+    pub source_depth: u64,
+    /// Best bid size at target (FixedPoint scale).
+    pub target_depth: u64,
+    /// Optimal size from depth walking (FixedPoint scale).
+    pub optimal_size: u64,
+    /// Reason for the optimal size value.
+    pub optimal_size_reason: OptimalSizeReason,
+    /// Fraction of recent samples with premium above threshold (None = not enough history).
+    pub persistence_ratio: Option<f64>,
+    /// Whether a common open network exists (None = wallet status unknown).
+    pub has_transfer_path: Option<bool>,
+    /// 24h volume in USD at source (None = not reported).
+    pub source_volume_usd: Option<f64>,
+    /// 24h volume in USD at target (None = not reported).
+    pub target_volume_usd: Option<f64>,
+    /// Whether the symbol has conflicting canonical names across exchanges.
+    pub symbol_conflict: bool,
+}
+
+/// Confidence scorer.
+#[derive(Debug, Clone, Default)]
+pub struct ConfidenceScorer {
+    config: ConfidenceConfig,
+}
+
+impl ConfidenceScorer {
+    /// Create a scorer with the given configuration.
+    pub fn new(config: ConfidenceConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the scorer configuration.
+    pub fn config(&self) -> &ConfidenceConfig {
+        &self.config
+    }
+
+    /// Score an opportunity, returning (score, breakdown).
+    pub fn score(&self, inputs: &ConfidenceInputs) -> (u8, ConfidenceBreakdown) {
+        let breakdown = ConfidenceBreakdown {
+            quote_age: self.quote_age_score(inputs),
+            depth: Self::depth_score(inputs),
+            persistence: inputs
+                .persistence_ratio
+                .map(ratio_to_score)
+                .unwrap_or(NEUTRAL_SCORE),
+            transfer_path: match inputs.has_transfer_path {
+                Some(true) => 100,
+                Some(false) => 0,
+                None => NEUTRAL_SCORE,
+            },
+            activity: self.activity_score(inputs),
+            symbol_mapping: if inputs.symbol_conflict { 0 } else { 100 },
+        };
+
+        let w = &self.config.weights;
+        let weighted = [
+            (breakdown.quote_age, w.quote_age),
+            (breakdown.depth, w.depth),
+            (breakdown.persistence, w.persistence),
+            (breakdown.transfer_path, w.transfer_path),
+            (breakdown.activity, w.activity),
+            (breakdown.symbol_mapping, w.symbol_mapping),
+        ];
+        let total_weight: u32 = weighted.iter().map(|(_, w)| w).sum();
+        let sum: u32 = weighted.iter().map(|(s, w)| *s as u32 * w).sum();
+        let mut score = (sum + total_weight / 2)
+            .checked_div(total_weight)
+            .map_or(NEUTRAL_SCORE, |score| score as u8);
+
+        // Conflicting mappings mean the two legs may be different coins
+        if inputs.symbol_conflict {
+            score = score.min(self.config.conflict_score_cap);
+        }
+
+        (score, breakdown)
+    }
+
+    fn quote_age_score(&self, inputs: &ConfidenceInputs) -> u8 {
+        if inputs.source_timestamp_ms == 0 || inputs.target_timestamp_ms == 0 {
+            return NEUTRAL_SCORE;
+        }
+        if self.config.max_quote_skew_ms == 0 {
+            return 100;
+        }
+        let skew = inputs
+            .source_timestamp_ms
+            .abs_diff(inputs.target_timestamp_ms);
+        ratio_to_score(1.0 - skew as f64 / self.config.max_quote_skew_ms as f64)
+    }
+
+    fn depth_score(inputs: &ConfidenceInputs) -> u8 {
+        if inputs.optimal_size == 0 {
+            return match inputs.optimal_size_reason {
+                OptimalSizeReason::NotProfitable
+                | OptimalSizeReason::BelowMinOrder
+                | OptimalSizeReason::NoTransferNetwork => 0,
+                _ => NEUTRAL_SCORE,
+            };
+        }
+        // Sizes that fit within the best level are the most reliable
+        let top_depth = inputs.source_depth.min(inputs.target_depth);
+        ratio_to_score(top_depth as f64 / inputs.optimal_size as f64)
+    }
+
+    fn activity_score(&self, inputs: &ConfidenceInputs) -> u8 {
+        let known = |v: Option<f64>| v.filter(|v| *v > 0.0);
+        let volume = match (
+            known(inputs.source_volume_usd),
+            known(inputs.target_volume_usd),
+        ) {
+            (Some(s), Some(t)) => s.min(t),
+            (Some(v), None) | (None, Some(v)) => v,
+            (None, None) => return NEUTRAL_SCORE,
+        };
+        if self.config.target_volume_usd <= 0.0 {
+            return 100;
+        }
+        // Square root so thin markets are penalised without flattening mid-size ones
+        ratio_to_score((volume / self.config.target_volume_usd).sqrt())
+    }
+}
+
+/// Convert a 0.0-1.0 ratio to a 0-100 score.
+fn ratio_to_score(ratio: f64) -> u8 {
+    if ratio.is_nan() {
+        return 0;
+    }
+    (ratio.clamp(0.0, 1.0) * 100.0).round() as u8
+}
+
+/// USD value of a tick's 24h volume (None = not reported or quote rate unknown).
+///
+/// Ticks carry quote-currency turnover, so the volume is converted with the
+/// quote's USD rate on that exchange (KRW through the exchange's USDT/KRW).
+pub fn volume_usd(tick: &PriceTick, rates: &ConversionRates) -> Option<f64> {
+    let volume = tick.volume_24h();
+    let quote_usd = rates.quote_usd_for(tick.exchange(), tick.quote_currency());
+    (volume.0 > 0 && quote_usd > 0.0).then(|| volume.to_f64() * quote_usd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrage_core::{Exchange, FixedPoint, QuoteCurrency};
+
+    fn full_inputs() -> ConfidenceInputs {
+        ConfidenceInputs {
+            source_timestamp_ms: 1_000,
+            target_timestamp_ms: 1_000,
+            source_depth: FixedPoint::from_f64(2.0).0,
+            target_depth: FixedPoint::from_f64(2.0).0,
+            optimal_size: FixedPoint::from_f64(1.0).0,
+            optimal_size_reason: OptimalSizeReason::Ok,
+            persistence_ratio: Some(1.0),
+            has_transfer_path: Some(true),
+            source_volume_usd: Some(5_000_000.0),
+            target_volume_usd: Some(2_000_000.0),
+            symbol_conflict: false,
+        }
+    }
+
+    #[test]
+    fn test_score_all_factors_good() {
+        let scorer = ConfidenceScorer::default();
+        let (score, breakdown) = scorer.score(&full_inputs());
+
+        assert_eq!(score, 100);
+        assert_eq!(breakdown.quote_age, 100);
+        assert_eq!(breakdown.depth, 100);
+        assert_eq!(breakdown.transfer_path, 100);
+    }
+
+    #[test]
+    fn test_score_unknown_inputs_are_neutral() {
+        let scorer = ConfidenceScorer::default();
+        let (score, breakdown) = scorer.score(&ConfidenceInputs::default());
+
+        assert_eq!(breakdown.quote_age, NEUTRAL_SCORE);
+        assert_eq!(breakdown.depth, NEUTRAL_SCORE);
+        assert_eq!(breakdown.persistence, NEUTRAL_SCORE);
+        assert_eq!(breakdown.transfer_path, NEUTRAL_SCORE);
+        assert_eq!(breakdown.activity, NEUTRAL_SCORE);
+        // Only symbol mapping is known (no conflict)
+        assert_eq!(score, 55);
+    }
+
+    #[test]
+    fn test_score_quote_skew() {
+        let scorer = ConfidenceScorer::default();
+        let inputs = ConfidenceInputs {
+            target_timestamp_ms: 1_000 + 2_500, // half of max skew
+            ..full_inputs()
+        };
+        let (_, breakdown) = scorer.score(&inputs);
+        assert_eq!(breakdown.quote_age, 50);
+
+        let inputs = ConfidenceInputs {
+            target_timestamp_ms: 60_000,
+            ..full_inputs()
+        };
+        let (_, breakdown) = scorer.score(&inputs);
+        assert_eq!(breakdown.quote_age, 0);
+    }
+
+    #[test]
+    fn test_score_depth_relative_to_optimal_size() {
+        let scorer = ConfidenceScorer::default();
+        let inputs = ConfidenceInputs {
+            source_depth: FixedPoint::from_f64(0.25).0,
+            ..full_inputs()
+        };
+        let (_, breakdown) = scorer.score(&inputs);
+        assert_eq!(breakdown.depth, 25);
+
+        let inputs = ConfidenceInputs {
+            optimal_size: 0,
+            optimal_size_reason: OptimalSizeReason::NotProfitable,
+            ..full_inputs()
+        };
+        let (_, breakdown) = scorer.score(&inputs);
+        assert_eq!(breakdown.depth, 0);
+    }
+
+    #[test]
+    fn test_score_no_transfer_path_lowers_score() {
+        let scorer = ConfidenceScorer::default();
+        let inputs = ConfidenceInputs {
+            has_transfer_path: Some(false),
+            ..full_inputs()
+        };
+        let (score, breakdown) = scorer.score(&inputs);
+        assert_eq!(breakdown.transfer_path, 0);
+        assert_eq!(score, 80);
+    }
+
+    #[test]
+    fn test_score_activity_uses_thinner_venue() {
+        let scorer = ConfidenceScorer::default();
+        let inputs = ConfidenceInputs {
+            source_volume_usd: Some(250_000.0),
+            ..full_inputs()
+        };
+        let (_, breakdown) = scorer.score(&inputs);
+        // sqrt(0.25) = 0.5
+        assert_eq!(breakdown.activity, 50);
+    }
+
+    #[test]
+    fn test_volume_usd_converts_quote_turnover() {
+        let rates = ConversionRates::uniform_krw(Some(1400.0), Some(1450.0), None);
+        let tick = |exchange, quote, volume| {
+            PriceTick::with_quote(
+                exchange,
+                1,
+                FixedPoint::from_f64(100.0),
+                FixedPoint::from_f64(100.0),
+                FixedPoint::from_f64(100.0),
+                quote,
+            )
+            .with_volume_24h(FixedPoint::from_f64(volume))
+        };
+
+        let usdt = tick(Exchange::Binance, QuoteCurrency::USDT, 2_000_000.0);
+        assert_eq!(volume_usd(&usdt, &rates), Some(2_000_000.0));
+
+        // KRW turnover goes through the exchange's USDT/KRW rate
+        let krw = tick(Exchange::Upbit, QuoteCurrency::KRW, 1_450_000_000.0);
+        let usd = volume_usd(&krw, &rates).unwrap();
+        assert!((usd - 1_000_000.0).abs() < 1e-3);
+
+        // Unknown KRW rate or unreported volume
+        let no_rate = ConversionRates::uniform_krw(None, None, None);
+        assert_eq!(volume_usd(&krw, &no_rate), None);
+        assert_eq!(
+            volume_usd(&tick(Exchange::Binance, QuoteCurrency::USDT, 0.0), &rates),
+            None
+        );
+    }
+
+    #[test]
+    fn test_score_symbol_conflict_caps_score() {
+        let scorer = ConfidenceScorer::default();
+        let inputs = ConfidenceInputs {
+            symbol_conflict: true,
+            ..full_inputs()
+        };
+        let (score, breakdown) = scorer.score(&inputs);
+        assert_eq!(breakdown.symbol_mapping, 0);
+        assert_eq!(score, 10);
+    }
+}
@@ -0,0 +1,324 @@
+//! Stablecoin depeg monitoring.
+//!
+//! Premiums are computed in stablecoin units assuming USDT, USDC and USD trade at
+//! parity. When a stablecoin drifts off its peg, a route between a USDT market and
+//! a USD or USDC market shows a premium that is really the peg move, not the asset.
+//! This module tracks stablecoin rates per exchange, raises events when they cross
+//! configured thresholds, and lets the detector reprice or drop affected routes.
+
+use crate::ConversionRates;
+use arbitrage_core::{Exchange, QuoteCurrency};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::{PoisonError, RwLock};
+
+/// Peg key: (exchange, stablecoin, reference currency), e.g. (Binance, USDC, USDT).
+pub type PegKey = (Exchange, QuoteCurrency, QuoteCurrency);
+
+/// Severity of a stablecoin's deviation from parity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepegLevel {
+    /// Within the warning threshold.
+    #[default]
+    Normal,
+    /// Past the warning threshold.
+    Warning,
+    /// Past the critical threshold.
+    Critical,
+}
+
+/// How the detector treats routes with an off-peg stablecoin leg.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepegPolicy {
+    /// Reprice stablecoin legs at their live USD value before applying thresholds.
+    #[default]
+    Reprice,
+    /// Keep the quoted premium, but drop routes that only clear the threshold
+    /// because of the peg move.
+    Suppress,
+}
+
+/// Depeg monitor configuration.
+#[derive(Debug, Clone)]
+pub struct DepegConfig {
+    /// Deviation from parity that raises a warning (bps).
+    pub warn_bps: u32,
+    /// Deviation from parity that is critical (bps).
+    pub critical_bps: u32,
+    /// A level is held until the deviation falls this far below its threshold (bps).
+    pub hysteresis_bps: u32,
+    /// Treatment of routes with an off-peg leg.
+    pub policy: DepegPolicy,
+}
+
+impl Default for DepegConfig {
+    fn default() -> Self {
+        Self {
+            warn_bps: 50,
+            critical_bps: 200,
+            hysteresis_bps: 10,
+            policy: DepegPolicy::Reprice,
+        }
+    }
+}
+
+/// Emitted when a stablecoin rate moves into a different depeg level.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DepegEvent {
+    pub exchange: Exchange,
+    pub stablecoin: QuoteCurrency,
+    /// Currency the stablecoin is priced in (USD or another stablecoin).
+    pub reference: QuoteCurrency,
+    pub rate: f64,
+    /// Signed deviation from parity (bps).
+    pub deviation_bps: i32,
+    pub level: DepegLevel,
+    pub previous_level: DepegLevel,
+    pub timestamp_ms: u64,
+}
+
+/// Latest observation for one peg.
+#[derive(Debug, Clone, Copy)]
+struct PegState {
+    deviation_bps: i32,
+    level: DepegLevel,
+}
+
+/// Tracks stablecoin pegs per exchange (lock-free via DashMap).
+#[derive(Debug, Default)]
+pub struct DepegMonitor {
+    config: RwLock<DepegConfig>,
+    pegs: DashMap<PegKey, PegState>,
+}
+
+impl DepegMonitor {
+    /// Create a monitor with the given configuration.
+    pub fn new(config: DepegConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            pegs: DashMap::new(),
+        }
+    }
+
+    /// Get a copy of the configuration.
+    pub fn config(&self) -> DepegConfig {
+        self.config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the configuration. Levels are re-evaluated on each peg's next rate.
+    pub fn set_config(&self, config: DepegConfig) {
+        *self.config.write().unwrap_or_else(PoisonError::into_inner) = config;
+    }
+
+    /// Record a stablecoin rate (e.g. USDT/USD = 0.9985 or USDC/USDT = 1.0002).
+    ///
+    /// Returns an event when the peg changes level. Rates against non-USD
+    /// currencies (e.g. USDT/KRW) have no parity and are ignored.
+    pub fn observe(
+        &self,
+        exchange: Exchange,
+        stablecoin: QuoteCurrency,
+        reference: QuoteCurrency,
+        rate: f64,
+        timestamp_ms: u64,
+    ) -> Option<DepegEvent> {
+        if !stablecoin.is_usd_stablecoin()
+            || !reference.is_usd_equivalent()
+            || stablecoin == reference
+            || rate <= 0.0
+        {
+            return None;
+        }
+
+        let deviation_bps = ((rate - 1.0) * 10_000.0).round() as i32;
+        let mut entry = self
+            .pegs
+            .entry((exchange, stablecoin, reference))
+            .or_insert(PegState {
+                deviation_bps,
+                level: DepegLevel::Normal,
+            });
+        let previous_level = entry.level;
+        let level = self.next_level(deviation_bps.unsigned_abs(), previous_level);
+        *entry = PegState {
+            deviation_bps,
+            level,
+        };
+
+        (level != previous_level).then_some(DepegEvent {
+            exchange,
+            stablecoin,
+            reference,
+            rate,
+            deviation_bps,
+            level,
+            previous_level,
+            timestamp_ms,
+        })
+    }
+
+    /// Worst level of any peg involving the stablecoin, on any exchange.
+    ///
+    /// A cross rate like USDC/USDT cannot tell which side moved, so both count.
+    pub fn level(&self, stablecoin: QuoteCurrency) -> DepegLevel {
+        self.pegs
+            .iter()
+            .filter(|peg| {
+                let (_, base, reference) = *peg.key();
+                base == stablecoin || reference == stablecoin
+            })
+            .map(|peg| peg.level)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Latest deviation from parity (bps) for one peg.
+    pub fn deviation_bps(&self, key: PegKey) -> Option<i32> {
+        self.pegs.get(&key).map(|peg| peg.deviation_bps)
+    }
+
+    /// Premium of a route with both legs valued at their live USD rates.
+    ///
+    /// Returns None when neither leg is quoted in an off-peg stablecoin. KRW legs
+    /// are converted into the other leg's stablecoin, so they carry no peg move.
+    pub fn reprice(
+        &self,
+        rates: &ConversionRates,
+        buy: (Exchange, QuoteCurrency),
+        sell: (Exchange, QuoteCurrency),
+        premium_bps: i32,
+    ) -> Option<i32> {
+        if buy.1 == QuoteCurrency::KRW || sell.1 == QuoteCurrency::KRW {
+            return None;
+        }
+        let off_peg = [buy.1, sell.1]
+            .into_iter()
+            .any(|quote| quote.is_usd_stablecoin() && self.level(quote) > DepegLevel::Normal);
+        if !off_peg {
+            return None;
+        }
+
+        let buy_usd = rates.quote_usd_for(buy.0, buy.1);
+        let sell_usd = rates.quote_usd_for(sell.0, sell.1);
+        if buy_usd <= 0.0 || sell_usd <= 0.0 {
+            return None;
+        }
+        let ratio = (1.0 + premium_bps as f64 / 10_000.0) * sell_usd / buy_usd;
+        Some(((ratio - 1.0) * 10_000.0).round() as i32)
+    }
+
+    /// Level for a deviation, holding levels already reached within the hysteresis band.
+    fn next_level(&self, deviation_bps: u32, current: DepegLevel) -> DepegLevel {
+        let config = self.config();
+        let threshold = |level: DepegLevel, bps: u32| {
+            if current >= level {
+                bps.saturating_sub(config.hysteresis_bps)
+            } else {
+                bps
+            }
+        };
+
+        if deviation_bps >= threshold(DepegLevel::Critical, config.critical_bps) {
+            DepegLevel::Critical
+        } else if deviation_bps >= threshold(DepegLevel::Warning, config.warn_bps) {
+            DepegLevel::Warning
+        } else {
+            DepegLevel::Normal
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depeg_events_on_level_changes_with_hysteresis() {
+        let monitor = DepegMonitor::default();
+        let observe = |rate| {
+            monitor.observe(
+                Exchange::Binance,
+                QuoteCurrency::USDT,
+                QuoteCurrency::USD,
+                rate,
+                0,
+            )
+        };
+
+        assert_eq!(observe(0.9990), None);
+        let event = observe(0.9940).expect("warning");
+        assert_eq!(event.deviation_bps, -60);
+        assert_eq!(
+            (event.previous_level, event.level),
+            (DepegLevel::Normal, DepegLevel::Warning)
+        );
+        // Same level: no repeated event
+        assert_eq!(observe(0.9930), None);
+        assert_eq!(observe(0.9750).unwrap().level, DepegLevel::Critical);
+        // 195 bps is inside the 10 bps hysteresis band below critical
+        assert_eq!(observe(0.9805), None);
+        assert_eq!(observe(0.9850).unwrap().level, DepegLevel::Warning);
+        assert_eq!(observe(0.9999).unwrap().level, DepegLevel::Normal);
+
+        assert_eq!(monitor.level(QuoteCurrency::USDT), DepegLevel::Normal);
+        assert_eq!(
+            monitor.deviation_bps((Exchange::Binance, QuoteCurrency::USDT, QuoteCurrency::USD)),
+            Some(-1)
+        );
+    }
+
+    #[test]
+    fn test_non_usd_rates_are_ignored() {
+        let monitor = DepegMonitor::default();
+        let event = monitor.observe(
+            Exchange::Upbit,
+            QuoteCurrency::USDT,
+            QuoteCurrency::KRW,
+            1400.0,
+            0,
+        );
+        assert_eq!(event, None);
+        assert_eq!(monitor.level(QuoteCurrency::USDT), DepegLevel::Normal);
+    }
+
+    #[test]
+    fn test_reprice_only_routes_with_off_peg_leg() {
+        let monitor = DepegMonitor::default();
+        let mut rates = ConversionRates::uniform_krw(Some(1400.0), Some(1400.0), None);
+        let binance_usdt = (Exchange::Binance, QuoteCurrency::USDT);
+        let coinbase_usd = (Exchange::Coinbase, QuoteCurrency::USD);
+
+        // On peg: nothing to reprice
+        assert_eq!(
+            monitor.reprice(&rates, coinbase_usd, binance_usdt, 200),
+            None
+        );
+
+        // USDT at 0.98: a 2% premium selling into USDT is worth ~0% in USD
+        rates.usdt_usd = 0.98;
+        monitor.observe(
+            Exchange::Binance,
+            QuoteCurrency::USDT,
+            QuoteCurrency::USD,
+            0.98,
+            0,
+        );
+        assert_eq!(
+            monitor.reprice(&rates, coinbase_usd, binance_usdt, 200),
+            Some(-4)
+        );
+        assert_eq!(
+            monitor.reprice(&rates, binance_usdt, coinbase_usd, 0),
+            Some(204)
+        );
+
+        // KRW legs are already converted into the stablecoin they are compared against
+        let upbit_krw = (Exchange::Upbit, QuoteCurrency::KRW);
+        assert_eq!(monitor.reprice(&rates, binance_usdt, upbit_krw, 200), None);
+    }
+}
@@ -2,8 +2,13 @@
 //!
 //! This module implements the depth-walking algorithm that calculates the
 //! maximum profitable trade size given two orderbooks and fee structures.
+//!
+//! Cached orderbooks only hold the top N levels, so the walk can run off the
+//! end of a book while the trade is still profitable. In that case the result
+//! is flagged `depth_exhausted`, and `calculate_optimal_size_with_impact` can
+//! extrapolate slippage past the observed depth and apply venue order rules.
 
-use arbitrage_core::FixedPoint;
+use arbitrage_core::{FixedPoint, MarketRules};
 
 /// Result of optimal size calculation.
 #[derive(Debug, Clone, Copy, Default)]
@@ -21,6 +26,12 @@ pub struct OptimalSizeResult {
     pub levels_consumed_buy: usize,
     /// Number of sell orderbook levels consumed.
     pub levels_consumed_sell: usize,
+    /// The observed depth of a book ran out while the trade was still profitable.
+    pub depth_exhausted: bool,
+    /// Part of `amount` filled against extrapolated (synthetic) levels.
+    pub extrapolated_amount: u64,
+    /// The profitable size is below a venue's minimum order.
+    pub below_min_order: bool,
 }
 
 impl OptimalSizeResult {
@@ -66,6 +77,126 @@ impl Default for DepthFeeConfig {
     }
 }
 
+/// Fallback market-impact model for sizing beyond the observed depth.
+///
+/// When a walk exhausts a cached book, synthetic levels are appended after
+/// the last observed level. Each synthetic level has the average observed
+/// level size and steps the price by the average observed level spacing
+/// (or `fallback_step_bps` for a single-level book).
+#[derive(Debug, Clone, Copy)]
+pub struct ImpactModelConfig {
+    /// Number of synthetic levels appended to each book (0 disables).
+    pub synthetic_levels: usize,
+    /// Cap on synthetic quantity per book, as a fraction of its observed quantity.
+    pub max_extrapolation_ratio: f64,
+    /// Price step between synthetic levels when it cannot be derived from the book.
+    pub fallback_step_bps: u32,
+}
+
+impl Default for ImpactModelConfig {
+    fn default() -> Self {
+        Self {
+            synthetic_levels: 10,
+            max_extrapolation_ratio: 0.5,
+            fallback_step_bps: 5,
+        }
+    }
+}
+
+impl ImpactModelConfig {
+    /// Extend a book with synthetic levels.
+    ///
+    /// `ascending` is true for asks (prices increase away from the top).
+    pub fn extend(&self, levels: &[(u64, u64)], ascending: bool) -> Vec<(u64, u64)> {
+        let mut extended = levels.to_vec();
+        let (Some(&(first_price, _)), Some(&(last_price, _))) = (levels.first(), levels.last())
+        else {
+            return extended;
+        };
+        if self.synthetic_levels == 0 || self.max_extrapolation_ratio <= 0.0 {
+            return extended;
+        }
+
+        let observed_qty: u128 = levels.iter().map(|&(_, qty)| qty as u128).sum();
+        let avg_qty = observed_qty / levels.len() as u128;
+        let budget = (observed_qty as f64 * self.max_extrapolation_ratio) as u128;
+        let level_qty = avg_qty.min(budget / self.synthetic_levels as u128) as u64;
+        if level_qty == 0 {
+            return extended;
+        }
+
+        let spread = first_price.abs_diff(last_price);
+        let step = if levels.len() > 1 && spread > 0 {
+            spread / (levels.len() as u64 - 1)
+        } else {
+            (last_price as u128 * self.fallback_step_bps as u128 / 10000) as u64
+        }
+        .max(1);
+
+        let mut price = last_price;
+        for _ in 0..self.synthetic_levels {
+            price = if ascending {
+                price.saturating_add(step)
+            } else {
+                match price.checked_sub(step) {
+                    Some(p) if p > 0 => p,
+                    _ => break,
+                }
+            };
+            extended.push((price, level_qty));
+        }
+        extended
+    }
+}
+
+/// Venue order rules for the two legs.
+///
+/// `min_notional` must be expressed in the same currency as the book prices
+/// passed to the sizing function.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SizingConstraints {
+    /// Rules of the market we buy on.
+    pub buy: MarketRules,
+    /// Rules of the market we sell on.
+    pub sell: MarketRules,
+}
+
+impl SizingConstraints {
+    fn is_unrestricted(&self) -> bool {
+        self.buy == MarketRules::default() && self.sell == MarketRules::default()
+    }
+
+    /// Rules for the combined trade: the coarser lot size of the two legs.
+    pub(crate) fn lot(&self) -> MarketRules {
+        MarketRules {
+            lot_size: self.buy.lot_size.max(self.sell.lot_size),
+            ..MarketRules::default()
+        }
+    }
+
+    fn accepts(&self, result: &OptimalSizeResult) -> bool {
+        self.buy.accepts(result.amount, result.avg_buy_price)
+            && self.sell.accepts(result.amount, result.avg_sell_price)
+    }
+
+    /// Smallest quantity that meets both venues' minimums at the given top-of-book prices.
+    fn min_amount(&self, best_ask: u64, best_bid: u64) -> u64 {
+        let for_notional = |min_notional: u64, price: u64| -> u64 {
+            if min_notional == 0 || price == 0 {
+                return 0;
+            }
+            (min_notional as u128 * FixedPoint::SCALE as u128).div_ceil(price as u128) as u64
+        };
+        let amount = self
+            .buy
+            .min_qty
+            .max(self.sell.min_qty)
+            .max(for_notional(self.buy.min_notional, best_ask))
+            .max(for_notional(self.sell.min_notional, best_bid));
+        self.lot().round_up(amount)
+    }
+}
+
 /// Calculate optimal arbitrage size using depth-walking algorithm.
 ///
 /// This function walks through both orderbooks simultaneously to find
@@ -94,6 +225,87 @@ pub fn calculate_optimal_size(
     buy_asks: &[(u64, u64)],
     sell_bids: &[(u64, u64)],
     fees: DepthFeeConfig,
+) -> OptimalSizeResult {
+    walk_books(buy_asks, sell_bids, fees, None)
+}
+
+/// Calculate optimal size with extrapolated impact and venue order rules.
+///
+/// Runs the depth walk on the observed books. If a book is exhausted while
+/// still profitable and an impact model is given, the walk is repeated on the
+/// books extended with synthetic levels. The size is then rounded down to the
+/// coarser lot size of the two venues; if it falls below either venue's
+/// minimum order, the minimum size is used when it is still profitable,
+/// otherwise the result is empty with `below_min_order` set.
+pub fn calculate_optimal_size_with_impact(
+    buy_asks: &[(u64, u64)],
+    sell_bids: &[(u64, u64)],
+    fees: DepthFeeConfig,
+    impact: Option<&ImpactModelConfig>,
+    constraints: &SizingConstraints,
+) -> OptimalSizeResult {
+    let observed = walk_books(buy_asks, sell_bids, fees, None);
+
+    let extended = match impact {
+        Some(model) if observed.depth_exhausted => {
+            Some((model.extend(buy_asks, true), model.extend(sell_bids, false)))
+        }
+        _ => None,
+    };
+    let (asks, bids) = match &extended {
+        Some((asks, bids)) => (asks.as_slice(), bids.as_slice()),
+        None => (buy_asks, sell_bids),
+    };
+
+    // Re-walk to a fixed target, keeping the observed-depth flags
+    let walk_to = |target: u64| OptimalSizeResult {
+        depth_exhausted: observed.depth_exhausted,
+        extrapolated_amount: 0,
+        ..walk_books(asks, bids, fees, Some(target))
+    };
+
+    let mut result = if extended.is_some() {
+        OptimalSizeResult {
+            depth_exhausted: true,
+            ..walk_books(asks, bids, fees, None)
+        }
+    } else {
+        observed
+    };
+
+    if result.amount > 0 && !constraints.is_unrestricted() {
+        let rounded = constraints.lot().round_down(result.amount);
+        if rounded != result.amount {
+            result = walk_to(rounded);
+        }
+        if !constraints.accepts(&result) {
+            let min_amount = constraints.min_amount(buy_asks[0].0, sell_bids[0].0);
+            let lifted = walk_to(min_amount);
+            result =
+                if lifted.amount == min_amount && lifted.profit > 0 && constraints.accepts(&lifted)
+                {
+                    lifted
+                } else {
+                    OptimalSizeResult {
+                        depth_exhausted: observed.depth_exhausted,
+                        below_min_order: true,
+                        ..OptimalSizeResult::default()
+                    }
+                };
+        }
+    }
+
+    result.extrapolated_amount = result.amount.saturating_sub(observed.amount);
+    result
+}
+
+/// Walk both books until the trade stops being profitable, or until
+/// `target` is filled when given (regardless of marginal profitability).
+fn walk_books(
+    buy_asks: &[(u64, u64)],
+    sell_bids: &[(u64, u64)],
+    fees: DepthFeeConfig,
+    target: Option<u64>,
 ) -> OptimalSizeResult {
     if buy_asks.is_empty() || sell_bids.is_empty() {
         return OptimalSizeResult::default();
@@ -111,7 +323,17 @@ pub fn calculate_optimal_size(
     let mut total_buy_cost: u128 = 0;
     let mut total_sell_revenue: u128 = 0;
 
-    while i < buy_asks.len() && j < sell_bids.len() {
+    let mut depth_exhausted = false;
+
+    loop {
+        if target.is_some_and(|t| total_amount >= t) {
+            break;
+        }
+        if i >= buy_asks.len() || j >= sell_bids.len() {
+            depth_exhausted = true;
+            break;
+        }
+
         let (buy_price, _) = buy_asks[i];
         let (sell_price, _) = sell_bids[j];
 
@@ -121,13 +343,15 @@ pub fn calculate_optimal_size(
         let effective_buy = buy_price as u128 * (10000 + fees.buy_fee_bps as u128) / 10000;
         let effective_sell = sell_price as u128 * (10000 - fees.sell_fee_bps as u128) / 10000;
 
-        // Check if still profitable
-        if effective_sell <= effective_buy {
+        // Check if still profitable (a fixed target fills through unprofitable levels)
+        if target.is_none() && effective_sell <= effective_buy {
             break;
         }
 
         // Trade minimum of remaining quantities at each level
-        let qty = buy_remaining.min(sell_remaining);
+        let qty = buy_remaining
+            .min(sell_remaining)
+            .min(target.map_or(u64::MAX, |t| t - total_amount));
         if qty == 0 {
             break;
         }
@@ -169,15 +393,10 @@ pub fn calculate_optimal_size(
 
     // Calculate average prices
     let (avg_buy, avg_sell) = if total_amount > 0 {
-        let total_amount_scaled = total_amount as u128 / FixedPoint::SCALE as u128;
-        if total_amount_scaled > 0 {
-            (
-                (total_buy_cost * FixedPoint::SCALE as u128 / total_amount_scaled) as u64,
-                (total_sell_revenue * FixedPoint::SCALE as u128 / total_amount_scaled) as u64,
-            )
-        } else {
-            (buy_asks[0].0, sell_bids[0].0)
-        }
+        (
+            (total_buy_cost * FixedPoint::SCALE as u128 / total_amount as u128) as u64,
+            (total_sell_revenue * FixedPoint::SCALE as u128 / total_amount as u128) as u64,
+        )
     } else {
         (0, 0)
     };
@@ -197,6 +416,8 @@ pub fn calculate_optimal_size(
         } else {
             0
         },
+        depth_exhausted,
+        ..OptimalSizeResult::default()
     }
 }
 
@@ -381,4 +602,155 @@ mod tests {
         assert!(result.levels_consumed_buy >= 1);
         assert!(result.levels_consumed_sell >= 1);
     }
+
+    #[test]
+    fn test_average_prices_for_fractional_amounts() {
+        let buy_asks = vec![(fp(100.0), fp(0.25)), (fp(101.0), fp(0.25))];
+        let sell_bids = vec![(fp(110.0), fp(1.0))];
+
+        let result = calculate_optimal_size(&buy_asks, &sell_bids, DepthFeeConfig::default());
+
+        assert_eq!(result.amount, fp(0.5));
+        assert_eq!(result.avg_buy_price, fp(100.5));
+        assert_eq!(result.avg_sell_price, fp(110.0));
+    }
+
+    #[test]
+    fn test_depth_exhausted_flag() {
+        // Still profitable when the buy book runs out
+        let buy_asks = vec![(fp(100.0), fp(5.0))];
+        let sell_bids = vec![(fp(102.0), fp(10.0))];
+        let result = calculate_optimal_size(&buy_asks, &sell_bids, DepthFeeConfig::default());
+        assert!(result.depth_exhausted);
+
+        // Stopped by profitability, not by depth
+        let buy_asks = vec![(fp(100.0), fp(5.0)), (fp(103.0), fp(5.0))];
+        let result = calculate_optimal_size(&buy_asks, &sell_bids, DepthFeeConfig::default());
+        assert!(!result.depth_exhausted);
+        assert_eq!(result.amount, fp(5.0));
+    }
+
+    #[test]
+    fn test_impact_model_extends_book() {
+        let model = ImpactModelConfig {
+            synthetic_levels: 2,
+            max_extrapolation_ratio: 1.0,
+            fallback_step_bps: 10,
+        };
+        let asks = vec![(fp(100.0), fp(1.0)), (fp(101.0), fp(3.0))];
+        let extended = model.extend(&asks, true);
+        assert_eq!(
+            &extended[2..],
+            &[(fp(102.0), fp(2.0)), (fp(103.0), fp(2.0))]
+        );
+
+        // Single level: fallback step, quantity capped by the extrapolation ratio
+        let model = ImpactModelConfig {
+            max_extrapolation_ratio: 0.5,
+            ..model
+        };
+        let bids = vec![(fp(100.0), fp(4.0))];
+        let extended = model.extend(&bids, false);
+        assert_eq!(&extended[1..], &[(fp(99.9), fp(1.0)), (fp(99.8), fp(1.0))]);
+    }
+
+    #[test]
+    fn test_optimal_size_with_impact_extrapolates() {
+        let buy_asks = vec![(fp(100.0), fp(5.0))];
+        let sell_bids = vec![(fp(102.0), fp(20.0))];
+        let fees = DepthFeeConfig::default();
+
+        let observed = calculate_optimal_size(&buy_asks, &sell_bids, fees);
+        let result = calculate_optimal_size_with_impact(
+            &buy_asks,
+            &sell_bids,
+            fees,
+            Some(&ImpactModelConfig::default()),
+            &SizingConstraints::default(),
+        );
+
+        assert!(result.depth_exhausted);
+        assert!(result.amount > observed.amount);
+        assert_eq!(result.extrapolated_amount, result.amount - observed.amount);
+        // Synthetic buy-side quantity is capped at half the observed depth
+        assert!(result.amount <= fp(7.5));
+
+        // Without an impact model the observed result is returned unchanged
+        let result = calculate_optimal_size_with_impact(
+            &buy_asks,
+            &sell_bids,
+            fees,
+            None,
+            &SizingConstraints::default(),
+        );
+        assert_eq!(result.amount, observed.amount);
+        assert_eq!(result.extrapolated_amount, 0);
+    }
+
+    #[test]
+    fn test_optimal_size_respects_lot_size() {
+        let buy_asks = vec![(fp(100.0), fp(1.2345))];
+        let sell_bids = vec![(fp(102.0), fp(10.0))];
+        let constraints = SizingConstraints {
+            buy: MarketRules {
+                lot_size: fp(0.001),
+                ..MarketRules::default()
+            },
+            sell: MarketRules {
+                lot_size: fp(0.01),
+                ..MarketRules::default()
+            },
+        };
+
+        let result = calculate_optimal_size_with_impact(
+            &buy_asks,
+            &sell_bids,
+            DepthFeeConfig::default(),
+            None,
+            &constraints,
+        );
+
+        assert_eq!(result.amount, fp(1.23));
+        assert!(result.is_profitable());
+    }
+
+    #[test]
+    fn test_optimal_size_min_notional() {
+        let buy_asks = vec![(fp(100.0), fp(0.05)), (fp(101.5), fp(1.0))];
+        let sell_bids = vec![(fp(101.0), fp(10.0))];
+        let fees = DepthFeeConfig {
+            buy_fee_bps: 0,
+            sell_fee_bps: 0,
+            withdrawal_fee: 0,
+        };
+
+        // Profitable size is 0.05 (5 USD); the sell venue requires 6 USD.
+        // Lifting to 0.06 crosses one unprofitable unit but stays profitable overall.
+        let constraints = SizingConstraints {
+            buy: MarketRules::default(),
+            sell: MarketRules {
+                min_notional: fp(6.0),
+                lot_size: fp(0.01),
+                ..MarketRules::default()
+            },
+        };
+        let result =
+            calculate_optimal_size_with_impact(&buy_asks, &sell_bids, fees, None, &constraints);
+        assert_eq!(result.amount, fp(0.06));
+        assert!(result.is_profitable());
+        assert!(!result.below_min_order);
+
+        // A minimum that cannot be reached profitably yields no size
+        let constraints = SizingConstraints {
+            sell: MarketRules {
+                min_notional: fp(50.0),
+                ..constraints.sell
+            },
+            ..constraints
+        };
+        let result =
+            calculate_optimal_size_with_impact(&buy_asks, &sell_bids, fees, None, &constraints);
+        assert_eq!(result.amount, 0);
+        assert!(result.below_min_order);
+    }
 }
@@ -3,13 +3,17 @@
 //! Monitors price feeds and detects profitable arbitrage opportunities.
 //! Uses lock-free data structures (DashMap) for real-time performance.
 
-use crate::{ConversionRates, PremiumMatrix};
+use crate::{
+    ConversionRates, DepegConfig, DepegMonitor, DepegPolicy, KimchiMarketPremium, PremiumHistory,
+    PremiumHistoryConfig, PremiumMatrix,
+};
 use arbitrage_core::{
-    ArbitrageOpportunity, Asset, Chain, Exchange, FixedPoint, QuoteCurrency, UsdlikePremium,
-    UsdlikeQuote,
+    pair_id_to_symbol, ArbitrageOpportunity, Asset, Chain, Exchange, FixedPoint, PremiumStats,
+    QuoteCurrency, UsdlikePremium, UsdlikeQuote,
 };
 use dashmap::DashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard};
 
 static OPPORTUNITY_ID: AtomicU64 = AtomicU64::new(1);
 
@@ -22,6 +26,13 @@ pub struct DetectorConfig {
     pub max_staleness_ms: u64,
     /// Enabled exchanges.
     pub enabled_exchanges: Vec<Exchange>,
+    /// Premium history settings. `retention_ms` is also the statistics window.
+    pub premium_history: PremiumHistoryConfig,
+    /// Also detect routes whose premium z-score reaches this value, even when
+    /// below `min_premium_bps` (None = absolute threshold only).
+    pub min_premium_zscore: Option<f64>,
+    /// Stablecoin depeg thresholds and treatment of affected routes.
+    pub depeg: DepegConfig,
 }
 
 impl Default for DetectorConfig {
@@ -36,39 +47,43 @@ impl Default for DetectorConfig {
                 Exchange::Okx,
                 Exchange::Bybit,
             ],
+            premium_history: PremiumHistoryConfig::default(),
+            min_premium_zscore: None,
+            depeg: DepegConfig::default(),
         }
     }
 }
 
-/// Get asset for a pair_id using the symbol registry (DashMap version).
+/// Get asset for a pair_id from the detector's symbols, then the global pair registry.
 fn asset_for_pair_id_dashmap(pair_id: u32, symbol_registry: &DashMap<u32, String>) -> Asset {
     if let Some(symbol) = symbol_registry.get(&pair_id) {
         return Asset::from_symbol(&symbol);
     }
-
-    // Fallback to legacy hardcoded pair_ids for backwards compatibility
-    match pair_id {
-        1 => Asset::btc(),
-        2 => Asset::eth(),
-        3 => Asset::sol(),
-        _ => Asset::native("UNKNOWN", Chain::Ethereum, 18),
+    match pair_id_to_symbol(pair_id) {
+        Some(symbol) => Asset::from_symbol(&symbol),
+        None => Asset::native("UNKNOWN", Chain::Ethereum, 18),
     }
 }
 
 /// Opportunity detector that monitors prices and detects arbitrage.
 /// Uses lock-free DashMap for concurrent price updates without blocking.
 pub struct OpportunityDetector {
-    config: DetectorConfig,
+    /// Replaceable at runtime via `set_config`
+    config: RwLock<DetectorConfig>,
     /// Per-pair price matrices (lock-free concurrent access)
     matrices: DashMap<u32, PremiumMatrix>,
     /// Maps pair_id -> symbol for dynamic markets (lock-free)
     symbol_registry: DashMap<u32, String>,
+    /// Rolling premium history per route (lock-free)
+    history: PremiumHistory,
+    /// Stablecoin peg state (lock-free)
+    depeg: DepegMonitor,
 }
 
 impl std::fmt::Debug for OpportunityDetector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OpportunityDetector")
-            .field("config", &self.config)
+            .field("config", &*self.read_config())
             .field("matrices_count", &self.matrices.len())
             .field("symbol_registry_count", &self.symbol_registry.len())
             .finish()
@@ -78,11 +93,41 @@ impl std::fmt::Debug for OpportunityDetector {
 impl OpportunityDetector {
     /// Create a new detector with the given configuration.
     pub fn new(config: DetectorConfig) -> Self {
+        let history = PremiumHistory::new(config.premium_history.clone());
+        let depeg = DepegMonitor::new(config.depeg.clone());
         Self {
-            config,
+            config: RwLock::new(config),
             matrices: DashMap::new(),
             symbol_registry: DashMap::new(),
+            history,
+            depeg,
+        }
+    }
+
+    /// Get a copy of the current configuration.
+    pub fn config(&self) -> DetectorConfig {
+        self.read_config().clone()
+    }
+
+    /// Replace the configuration at runtime.
+    ///
+    /// Thresholds, staleness and depeg settings apply from the next update or
+    /// detection pass. The premium history window is fixed at construction, so
+    /// `premium_history` is kept as it was.
+    pub fn set_config(&self, mut config: DetectorConfig) {
+        let mut current = self.config.write().unwrap_or_else(PoisonError::into_inner);
+        config.premium_history = current.premium_history.clone();
+        if config.max_staleness_ms != current.max_staleness_ms {
+            for mut matrix in self.matrices.iter_mut() {
+                matrix.set_max_staleness_ms(config.max_staleness_ms);
+            }
         }
+        self.depeg.set_config(config.depeg.clone());
+        *current = config;
+    }
+
+    fn read_config(&self) -> RwLockReadGuard<'_, DetectorConfig> {
+        self.config.read().unwrap_or_else(PoisonError::into_inner)
     }
 
     /// Register a symbol with its pair_id (lock-free).
@@ -122,10 +167,12 @@ impl OpportunityDetector {
     }
 
     /// Get symbol for a pair_id from the registry.
+    /// Falls back to the shared pair registry for pairs not registered with this detector.
     pub fn pair_id_to_symbol(&self, pair_id: u32) -> Option<String> {
         self.symbol_registry
             .get(&pair_id)
             .map(|r| r.value().clone())
+            .or_else(|| arbitrage_core::pair_id_to_symbol(pair_id))
     }
 
     /// Get the premium matrix for a pair_id.
@@ -182,7 +229,8 @@ impl OpportunityDetector {
             matrix.update_price_with_bid_ask(exchange, price, bid, ask, bid_size, ask_size, quote);
         } else {
             // Insert new matrix with configured staleness threshold
-            let mut matrix = PremiumMatrix::with_staleness(pair_id, self.config.max_staleness_ms);
+            let mut matrix =
+                PremiumMatrix::with_staleness(pair_id, self.read_config().max_staleness_ms);
             matrix.update_price_with_bid_ask(exchange, price, bid, ask, bid_size, ask_size, quote);
             self.matrices.insert(pair_id, matrix);
         }
@@ -219,7 +267,8 @@ impl OpportunityDetector {
             );
         } else {
             // Insert new matrix with configured staleness threshold
-            let mut matrix = PremiumMatrix::with_staleness(pair_id, self.config.max_staleness_ms);
+            let mut matrix =
+                PremiumMatrix::with_staleness(pair_id, self.read_config().max_staleness_ms);
             matrix.update_price_with_bid_ask_and_raw(
                 exchange, price, bid, ask, raw_bid, raw_ask, bid_size, ask_size, quote,
             );
@@ -251,6 +300,10 @@ impl OpportunityDetector {
     }
 
     /// Detect opportunities for a specific pair with all exchange rates.
+    ///
+    /// Applies the same KRW rates to every Korean exchange and treats stablecoins
+    /// as pegged; use [`Self::detect_with_conversion_rates`] with a live snapshot
+    /// to price each exchange with its own rates.
     pub fn detect_with_all_rates(
         &self,
         pair_id: u32,
@@ -258,23 +311,8 @@ impl OpportunityDetector {
         usdt_krw_rate: Option<f64>,
         usdc_krw_rate: Option<f64>,
     ) -> Vec<ArbitrageOpportunity> {
-        let rates = ConversionRates {
-            usdt_usd: 1.0,
-            usdc_usd: 1.0,
-            usd_krw: usd_krw_rate.unwrap_or(0.0),
-            upbit_usdt_krw: usdt_krw_rate.unwrap_or(0.0),
-            upbit_usdc_krw: usdc_krw_rate.unwrap_or(0.0),
-            bithumb_usdt_krw: usdt_krw_rate.unwrap_or(0.0),
-            bithumb_usdc_krw: usdc_krw_rate.unwrap_or(0.0),
-        };
-
-        self.detect_with_conversion_rates(
-            pair_id,
-            &rates,
-            usd_krw_rate,
-            usdt_krw_rate,
-            usdc_krw_rate,
-        )
+        let rates = ConversionRates::uniform_krw(usd_krw_rate, usdt_krw_rate, usdc_krw_rate);
+        self.detect_with_conversion_rates(pair_id, &rates)
     }
 
     /// Detect opportunities using full ConversionRates (supports per-exchange rates).
@@ -282,9 +320,6 @@ impl OpportunityDetector {
         &self,
         pair_id: u32,
         rates: &ConversionRates,
-        usd_krw_rate: Option<f64>,
-        usdt_krw_rate: Option<f64>,
-        usdc_krw_rate: Option<f64>,
     ) -> Vec<ArbitrageOpportunity> {
         let Some(matrix) = self.matrices.get(&pair_id) else {
             tracing::debug!(pair_id = pair_id, "detect: no matrix found for pair_id");
@@ -301,6 +336,15 @@ impl OpportunityDetector {
         let asset = asset_for_pair_id_dashmap(pair_id, &self.symbol_registry);
 
         let premiums = matrix.all_premiums_multi_denomination(rates);
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let (min_premium_bps, min_premium_zscore) = {
+            let config = self.read_config();
+            (config.min_premium_bps, config.min_premium_zscore)
+        };
+        let depeg_policy = self.depeg.config().policy;
 
         for (
             buy_ex,
@@ -324,7 +368,38 @@ impl OpportunityDetector {
                 continue;
             }
 
-            if usdlike_premium_bps < self.config.min_premium_bps {
+            self.history
+                .record(pair_id, buy_ex, sell_ex, usdlike_premium_bps, now_ms);
+
+            // Off-peg stablecoin legs: price in USD, or drop premiums made by the peg move
+            let repriced_bps = self.depeg.reprice(
+                rates,
+                (buy_ex, buy_quote),
+                (sell_ex, sell_quote),
+                usdlike_premium_bps,
+            );
+            let usdlike_premium_bps = match (repriced_bps, depeg_policy) {
+                (Some(bps), DepegPolicy::Reprice) => bps,
+                (Some(bps), DepegPolicy::Suppress) if bps < min_premium_bps => continue,
+                _ => usdlike_premium_bps,
+            };
+
+            // Statistics are only needed for routes that may be reported
+            let meets_premium = usdlike_premium_bps >= min_premium_bps;
+            let premium_stats = if meets_premium || min_premium_zscore.is_some() {
+                self.premium_stats(pair_id, buy_ex, sell_ex)
+            } else {
+                None
+            };
+
+            // Deviation trigger: unusually high premium relative to this route's history
+            let meets_zscore = usdlike_premium_bps > 0
+                && matches!(
+                    (min_premium_zscore, premium_stats),
+                    (Some(min_z), Some(stats)) if stats.zscore >= min_z
+                );
+
+            if !meets_premium && !meets_zscore {
                 continue;
             }
 
@@ -340,6 +415,14 @@ impl OpportunityDetector {
                     quote,
                 });
 
+                // KRW rates come from whichever side of the route trades in KRW
+                let krw_exchange = if sell_quote == QuoteCurrency::KRW {
+                    sell_ex
+                } else {
+                    buy_ex
+                };
+                let positive = |rate: f64| (rate > 0.0).then_some(rate);
+
                 let mut opp = ArbitrageOpportunity::with_all_rates(
                     OPPORTUNITY_ID.fetch_add(1, Ordering::SeqCst),
                     buy_ex,
@@ -349,14 +432,15 @@ impl OpportunityDetector {
                     asset.clone(),
                     buy_ask,
                     sell_bid,
-                    usd_krw_rate,
-                    usdt_krw_rate,
-                    usdc_krw_rate,
+                    positive(rates.usd_krw),
+                    positive(rates.usdt_krw_for(krw_exchange)),
+                    positive(rates.usdc_krw_for(krw_exchange)),
                 )
                 .with_depth(buy_ask_size, sell_bid_size)
                 .with_price_timestamps(buy_timestamp_ms, sell_timestamp_ms)
                 .with_raw_prices(buy_ask_raw, sell_bid_raw)
-                .with_pair_id(pair_id);
+                .with_pair_id(pair_id)
+                .with_premium_stats(premium_stats);
 
                 opp.usdlike_premium = usdlike_premium;
                 opp.kimchi_premium_bps = kimchi_premium;
@@ -395,17 +479,21 @@ impl OpportunityDetector {
         usd_krw_rate: Option<f64>,
         usdt_krw_rate: Option<f64>,
         usdc_krw_rate: Option<f64>,
+    ) -> Vec<ArbitrageOpportunity> {
+        let rates = ConversionRates::uniform_krw(usd_krw_rate, usdt_krw_rate, usdc_krw_rate);
+        self.detect_all_with_conversion_rates(&rates)
+    }
+
+    /// Detect opportunities for all tracked pairs using one rates snapshot.
+    pub fn detect_all_with_conversion_rates(
+        &self,
+        rates: &ConversionRates,
     ) -> Vec<ArbitrageOpportunity> {
         let pair_ids: Vec<u32> = self.matrices.iter().map(|r| *r.key()).collect();
         let mut all_opportunities = Vec::new();
 
         for pair_id in pair_ids {
-            all_opportunities.extend(self.detect_with_all_rates(
-                pair_id,
-                usd_krw_rate,
-                usdt_krw_rate,
-                usdc_krw_rate,
-            ));
+            all_opportunities.extend(self.detect_with_conversion_rates(pair_id, rates));
         }
 
         all_opportunities
@@ -417,6 +505,70 @@ impl OpportunityDetector {
         for mut entry in self.matrices.iter_mut() {
             entry.value_mut().clear_exchange(exchange);
         }
+        self.history.clear_exchange(exchange);
+    }
+
+    /// Kimchi premium of every KRW market against its overseas reference price.
+    /// `weight_usd` supplies the index weight (24h USD volume) for (pair_id, exchange).
+    pub fn kimchi_markets(
+        &self,
+        usd_krw: f64,
+        weight_usd: impl Fn(u32, Exchange) -> f64,
+    ) -> Vec<KimchiMarketPremium> {
+        let mut markets = Vec::new();
+        for entry in self.matrices.iter() {
+            let pair_id = *entry.key();
+            let premiums = entry.value().kimchi_premiums(usd_krw);
+            if premiums.is_empty() {
+                continue;
+            }
+            let symbol = self
+                .pair_id_to_symbol(pair_id)
+                .unwrap_or_else(|| pair_id.to_string());
+            markets.extend(premiums.into_iter().map(|(exchange, premium_bps)| {
+                KimchiMarketPremium {
+                    pair_id,
+                    symbol: symbol.clone(),
+                    exchange,
+                    premium_bps,
+                    weight_usd: weight_usd(pair_id, exchange),
+                }
+            }));
+        }
+        markets
+    }
+
+    /// Get the rolling premium history.
+    pub fn premium_history(&self) -> &PremiumHistory {
+        &self.history
+    }
+
+    /// Get the stablecoin depeg monitor (fed with stablecoin rates).
+    pub fn depeg_monitor(&self) -> &DepegMonitor {
+        &self.depeg
+    }
+
+    /// Premium statistics for a route over the configured history window.
+    /// Returns None until enough history has been collected.
+    pub fn premium_stats(
+        &self,
+        pair_id: u32,
+        buy: Exchange,
+        sell: Exchange,
+    ) -> Option<PremiumStats> {
+        let retention_ms = self.read_config().premium_history.retention_ms;
+        self.history.stats(pair_id, buy, sell, retention_ms)
+    }
+
+    /// Fraction of recent samples where the route's premium was above threshold.
+    /// Returns None until enough history has been collected.
+    pub fn persistence_ratio(&self, pair_id: u32, buy: Exchange, sell: Exchange) -> Option<f64> {
+        let (min_premium_bps, retention_ms) = {
+            let config = self.read_config();
+            (config.min_premium_bps, config.premium_history.retention_ms)
+        };
+        self.history
+            .fraction_at_or_above(pair_id, buy, sell, min_premium_bps, retention_ms)
     }
 
     /// Expire stale prices from all matrices.
@@ -562,6 +714,47 @@ mod tests {
         assert_eq!(opp.premium_bps, 100);
     }
 
+    #[test]
+    fn test_detector_set_config_applies_to_next_pass() {
+        let detector = OpportunityDetector::new(DetectorConfig {
+            min_premium_bps: 200,
+            ..Default::default()
+        });
+        for (exchange, price) in [(Exchange::Binance, 50000.0), (Exchange::Coinbase, 50500.0)] {
+            detector.update_price_with_bid_ask(
+                exchange,
+                1,
+                FixedPoint::from_f64(price),
+                FixedPoint::from_f64(price),
+                FixedPoint::from_f64(price),
+                FixedPoint::from_f64(1.0),
+                FixedPoint::from_f64(1.0),
+                QuoteCurrency::USD,
+            );
+        }
+        assert!(detector.detect(1).is_empty());
+
+        let window_ms = detector.config().premium_history.retention_ms;
+        detector.set_config(DetectorConfig {
+            min_premium_bps: 50,
+            premium_history: PremiumHistoryConfig {
+                retention_ms: window_ms * 2,
+                ..Default::default()
+            },
+            depeg: DepegConfig {
+                warn_bps: 25,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        assert_eq!(detector.detect(1).len(), 1);
+        assert_eq!(detector.config().min_premium_bps, 50);
+        // History window is fixed at construction
+        assert_eq!(detector.config().premium_history.retention_ms, window_ms);
+        assert_eq!(detector.depeg_monitor().config().warn_bps, 25);
+    }
+
     #[test]
     fn test_detector_multiple_pairs() {
         let config = DetectorConfig {
@@ -754,6 +947,118 @@ mod tests {
         assert!(upbit_price.is_some());
     }
 
+    #[test]
+    fn test_detector_records_premium_persistence() {
+        let config = DetectorConfig {
+            min_premium_bps: 50,
+            premium_history: PremiumHistoryConfig {
+                sample_interval_ms: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let detector = OpportunityDetector::new(config);
+
+        detector.update_price_with_bid_ask(
+            Exchange::Binance,
+            1,
+            FixedPoint::from_f64(50000.0),
+            FixedPoint::from_f64(49999.0),
+            FixedPoint::from_f64(50000.0),
+            FixedPoint::from_f64(1.0),
+            FixedPoint::from_f64(1.0),
+            QuoteCurrency::USD,
+        );
+        detector.update_price_with_bid_ask(
+            Exchange::Coinbase,
+            1,
+            FixedPoint::from_f64(50500.0),
+            FixedPoint::from_f64(50500.0),
+            FixedPoint::from_f64(50501.0),
+            FixedPoint::from_f64(1.0),
+            FixedPoint::from_f64(1.0),
+            QuoteCurrency::USD,
+        );
+
+        // Not enough history after a single detection
+        detector.detect(1);
+        assert_eq!(
+            detector.persistence_ratio(1, Exchange::Binance, Exchange::Coinbase),
+            None
+        );
+
+        detector.detect(1);
+        assert_eq!(
+            detector.persistence_ratio(1, Exchange::Binance, Exchange::Coinbase),
+            Some(1.0)
+        );
+        // Reverse direction is recorded but never above threshold
+        assert_eq!(
+            detector.persistence_ratio(1, Exchange::Coinbase, Exchange::Binance),
+            Some(0.0)
+        );
+        assert!(detector
+            .premium_stats(1, Exchange::Binance, Exchange::Coinbase)
+            .is_some());
+
+        detector.clear_exchange_prices(Exchange::Coinbase);
+        assert_eq!(
+            detector.persistence_ratio(1, Exchange::Binance, Exchange::Coinbase),
+            None
+        );
+    }
+
+    #[test]
+    fn test_detector_zscore_trigger_below_absolute_threshold() {
+        let config = DetectorConfig {
+            min_premium_bps: 500,
+            premium_history: PremiumHistoryConfig {
+                sample_interval_ms: 0,
+                ..Default::default()
+            },
+            min_premium_zscore: Some(2.0),
+            ..Default::default()
+        };
+        let detector = OpportunityDetector::new(config);
+
+        let set_coinbase = |price: f64| {
+            detector.update_price_with_bid_ask(
+                Exchange::Coinbase,
+                1,
+                FixedPoint::from_f64(price),
+                FixedPoint::from_f64(price),
+                FixedPoint::from_f64(price + 1.0),
+                FixedPoint::from_f64(1.0),
+                FixedPoint::from_f64(1.0),
+                QuoteCurrency::USD,
+            );
+        };
+        detector.update_price_with_bid_ask(
+            Exchange::Binance,
+            1,
+            FixedPoint::from_f64(50000.0),
+            FixedPoint::from_f64(49999.0),
+            FixedPoint::from_f64(50000.0),
+            FixedPoint::from_f64(1.0),
+            FixedPoint::from_f64(1.0),
+            QuoteCurrency::USD,
+        );
+
+        // Stable ~10-12 bps premium history
+        for i in 0..20 {
+            set_coinbase(50050.0 + (i % 2) as f64 * 10.0);
+            assert!(detector.detect(1).is_empty());
+        }
+
+        // Spike to ~100 bps: below 500 bps absolute threshold but far from normal
+        set_coinbase(50500.0);
+        let opps = detector.detect(1);
+        assert_eq!(opps.len(), 1);
+        let stats = opps[0].premium_stats.expect("premium stats");
+        assert!(stats.zscore >= 2.0);
+        assert_eq!(stats.samples, 21);
+    }
+
     #[test]
     fn test_dynamic_market_registered_pair_ids_includes_new_markets() {
         let config = DetectorConfig::default();
@@ -850,10 +1155,11 @@ mod tests {
         };
         let detector = OpportunityDetector::new(config);
 
-        // 레거시 마켓 (pair_id 1 = BTC) 설정
+        // 레거시 마켓 (BTC, detector에 등록하지 않음) 설정
+        let btc_pair_id = arbitrage_core::symbol_to_pair_id("BTC");
         detector.update_price_with_bid_ask(
             Exchange::Binance,
-            1, // BTC
+            btc_pair_id,
             FixedPoint::from_f64(50000.0),
             FixedPoint::from_f64(49999.0),
             FixedPoint::from_f64(50000.0),
@@ -863,7 +1169,7 @@ mod tests {
         );
         detector.update_price_with_bid_ask(
             Exchange::Coinbase,
-            1,
+            btc_pair_id,
             FixedPoint::from_f64(50500.0),
             FixedPoint::from_f64(50500.0),
             FixedPoint::from_f64(50501.0),
@@ -1662,4 +1968,82 @@ mod tests {
             assert!(found, "심볼 {} 기회가 detect_all에 포함되어야 함", symbol);
         }
     }
+
+    #[test]
+    fn test_depeg_policy_reprices_or_suppresses_peg_premium() {
+        // USDT at 0.98: BTC quotes ~2% higher in USDT than in USD without any real spread.
+        // Pair 1 is pure peg move; pair 2 has a real ~2% spread on top of it.
+        let detect = |policy| {
+            let config = DetectorConfig {
+                min_premium_bps: 50,
+                depeg: DepegConfig {
+                    policy,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let detector = OpportunityDetector::new(config);
+            for (pair_id, usd_price) in [(1, 49_000.0), (2, 48_000.0)] {
+                for (exchange, price, quote) in [
+                    (Exchange::Coinbase, usd_price, QuoteCurrency::USD),
+                    (Exchange::Binance, 50_000.0, QuoteCurrency::USDT),
+                ] {
+                    let price = FixedPoint::from_f64(price);
+                    let size = FixedPoint::from_f64(1.0);
+                    detector.update_price_with_bid_ask(
+                        exchange, pair_id, price, price, price, size, size, quote,
+                    );
+                }
+            }
+            let mut rates = ConversionRates::uniform_krw(None, None, None);
+            rates.usdt_usd = 0.98;
+
+            let at_par = detector.detect_with_conversion_rates(1, &rates);
+            assert_eq!(at_par[0].premium_bps, 204);
+            let event = detector.depeg_monitor().observe(
+                Exchange::Binance,
+                QuoteCurrency::USDT,
+                QuoteCurrency::USD,
+                0.98,
+                0,
+            );
+            assert_eq!(event.map(|e| e.level), Some(crate::DepegLevel::Critical));
+            (
+                detector.detect_with_conversion_rates(1, &rates),
+                detector.detect_with_conversion_rates(2, &rates),
+            )
+        };
+
+        // 50,000 USDT = 49,000 USD: nothing left after repricing
+        let (peg_only, real_spread) = detect(DepegPolicy::Reprice);
+        assert!(peg_only.is_empty());
+        assert_eq!(real_spread[0].premium_bps, 208);
+
+        // Suppress drops the peg-only route but keeps the quoted premium otherwise
+        let (peg_only, real_spread) = detect(DepegPolicy::Suppress);
+        assert!(peg_only.is_empty());
+        assert_eq!(real_spread[0].premium_bps, 416);
+    }
+
+    #[test]
+    fn test_asset_for_unregistered_pair_id_uses_global_registry() {
+        let symbols = DashMap::new();
+        let pair_id = arbitrage_core::symbol_to_pair_id("DETECTORREGTEST");
+        assert_eq!(
+            asset_for_pair_id_dashmap(pair_id, &symbols).symbol.as_str(),
+            "DETECTORREGTEST"
+        );
+
+        // The detector's own symbols win over the global registry
+        symbols.insert(pair_id, "OVERRIDE".to_string());
+        assert_eq!(
+            asset_for_pair_id_dashmap(pair_id, &symbols).symbol.as_str(),
+            "OVERRIDE"
+        );
+
+        assert_eq!(
+            asset_for_pair_id_dashmap(u32::MAX, &DashMap::new()).symbol.as_str(),
+            "UNKNOWN"
+        );
+    }
 }
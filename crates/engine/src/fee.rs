@@ -2,10 +2,23 @@
 //!
 //! This module provides fee structures for trading and withdrawals,
 //! with default values and support for runtime updates from exchange APIs.
+//! Trading fees can be refined with VIP tier schedules, fee-token discounts
+//! (BNB, GT) and per-market overrides.
 
 use arbitrage_core::{Exchange, FixedPoint};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Order role of a trade leg (determines maker or taker fee).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeRole {
+    Maker,
+    /// Market orders (default for arbitrage legs).
+    #[default]
+    Taker,
+}
+
 /// Trading and withdrawal fee configuration for an exchange.
 #[derive(Debug, Clone, Copy)]
 pub struct FeeConfig {
@@ -74,6 +87,94 @@ impl FeeConfig {
     }
 }
 
+/// VIP tier trading fees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeTier {
+    /// Tier level (0 = regular account).
+    pub level: u8,
+    /// Maker fee in basis points.
+    pub maker_fee_bps: i32,
+    /// Taker fee in basis points.
+    pub taker_fee_bps: i32,
+}
+
+impl FeeTier {
+    pub fn new(level: u8, maker_fee_bps: i32, taker_fee_bps: i32) -> Self {
+        Self {
+            level,
+            maker_fee_bps,
+            taker_fee_bps,
+        }
+    }
+}
+
+/// Discount for paying fees with the exchange token (e.g. BNB, GT).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeTokenDiscount {
+    /// Fee token symbol.
+    pub token: String,
+    /// Discount in percent of the trading fee.
+    pub discount_pct: u8,
+}
+
+/// Published fee schedule for an exchange.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    /// VIP tiers, ordered by level.
+    pub tiers: Vec<FeeTier>,
+    /// Fee-token discount, if the exchange offers one.
+    pub fee_token: Option<FeeTokenDiscount>,
+}
+
+impl FeeSchedule {
+    /// Get the fee tier for a level.
+    pub fn tier(&self, level: u8) -> Option<FeeTier> {
+        self.tiers.iter().find(|t| t.level == level).copied()
+    }
+
+    /// Get the default fee schedule for an exchange (spot, published rates).
+    /// Fractional rates are rounded up to whole basis points.
+    pub fn default_for_exchange(exchange: Exchange) -> Option<Self> {
+        let fee_token = |token: &str, discount_pct| {
+            Some(FeeTokenDiscount {
+                token: token.to_string(),
+                discount_pct,
+            })
+        };
+        match exchange {
+            Exchange::Binance => Some(Self {
+                tiers: vec![
+                    FeeTier::new(0, 10, 10),
+                    FeeTier::new(1, 9, 10),
+                    FeeTier::new(2, 8, 10),
+                    FeeTier::new(3, 5, 6),
+                    FeeTier::new(4, 4, 6),
+                ],
+                fee_token: fee_token("BNB", 25),
+            }),
+            Exchange::Bybit => Some(Self {
+                tiers: vec![
+                    FeeTier::new(0, 10, 10),
+                    FeeTier::new(1, 7, 8),
+                    FeeTier::new(2, 6, 8),
+                    FeeTier::new(3, 5, 8),
+                ],
+                fee_token: None,
+            }),
+            Exchange::GateIO => Some(Self {
+                tiers: vec![
+                    FeeTier::new(0, 20, 20),
+                    FeeTier::new(1, 19, 19),
+                    FeeTier::new(2, 18, 18),
+                    FeeTier::new(3, 17, 17),
+                ],
+                fee_token: fee_token("GT", 15),
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// Per-asset withdrawal fee configuration.
 #[derive(Debug, Clone)]
 pub struct AssetWithdrawalFee {
@@ -87,13 +188,86 @@ pub struct AssetWithdrawalFee {
     pub min_withdrawal: u64,
 }
 
+/// Withdrawal and deposit status of one network for an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkWithdrawalFee {
+    /// Canonical network name (shared across exchanges).
+    pub network: String,
+    /// Withdrawal fee in asset units (FixedPoint scale), None if not reported.
+    pub fee: Option<u64>,
+    /// Minimum withdrawal amount (FixedPoint scale).
+    pub min_withdrawal: u64,
+    pub withdraw_enabled: bool,
+    pub deposit_enabled: bool,
+}
+
+/// Withdrawal cost of moving an asset between two exchanges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferFee {
+    /// Transferable: withdrawal fee and minimum withdrawal in asset units
+    /// (FixedPoint scale) of the network used.
+    Open { fee: u64, min_withdrawal: u64 },
+    /// Live network data shows no network with withdrawals open on the source
+    /// and deposits open on the destination.
+    NoOpenNetwork,
+}
+
+impl TransferFee {
+    /// Withdrawal fee (None = no open network).
+    pub fn fee(&self) -> Option<u64> {
+        match self {
+            Self::Open { fee, .. } => Some(*fee),
+            Self::NoOpenNetwork => None,
+        }
+    }
+
+    /// Minimum withdrawal (0 = none or no open network).
+    pub fn min_withdrawal(&self) -> u64 {
+        match self {
+            Self::Open { min_withdrawal, .. } => *min_withdrawal,
+            Self::NoOpenNetwork => 0,
+        }
+    }
+}
+
+/// Market selector for fee overrides: (base, quote), either side may be a wildcard.
+type MarketFeeKey = (Exchange, Option<String>, Option<String>);
+
+/// Trade leg for fee lookup.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeLeg<'a> {
+    pub exchange: Exchange,
+    /// Quote currency of the market (None = unknown, no market override).
+    pub quote: Option<&'a str>,
+    pub role: FeeRole,
+}
+
+impl<'a> FeeLeg<'a> {
+    /// Taker leg on an exchange.
+    pub fn taker(exchange: Exchange, quote: Option<&'a str>) -> Self {
+        Self {
+            exchange,
+            quote,
+            role: FeeRole::Taker,
+        }
+    }
+}
+
 /// Fee manager for all exchanges.
 #[derive(Debug, Default)]
 pub struct FeeManager {
-    /// Base fee config per exchange.
+    /// Account fee config per exchange (defaults, VIP tier or API rates).
     exchange_fees: HashMap<Exchange, FeeConfig>,
     /// Per-asset withdrawal fees: (exchange, asset) -> fee.
     withdrawal_fees: HashMap<(Exchange, String), AssetWithdrawalFee>,
+    /// Live per-network status and fees: (exchange, asset) -> networks.
+    network_fees: HashMap<(Exchange, String), Vec<NetworkWithdrawalFee>>,
+    /// Published fee schedules (VIP tiers, fee token).
+    schedules: HashMap<Exchange, FeeSchedule>,
+    /// Exchanges where fees are paid with the fee token.
+    fee_token_enabled: HashMap<Exchange, bool>,
+    /// Per-market fee overrides.
+    market_fees: HashMap<MarketFeeKey, FeeConfig>,
 }
 
 impl FeeManager {
@@ -120,12 +294,24 @@ impl FeeManager {
         for exchange in exchanges {
             self.exchange_fees
                 .insert(exchange, FeeConfig::default_for_exchange(exchange));
+            if let Some(schedule) = FeeSchedule::default_for_exchange(exchange) {
+                self.schedules.insert(exchange, schedule);
+            }
         }
 
+        self.set_default_market_fees();
+
         // Initialize some common withdrawal fees (can be updated via API)
         self.set_default_withdrawal_fees();
     }
 
+    /// Set known per-market fee differences.
+    fn set_default_market_fees(&mut self) {
+        // Upbit BTC and USDT markets charge more than the KRW market
+        self.set_market_fees(Exchange::Upbit, None, Some("BTC"), FeeConfig::new(25));
+        self.set_market_fees(Exchange::Upbit, None, Some("USDT"), FeeConfig::new(25));
+    }
+
     /// Set common default withdrawal fees.
     fn set_default_withdrawal_fees(&mut self) {
         // Binance BTC withdrawal
@@ -181,6 +367,125 @@ impl FeeManager {
             .unwrap_or_else(|| FeeConfig::default_for_exchange(exchange))
     }
 
+    /// Set the published fee schedule for an exchange.
+    pub fn set_fee_schedule(&mut self, exchange: Exchange, schedule: FeeSchedule) {
+        self.schedules.insert(exchange, schedule);
+    }
+
+    /// Get the fee schedule for an exchange.
+    pub fn fee_schedule(&self, exchange: Exchange) -> Option<&FeeSchedule> {
+        self.schedules.get(&exchange)
+    }
+
+    /// Use the account's VIP tier rates from the exchange's fee schedule.
+    /// Returns false if the tier is not in the schedule.
+    pub fn set_vip_tier(&mut self, exchange: Exchange, level: u8) -> bool {
+        let Some(tier) = self.schedules.get(&exchange).and_then(|s| s.tier(level)) else {
+            return false;
+        };
+        let fees = self.get_trading_fees(exchange);
+        self.exchange_fees.insert(
+            exchange,
+            FeeConfig {
+                maker_fee_bps: tier.maker_fee_bps,
+                taker_fee_bps: tier.taker_fee_bps,
+                ..fees
+            },
+        );
+        true
+    }
+
+    /// Enable or disable paying fees with the exchange's fee token.
+    pub fn set_fee_token_enabled(&mut self, exchange: Exchange, enabled: bool) {
+        self.fee_token_enabled.insert(exchange, enabled);
+    }
+
+    /// Fee-token discount in percent currently applied on an exchange.
+    pub fn fee_token_discount_pct(&self, exchange: Exchange) -> u8 {
+        if !self
+            .fee_token_enabled
+            .get(&exchange)
+            .copied()
+            .unwrap_or(false)
+        {
+            return 0;
+        }
+        self.schedules
+            .get(&exchange)
+            .and_then(|s| s.fee_token.as_ref())
+            .map(|t| t.discount_pct.min(100))
+            .unwrap_or(0)
+    }
+
+    /// Override trading fees for a market. `None` matches any base or quote,
+    /// e.g. `(None, Some("BTC"))` covers every BTC-quoted market.
+    pub fn set_market_fees(
+        &mut self,
+        exchange: Exchange,
+        base: Option<&str>,
+        quote: Option<&str>,
+        fees: FeeConfig,
+    ) {
+        let key = (
+            exchange,
+            base.map(str::to_uppercase),
+            quote.map(str::to_uppercase),
+        );
+        self.market_fees.insert(key, fees);
+    }
+
+    /// Remove all per-market overrides for an exchange.
+    pub fn clear_market_fees(&mut self, exchange: Exchange) {
+        self.market_fees.retain(|(ex, _, _), _| *ex != exchange);
+    }
+
+    /// Resolve trading fees for a market: exact market override, then quote-wide,
+    /// then base-wide, then the account fees.
+    pub fn get_market_trading_fees(
+        &self,
+        exchange: Exchange,
+        base: &str,
+        quote: Option<&str>,
+    ) -> FeeConfig {
+        let base = base.to_uppercase();
+        let quote = quote.map(str::to_uppercase);
+
+        let mut candidates = Vec::with_capacity(3);
+        if let Some(quote) = &quote {
+            candidates.push((exchange, Some(base.clone()), Some(quote.clone())));
+            candidates.push((exchange, None, Some(quote.clone())));
+        }
+        candidates.push((exchange, Some(base), None));
+
+        candidates
+            .iter()
+            .find_map(|key| self.market_fees.get(key).copied())
+            .unwrap_or_else(|| self.get_trading_fees(exchange))
+    }
+
+    /// Effective fee in basis points for a trade leg, including the fee-token discount.
+    /// Discounted fees are rounded up; rebates are reported as 0.
+    pub fn trading_fee_bps(
+        &self,
+        exchange: Exchange,
+        base: &str,
+        quote: Option<&str>,
+        role: FeeRole,
+    ) -> u32 {
+        let fees = self.get_market_trading_fees(exchange, base, quote);
+        let fee_bps = match role {
+            FeeRole::Maker => fees.maker_fee_bps,
+            FeeRole::Taker => fees.taker_fee_bps,
+        };
+        if fee_bps <= 0 {
+            return 0;
+        }
+
+        let discount_pct = self.fee_token_discount_pct(exchange) as u32;
+        let fee_bps = fee_bps as u32;
+        (fee_bps * (100 - discount_pct)).div_ceil(100)
+    }
+
     /// Get withdrawal fee for a specific asset on an exchange.
     pub fn get_withdrawal_fee(
         &self,
@@ -198,6 +503,13 @@ impl FeeManager {
             .unwrap_or(0)
     }
 
+    /// Take over withdrawal and network fees (refreshed from exchange APIs) from
+    /// another manager, e.g. when trading fee tables are rebuilt from settings.
+    pub fn carry_over_transfer_fees(&mut self, previous: FeeManager) {
+        self.withdrawal_fees = previous.withdrawal_fees;
+        self.network_fees = previous.network_fees;
+    }
+
     /// Update trading fees for an exchange.
     pub fn update_trading_fees(&mut self, exchange: Exchange, fees: FeeConfig) {
         self.exchange_fees.insert(exchange, fees);
@@ -223,14 +535,95 @@ impl FeeManager {
         );
     }
 
+    /// Replace the live network fees of an exchange (from a wallet status refresh).
+    pub fn update_network_fees(
+        &mut self,
+        exchange: Exchange,
+        assets: impl IntoIterator<Item = (String, Vec<NetworkWithdrawalFee>)>,
+    ) {
+        self.network_fees.retain(|(ex, _), _| *ex != exchange);
+        for (asset, networks) in assets {
+            self.network_fees.insert((exchange, asset), networks);
+        }
+    }
+
+    /// Get live network fees for an asset on an exchange.
+    pub fn get_network_fees(&self, exchange: Exchange, asset: &str) -> &[NetworkWithdrawalFee] {
+        self.network_fees
+            .get(&(exchange, asset.to_string()))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Networks open for moving an asset from `from` to `to`: withdrawals open
+    /// on `from`, deposits open on `to`. Without network data for `to`, only
+    /// the withdrawal side is checked.
+    fn open_networks<'a>(
+        &'a self,
+        from: Exchange,
+        to: Exchange,
+        asset: &str,
+    ) -> impl Iterator<Item = &'a NetworkWithdrawalFee> {
+        let deposit_networks = self.get_network_fees(to, asset);
+        self.get_network_fees(from, asset)
+            .iter()
+            .filter(|n| n.withdraw_enabled)
+            .filter(move |n| {
+                deposit_networks.is_empty()
+                    || deposit_networks
+                        .iter()
+                        .any(|d| d.deposit_enabled && d.network == n.network)
+            })
+    }
+
+    /// Cheapest open network with a known fee; on equal fees the one with the
+    /// lower minimum withdrawal.
+    pub fn cheapest_withdrawal_network(
+        &self,
+        from: Exchange,
+        to: Exchange,
+        asset: &str,
+    ) -> Option<&NetworkWithdrawalFee> {
+        self.open_networks(from, to, asset)
+            .filter(|n| n.fee.is_some())
+            .min_by_key(|n| (n.fee, n.min_withdrawal))
+    }
+
+    /// Withdrawal cost of moving an asset between exchanges: the cheapest open
+    /// network if live data is available, otherwise the per-asset default.
+    /// Live data with no open network gives `NoOpenNetwork` rather than the
+    /// default fee, so a closed route is never priced as transferable.
+    pub fn transfer_fee(&self, from: Exchange, to: Exchange, asset: &str) -> TransferFee {
+        if let Some(network) = self.cheapest_withdrawal_network(from, to, asset) {
+            return TransferFee::Open {
+                fee: network.fee.unwrap_or(0),
+                min_withdrawal: network.min_withdrawal,
+            };
+        }
+
+        // Open networks without a reported fee use the per-asset default
+        let open_min = self
+            .open_networks(from, to, asset)
+            .map(|n| n.min_withdrawal)
+            .min();
+        if open_min.is_none() && !self.get_network_fees(from, asset).is_empty() {
+            return TransferFee::NoOpenNetwork;
+        }
+        let default = self.get_withdrawal_fee(from, asset);
+        TransferFee::Open {
+            fee: default.map_or(0, |f| f.fee),
+            min_withdrawal: open_min.or(default.map(|f| f.min_withdrawal)).unwrap_or(0),
+        }
+    }
+
     /// Get fees for an arbitrage pair (buy exchange -> sell exchange).
-    /// Returns (buy_fee_bps, sell_fee_bps, withdrawal_fee).
+    /// Returns (buy_fee_bps, sell_fee_bps, transfer_fee).
     pub fn get_arbitrage_fees(
         &self,
         buy_exchange: Exchange,
         sell_exchange: Exchange,
         asset: &str,
-    ) -> (u32, u32, u64) {
+    ) -> (u32, u32, TransferFee) {
         let buy_fees = self.get_trading_fees(buy_exchange);
         let sell_fees = self.get_trading_fees(sell_exchange);
 
@@ -238,8 +631,24 @@ impl FeeManager {
         let buy_fee = buy_fees.taker_fee_bps.max(0) as u32;
         let sell_fee = sell_fees.taker_fee_bps.max(0) as u32;
 
-        // Get withdrawal fee from buy exchange (where we buy, then withdraw)
-        let withdrawal_fee = self.get_withdrawal_fee_amount(buy_exchange, asset);
+        // Withdrawal fee from buy exchange (where we buy, then withdraw) on the cheapest open network
+        let withdrawal_fee = self.transfer_fee(buy_exchange, sell_exchange, asset);
+
+        (buy_fee, sell_fee, withdrawal_fee)
+    }
+
+    /// Get fees for an arbitrage pair with market-specific rates, fee-token
+    /// discounts and the order role of each leg.
+    /// Returns (buy_fee_bps, sell_fee_bps, transfer_fee).
+    pub fn get_arbitrage_fees_for_legs(
+        &self,
+        buy: FeeLeg<'_>,
+        sell: FeeLeg<'_>,
+        asset: &str,
+    ) -> (u32, u32, TransferFee) {
+        let buy_fee = self.trading_fee_bps(buy.exchange, asset, buy.quote, buy.role);
+        let sell_fee = self.trading_fee_bps(sell.exchange, asset, sell.quote, sell.role);
+        let withdrawal_fee = self.transfer_fee(buy.exchange, sell.exchange, asset);
 
         (buy_fee, sell_fee, withdrawal_fee)
     }
@@ -301,7 +710,7 @@ mod tests {
 
         assert_eq!(buy_fee, 10); // Binance taker
         assert_eq!(sell_fee, 5); // Upbit taker
-        assert!(withdrawal > 0); // Binance BTC withdrawal
+        assert!(withdrawal.fee().unwrap() > 0); // Binance BTC withdrawal
     }
 
     #[test]
@@ -323,4 +732,283 @@ mod tests {
         let sol_fee = manager.get_withdrawal_fee(Exchange::Binance, "SOL");
         assert!(sol_fee.is_some());
     }
+
+    #[test]
+    fn test_vip_tier_rates() {
+        let mut manager = FeeManager::new();
+
+        assert!(manager.set_vip_tier(Exchange::Binance, 3));
+        let fees = manager.get_trading_fees(Exchange::Binance);
+        assert_eq!((fees.maker_fee_bps, fees.taker_fee_bps), (5, 6));
+
+        // Unknown tier or exchange without schedule leaves fees unchanged
+        assert!(!manager.set_vip_tier(Exchange::Binance, 42));
+        assert!(!manager.set_vip_tier(Exchange::Upbit, 1));
+        assert_eq!(manager.get_trading_fees(Exchange::Upbit).taker_fee_bps, 5);
+    }
+
+    #[test]
+    fn test_fee_token_discount() {
+        let mut manager = FeeManager::new();
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Binance, "ETH", Some("USDT"), FeeRole::Taker),
+            10
+        );
+
+        manager.set_fee_token_enabled(Exchange::Binance, true);
+        assert_eq!(manager.fee_token_discount_pct(Exchange::Binance), 25);
+        // 10 bps * 75% = 7.5 bps, rounded up
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Binance, "ETH", Some("USDT"), FeeRole::Taker),
+            8
+        );
+
+        // No fee token on Upbit
+        manager.set_fee_token_enabled(Exchange::Upbit, true);
+        assert_eq!(manager.fee_token_discount_pct(Exchange::Upbit), 0);
+    }
+
+    #[test]
+    fn test_market_overrides() {
+        let mut manager = FeeManager::new();
+
+        // Upbit BTC market vs KRW market
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Upbit, "ETH", Some("BTC"), FeeRole::Taker),
+            25
+        );
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Upbit, "ETH", Some("KRW"), FeeRole::Taker),
+            5
+        );
+
+        // Promotions come from the account fee sync, not defaults
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Binance, "BTC", Some("FDUSD"), FeeRole::Taker),
+            10
+        );
+        manager.set_market_fees(
+            Exchange::Binance,
+            Some("BTC"),
+            Some("FDUSD"),
+            FeeConfig::new(0),
+        );
+        // Case-insensitive
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Binance, "btc", Some("fdusd"), FeeRole::Taker),
+            0
+        );
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Binance, "ETH", Some("FDUSD"), FeeRole::Taker),
+            10
+        );
+
+        // Exact market beats quote-wide override
+        manager.set_market_fees(
+            Exchange::Upbit,
+            Some("SOL"),
+            Some("BTC"),
+            FeeConfig::new(12),
+        );
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Upbit, "SOL", Some("BTC"), FeeRole::Taker),
+            12
+        );
+
+        manager.clear_market_fees(Exchange::Upbit);
+        assert_eq!(
+            manager.trading_fee_bps(Exchange::Upbit, "ETH", Some("BTC"), FeeRole::Taker),
+            5
+        );
+    }
+
+    #[test]
+    fn test_arbitrage_fees_for_legs_maker_taker() {
+        let manager = FeeManager::new();
+
+        let buy = FeeLeg {
+            exchange: Exchange::Coinbase,
+            quote: Some("USD"),
+            role: FeeRole::Maker,
+        };
+        let sell = FeeLeg::taker(Exchange::Upbit, Some("KRW"));
+        let (buy_fee, sell_fee, withdrawal) = manager.get_arbitrage_fees_for_legs(buy, sell, "BTC");
+
+        assert_eq!(buy_fee, 40); // Coinbase maker
+        assert_eq!(sell_fee, 5); // Upbit KRW taker
+
+        // No Coinbase BTC withdrawal fee configured
+        assert_eq!(
+            withdrawal,
+            TransferFee::Open {
+                fee: 0,
+                min_withdrawal: 0
+            }
+        );
+    }
+
+    fn network(
+        name: &str,
+        fee: Option<f64>,
+        withdraw: bool,
+        deposit: bool,
+    ) -> NetworkWithdrawalFee {
+        NetworkWithdrawalFee {
+            network: name.to_string(),
+            fee: fee.map(|f| FixedPoint::from_f64(f).0),
+            min_withdrawal: 0,
+            withdraw_enabled: withdraw,
+            deposit_enabled: deposit,
+        }
+    }
+
+    #[test]
+    fn test_cheapest_network_open_on_both_sides() {
+        let mut manager = FeeManager::new();
+        manager.update_network_fees(
+            Exchange::Binance,
+            vec![(
+                "USDT".to_string(),
+                vec![
+                    network("ETH", Some(3.0), true, true),
+                    network("TRX", Some(1.0), true, true),
+                    network("SOL", Some(0.5), false, true), // withdrawals suspended
+                    network("APT", Some(0.1), true, true),  // not supported on Upbit
+                ],
+            )],
+        );
+        manager.update_network_fees(
+            Exchange::Upbit,
+            vec![(
+                "USDT".to_string(),
+                vec![
+                    network("ETH", None, true, true),
+                    network("TRX", None, true, false), // deposits suspended
+                    network("SOL", None, true, true),
+                ],
+            )],
+        );
+
+        let best = manager
+            .cheapest_withdrawal_network(Exchange::Binance, Exchange::Upbit, "USDT")
+            .unwrap();
+        assert_eq!(best.network, "ETH");
+
+        let (_, _, withdrawal) =
+            manager.get_arbitrage_fees(Exchange::Binance, Exchange::Upbit, "USDT");
+        assert_eq!(withdrawal.fee(), Some(FixedPoint::from_f64(3.0).0));
+
+        // Unknown destination: cheapest withdrawable network
+        assert_eq!(
+            manager
+                .transfer_fee(Exchange::Binance, Exchange::Bybit, "USDT")
+                .fee(),
+            Some(FixedPoint::from_f64(0.1).0)
+        );
+    }
+
+    #[test]
+    fn test_network_fees_fall_back_to_default_and_replace() {
+        let mut manager = FeeManager::new();
+        let default_fee = manager.get_withdrawal_fee_amount(Exchange::Binance, "BTC");
+
+        // No live fee reported
+        manager.update_network_fees(
+            Exchange::Binance,
+            vec![("BTC".to_string(), vec![network("BTC", None, true, true)])],
+        );
+        assert_eq!(
+            manager
+                .transfer_fee(Exchange::Binance, Exchange::Upbit, "BTC")
+                .fee(),
+            Some(default_fee)
+        );
+
+        manager.update_network_fees(
+            Exchange::Binance,
+            vec![(
+                "BTC".to_string(),
+                vec![network("BTC", Some(0.00002), true, true)],
+            )],
+        );
+        assert_eq!(
+            manager
+                .transfer_fee(Exchange::Binance, Exchange::Upbit, "BTC")
+                .fee(),
+            Some(FixedPoint::from_f64(0.00002).0)
+        );
+
+        // A refresh replaces all assets of the exchange
+        manager.update_network_fees(Exchange::Binance, Vec::new());
+        assert!(manager
+            .get_network_fees(Exchange::Binance, "BTC")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_closed_routes_are_not_transferable() {
+        let mut manager = FeeManager::new();
+        manager.update_network_fees(
+            Exchange::Binance,
+            vec![(
+                "BTC".to_string(),
+                vec![
+                    network("BTC", Some(0.0001), false, true), // withdrawals suspended
+                    network("LIGHTNING", Some(0.00001), true, true),
+                ],
+            )],
+        );
+        manager.update_network_fees(
+            Exchange::Upbit,
+            vec![("BTC".to_string(), vec![network("BTC", None, true, true)])],
+        );
+
+        // Live data, nothing open on both sides: no default-fee fallback
+        assert_eq!(
+            manager.transfer_fee(Exchange::Binance, Exchange::Upbit, "BTC"),
+            TransferFee::NoOpenNetwork
+        );
+        let (_, _, transfer) =
+            manager.get_arbitrage_fees(Exchange::Binance, Exchange::Upbit, "BTC");
+        assert_eq!(transfer.fee(), None);
+
+        // No live data for the source: per-asset default
+        let default = manager.get_withdrawal_fee(Exchange::Upbit, "ETH").unwrap();
+        assert_eq!(
+            manager.transfer_fee(Exchange::Upbit, Exchange::Binance, "ETH"),
+            TransferFee::Open {
+                fee: default.fee,
+                min_withdrawal: default.min_withdrawal
+            }
+        );
+    }
+
+    #[test]
+    fn test_transfer_fee_reports_minimum_withdrawal() {
+        let with_min = |name: &str, fee: f64, min: f64| NetworkWithdrawalFee {
+            min_withdrawal: FixedPoint::from_f64(min).0,
+            ..network(name, Some(fee), true, true)
+        };
+        let mut manager = FeeManager::new();
+        manager.update_network_fees(
+            Exchange::Binance,
+            vec![(
+                "USDT".to_string(),
+                vec![
+                    with_min("ETH", 1.0, 50.0),
+                    with_min("TRX", 1.0, 10.0),
+                    with_min("SOL", 2.0, 1.0),
+                ],
+            )],
+        );
+
+        // Equal fees: the lower minimum wins
+        assert_eq!(
+            manager.transfer_fee(Exchange::Binance, Exchange::Upbit, "USDT"),
+            TransferFee::Open {
+                fee: FixedPoint::from_f64(1.0).0,
+                min_withdrawal: FixedPoint::from_f64(10.0).0
+            }
+        );
+    }
 }
@@ -0,0 +1,323 @@
+//! Per-chain gas price oracle and gas costing of route steps.
+//!
+//! EVM chains are sampled from fee history (the next block's base fee plus a
+//! priority fee); Solana from recent prioritization fees per compute unit on top
+//! of the fixed signature fee. Route steps that send a transaction (DEX swaps,
+//! withdrawals, bridge transfers) are priced in the chain's native token and
+//! converted to USD with the native token's price. L1 data fees of rollups are
+//! not modelled.
+
+use arbitrage_core::{Chain, RouteStep};
+use dashmap::DashMap;
+use std::collections::VecDeque;
+
+/// Solana base fee per signature (lamports).
+pub const LAMPORTS_PER_SIGNATURE: u128 = 5_000;
+
+/// Gas price on one chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasPrice {
+    /// EVM: base fee per gas (wei). Solana: fee per signature (lamports).
+    pub base_fee: u128,
+    /// EVM: priority fee per gas (wei). Solana: micro-lamports per compute unit.
+    pub priority_fee: u128,
+}
+
+impl GasPrice {
+    /// Cost in native tokens of a transaction using `units` gas (compute units on Solana).
+    pub fn cost_native(&self, chain: Chain, units: u64) -> f64 {
+        let smallest_units = if chain == Chain::Solana {
+            self.base_fee as f64 + self.priority_fee as f64 * units as f64 / 1e6
+        } else {
+            (self.base_fee + self.priority_fee) as f64 * units as f64
+        };
+        smallest_units / 10f64.powi(chain.native_decimals() as i32)
+    }
+}
+
+/// Typical gas prices, used until a chain has live samples.
+/// None for chains we do not price (Cosmos).
+pub fn default_gas_price(chain: Chain) -> Option<GasPrice> {
+    const GWEI: u128 = 1_000_000_000;
+    let (base_fee, priority_fee) = match chain {
+        Chain::Ethereum => (10 * GWEI, GWEI),
+        Chain::Arbitrum => (GWEI / 100, 0),
+        Chain::Optimism | Chain::Base => (GWEI / 200, GWEI / 1000),
+        Chain::Polygon => (30 * GWEI, 30 * GWEI),
+        Chain::Avalanche => (2 * GWEI, GWEI / 2),
+        Chain::Bsc => (GWEI, 0),
+        Chain::Solana => (LAMPORTS_PER_SIGNATURE, 10_000),
+        Chain::Cosmos | Chain::Osmosis => return None,
+    };
+    Some(GasPrice {
+        base_fee,
+        priority_fee,
+    })
+}
+
+/// Gas (compute units on Solana) used by each kind of route step.
+#[derive(Debug, Clone, Copy)]
+pub struct StepGasUnits {
+    /// Single-hop DEX swap.
+    pub swap: u64,
+    /// Token transfer (the on-chain leg of a withdrawal).
+    pub transfer: u64,
+    /// Bridge deposit on the source chain.
+    pub bridge: u64,
+}
+
+/// Gas oracle configuration.
+#[derive(Debug, Clone)]
+pub struct GasOracleConfig {
+    /// Fee samples kept per chain.
+    pub history_len: usize,
+    /// Percentile of recent priority fees to pay (0-100).
+    pub priority_percentile: f64,
+    /// Samples older than this are ignored (ms).
+    pub max_age_ms: u64,
+    /// Gas per step on EVM chains.
+    pub evm_units: StepGasUnits,
+    /// Compute units per step on Solana.
+    pub solana_units: StepGasUnits,
+}
+
+impl Default for GasOracleConfig {
+    fn default() -> Self {
+        Self {
+            history_len: 20,
+            priority_percentile: 50.0,
+            max_age_ms: 300_000,
+            evm_units: StepGasUnits {
+                swap: 150_000,
+                transfer: 65_000,
+                bridge: 250_000,
+            },
+            solana_units: StepGasUnits {
+                swap: 200_000,
+                transfer: 30_000,
+                bridge: 400_000,
+            },
+        }
+    }
+}
+
+/// One fee observation.
+#[derive(Debug, Clone, Copy)]
+struct FeeSample {
+    timestamp_ms: u64,
+    base_fee: u128,
+    priority_fee: u128,
+}
+
+/// Gas cost of a route.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RouteGasCost {
+    /// Total cost in USD.
+    pub usd: f64,
+    /// Steps that send a transaction and were priced.
+    pub priced_steps: u8,
+    /// Steps that send a transaction but had no gas or native token price.
+    pub unpriced_steps: u8,
+}
+
+/// Rolling fee samples per chain (lock-free via DashMap).
+#[derive(Debug, Default)]
+pub struct GasOracle {
+    config: GasOracleConfig,
+    samples: DashMap<Chain, VecDeque<FeeSample>>,
+}
+
+impl GasOracle {
+    /// Create an oracle with the given configuration.
+    pub fn new(config: GasOracleConfig) -> Self {
+        Self {
+            config,
+            samples: DashMap::new(),
+        }
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &GasOracleConfig {
+        &self.config
+    }
+
+    /// Record a fee observation (units as in [`GasPrice`]).
+    pub fn record(&self, chain: Chain, base_fee: u128, priority_fee: u128, timestamp_ms: u64) {
+        let mut samples = self.samples.entry(chain).or_default();
+        if samples
+            .back()
+            .is_some_and(|last| timestamp_ms < last.timestamp_ms)
+        {
+            return;
+        }
+        samples.push_back(FeeSample {
+            timestamp_ms,
+            base_fee,
+            priority_fee,
+        });
+        while samples.len() > self.config.history_len.max(1) {
+            samples.pop_front();
+        }
+    }
+
+    /// Live gas price: the latest base fee and the configured percentile of
+    /// recent priority fees. None if the chain has no fresh samples.
+    pub fn gas_price(&self, chain: Chain, now_ms: u64) -> Option<GasPrice> {
+        let samples = self.samples.get(&chain)?;
+        let cutoff = now_ms.saturating_sub(self.config.max_age_ms);
+        let fresh: Vec<&FeeSample> = samples
+            .iter()
+            .filter(|s| s.timestamp_ms >= cutoff)
+            .collect();
+        let latest = fresh.last()?;
+
+        let mut priority: Vec<u128> = fresh.iter().map(|s| s.priority_fee).collect();
+        priority.sort_unstable();
+        let rank =
+            self.config.priority_percentile.clamp(0.0, 100.0) / 100.0 * (priority.len() - 1) as f64;
+        Some(GasPrice {
+            base_fee: latest.base_fee,
+            priority_fee: priority[rank.round() as usize],
+        })
+    }
+
+    /// Live gas price, falling back to [`default_gas_price`].
+    pub fn gas_price_or_default(&self, chain: Chain, now_ms: u64) -> Option<GasPrice> {
+        self.gas_price(chain, now_ms)
+            .or_else(|| default_gas_price(chain))
+    }
+
+    /// Chain and gas units of a step that sends a transaction.
+    /// CEX trades and deposits (the receiving side of a withdrawal) cost no gas.
+    pub fn step_units(&self, step: &RouteStep) -> Option<(Chain, u64)> {
+        let units = |chain: Chain| {
+            if chain == Chain::Solana {
+                self.config.solana_units
+            } else {
+                self.config.evm_units
+            }
+        };
+        match *step {
+            RouteStep::Trade { exchange, .. } if exchange.exchange_type().is_dex() => {
+                let chain = exchange.chain()?;
+                Some((chain, units(chain).swap))
+            }
+            RouteStep::Withdraw { chain, .. } => Some((chain, units(chain).transfer)),
+            RouteStep::Bridge { source_chain, .. } => {
+                Some((source_chain, units(source_chain).bridge))
+            }
+            RouteStep::Trade { .. } | RouteStep::Deposit { .. } => None,
+        }
+    }
+
+    /// Gas cost of a route in USD, given native token prices in USD.
+    pub fn route_cost(
+        &self,
+        route: &[RouteStep],
+        native_usd: impl Fn(Chain) -> Option<f64>,
+        now_ms: u64,
+    ) -> RouteGasCost {
+        let mut cost = RouteGasCost::default();
+        for (chain, units) in route.iter().filter_map(|step| self.step_units(step)) {
+            let usd = self
+                .gas_price_or_default(chain, now_ms)
+                .zip(native_usd(chain).filter(|price| *price > 0.0))
+                .map(|(gas, price)| gas.cost_native(chain, units) * price);
+            match usd {
+                Some(usd) => {
+                    cost.usd += usd;
+                    cost.priced_steps += 1;
+                }
+                None => cost.unpriced_steps += 1,
+            }
+        }
+        cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrage_core::{Exchange, FixedPoint, TradeSide};
+
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn test_gas_price_percentile_and_staleness() {
+        let oracle = GasOracle::default();
+        assert_eq!(oracle.gas_price(Chain::Ethereum, 0), None);
+
+        for (i, priority) in [1, 5, 2, 3, 4].into_iter().enumerate() {
+            oracle.record(
+                Chain::Ethereum,
+                (20 + i as u128) * GWEI,
+                priority * GWEI,
+                1_000 * i as u64,
+            );
+        }
+        let price = oracle.gas_price(Chain::Ethereum, 5_000).unwrap();
+        assert_eq!(price.base_fee, 24 * GWEI);
+        assert_eq!(price.priority_fee, 3 * GWEI);
+
+        // All samples expired: fall back to defaults
+        assert_eq!(oracle.gas_price(Chain::Ethereum, 1_000_000), None);
+        assert_eq!(
+            oracle.gas_price_or_default(Chain::Ethereum, 1_000_000),
+            default_gas_price(Chain::Ethereum)
+        );
+        assert_eq!(default_gas_price(Chain::Cosmos), None);
+    }
+
+    #[test]
+    fn test_cost_native() {
+        // 150k gas at 20 + 2 gwei = 0.0033 ETH
+        let evm = GasPrice {
+            base_fee: 20 * GWEI,
+            priority_fee: 2 * GWEI,
+        };
+        assert!((evm.cost_native(Chain::Ethereum, 150_000) - 0.0033).abs() < 1e-12);
+
+        // 5000 lamports + 200k CU at 50k micro-lamports = 15000 lamports
+        let solana = GasPrice {
+            base_fee: LAMPORTS_PER_SIGNATURE,
+            priority_fee: 50_000,
+        };
+        assert!((solana.cost_native(Chain::Solana, 200_000) - 0.000015).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_route_cost_prices_dex_and_withdrawal_steps() {
+        let oracle = GasOracle::default();
+        oracle.record(Chain::Ethereum, 20 * GWEI, 2 * GWEI, 1_000);
+        let price = FixedPoint::from_f64(2_000.0);
+        let route = [
+            RouteStep::trade(Exchange::UniswapV3, 2, TradeSide::Buy, price, 50),
+            RouteStep::withdraw(Exchange::Binance, Chain::Ethereum),
+            RouteStep::deposit(Exchange::Coinbase, Chain::Ethereum),
+            RouteStep::trade(Exchange::Coinbase, 2, TradeSide::Sell, price, 50),
+        ];
+
+        // (150k + 65k) gas at 22 gwei = 0.00473 ETH = $9.46
+        let cost = oracle.route_cost(&route, |_| Some(2_000.0), 2_000);
+        assert!((cost.usd - 9.46).abs() < 1e-9);
+        assert_eq!((cost.priced_steps, cost.unpriced_steps), (2, 0));
+
+        // No native token price: the steps are counted but not priced
+        let cost = oracle.route_cost(&route, |_| None, 2_000);
+        assert_eq!(cost.usd, 0.0);
+        assert_eq!(cost.unpriced_steps, 2);
+
+        // CEX-only trades cost no gas
+        let cex = [RouteStep::trade(
+            Exchange::Binance,
+            2,
+            TradeSide::Buy,
+            price,
+            50,
+        )];
+        assert_eq!(
+            oracle.route_cost(&cex, |_| Some(2_000.0), 0),
+            RouteGasCost::default()
+        );
+    }
+}
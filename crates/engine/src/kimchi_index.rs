@@ -0,0 +1,239 @@
+//! Market-wide kimchi premium index.
+//!
+//! Aggregates the per-market kimchi premium (KRW price via forex vs overseas USD
+//! price) into a volume-weighted index per Korean exchange and combined, and flags
+//! markets that trade away from the index.
+
+use arbitrage_core::Exchange;
+use serde::{Deserialize, Serialize};
+
+/// Kimchi index configuration.
+#[derive(Debug, Clone)]
+pub struct KimchiIndexConfig {
+    /// Deviation from the exchange index (bps) at which a market is flagged.
+    pub outlier_threshold_bps: f64,
+    /// Minimum number of markets required to publish an index.
+    pub min_markets: usize,
+}
+
+impl Default for KimchiIndexConfig {
+    fn default() -> Self {
+        Self {
+            outlier_threshold_bps: 300.0,
+            min_markets: 3,
+        }
+    }
+}
+
+/// Kimchi premium of a single KRW market.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KimchiMarketPremium {
+    pub pair_id: u32,
+    pub symbol: String,
+    /// Korean exchange of the KRW market.
+    pub exchange: Exchange,
+    /// Premium over the overseas reference price (bps).
+    pub premium_bps: i32,
+    /// Index weight, usually 24h volume in USD (0 = unknown).
+    pub weight_usd: f64,
+}
+
+/// Index for a single Korean exchange.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KimchiExchangeIndex {
+    pub exchange: Exchange,
+    /// Weighted mean premium (bps).
+    pub index_bps: f64,
+    /// Number of markets in the index.
+    pub markets: u32,
+    /// Total weight (USD volume) behind the index.
+    pub weight_usd: f64,
+}
+
+/// Market trading away from its exchange index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KimchiIndexOutlier {
+    pub symbol: String,
+    pub exchange: Exchange,
+    pub premium_bps: i32,
+    /// Premium minus the exchange index (bps).
+    pub deviation_bps: f64,
+}
+
+/// Market-wide kimchi premium index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KimchiIndex {
+    /// Calculation time (ms since epoch).
+    pub timestamp_ms: u64,
+    /// Combined index across all Korean exchanges (bps).
+    pub index_bps: f64,
+    /// Number of markets in the combined index.
+    pub markets: u32,
+    /// Per-exchange indices.
+    pub exchanges: Vec<KimchiExchangeIndex>,
+    /// Markets deviating from their exchange index, largest deviation first.
+    pub outliers: Vec<KimchiIndexOutlier>,
+}
+
+/// Compute the kimchi index from per-market premiums.
+/// Returns None when fewer than `min_markets` markets are available.
+pub fn compute_kimchi_index(
+    markets: &[KimchiMarketPremium],
+    config: &KimchiIndexConfig,
+    timestamp_ms: u64,
+) -> Option<KimchiIndex> {
+    if markets.is_empty() || markets.len() < config.min_markets {
+        return None;
+    }
+
+    let (index_bps, _) = weighted_mean(markets.iter())?;
+
+    let mut korean_exchanges: Vec<Exchange> = markets.iter().map(|m| m.exchange).collect();
+    korean_exchanges.sort_by_key(|ex| *ex as u16);
+    korean_exchanges.dedup();
+
+    let mut exchanges = Vec::with_capacity(korean_exchanges.len());
+    let mut outliers = Vec::new();
+    for exchange in korean_exchanges {
+        let group = || markets.iter().filter(move |m| m.exchange == exchange);
+        let Some((exchange_index, weight_usd)) = weighted_mean(group()) else {
+            continue;
+        };
+        exchanges.push(KimchiExchangeIndex {
+            exchange,
+            index_bps: exchange_index,
+            markets: group().count() as u32,
+            weight_usd,
+        });
+
+        outliers.extend(group().filter_map(|m| {
+            let deviation_bps = m.premium_bps as f64 - exchange_index;
+            (deviation_bps.abs() >= config.outlier_threshold_bps).then(|| KimchiIndexOutlier {
+                symbol: m.symbol.clone(),
+                exchange,
+                premium_bps: m.premium_bps,
+                deviation_bps,
+            })
+        }));
+    }
+    outliers.sort_by(|a, b| b.deviation_bps.abs().total_cmp(&a.deviation_bps.abs()));
+
+    Some(KimchiIndex {
+        timestamp_ms,
+        index_bps,
+        markets: markets.len() as u32,
+        exchanges,
+        outliers,
+    })
+}
+
+/// Volume-weighted mean premium, returning (mean_bps, total_weight).
+/// Markets without volume data are ignored unless no market has any,
+/// in which case all markets are weighted equally.
+fn weighted_mean<'a>(
+    markets: impl Iterator<Item = &'a KimchiMarketPremium> + Clone,
+) -> Option<(f64, f64)> {
+    let known = |m: &&KimchiMarketPremium| m.weight_usd.is_finite() && m.weight_usd > 0.0;
+    let total_weight: f64 = markets.clone().filter(known).map(|m| m.weight_usd).sum();
+
+    if total_weight > 0.0 {
+        let sum: f64 = markets
+            .filter(known)
+            .map(|m| m.premium_bps as f64 * m.weight_usd)
+            .sum();
+        return Some((sum / total_weight, total_weight));
+    }
+
+    let (count, sum) = markets.fold((0usize, 0.0), |(n, s), m| (n + 1, s + m.premium_bps as f64));
+    (count > 0).then(|| (sum / count as f64, 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(
+        symbol: &str,
+        exchange: Exchange,
+        premium_bps: i32,
+        weight_usd: f64,
+    ) -> KimchiMarketPremium {
+        KimchiMarketPremium {
+            pair_id: 0,
+            symbol: symbol.to_string(),
+            exchange,
+            premium_bps,
+            weight_usd,
+        }
+    }
+
+    #[test]
+    fn test_index_is_volume_weighted_per_exchange_and_combined() {
+        let markets = vec![
+            market("BTC", Exchange::Upbit, 200, 3_000_000.0),
+            market("ETH", Exchange::Upbit, 100, 1_000_000.0),
+            market("BTC", Exchange::Bithumb, 300, 1_000_000.0),
+        ];
+        let index = compute_kimchi_index(&markets, &KimchiIndexConfig::default(), 42).unwrap();
+
+        assert_eq!(index.timestamp_ms, 42);
+        assert_eq!(index.markets, 3);
+        // (200*3 + 100*1 + 300*1) / 5
+        assert!((index.index_bps - 200.0).abs() < 1e-9);
+
+        let upbit = index
+            .exchanges
+            .iter()
+            .find(|e| e.exchange == Exchange::Upbit)
+            .unwrap();
+        assert!((upbit.index_bps - 175.0).abs() < 1e-9);
+        assert_eq!(upbit.markets, 2);
+        assert!((upbit.weight_usd - 4_000_000.0).abs() < 1e-9);
+
+        let bithumb = index
+            .exchanges
+            .iter()
+            .find(|e| e.exchange == Exchange::Bithumb)
+            .unwrap();
+        assert!((bithumb.index_bps - 300.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_index_without_volume_uses_equal_weights() {
+        let markets = vec![
+            market("BTC", Exchange::Upbit, 100, 0.0),
+            market("ETH", Exchange::Upbit, 200, 0.0),
+            market("XRP", Exchange::Upbit, 300, 0.0),
+        ];
+        let index = compute_kimchi_index(&markets, &KimchiIndexConfig::default(), 0).unwrap();
+        assert!((index.index_bps - 200.0).abs() < 1e-9);
+        assert_eq!(index.exchanges[0].weight_usd, 0.0);
+    }
+
+    #[test]
+    fn test_index_flags_outliers() {
+        let markets = vec![
+            market("BTC", Exchange::Upbit, 200, 1_000_000.0),
+            market("ETH", Exchange::Upbit, 210, 1_000_000.0),
+            market("XRP", Exchange::Upbit, 190, 1_000_000.0),
+            market("DOGE", Exchange::Upbit, 1200, 10_000.0),
+            market("SOL", Exchange::Upbit, -500, 10_000.0),
+        ];
+        let index = compute_kimchi_index(&markets, &KimchiIndexConfig::default(), 0).unwrap();
+
+        let symbols: Vec<&str> = index.outliers.iter().map(|o| o.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["DOGE", "SOL"]);
+        assert!(index.outliers[0].deviation_bps > 900.0);
+        assert!(index.outliers[1].deviation_bps < -600.0);
+    }
+
+    #[test]
+    fn test_index_requires_min_markets() {
+        let markets = vec![
+            market("BTC", Exchange::Upbit, 200, 1.0),
+            market("ETH", Exchange::Upbit, 200, 1.0),
+        ];
+        assert!(compute_kimchi_index(&markets, &KimchiIndexConfig::default(), 0).is_none());
+        assert!(compute_kimchi_index(&[], &KimchiIndexConfig::default(), 0).is_none());
+    }
+}
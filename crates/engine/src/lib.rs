@@ -3,16 +3,38 @@
 //! This crate contains the core logic for detecting arbitrage opportunities
 //! across multiple exchanges and calculating optimal routes.
 
+pub mod allocator;
+pub mod amm;
+pub mod bridge;
+pub mod confidence;
+pub mod depeg;
 pub mod depth;
 pub mod detector;
 pub mod fee;
+pub mod gas;
+pub mod kimchi_index;
+pub mod lifecycle;
 pub mod orderbook;
 pub mod premium;
+pub mod premium_history;
 pub mod route;
+pub mod synthetic;
+pub mod transfer_risk;
 
+pub use allocator::*;
+pub use amm::*;
+pub use bridge::*;
+pub use confidence::*;
+pub use depeg::*;
 pub use depth::*;
 pub use detector::*;
 pub use fee::*;
+pub use gas::*;
+pub use kimchi_index::*;
+pub use lifecycle::*;
 pub use orderbook::*;
 pub use premium::*;
+pub use premium_history::*;
 pub use route::*;
+pub use synthetic::*;
+pub use transfer_risk::*;
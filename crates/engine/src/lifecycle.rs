@@ -0,0 +1,474 @@
+//! Opportunity lifecycle tracking.
+//!
+//! The detector reports a fresh opportunity on every pass. This module follows each
+//! route (pair, buy exchange, sell exchange, quotes) across passes as an episode with
+//! a stable id, so a spread that stays open for 40 minutes is one opportunity with a
+//! first-seen time, a peak premium and, once it ends, a closing reason.
+
+use arbitrage_core::{ArbitrageOpportunity, Exchange, QuoteCurrency};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Mutex, PoisonError, RwLock};
+
+/// Episode key: (pair_id, buy exchange, sell exchange, buy quote, sell quote).
+pub type EpisodeKey = (u32, Exchange, Exchange, QuoteCurrency, QuoteCurrency);
+
+/// Why an episode ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseReason {
+    /// A detection pass for the pair no longer reported the route.
+    BelowThreshold,
+    /// The route was not observed within the stale timeout.
+    Stale,
+    /// One of the route's exchanges disconnected.
+    ExchangeDisconnected,
+    /// The tracker was shut down with the episode still open.
+    Shutdown,
+}
+
+impl CloseReason {
+    /// Stable name for storage.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CloseReason::BelowThreshold => "below_threshold",
+            CloseReason::Stale => "stale",
+            CloseReason::ExchangeDisconnected => "exchange_disconnected",
+            CloseReason::Shutdown => "shutdown",
+        }
+    }
+}
+
+/// One continuous period during which a route was reported as an opportunity.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpportunityEpisode {
+    /// Stable id, reused as the opportunity id on every pass.
+    pub id: u64,
+    pub pair_id: u32,
+    pub symbol: String,
+    pub buy_exchange: Exchange,
+    pub sell_exchange: Exchange,
+    pub buy_quote: QuoteCurrency,
+    pub sell_quote: QuoteCurrency,
+    pub first_seen_ms: u64,
+    pub last_seen_ms: u64,
+    pub peak_premium_bps: i32,
+    pub peak_at_ms: u64,
+    pub last_premium_bps: i32,
+    /// Time between consecutive observations at or above the threshold.
+    pub time_above_threshold_ms: u64,
+    /// Detection passes that reported the route.
+    pub observations: u32,
+    pub closed_at_ms: Option<u64>,
+    pub close_reason: Option<CloseReason>,
+}
+
+impl OpportunityEpisode {
+    /// Time from first sighting until close (or the latest sighting while open).
+    pub fn duration_ms(&self) -> u64 {
+        self.closed_at_ms
+            .unwrap_or(self.last_seen_ms)
+            .saturating_sub(self.first_seen_ms)
+    }
+}
+
+/// Episode state change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", content = "episode", rename_all = "snake_case")]
+pub enum LifecycleEvent {
+    /// First sighting of the route.
+    Opened(OpportunityEpisode),
+    /// The route reached a new peak premium.
+    Updated(OpportunityEpisode),
+    /// The episode ended (see `close_reason`).
+    Closed(OpportunityEpisode),
+}
+
+impl LifecycleEvent {
+    /// Episode carried by the event.
+    pub fn episode(&self) -> &OpportunityEpisode {
+        match self {
+            LifecycleEvent::Opened(episode)
+            | LifecycleEvent::Updated(episode)
+            | LifecycleEvent::Closed(episode) => episode,
+        }
+    }
+}
+
+/// Lifecycle tracker configuration.
+#[derive(Debug, Clone)]
+pub struct LifecycleConfig {
+    /// Premium counted towards `time_above_threshold_ms` (bps).
+    pub threshold_bps: i32,
+    /// Open episodes not observed for this long are closed as stale (ms).
+    pub stale_after_ms: u64,
+    /// Closed episodes kept for `drain_closed` before the oldest are dropped.
+    pub max_closed_buffer: usize,
+}
+
+impl Default for LifecycleConfig {
+    fn default() -> Self {
+        Self {
+            threshold_bps: 30,
+            stale_after_ms: 30_000,
+            max_closed_buffer: 10_000,
+        }
+    }
+}
+
+/// Tracks opportunity episodes per route (lock-free per route via DashMap).
+#[derive(Debug, Default)]
+pub struct OpportunityTracker {
+    config: RwLock<LifecycleConfig>,
+    open: DashMap<EpisodeKey, OpportunityEpisode>,
+    closed: Mutex<VecDeque<OpportunityEpisode>>,
+}
+
+impl OpportunityTracker {
+    /// Create a tracker with the given configuration.
+    pub fn new(config: LifecycleConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            open: DashMap::new(),
+            closed: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> LifecycleConfig {
+        self.config
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the configuration; open episodes are judged by it from the next pass.
+    pub fn set_config(&self, config: LifecycleConfig) {
+        *self.config.write().unwrap_or_else(PoisonError::into_inner) = config;
+    }
+
+    /// Record one detection pass for a pair.
+    ///
+    /// Rewrites each opportunity's `id` and `discovered_at_ms` to its episode's id and
+    /// first-seen time, and closes open episodes of the pair that the pass no longer
+    /// reported.
+    pub fn observe(
+        &self,
+        pair_id: u32,
+        opportunities: &mut [ArbitrageOpportunity],
+        now_ms: u64,
+    ) -> Vec<LifecycleEvent> {
+        let mut events = Vec::new();
+        let mut seen = HashSet::with_capacity(opportunities.len());
+        let threshold_bps = self.config().threshold_bps;
+
+        for opp in opportunities.iter_mut() {
+            let key = (
+                pair_id,
+                opp.source_exchange,
+                opp.target_exchange,
+                opp.source_quote,
+                opp.target_quote,
+            );
+            let premium_bps = opp
+                .usdlike_premium
+                .map(|p| p.bps)
+                .unwrap_or(opp.premium_bps);
+            seen.insert(key);
+
+            let (id, first_seen_ms) = match self.open.entry(key) {
+                Entry::Occupied(mut entry) => {
+                    let episode = entry.get_mut();
+                    if episode.last_premium_bps >= threshold_bps {
+                        episode.time_above_threshold_ms +=
+                            now_ms.saturating_sub(episode.last_seen_ms);
+                    }
+                    episode.last_seen_ms = now_ms;
+                    episode.last_premium_bps = premium_bps;
+                    episode.observations += 1;
+                    if premium_bps > episode.peak_premium_bps {
+                        episode.peak_premium_bps = premium_bps;
+                        episode.peak_at_ms = now_ms;
+                        events.push(LifecycleEvent::Updated(episode.clone()));
+                    }
+                    (episode.id, episode.first_seen_ms)
+                }
+                Entry::Vacant(entry) => {
+                    let episode = OpportunityEpisode {
+                        id: opp.id,
+                        pair_id,
+                        symbol: opp.asset.symbol.to_string(),
+                        buy_exchange: opp.source_exchange,
+                        sell_exchange: opp.target_exchange,
+                        buy_quote: opp.source_quote,
+                        sell_quote: opp.target_quote,
+                        first_seen_ms: now_ms,
+                        last_seen_ms: now_ms,
+                        peak_premium_bps: premium_bps,
+                        peak_at_ms: now_ms,
+                        last_premium_bps: premium_bps,
+                        time_above_threshold_ms: 0,
+                        observations: 1,
+                        closed_at_ms: None,
+                        close_reason: None,
+                    };
+                    events.push(LifecycleEvent::Opened(episode.clone()));
+                    entry.insert(episode);
+                    (opp.id, now_ms)
+                }
+            };
+            opp.id = id;
+            opp.discovered_at_ms = first_seen_ms;
+        }
+
+        events.extend(self.close_where(
+            |key, _| key.0 == pair_id && !seen.contains(key),
+            CloseReason::BelowThreshold,
+            |_| now_ms,
+        ));
+        events
+    }
+
+    /// Close episodes not observed within the stale timeout, as of their last sighting.
+    pub fn expire(&self, now_ms: u64) -> Vec<LifecycleEvent> {
+        let stale_after_ms = self.config().stale_after_ms;
+        self.close_where(
+            |_, episode| now_ms.saturating_sub(episode.last_seen_ms) > stale_after_ms,
+            CloseReason::Stale,
+            |episode| episode.last_seen_ms,
+        )
+    }
+
+    /// Close all episodes with a leg on the exchange.
+    pub fn close_exchange(&self, exchange: Exchange, now_ms: u64) -> Vec<LifecycleEvent> {
+        self.close_where(
+            |key, _| key.1 == exchange || key.2 == exchange,
+            CloseReason::ExchangeDisconnected,
+            |_| now_ms,
+        )
+    }
+
+    /// Close every open episode.
+    pub fn close_all(&self, now_ms: u64) -> Vec<LifecycleEvent> {
+        self.close_where(|_, _| true, CloseReason::Shutdown, |_| now_ms)
+    }
+
+    /// Snapshot of open episodes.
+    pub fn open_episodes(&self) -> Vec<OpportunityEpisode> {
+        self.open
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
+
+    /// Number of open episodes.
+    pub fn open_count(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Take closed episodes queued since the last call (oldest first).
+    pub fn drain_closed(&self) -> Vec<OpportunityEpisode> {
+        self.closed
+            .lock()
+            .map(|mut closed| closed.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    /// Put drained episodes back in front of the queue (e.g., after a failed
+    /// write) so the next `drain_closed` returns them again, oldest first.
+    pub fn requeue_closed(&self, episodes: Vec<OpportunityEpisode>) {
+        if let Ok(mut buffer) = self.closed.lock() {
+            for episode in episodes.into_iter().rev() {
+                buffer.push_front(episode);
+            }
+            let excess = buffer.len().saturating_sub(self.config().max_closed_buffer);
+            buffer.drain(..excess);
+        }
+    }
+
+    /// Remove matching open episodes, mark them closed and queue them for `drain_closed`.
+    fn close_where(
+        &self,
+        matches: impl Fn(&EpisodeKey, &OpportunityEpisode) -> bool,
+        reason: CloseReason,
+        closed_at: impl Fn(&OpportunityEpisode) -> u64,
+    ) -> Vec<LifecycleEvent> {
+        let keys: Vec<EpisodeKey> = self
+            .open
+            .iter()
+            .filter(|entry| matches(entry.key(), entry.value()))
+            .map(|entry| *entry.key())
+            .collect();
+
+        let mut closed = Vec::with_capacity(keys.len());
+        for key in keys {
+            if let Some((_, mut episode)) = self.open.remove(&key) {
+                episode.closed_at_ms = Some(closed_at(&episode));
+                episode.close_reason = Some(reason);
+                closed.push(episode);
+            }
+        }
+
+        if !closed.is_empty() {
+            if let Ok(mut buffer) = self.closed.lock() {
+                buffer.extend(closed.iter().cloned());
+                let excess = buffer.len().saturating_sub(self.config().max_closed_buffer);
+                buffer.drain(..excess);
+            }
+        }
+        closed.into_iter().map(LifecycleEvent::Closed).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbitrage_core::{Asset, FixedPoint};
+
+    fn opportunity(
+        id: u64,
+        buy: Exchange,
+        sell: Exchange,
+        premium_bps: i32,
+    ) -> ArbitrageOpportunity {
+        let mut opp = ArbitrageOpportunity::new(
+            id,
+            buy,
+            sell,
+            Asset::btc(),
+            FixedPoint::from_f64(50_000.0),
+            FixedPoint::from_f64(50_500.0),
+        );
+        opp.premium_bps = premium_bps;
+        opp
+    }
+
+    #[test]
+    fn test_episode_keeps_identity_and_peak_until_closed() {
+        let tracker = OpportunityTracker::default();
+        let route =
+            |id, premium| vec![opportunity(id, Exchange::Binance, Exchange::Upbit, premium)];
+
+        let mut pass = route(10, 50);
+        let events = tracker.observe(1, &mut pass, 1_000);
+        assert!(matches!(events.as_slice(), [LifecycleEvent::Opened(_)]));
+
+        // Same route on later passes keeps the first id and first-seen time
+        let mut pass = route(11, 80);
+        let events = tracker.observe(1, &mut pass, 3_000);
+        assert_eq!((pass[0].id, pass[0].discovered_at_ms), (10, 1_000));
+        assert!(
+            matches!(events.as_slice(), [LifecycleEvent::Updated(e)] if e.peak_premium_bps == 80)
+        );
+
+        // Lower premium: no event, and 20 bps is below the 30 bps threshold
+        let mut pass = route(12, 20);
+        assert!(tracker.observe(1, &mut pass, 6_000).is_empty());
+        let mut pass = route(13, 40);
+        assert!(tracker.observe(1, &mut pass, 9_000).is_empty());
+
+        // Route missing from the pass: closed
+        let events = tracker.observe(1, &mut [], 10_000);
+        let [LifecycleEvent::Closed(episode)] = events.as_slice() else {
+            panic!("expected close, got {events:?}");
+        };
+        assert_eq!(episode.id, 10);
+        assert_eq!(episode.close_reason, Some(CloseReason::BelowThreshold));
+        assert_eq!((episode.peak_premium_bps, episode.peak_at_ms), (80, 3_000));
+        // 1s-3s and 3s-6s were above threshold; 6s-9s started at 20 bps
+        assert_eq!(episode.time_above_threshold_ms, 5_000);
+        assert_eq!(episode.observations, 4);
+        assert_eq!(episode.duration_ms(), 9_000);
+
+        assert_eq!(tracker.drain_closed().len(), 1);
+        assert!(tracker.drain_closed().is_empty());
+        assert_eq!(tracker.open_count(), 0);
+    }
+
+    #[test]
+    fn test_routes_close_independently() {
+        let tracker = OpportunityTracker::default();
+        let mut pass = vec![
+            opportunity(1, Exchange::Binance, Exchange::Upbit, 100),
+            opportunity(2, Exchange::Coinbase, Exchange::Upbit, 100),
+        ];
+        tracker.observe(1, &mut pass, 0);
+        let mut other_pair = vec![opportunity(3, Exchange::Binance, Exchange::Bithumb, 100)];
+        tracker.observe(2, &mut other_pair, 0);
+
+        // A pass for pair 1 does not touch pair 2
+        let mut pass = vec![opportunity(4, Exchange::Binance, Exchange::Upbit, 100)];
+        let events = tracker.observe(1, &mut pass, 1_000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].episode().id, 2);
+        assert_eq!(tracker.open_count(), 2);
+
+        let events = tracker.close_exchange(Exchange::Bithumb, 2_000);
+        assert_eq!(
+            events[0].episode().close_reason,
+            Some(CloseReason::ExchangeDisconnected)
+        );
+
+        // Stale episodes close as of their last sighting
+        let events = tracker.expire(1_000 + 30_001);
+        assert_eq!(events[0].episode().closed_at_ms, Some(1_000));
+        assert_eq!(events[0].episode().close_reason, Some(CloseReason::Stale));
+        assert_eq!(tracker.open_count(), 0);
+    }
+
+    #[test]
+    fn test_closed_buffer_is_bounded() {
+        let tracker = OpportunityTracker::new(LifecycleConfig {
+            max_closed_buffer: 2,
+            ..Default::default()
+        });
+        for pair_id in 0..3 {
+            let mut pass = vec![opportunity(
+                pair_id as u64,
+                Exchange::Binance,
+                Exchange::Upbit,
+                100,
+            )];
+            tracker.observe(pair_id, &mut pass, 0);
+        }
+        assert_eq!(tracker.close_all(1).len(), 3);
+        assert_eq!(tracker.drain_closed().len(), 2);
+    }
+
+    #[test]
+    fn test_requeued_episodes_drain_first() {
+        let tracker = OpportunityTracker::new(LifecycleConfig {
+            max_closed_buffer: 2,
+            ..Default::default()
+        });
+        let close_pair = |pair_id: u32, now_ms: u64| {
+            let mut pass = vec![opportunity(
+                pair_id as u64,
+                Exchange::Binance,
+                Exchange::Upbit,
+                100,
+            )];
+            tracker.observe(pair_id, &mut pass, now_ms);
+            tracker.close_all(now_ms + 1);
+        };
+
+        close_pair(1, 0);
+        let drained = tracker.drain_closed();
+        assert_eq!(drained.len(), 1);
+
+        // Closed while the failed write was in flight
+        close_pair(2, 10);
+        tracker.requeue_closed(drained);
+        let drained = tracker.drain_closed();
+        let ids: Vec<u32> = drained.iter().map(|e| e.pair_id).collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        // The bound still applies, dropping the oldest
+        tracker.requeue_closed(drained);
+        close_pair(3, 20);
+        let ids: Vec<u32> = tracker.drain_closed().iter().map(|e| e.pair_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+}
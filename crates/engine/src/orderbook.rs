@@ -6,13 +6,24 @@
 use std::cmp::Reverse;
 use std::collections::BTreeMap;
 
-use arbitrage_core::FixedPoint;
+use arbitrage_core::{Exchange, FixedPoint};
 
 /// Default maximum levels to store per side.
 pub const DEFAULT_MAX_LEVELS: usize = 20;
 
+/// Default levels to cache for an exchange, matching the depth its feed delivers.
+pub fn default_max_levels(exchange: Exchange) -> usize {
+    match exchange {
+        // orderbook.50 stream
+        Exchange::Bybit => 50,
+        // Full level2 book
+        Exchange::Coinbase => 100,
+        _ => DEFAULT_MAX_LEVELS,
+    }
+}
+
 /// Side of the orderbook.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Side {
     Bid,
     Ask,
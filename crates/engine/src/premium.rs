@@ -226,22 +226,16 @@ impl DenominatedPrices {
         rates: &ConversionRates,
         exchange: Exchange,
     ) -> Option<UsdlikePrice> {
-        // If usdlike is already set (even for KRW markets pre-converted to USDT), use it
-        if let Some(existing) = self.usdlike {
-            return Some(existing);
-        }
-
-        // KRW markets without pre-conversion need rate-based conversion
         if self.original_quote != QuoteCurrency::KRW {
-            return None; // Non-KRW without usdlike shouldn't happen
+            return self.usdlike;
         }
 
-        // KRW price needs conversion based on target quote
+        // KRW price is converted with the exchange's own live stablecoin/KRW rate
         let krw_f64 = self.raw.to_f64();
         let rate = match target_quote {
             UsdlikeQuote::USDT => rates.usdt_krw_for(exchange),
             UsdlikeQuote::USDC => rates.usdc_krw_for(exchange),
-            UsdlikeQuote::BUSD => return None, // No BUSD/KRW rate
+            UsdlikeQuote::BUSD => 0.0, // No BUSD/KRW rate
         };
 
         if rate > 0.0 {
@@ -250,7 +244,24 @@ impl DenominatedPrices {
                 target_quote,
             ))
         } else {
-            None
+            // No live rate: fall back to a conversion made when the price was stored
+            self.usdlike
+        }
+    }
+
+    /// USD price at detection time.
+    /// KRW goes through the USD/KRW forex rate and stablecoins through their
+    /// live USD rate on the exchange; falls back to the stored `usd` price.
+    pub fn to_usd(&self, rates: &ConversionRates, exchange: Exchange) -> Option<FixedPoint> {
+        let rate = match self.original_quote {
+            QuoteCurrency::KRW if rates.usd_krw > 0.0 => 1.0 / rates.usd_krw,
+            QuoteCurrency::KRW => 0.0,
+            quote => rates.quote_usd_for(exchange, quote),
+        };
+        if rate > 0.0 {
+            Some(FixedPoint::from_f64(self.raw.to_f64() * rate))
+        } else {
+            self.usd
         }
     }
 
@@ -303,13 +314,27 @@ impl DenominatedPrices {
     }
 }
 
+/// Stablecoin prices in USD observed on one exchange.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StablecoinUsd {
+    /// USDT/USD rate (0 = unknown)
+    pub usdt_usd: f64,
+    /// USDC/USD rate (0 = unknown)
+    pub usdc_usd: f64,
+}
+
 /// Conversion rates needed for price denomination.
-#[derive(Debug, Clone, Copy, Default)]
+///
+/// A snapshot of live rates taken once per detection pass, so detection,
+/// sizing and broadcasting all price a route with the same numbers.
+#[derive(Debug, Clone, Default)]
 pub struct ConversionRates {
     /// USDT/USD rate (stablecoin price in USD)
     pub usdt_usd: f64,
     /// USDC/USD rate (stablecoin price in USD)
     pub usdc_usd: f64,
+    /// Per-exchange stablecoin/USD rates (override the global rates above)
+    pub exchange_stablecoin_usd: HashMap<Exchange, StablecoinUsd>,
     /// USD/KRW forex rate (하나은행)
     pub usd_krw: f64,
     /// USDT/KRW rate from Upbit
@@ -323,6 +348,57 @@ pub struct ConversionRates {
 }
 
 impl ConversionRates {
+    /// Rates with one KRW rate set for every Korean exchange and stablecoins at par.
+    pub fn uniform_krw(usd_krw: Option<f64>, usdt_krw: Option<f64>, usdc_krw: Option<f64>) -> Self {
+        Self {
+            usdt_usd: 1.0,
+            usdc_usd: 1.0,
+            usd_krw: usd_krw.unwrap_or(0.0),
+            upbit_usdt_krw: usdt_krw.unwrap_or(0.0),
+            upbit_usdc_krw: usdc_krw.unwrap_or(0.0),
+            bithumb_usdt_krw: usdt_krw.unwrap_or(0.0),
+            bithumb_usdc_krw: usdc_krw.unwrap_or(0.0),
+            ..Self::default()
+        }
+    }
+
+    /// Get USDT/USD rate for a specific exchange (global rate if unknown there).
+    pub fn usdt_usd_for(&self, exchange: Exchange) -> f64 {
+        self.exchange_stablecoin_usd
+            .get(&exchange)
+            .map(|p| p.usdt_usd)
+            .filter(|rate| *rate > 0.0)
+            .unwrap_or(self.usdt_usd)
+    }
+
+    /// Get USDC/USD rate for a specific exchange (global rate if unknown there).
+    pub fn usdc_usd_for(&self, exchange: Exchange) -> f64 {
+        self.exchange_stablecoin_usd
+            .get(&exchange)
+            .map(|p| p.usdc_usd)
+            .filter(|rate| *rate > 0.0)
+            .unwrap_or(self.usdc_usd)
+    }
+
+    /// USD value of one unit of `quote` on an exchange (0 = unknown).
+    /// KRW is converted through the exchange's USDT/KRW rate.
+    pub fn quote_usd_for(&self, exchange: Exchange, quote: QuoteCurrency) -> f64 {
+        match quote {
+            QuoteCurrency::USD => 1.0,
+            QuoteCurrency::USDT => self.usdt_usd_for(exchange),
+            QuoteCurrency::USDC => self.usdc_usd_for(exchange),
+            QuoteCurrency::BUSD => self.usdt_usd_for(exchange),
+            QuoteCurrency::KRW => {
+                let usdt_krw = self.usdt_krw_for(exchange);
+                if usdt_krw > 0.0 {
+                    self.usdt_usd_for(exchange) / usdt_krw
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
     /// Get USDT/KRW rate for a specific exchange.
     pub fn usdt_krw_for(&self, exchange: Exchange) -> f64 {
         match exchange {
@@ -528,7 +604,9 @@ impl PremiumMatrix {
 
     /// Update price for an exchange with bid/ask from orderbook (legacy).
     /// For USD quotes, treats prices as USDT equivalent (1:1) for USDlike comparison.
-    /// For KRW quotes, assumes prices are already converted to USDT via USDT/KRW rate.
+    /// For KRW quotes, prices are raw KRW and are converted at detection time with
+    /// the exchange's live rates (use `update_price_with_bid_ask_and_raw` to also
+    /// store a pre-converted price).
     pub fn update_price_with_bid_ask(
         &mut self,
         exchange: Exchange,
@@ -539,31 +617,31 @@ impl PremiumMatrix {
         ask_size: FixedPoint,
         quote: QuoteCurrency,
     ) {
-        // For USD and KRW quotes, treat as USDT equivalent for USDlike comparison
-        // KRW prices are already converted to USDT in main.rs via USDT/KRW rate
+        // For USD quotes, treat as USDT equivalent for USDlike comparison
         let usdlike_quote = UsdlikeQuote::from_quote_currency(quote).or_else(|| {
-            if quote == QuoteCurrency::USD || quote == QuoteCurrency::KRW {
+            if quote == QuoteCurrency::USD {
                 Some(UsdlikeQuote::USDT)
             } else {
                 None
             }
         });
+        let converted = |p: FixedPoint| (quote != QuoteCurrency::KRW).then_some(p);
 
         let mid_denom = DenominatedPrices {
             usdlike: usdlike_quote.map(|q| UsdlikePrice::new(price, q)),
-            usd: Some(price),
+            usd: converted(price),
             raw: price,
             original_quote: quote,
         };
         let bid_denom = DenominatedPrices {
             usdlike: usdlike_quote.map(|q| UsdlikePrice::new(bid, q)),
-            usd: Some(bid),
+            usd: converted(bid),
             raw: bid,
             original_quote: quote,
         };
         let ask_denom = DenominatedPrices {
             usdlike: usdlike_quote.map(|q| UsdlikePrice::new(ask, q)),
-            usd: Some(ask),
+            usd: converted(ask),
             raw: ask,
             original_quote: quote,
         };
@@ -807,6 +885,54 @@ impl PremiumMatrix {
         Some(FixedPoint::premium_bps(buy_ask_usd, sell_bid_usd))
     }
 
+    /// Kimchi premium of each KRW market against the overseas reference price.
+    /// KRW mid prices are converted via the USD/KRW forex rate and compared with the
+    /// median USD mid price of the non-KRW CEX markets. Stale entries are ignored.
+    /// Returns (korean_exchange, premium_bps).
+    pub fn kimchi_premiums(&self, usd_krw: f64) -> Vec<(Exchange, i32)> {
+        if usd_krw <= 0.0 {
+            return Vec::new();
+        }
+
+        let fresh = || {
+            self.prices.iter().filter_map(|(&id, entry)| {
+                if entry.is_stale(self.max_staleness_ms) {
+                    return None;
+                }
+                Exchange::from_id(id).map(|ex| (ex, entry))
+            })
+        };
+
+        let mut overseas: Vec<u64> = fresh()
+            .filter(|(ex, entry)| {
+                entry.mid.original_quote != QuoteCurrency::KRW && !ex.exchange_type().is_dex()
+            })
+            .filter_map(|(_, entry)| entry.mid.usd)
+            .filter(|usd| usd.0 > 0)
+            .map(|usd| usd.0)
+            .collect();
+        if overseas.is_empty() {
+            return Vec::new();
+        }
+        overseas.sort_unstable();
+        let mid = overseas.len() / 2;
+        let reference = if overseas.len().is_multiple_of(2) {
+            FixedPoint((overseas[mid - 1] + overseas[mid]) / 2)
+        } else {
+            FixedPoint(overseas[mid])
+        };
+
+        fresh()
+            .filter(|(_, entry)| {
+                entry.mid.original_quote == QuoteCurrency::KRW && entry.mid.raw.0 > 0
+            })
+            .map(|(ex, entry)| {
+                let korean_usd = FixedPoint::from_f64(entry.mid.raw.to_f64() / usd_krw);
+                (ex, FixedPoint::premium_bps(reference, korean_usd))
+            })
+            .collect()
+    }
+
     /// Calculate premium between buy and sell exchanges using USDlike prices.
     /// Uses ask price for buying and bid price for selling.
     pub fn get_premium(&self, buy_exchange: Exchange, sell_exchange: Exchange) -> Option<i32> {
@@ -1071,7 +1197,10 @@ impl PremiumMatrix {
                         };
 
                     // Kimchi premium (USD via forex)
-                    let kimchi_premium = match (buy_entry.ask.usd, sell_entry.bid.usd) {
+                    let kimchi_premium = match (
+                        buy_entry.ask.to_usd(rates, buy_ex),
+                        sell_entry.bid.to_usd(rates, sell_ex),
+                    ) {
                         (Some(buy), Some(sell)) => FixedPoint::premium_bps(buy, sell),
                         _ => 0,
                     };
@@ -1186,4 +1315,129 @@ mod tests {
         assert!(config.is_profitable(100)); // 100 bps > 50 bps
         assert!(!config.is_profitable(30)); // 30 bps < 50 bps
     }
+
+    #[test]
+    fn test_kimchi_premiums_against_overseas_median() {
+        let mut matrix = PremiumMatrix::new(1);
+        let usd = |p: f64| FixedPoint::from_f64(p);
+
+        matrix.update_price_with_quote(Exchange::Binance, usd(50000.0), QuoteCurrency::USDT);
+        matrix.update_price_with_quote(Exchange::Coinbase, usd(50200.0), QuoteCurrency::USD);
+        matrix.update_price_with_quote(Exchange::Kraken, usd(49800.0), QuoteCurrency::USD);
+        // 71,400,000 KRW at 1400 KRW/USD = 51,000 USD (2% above the 50,000 median)
+        matrix.update_price_with_bid_ask_and_raw(
+            Exchange::Upbit,
+            usd(49500.0),
+            usd(49500.0),
+            usd(49500.0),
+            usd(71_400_000.0),
+            usd(71_400_000.0),
+            usd(1.0),
+            usd(1.0),
+            QuoteCurrency::KRW,
+        );
+
+        let premiums = matrix.kimchi_premiums(1400.0);
+        assert_eq!(premiums, vec![(Exchange::Upbit, 200)]);
+
+        // No forex rate or no overseas reference: nothing to compare
+        assert!(matrix.kimchi_premiums(0.0).is_empty());
+        let mut korean_only = PremiumMatrix::new(2);
+        korean_only.update_price_with_quote(Exchange::Upbit, usd(1.0), QuoteCurrency::KRW);
+        assert!(korean_only.kimchi_premiums(1400.0).is_empty());
+    }
+
+    fn krw_premium_matrix() -> PremiumMatrix {
+        let mut matrix = PremiumMatrix::new(1);
+        let fp = FixedPoint::from_f64;
+        let krw = |exchange, raw: f64, usdt_krw: f64| {
+            let usd = fp(raw / usdt_krw);
+            (exchange, usd, fp(raw))
+        };
+
+        matrix.update_price_with_quote(Exchange::Binance, fp(48_000.0), QuoteCurrency::USDT);
+        // Stored prices were pre-converted with Upbit's rate for both exchanges
+        for (exchange, usd, raw) in [
+            krw(Exchange::Upbit, 70_000_000.0, 1400.0),
+            krw(Exchange::Bithumb, 70_000_000.0, 1400.0),
+        ] {
+            matrix.update_price_with_bid_ask_and_raw(
+                exchange,
+                usd,
+                usd,
+                usd,
+                raw,
+                raw,
+                fp(1.0),
+                fp(1.0),
+                QuoteCurrency::KRW,
+            );
+        }
+        matrix
+    }
+
+    /// (usdlike premium, kimchi premium) for one route.
+    fn route(
+        matrix: &PremiumMatrix,
+        rates: &ConversionRates,
+        buy: Exchange,
+        sell: Exchange,
+    ) -> (i32, i32) {
+        matrix
+            .all_premiums_multi_denomination(rates)
+            .into_iter()
+            .find(|p| p.0 == buy && p.1 == sell)
+            .map(|p| (p.10, p.12))
+            .expect("route present")
+    }
+
+    #[test]
+    fn test_korean_exchanges_use_their_own_stablecoin_rates() {
+        let matrix = krw_premium_matrix();
+        let rates = ConversionRates {
+            upbit_usdt_krw: 1400.0,
+            bithumb_usdt_krw: 1450.0,
+            usd_krw: 1400.0,
+            ..ConversionRates::uniform_krw(None, None, None)
+        };
+
+        // Upbit: 70M / 1400 = 50,000 USDT; Bithumb: 70M / 1450 = 48,275.86 USDT
+        let (upbit_bps, _) = route(&matrix, &rates, Exchange::Binance, Exchange::Upbit);
+        let (bithumb_bps, _) = route(&matrix, &rates, Exchange::Binance, Exchange::Bithumb);
+        assert_eq!(upbit_bps, 416);
+        assert_eq!(bithumb_bps, 57);
+
+        assert_eq!(rates.usdt_krw_for(Exchange::Bithumb), 1450.0);
+        assert_eq!(
+            rates.quote_usd_for(Exchange::Upbit, QuoteCurrency::KRW),
+            1.0 / 1400.0
+        );
+    }
+
+    #[test]
+    fn test_stablecoin_depeg_moves_kimchi_premium() {
+        let matrix = krw_premium_matrix();
+        let pegged = ConversionRates::uniform_krw(Some(1400.0), Some(1400.0), Some(1400.0));
+        let mut depegged = pegged.clone();
+        depegged.exchange_stablecoin_usd.insert(
+            Exchange::Binance,
+            StablecoinUsd {
+                usdt_usd: 0.98,
+                usdc_usd: 0.0,
+            },
+        );
+
+        let (_, pegged_kimchi) = route(&matrix, &pegged, Exchange::Binance, Exchange::Upbit);
+        let (usdlike_bps, depegged_kimchi) =
+            route(&matrix, &depegged, Exchange::Binance, Exchange::Upbit);
+
+        // 50,000 USD vs 48,000 USDT at par, then at 0.98 USD per USDT (47,040 USD)
+        assert_eq!(pegged_kimchi, 416);
+        assert_eq!(depegged_kimchi, 629);
+        // The USDT-denominated premium does not depend on the USDT/USD rate
+        assert_eq!(usdlike_bps, 416);
+        // Unknown per-exchange prices fall back to the global rate
+        assert_eq!(depegged.usdc_usd_for(Exchange::Binance), 1.0);
+        assert_eq!(depegged.usdt_usd_for(Exchange::Upbit), 1.0);
+    }
 }
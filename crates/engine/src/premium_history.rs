@@ -0,0 +1,467 @@
+//! Rolling premium time-series per route.
+//!
+//! `PremiumMatrix` only holds the latest price per exchange. This module keeps a
+//! downsampled history of the premium for each (pair, buy exchange, sell exchange)
+//! route so a persistent premium can be told apart from a one-tick spike.
+
+use arbitrage_core::{Exchange, PremiumStats};
+use dashmap::DashMap;
+use std::collections::VecDeque;
+
+/// Route key for premium history: (pair_id, buy exchange, sell exchange).
+pub type RouteKey = (u32, Exchange, Exchange);
+
+/// A single premium observation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PremiumSample {
+    /// Observation time (ms since epoch).
+    pub timestamp_ms: u64,
+    /// Premium in basis points.
+    pub premium_bps: i32,
+}
+
+/// Premium history configuration.
+#[derive(Debug, Clone)]
+pub struct PremiumHistoryConfig {
+    /// How long samples are retained (ms). Longest window that can be queried.
+    pub retention_ms: u64,
+    /// Minimum spacing between stored samples (ms). Faster updates replace the
+    /// latest sample instead of appending.
+    pub sample_interval_ms: u64,
+    /// Minimum samples in a window before statistics are reported.
+    pub min_samples: usize,
+    /// Most routes kept. When a new route would exceed it, the least recently
+    /// updated routes are evicted down to 90% of the cap.
+    pub max_routes: usize,
+}
+
+impl Default for PremiumHistoryConfig {
+    fn default() -> Self {
+        Self {
+            retention_ms: 600_000, // 10 minutes
+            sample_interval_ms: 1_000,
+            min_samples: 2,
+            max_routes: 5_000,
+        }
+    }
+}
+
+/// Rolling premium history for all routes (lock-free per route via DashMap).
+#[derive(Debug, Default)]
+pub struct PremiumHistory {
+    config: PremiumHistoryConfig,
+    series: DashMap<RouteKey, VecDeque<PremiumSample>>,
+}
+
+impl PremiumHistory {
+    /// Create a history store with the given configuration.
+    pub fn new(config: PremiumHistoryConfig) -> Self {
+        Self {
+            config,
+            series: DashMap::new(),
+        }
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &PremiumHistoryConfig {
+        &self.config
+    }
+
+    /// Number of routes with history.
+    pub fn route_count(&self) -> usize {
+        self.series.len()
+    }
+
+    /// Record a premium observation for a route.
+    pub fn record(
+        &self,
+        pair_id: u32,
+        buy: Exchange,
+        sell: Exchange,
+        premium_bps: i32,
+        timestamp_ms: u64,
+    ) {
+        let sample = PremiumSample {
+            timestamp_ms,
+            premium_bps,
+        };
+        let key = (pair_id, buy, sell);
+        if !self.series.contains_key(&key) && self.series.len() >= self.config.max_routes {
+            self.evict_routes();
+        }
+        let mut series = self.series.entry(key).or_default();
+
+        match series.back_mut() {
+            Some(last)
+                if timestamp_ms.saturating_sub(last.timestamp_ms)
+                    < self.config.sample_interval_ms =>
+            {
+                // Within the sampling interval: keep only the latest value
+                last.premium_bps = premium_bps;
+            }
+            _ => series.push_back(sample),
+        }
+
+        let cutoff = timestamp_ms.saturating_sub(self.config.retention_ms);
+        while series.front().is_some_and(|s| s.timestamp_ms < cutoff) {
+            series.pop_front();
+        }
+    }
+
+    /// Evict the least recently updated routes down to 90% of `max_routes`.
+    /// Evicting in batches keeps the sort off the per-sample path.
+    fn evict_routes(&self) {
+        let keep = self.config.max_routes - self.config.max_routes / 10;
+        let mut routes: Vec<(u64, RouteKey)> = self
+            .series
+            .iter()
+            .map(|entry| {
+                let latest = entry.value().back().map_or(0, |s| s.timestamp_ms);
+                (latest, *entry.key())
+            })
+            .collect();
+        if routes.len() <= keep {
+            return;
+        }
+        let evict = routes.len() - keep;
+        routes.select_nth_unstable_by_key(evict - 1, |(latest, _)| *latest);
+        for (_, key) in &routes[..evict] {
+            self.series.remove(key);
+        }
+    }
+
+    /// Get samples for a route within `window_ms` of its latest sample.
+    pub fn samples(
+        &self,
+        pair_id: u32,
+        buy: Exchange,
+        sell: Exchange,
+        window_ms: u64,
+    ) -> Vec<PremiumSample> {
+        let Some(series) = self.series.get(&(pair_id, buy, sell)) else {
+            return Vec::new();
+        };
+        let Some(latest) = series.back() else {
+            return Vec::new();
+        };
+        let cutoff = latest.timestamp_ms.saturating_sub(window_ms);
+        series
+            .iter()
+            .filter(|s| s.timestamp_ms >= cutoff)
+            .copied()
+            .collect()
+    }
+
+    /// Compute statistics for a route over `window_ms`.
+    /// Returns None until `min_samples` observations exist in the window.
+    pub fn stats(
+        &self,
+        pair_id: u32,
+        buy: Exchange,
+        sell: Exchange,
+        window_ms: u64,
+    ) -> Option<PremiumStats> {
+        let samples = self.samples(pair_id, buy, sell, window_ms);
+        if samples.len() < self.config.min_samples {
+            return None;
+        }
+        compute_premium_stats(&samples)
+    }
+
+    /// Fraction of samples in the window with premium at or above `threshold_bps`.
+    pub fn fraction_at_or_above(
+        &self,
+        pair_id: u32,
+        buy: Exchange,
+        sell: Exchange,
+        threshold_bps: i32,
+        window_ms: u64,
+    ) -> Option<f64> {
+        let samples = self.samples(pair_id, buy, sell, window_ms);
+        if samples.len() < self.config.min_samples {
+            return None;
+        }
+        let above = samples
+            .iter()
+            .filter(|s| s.premium_bps >= threshold_bps)
+            .count();
+        Some(above as f64 / samples.len() as f64)
+    }
+
+    /// Drop all history involving an exchange (e.g., on reconnection).
+    pub fn clear_exchange(&self, exchange: Exchange) {
+        self.series
+            .retain(|(_, buy, sell), _| *buy != exchange && *sell != exchange);
+    }
+}
+
+/// Compute mean, stddev, z-score, percentile and mean-reversion half-life
+/// for a premium series (oldest first). The latest sample is the one scored,
+/// against the samples before it so a spike does not dilute its own z-score.
+/// Returns None for fewer than two samples.
+pub fn compute_premium_stats(samples: &[PremiumSample]) -> Option<PremiumStats> {
+    let (latest, _) = samples.split_last()?;
+    let values: Vec<f64> = samples.iter().map(|s| s.premium_bps as f64).collect();
+    let window = &values[..values.len() - 1];
+    if window.is_empty() {
+        return None;
+    }
+    let n = window.len() as f64;
+
+    let mean = window.iter().sum::<f64>() / n;
+    let variance = if window.len() > 1 {
+        window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+    } else {
+        0.0
+    };
+    let stddev = variance.sqrt();
+    let latest_bps = latest.premium_bps as f64;
+
+    let zscore = if stddev > 0.0 {
+        (latest_bps - mean) / stddev
+    } else {
+        0.0
+    };
+
+    let at_or_below = window.iter().filter(|v| **v <= latest_bps).count();
+    let percentile = at_or_below as f64 / n * 100.0;
+
+    Some(PremiumStats {
+        samples: samples.len() as u32,
+        mean_bps: mean,
+        stddev_bps: stddev,
+        zscore,
+        percentile,
+        half_life_ms: half_life_ms(samples, &values),
+    })
+}
+
+/// Estimate mean-reversion half-life with an AR(1) fit: dx_t = a + b * x_{t-1}.
+/// Half-life (in steps) is -ln(2) / ln(1 + b), only defined for -1 < b < 0.
+fn half_life_ms(samples: &[PremiumSample], values: &[f64]) -> Option<f64> {
+    if values.len() < 3 {
+        return None;
+    }
+
+    let prev = &values[..values.len() - 1];
+    let diffs: Vec<f64> = values.windows(2).map(|w| w[1] - w[0]).collect();
+    let m = prev.len() as f64;
+    let prev_mean = prev.iter().sum::<f64>() / m;
+    let diff_mean = diffs.iter().sum::<f64>() / m;
+
+    let cov: f64 = prev
+        .iter()
+        .zip(&diffs)
+        .map(|(x, d)| (x - prev_mean) * (d - diff_mean))
+        .sum();
+    let var: f64 = prev.iter().map(|x| (x - prev_mean).powi(2)).sum();
+    if var == 0.0 {
+        return None;
+    }
+
+    let b = cov / var;
+    if b <= -1.0 || b >= 0.0 {
+        return None; // Not mean-reverting (or oscillating)
+    }
+    let half_life_steps = -std::f64::consts::LN_2 / (1.0 + b).ln();
+
+    let first = samples.first()?.timestamp_ms;
+    let last = samples.last()?.timestamp_ms;
+    let avg_interval_ms = last.saturating_sub(first) as f64 / m;
+
+    Some(half_life_steps * avg_interval_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[i32]) -> Vec<PremiumSample> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| PremiumSample {
+                timestamp_ms: i as u64 * 1_000,
+                premium_bps: *v,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_stats_mean_stddev_zscore() {
+        let stats = compute_premium_stats(&series(&[90, 110, 90, 110, 200])).unwrap();
+
+        // The latest sample is scored against the four before it
+        assert_eq!(stats.samples, 5);
+        assert!((stats.mean_bps - 100.0).abs() < 1e-9);
+        // sample stddev = sqrt(400 / 3)
+        assert!((stats.stddev_bps - 11.547).abs() < 1e-3);
+        assert!((stats.zscore - 8.660).abs() < 1e-3);
+        assert_eq!(stats.percentile, 100.0);
+    }
+
+    #[test]
+    fn test_compute_stats_needs_preceding_window() {
+        assert_eq!(compute_premium_stats(&series(&[])), None);
+        assert_eq!(compute_premium_stats(&series(&[100])), None);
+
+        let stats = compute_premium_stats(&series(&[100, 200])).unwrap();
+        assert_eq!(stats.mean_bps, 100.0);
+        assert_eq!(stats.zscore, 0.0);
+    }
+
+    #[test]
+    fn test_compute_stats_constant_series() {
+        let stats = compute_premium_stats(&series(&[300, 300, 300])).unwrap();
+
+        assert_eq!(stats.stddev_bps, 0.0);
+        assert_eq!(stats.zscore, 0.0);
+        assert_eq!(stats.half_life_ms, None);
+    }
+
+    #[test]
+    fn test_compute_stats_percentile_of_latest() {
+        let stats = compute_premium_stats(&series(&[10, 20, 30, 40, 25])).unwrap();
+        // 10 and 20 of the preceding 10, 20, 30, 40 are <= 25
+        assert_eq!(stats.percentile, 50.0);
+    }
+
+    #[test]
+    fn test_half_life_mean_reverting_series() {
+        // Deviation from 100 halves every step: x_t - 100 = 0.5 * (x_{t-1} - 100)
+        let stats = compute_premium_stats(&series(&[1700, 900, 500, 300, 200, 150, 125])).unwrap();
+
+        let half_life = stats.half_life_ms.unwrap();
+        assert!((half_life - 1_000.0).abs() < 1.0, "half_life={half_life}");
+    }
+
+    #[test]
+    fn test_half_life_trending_series_is_none() {
+        let stats = compute_premium_stats(&series(&[100, 200, 400, 800, 1600])).unwrap();
+        assert_eq!(stats.half_life_ms, None);
+    }
+
+    #[test]
+    fn test_history_downsamples_within_interval() {
+        let history = PremiumHistory::default();
+
+        history.record(1, Exchange::Binance, Exchange::Upbit, 100, 10_000);
+        history.record(1, Exchange::Binance, Exchange::Upbit, 150, 10_500);
+        history.record(1, Exchange::Binance, Exchange::Upbit, 200, 11_000);
+
+        let samples = history.samples(1, Exchange::Binance, Exchange::Upbit, u64::MAX);
+        assert_eq!(
+            samples,
+            vec![
+                PremiumSample {
+                    timestamp_ms: 10_000,
+                    premium_bps: 150
+                },
+                PremiumSample {
+                    timestamp_ms: 11_000,
+                    premium_bps: 200
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_retention_and_window() {
+        let history = PremiumHistory::new(PremiumHistoryConfig {
+            retention_ms: 5_000,
+            sample_interval_ms: 0,
+            min_samples: 2,
+            ..Default::default()
+        });
+
+        for i in 0..10u64 {
+            history.record(1, Exchange::Binance, Exchange::Upbit, i as i32, i * 1_000);
+        }
+
+        // Retention keeps 4..=9
+        assert_eq!(
+            history
+                .samples(1, Exchange::Binance, Exchange::Upbit, u64::MAX)
+                .len(),
+            6
+        );
+        // Window of 2s relative to latest keeps 7..=9
+        assert_eq!(
+            history
+                .samples(1, Exchange::Binance, Exchange::Upbit, 2_000)
+                .len(),
+            3
+        );
+        assert!(history
+            .stats(1, Exchange::Binance, Exchange::Upbit, 2_000)
+            .is_some());
+        assert!(history
+            .stats(1, Exchange::Binance, Exchange::Upbit, 0)
+            .is_none());
+    }
+
+    #[test]
+    fn test_history_fraction_at_or_above() {
+        let history = PremiumHistory::new(PremiumHistoryConfig {
+            sample_interval_ms: 0,
+            ..Default::default()
+        });
+
+        history.record(1, Exchange::Binance, Exchange::Upbit, 50, 100);
+        assert_eq!(
+            history.fraction_at_or_above(1, Exchange::Binance, Exchange::Upbit, 30, 60_000),
+            None
+        );
+
+        history.record(1, Exchange::Binance, Exchange::Upbit, 10, 200);
+        history.record(1, Exchange::Binance, Exchange::Upbit, 40, 300);
+        history.record(1, Exchange::Binance, Exchange::Upbit, 30, 400);
+        assert_eq!(
+            history.fraction_at_or_above(1, Exchange::Binance, Exchange::Upbit, 30, 60_000),
+            Some(0.75)
+        );
+    }
+
+    #[test]
+    fn test_history_clear_exchange() {
+        let history = PremiumHistory::default();
+        history.record(1, Exchange::Binance, Exchange::Upbit, 100, 1_000);
+        history.record(1, Exchange::Binance, Exchange::Coinbase, 100, 1_000);
+
+        history.clear_exchange(Exchange::Upbit);
+
+        assert_eq!(history.route_count(), 1);
+        assert!(history
+            .samples(1, Exchange::Binance, Exchange::Upbit, u64::MAX)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_history_evicts_least_recently_updated_routes() {
+        let history = PremiumHistory::new(PremiumHistoryConfig {
+            max_routes: 10,
+            ..Default::default()
+        });
+
+        for pair_id in 0..10u32 {
+            history.record(pair_id, Exchange::Binance, Exchange::Upbit, 100, 1_000);
+        }
+        // Pair 0 is updated again and survives the eviction
+        history.record(0, Exchange::Binance, Exchange::Upbit, 100, 5_000);
+        history.record(10, Exchange::Binance, Exchange::Upbit, 100, 5_000);
+
+        assert_eq!(history.route_count(), 10);
+        assert!(!history
+            .samples(0, Exchange::Binance, Exchange::Upbit, u64::MAX)
+            .is_empty());
+        assert!(!history
+            .samples(10, Exchange::Binance, Exchange::Upbit, u64::MAX)
+            .is_empty());
+
+        // Updating existing routes never evicts
+        for pair_id in 0..10u32 {
+            history.record(pair_id, Exchange::Binance, Exchange::Upbit, 100, 9_000);
+        }
+        assert!(history.route_count() <= 10);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Calculates optimal routes including bridges and withdrawals.
 
+use crate::bridge::{BridgeQuote, BridgeQuoteRequest, BridgeQuotes};
 use arbitrage_core::{BridgeProtocol, Chain, Exchange, RouteStep, TradeSide};
 
 /// Cost breakdown for a route.
@@ -32,6 +33,8 @@ pub struct Route {
     amount: u64,
     buy_price: u64,
     sell_price: u64,
+    /// Quoted fee of each bridge step in order (None = unquoted).
+    bridge_fees_bps: Vec<Option<i32>>,
 }
 
 impl Route {
@@ -42,6 +45,7 @@ impl Route {
             amount,
             buy_price,
             sell_price,
+            bridge_fees_bps: Vec::new(),
         }
     }
 
@@ -67,6 +71,7 @@ impl Route {
     /// Estimate costs for this route.
     pub fn estimate_costs(&self) -> RouteCosts {
         let mut costs = RouteCosts::default();
+        let mut bridge_fees = self.bridge_fees_bps.iter();
 
         for step in &self.steps {
             match step {
@@ -75,7 +80,8 @@ impl Route {
                     costs.gas_cost += 5; // Gas for DEX trades
                 }
                 RouteStep::Bridge { .. } => {
-                    costs.bridge_fee += 30; // 0.3% bridge fee
+                    // Quoted fee, else 0.3%
+                    costs.bridge_fee += bridge_fees.next().copied().flatten().unwrap_or(30);
                     costs.gas_cost += 20; // Gas for bridge tx
                 }
                 RouteStep::Withdraw { .. } => {
@@ -108,12 +114,13 @@ impl Route {
 }
 
 /// Builder for constructing routes step by step.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct RouteBuilder {
     steps: Vec<RouteStep>,
     amount: u64,
     buy_price: u64,
     sell_price: u64,
+    bridge_fees_bps: Vec<Option<i32>>,
 }
 
 impl RouteBuilder {
@@ -167,6 +174,16 @@ impl RouteBuilder {
             source_chain: source,
             dest_chain: dest,
         });
+        self.bridge_fees_bps.push(None);
+        self
+    }
+
+    /// Add a bridge step priced by a quote for `amount`.
+    pub fn bridge_quote(mut self, quote: &BridgeQuote, amount: u64) -> Self {
+        self = self.bridge(quote.protocol, quote.source_chain, quote.dest_chain);
+        if let Some(fee) = self.bridge_fees_bps.last_mut() {
+            *fee = Some(quote.fee_bps(amount));
+        }
         self
     }
 
@@ -178,55 +195,35 @@ impl RouteBuilder {
 
     /// Build the final route.
     pub fn build(self) -> Route {
-        Route::new(self.steps, self.amount, self.buy_price, self.sell_price)
+        let mut route = Route::new(self.steps, self.amount, self.buy_price, self.sell_price);
+        route.bridge_fees_bps = self.bridge_fees_bps;
+        route
     }
 }
 
 /// Finds optimal routes between exchanges.
 #[derive(Debug, Default)]
 pub struct RouteFinder {
-    /// Known bridge routes.
-    bridges: Vec<BridgeInfo>,
-}
-
-/// Information about a bridge connection.
-#[derive(Debug, Clone)]
-struct BridgeInfo {
-    protocol: BridgeProtocol,
-    source: Chain,
-    dest: Chain,
+    /// Bridge quoters.
+    bridges: BridgeQuotes,
 }
 
 impl RouteFinder {
-    /// Create a new route finder.
+    /// Create a route finder with the default bridge quoters.
     pub fn new() -> Self {
-        // Initialize with common bridges
-        let bridges = vec![
-            BridgeInfo {
-                protocol: BridgeProtocol::Stargate,
-                source: Chain::Ethereum,
-                dest: Chain::Arbitrum,
-            },
-            BridgeInfo {
-                protocol: BridgeProtocol::Stargate,
-                source: Chain::Ethereum,
-                dest: Chain::Optimism,
-            },
-            BridgeInfo {
-                protocol: BridgeProtocol::Stargate,
-                source: Chain::Arbitrum,
-                dest: Chain::Ethereum,
-            },
-            BridgeInfo {
-                protocol: BridgeProtocol::LayerZero,
-                source: Chain::Ethereum,
-                dest: Chain::Bsc,
-            },
-        ];
+        Self::with_bridges(BridgeQuotes::with_defaults())
+    }
 
+    /// Create a route finder with the given bridge quoters.
+    pub fn with_bridges(bridges: BridgeQuotes) -> Self {
         Self { bridges }
     }
 
+    /// Get the bridge quoters.
+    pub fn bridges(&self) -> &BridgeQuotes {
+        &self.bridges
+    }
+
     /// Find all possible routes between two exchanges on a chain.
     pub fn find_routes(
         &self,
@@ -239,14 +236,13 @@ impl RouteFinder {
         // Direct route (same chain or CEX-to-CEX)
         routes.push(RouteBuilder::new().buy(source, 1, 0).sell(target, 1, 0));
 
-        // If different chains might be involved, add bridge routes
-        // This is a simplified implementation
-        if self.might_need_bridge(source, target) {
-            for bridge in &self.bridges {
+        // DEXes on different chains: one route per protocol bridging the pair
+        if let Some((source_chain, dest_chain)) = self.bridge_lane(source, target) {
+            for protocol in self.bridges.protocols(source_chain, dest_chain) {
                 routes.push(
                     RouteBuilder::new()
                         .buy(source, 1, 0)
-                        .bridge(bridge.protocol, bridge.source, bridge.dest)
+                        .bridge(protocol, source_chain, dest_chain)
                         .sell(target, 1, 0),
                 );
             }
@@ -255,19 +251,37 @@ impl RouteFinder {
         routes
     }
 
-    /// Check if a route might need a bridge.
-    fn might_need_bridge(&self, source: Exchange, target: Exchange) -> bool {
-        // DEX to DEX on different chains might need bridge
-        let source_is_dex = matches!(
-            source,
-            Exchange::UniswapV2 | Exchange::UniswapV3 | Exchange::SushiSwap
-        );
-        let target_is_dex = matches!(
-            target,
-            Exchange::UniswapV2 | Exchange::UniswapV3 | Exchange::SushiSwap
-        );
-
-        source_is_dex && target_is_dex
+    /// Bridged routes between two exchanges priced by live quotes for the
+    /// request's amount, cheapest first. Lanes that cannot take the whole
+    /// amount are left out.
+    pub fn find_quoted_routes(
+        &self,
+        source: Exchange,
+        target: Exchange,
+        request: &BridgeQuoteRequest,
+    ) -> Vec<(RouteBuilder, BridgeQuote)> {
+        if self.bridge_lane(source, target) != Some((request.source_chain, request.dest_chain)) {
+            return Vec::new();
+        }
+        self.bridges
+            .quotes(request)
+            .into_iter()
+            .filter(|quote| quote.covers(request.amount))
+            .map(|quote| {
+                let route = RouteBuilder::new()
+                    .buy(source, 1, 0)
+                    .bridge_quote(&quote, request.amount)
+                    .sell(target, 1, 0)
+                    .with_amount(request.amount);
+                (route, quote)
+            })
+            .collect()
+    }
+
+    /// Chains to bridge between, if the exchanges sit on different chains.
+    fn bridge_lane(&self, source: Exchange, target: Exchange) -> Option<(Chain, Chain)> {
+        let (source_chain, dest_chain) = (source.chain()?, target.chain()?);
+        (source_chain != dest_chain).then_some((source_chain, dest_chain))
     }
 }
 
@@ -338,6 +352,50 @@ mod tests {
         assert!(profit > 0 || profit <= 100); // Some profit or small loss from fees
     }
 
+    #[test]
+    fn test_route_quoted_bridge_fee() {
+        let quote = BridgeQuote {
+            protocol: BridgeProtocol::Across,
+            source_chain: Chain::Ethereum,
+            dest_chain: Chain::Solana,
+            fee: 5_000_000,
+            estimated_time_secs: 10,
+            max_amount: u64::MAX,
+        };
+        let route = RouteBuilder::new()
+            .buy(Exchange::UniswapV3, 1, 50000)
+            .bridge_quote(&quote, 1_00000000)
+            .sell(Exchange::Raydium, 1, 50500)
+            .build();
+
+        // 0.05 of 1 unit = 500 bps
+        assert_eq!(route.estimate_costs().bridge_fee, 500);
+    }
+
+    #[test]
+    fn test_route_finder_bridges_dex_chains() {
+        let finder = RouteFinder::new();
+
+        // Ethereum DEX to Solana DEX: direct plus a bridged route
+        let routes = finder.find_routes(Exchange::UniswapV3, Exchange::Raydium, Chain::Ethereum);
+        assert!(routes.len() > 1);
+        assert!(routes[1].clone().build().has_bridge());
+
+        let request = BridgeQuoteRequest {
+            asset: "USDC".to_string(),
+            source_chain: Chain::Ethereum,
+            dest_chain: Chain::Solana,
+            amount: 100_000_000_000,
+            token_price_usd: 1.0,
+        };
+        let quoted = finder.find_quoted_routes(Exchange::UniswapV3, Exchange::Raydium, &request);
+        assert_eq!(quoted[0].1.protocol, BridgeProtocol::Wormhole);
+
+        // Same chain: nothing to bridge
+        let routes = finder.find_routes(Exchange::UniswapV2, Exchange::UniswapV3, Chain::Ethereum);
+        assert_eq!(routes.len(), 1);
+    }
+
     #[test]
     fn test_route_finder_find_best() {
         let finder = RouteFinder::new();
@@ -0,0 +1,225 @@
+//! Synthetic market load for benchmarks and latency budget tests.
+//!
+//! Generates reproducible price updates and orderbooks for many pairs across
+//! USD, USDT and KRW markets, so engine hot paths can be timed on realistic
+//! shapes without live feeds. Prices random-walk around a per-pair base with
+//! small cross-exchange spreads, and KRW markets carry a kimchi premium, so a
+//! share of routes crosses the detection threshold.
+
+use crate::{ConversionRates, OpportunityDetector, PremiumMatrix};
+use arbitrage_core::{Exchange, FixedPoint, QuoteCurrency};
+
+/// USD/KRW rate used by synthetic KRW markets.
+pub const SYNTHETIC_USD_KRW: f64 = 1_350.0;
+
+/// One synthetic price update.
+#[derive(Debug, Clone, Copy)]
+pub struct SyntheticTick {
+    pub exchange: Exchange,
+    pub pair_id: u32,
+    pub quote: QuoteCurrency,
+    pub price: FixedPoint,
+    pub bid: FixedPoint,
+    pub ask: FixedPoint,
+    pub bid_size: FixedPoint,
+    pub ask_size: FixedPoint,
+}
+
+/// Deterministic market generator (xorshift; the same seed replays the same load).
+#[derive(Debug, Clone)]
+pub struct SyntheticMarket {
+    markets: Vec<(Exchange, QuoteCurrency)>,
+    base_prices: Vec<f64>,
+    state: u64,
+}
+
+impl SyntheticMarket {
+    /// Market with `pairs` pairs (ids 1..=pairs) on Binance, Coinbase, Bybit,
+    /// OKX, Kraken (USD/USDT) and Upbit, Bithumb (KRW).
+    pub fn new(pairs: u32, seed: u64) -> Self {
+        let mut market = Self {
+            markets: vec![
+                (Exchange::Binance, QuoteCurrency::USDT),
+                (Exchange::Coinbase, QuoteCurrency::USD),
+                (Exchange::Bybit, QuoteCurrency::USDT),
+                (Exchange::Okx, QuoteCurrency::USDT),
+                (Exchange::Kraken, QuoteCurrency::USD),
+                (Exchange::Upbit, QuoteCurrency::KRW),
+                (Exchange::Bithumb, QuoteCurrency::KRW),
+            ],
+            base_prices: Vec::with_capacity(pairs as usize),
+            state: seed.max(1),
+        };
+        for _ in 0..pairs {
+            // Log-uniform from $0.01 to $100k
+            let exponent = market.next_unit() * 7.0 - 2.0;
+            market.base_prices.push(10f64.powf(exponent));
+        }
+        market
+    }
+
+    /// Number of pairs.
+    pub fn pairs(&self) -> u32 {
+        self.base_prices.len() as u32
+    }
+
+    /// Exchanges and their quote currencies.
+    pub fn markets(&self) -> &[(Exchange, QuoteCurrency)] {
+        &self.markets
+    }
+
+    /// Rates pricing the synthetic KRW markets (stablecoins at par).
+    pub fn rates(&self) -> ConversionRates {
+        ConversionRates::uniform_krw(
+            Some(SYNTHETIC_USD_KRW),
+            Some(SYNTHETIC_USD_KRW),
+            Some(SYNTHETIC_USD_KRW),
+        )
+    }
+
+    /// Next tick for a given pair and market index.
+    pub fn tick(&mut self, pair_id: u32, market: usize) -> SyntheticTick {
+        let (exchange, quote) = self.markets[market % self.markets.len()];
+        let base = self.base_prices[(pair_id.max(1) - 1) as usize % self.base_prices.len()];
+
+        // ±40 bps around the base, KRW markets +1-3% kimchi premium
+        let mut price = base * (1.0 + (self.next_unit() - 0.5) * 0.008);
+        if quote == QuoteCurrency::KRW {
+            price *= SYNTHETIC_USD_KRW * (1.01 + self.next_unit() * 0.02);
+        }
+        let half_spread = price * 0.0002;
+        let size = 1_000.0 / base * (0.5 + self.next_unit());
+        SyntheticTick {
+            exchange,
+            pair_id,
+            quote,
+            price: FixedPoint::from_f64(price),
+            bid: FixedPoint::from_f64(price - half_spread),
+            ask: FixedPoint::from_f64(price + half_spread),
+            bid_size: FixedPoint::from_f64(size),
+            ask_size: FixedPoint::from_f64(size),
+        }
+    }
+
+    /// Next tick for a random pair and market.
+    pub fn next_tick(&mut self) -> SyntheticTick {
+        let pair_id = (self.next_u64() % self.pairs().max(1) as u64) as u32 + 1;
+        let market = (self.next_u64() % self.markets.len() as u64) as usize;
+        self.tick(pair_id, market)
+    }
+
+    /// Price every market of every pair once.
+    pub fn populate(&mut self, detector: &OpportunityDetector) {
+        for pair_id in 1..=self.pairs() {
+            for market in 0..self.markets.len() {
+                let tick = self.tick(pair_id, market);
+                apply_to_detector(detector, &tick);
+            }
+        }
+    }
+
+    /// Price every market of one pair into a standalone matrix.
+    pub fn matrix(&mut self, pair_id: u32) -> PremiumMatrix {
+        let mut matrix = PremiumMatrix::new(pair_id);
+        for market in 0..self.markets.len() {
+            let tick = self.tick(pair_id, market);
+            apply_to_matrix(&mut matrix, &tick);
+        }
+        matrix
+    }
+
+    /// Orderbook side with `levels` levels stepping away from `price` by
+    /// `step_bps`; asks step up, bids step down. Prices and sizes are FixedPoint.
+    pub fn book_side(
+        &mut self,
+        price: f64,
+        levels: usize,
+        step_bps: f64,
+        ask: bool,
+    ) -> Vec<(u64, u64)> {
+        let direction = if ask { 1.0 } else { -1.0 };
+        (0..levels)
+            .map(|level| {
+                let level_price = price * (1.0 + direction * step_bps * level as f64 / 10_000.0);
+                let size = 0.1 + self.next_unit() * 2.0;
+                (
+                    FixedPoint::from_f64(level_price).0,
+                    FixedPoint::from_f64(size).0,
+                )
+            })
+            .collect()
+    }
+
+    /// Uniform in [0, 1).
+    pub fn next_unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
+/// Feed a tick into the detector.
+pub fn apply_to_detector(detector: &OpportunityDetector, tick: &SyntheticTick) {
+    detector.update_price_with_bid_ask(
+        tick.exchange,
+        tick.pair_id,
+        tick.price,
+        tick.bid,
+        tick.ask,
+        tick.bid_size,
+        tick.ask_size,
+        tick.quote,
+    );
+}
+
+/// Feed a tick into a premium matrix.
+pub fn apply_to_matrix(matrix: &mut PremiumMatrix, tick: &SyntheticTick) {
+    matrix.update_price_with_bid_ask(
+        tick.exchange,
+        tick.price,
+        tick.bid,
+        tick.ask,
+        tick.bid_size,
+        tick.ask_size,
+        tick.quote,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DetectorConfig;
+
+    #[test]
+    fn test_synthetic_market_is_reproducible() {
+        let mut a = SyntheticMarket::new(10, 42);
+        let mut b = SyntheticMarket::new(10, 42);
+        for _ in 0..100 {
+            let (x, y) = (a.next_tick(), b.next_tick());
+            assert_eq!(
+                (x.pair_id, x.exchange, x.price),
+                (y.pair_id, y.exchange, y.price)
+            );
+        }
+    }
+
+    #[test]
+    fn test_synthetic_market_produces_opportunities() {
+        let mut market = SyntheticMarket::new(20, 7);
+        let detector = OpportunityDetector::new(DetectorConfig::default());
+        market.populate(&detector);
+
+        let rates = market.rates();
+        let opps: usize = (1..=market.pairs())
+            .map(|pair_id| detector.detect_with_conversion_rates(pair_id, &rates).len())
+            .sum();
+        assert!(opps > 0);
+    }
+}
@@ -0,0 +1,332 @@
+//! Transfer-time price risk for cross-exchange opportunities.
+//!
+//! A trade that moves the asset on-chain is exposed to price moves until the
+//! deposit is credited on the sell exchange. This module estimates the transfer
+//! time (network confirmations plus exchange crediting delay), measures recent
+//! price volatility, and turns both into a risk-adjusted and a worst-case profit.
+
+use arbitrage_core::{Exchange, FixedPoint, TransferRisk};
+use dashmap::DashMap;
+use std::collections::VecDeque;
+
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+/// Transfer risk configuration.
+#[derive(Debug, Clone)]
+pub struct TransferRiskConfig {
+    /// Window of price samples used for volatility (ms).
+    pub volatility_window_ms: u64,
+    /// Minimum spacing between stored price samples (ms).
+    pub sample_interval_ms: u64,
+    /// Minimum samples before measured volatility is used.
+    pub min_samples: usize,
+    /// Volatility used until enough samples exist (bps per hour).
+    pub default_hourly_volatility_bps: f64,
+    /// Confirmation time for networks without a known estimate (ms).
+    pub default_confirmation_ms: u64,
+    /// Standard deviations of price risk deducted for the expected profit.
+    pub risk_aversion: f64,
+    /// Standard deviations of adverse move for the worst case (2.33 = 99% one-sided).
+    pub worst_case_z: f64,
+}
+
+impl Default for TransferRiskConfig {
+    fn default() -> Self {
+        Self {
+            volatility_window_ms: 3_600_000, // 1 hour
+            sample_interval_ms: 1_000,
+            min_samples: 30,
+            default_hourly_volatility_bps: 100.0,
+            default_confirmation_ms: 1_800_000,
+            risk_aversion: 0.5,
+            worst_case_z: 2.33,
+        }
+    }
+}
+
+/// Expected time until a deposit on `network` has enough confirmations (ms).
+/// Accepts canonical network names and common exchange network IDs.
+pub fn network_confirmation_ms(network: &str) -> Option<u64> {
+    let ms = match network.to_lowercase().as_str() {
+        "bitcoin" | "btc" => 1_800_000,
+        "bitcoin cash" | "bch" => 3_600_000,
+        "litecoin" | "ltc" => 900_000,
+        "dogecoin" | "doge" => 1_200_000,
+        "ethereum classic" | "etc" => 3_600_000,
+        "ethereum" | "eth" | "erc20" => 300_000,
+        "polygon" | "matic" | "pol" => 300_000,
+        "cardano" | "ada" => 600_000,
+        "arbitrum" | "arbitrum one" | "arbitrum nova" | "optimism" | "base" => 120_000,
+        "bsc (bnb chain)" | "bsc" | "bep20" => 60_000,
+        "tron" | "trx" | "trc20" => 60_000,
+        "solana" | "sol" | "avalanche" | "avalanche c-chain" | "avaxc" | "ton" => 30_000,
+        "xrp" | "stellar" | "xlm" | "aptos" | "apt" | "sui" => 10_000,
+        _ => return None,
+    };
+    Some(ms)
+}
+
+/// Delay between confirmation and the deposit being credited on an exchange (ms).
+/// Korean exchanges add travel-rule checks on incoming transfers.
+pub fn deposit_credit_delay_ms(exchange: Exchange) -> u64 {
+    match exchange {
+        Exchange::Upbit | Exchange::Bithumb => 600_000,
+        Exchange::Coinbase => 120_000,
+        _ => 60_000,
+    }
+}
+
+/// Realized volatility over `horizon_ms` from (timestamp_ms, price) samples, in bps.
+/// Uses the sum of squared log returns over elapsed time, so irregular sampling is fine.
+pub fn realized_volatility_bps(samples: &[(u64, f64)], horizon_ms: u64) -> Option<f64> {
+    let mut sum_sq = 0.0;
+    let mut elapsed_ms = 0.0;
+    for pair in samples.windows(2) {
+        let ((t0, p0), (t1, p1)) = (pair[0], pair[1]);
+        if p0 <= 0.0 || p1 <= 0.0 || t1 <= t0 {
+            continue;
+        }
+        let r = (p1 / p0).ln();
+        sum_sq += r * r;
+        elapsed_ms += (t1 - t0) as f64;
+    }
+    if elapsed_ms <= 0.0 {
+        return None;
+    }
+    let variance_per_ms = sum_sq / elapsed_ms;
+    Some((variance_per_ms * horizon_ms as f64).sqrt() * 10_000.0)
+}
+
+/// Rolling price samples per (exchange, pair) for volatility estimates.
+#[derive(Debug, Default)]
+pub struct PriceVolatility {
+    config: TransferRiskConfig,
+    series: DashMap<(Exchange, u32), VecDeque<(u64, f64)>>,
+}
+
+impl PriceVolatility {
+    pub fn new(config: TransferRiskConfig) -> Self {
+        Self {
+            config,
+            series: DashMap::new(),
+        }
+    }
+
+    /// Record a price observation.
+    pub fn record(&self, exchange: Exchange, pair_id: u32, price: f64, timestamp_ms: u64) {
+        if !price.is_finite() || price <= 0.0 {
+            return;
+        }
+        let mut series = self.series.entry((exchange, pair_id)).or_default();
+
+        match series.back() {
+            Some(&(last_ts, _)) if timestamp_ms < last_ts => return,
+            Some(&(last_ts, _)) if timestamp_ms - last_ts < self.config.sample_interval_ms => {
+                // Within the sampling interval: keep only the latest value
+                *series.back_mut().unwrap() = (last_ts, price);
+            }
+            _ => series.push_back((timestamp_ms, price)),
+        }
+
+        let cutoff = timestamp_ms.saturating_sub(self.config.volatility_window_ms);
+        while series.front().is_some_and(|&(ts, _)| ts < cutoff) {
+            series.pop_front();
+        }
+    }
+
+    /// Measured volatility over `horizon_ms` (bps), None until `min_samples` exist.
+    pub fn volatility_bps(&self, exchange: Exchange, pair_id: u32, horizon_ms: u64) -> Option<f64> {
+        let series = self.series.get(&(exchange, pair_id))?;
+        if series.len() < self.config.min_samples {
+            return None;
+        }
+        let samples: Vec<(u64, f64)> = series.iter().copied().collect();
+        realized_volatility_bps(&samples, horizon_ms)
+    }
+
+    /// Remove all samples for an exchange (e.g. on disconnect).
+    pub fn clear_exchange(&self, exchange: Exchange) {
+        self.series.retain(|(ex, _), _| *ex != exchange);
+    }
+}
+
+/// Inputs for assessing one opportunity.
+#[derive(Debug, Clone, Copy)]
+pub struct TransferRiskInputs<'a> {
+    pub pair_id: u32,
+    pub buy_exchange: Exchange,
+    pub sell_exchange: Exchange,
+    /// Transfer network (canonical name), None if unknown.
+    pub network: Option<&'a str>,
+    /// Trade notional in quote currency (FixedPoint scale).
+    pub notional: i64,
+    /// Profit after fees in quote currency (FixedPoint scale).
+    pub profit: i64,
+}
+
+/// Transfer risk model: transfer time estimates plus price volatility.
+#[derive(Debug, Default)]
+pub struct TransferRiskModel {
+    config: TransferRiskConfig,
+    volatility: PriceVolatility,
+}
+
+impl TransferRiskModel {
+    pub fn new(config: TransferRiskConfig) -> Self {
+        Self {
+            volatility: PriceVolatility::new(config.clone()),
+            config,
+        }
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &TransferRiskConfig {
+        &self.config
+    }
+
+    /// Price samples used for volatility.
+    pub fn volatility(&self) -> &PriceVolatility {
+        &self.volatility
+    }
+
+    /// Expected time from withdrawal to credited deposit (ms).
+    pub fn transfer_time_ms(&self, network: Option<&str>, sell_exchange: Exchange) -> u64 {
+        let confirmation_ms = network
+            .and_then(network_confirmation_ms)
+            .unwrap_or(self.config.default_confirmation_ms);
+        confirmation_ms + deposit_credit_delay_ms(sell_exchange)
+    }
+
+    /// Volatility over `horizon_ms` (bps): measured on the sell exchange, then the
+    /// buy exchange, then the configured default.
+    pub fn volatility_bps(
+        &self,
+        pair_id: u32,
+        buy_exchange: Exchange,
+        sell_exchange: Exchange,
+        horizon_ms: u64,
+    ) -> f64 {
+        self.volatility
+            .volatility_bps(sell_exchange, pair_id, horizon_ms)
+            .or_else(|| {
+                self.volatility
+                    .volatility_bps(buy_exchange, pair_id, horizon_ms)
+            })
+            .unwrap_or_else(|| {
+                self.config.default_hourly_volatility_bps * (horizon_ms as f64 / MS_PER_HOUR).sqrt()
+            })
+    }
+
+    /// Assess the transfer risk of a trade.
+    pub fn assess(&self, inputs: &TransferRiskInputs<'_>) -> TransferRisk {
+        let transfer_time_ms = self.transfer_time_ms(inputs.network, inputs.sell_exchange);
+        let volatility_bps = self.volatility_bps(
+            inputs.pair_id,
+            inputs.buy_exchange,
+            inputs.sell_exchange,
+            transfer_time_ms,
+        );
+
+        let notional = inputs.notional.max(0) as f64 / FixedPoint::SCALE as f64;
+        let profit = inputs.profit as f64 / FixedPoint::SCALE as f64;
+        let one_sigma = notional * volatility_bps / 10_000.0;
+        let to_fixed = |value: f64| (value * FixedPoint::SCALE as f64).round() as i64;
+
+        TransferRisk {
+            network: inputs.network.map(str::to_string),
+            transfer_time_ms,
+            volatility_bps,
+            expected_profit: to_fixed(profit - self.config.risk_aversion * one_sigma),
+            worst_case_profit: to_fixed(profit - self.config.worst_case_z * one_sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fp(value: f64) -> i64 {
+        (value * FixedPoint::SCALE as f64) as i64
+    }
+
+    #[test]
+    fn test_transfer_time_uses_network_and_crediting_delay() {
+        let model = TransferRiskModel::default();
+        assert_eq!(
+            model.transfer_time_ms(Some("Tron"), Exchange::Upbit),
+            60_000 + 600_000
+        );
+        assert_eq!(
+            model.transfer_time_ms(Some("erc20"), Exchange::Binance),
+            300_000 + 60_000
+        );
+        // Unknown network falls back to the default confirmation time
+        assert_eq!(
+            model.transfer_time_ms(None, Exchange::Binance),
+            1_800_000 + 60_000
+        );
+    }
+
+    #[test]
+    fn test_realized_volatility_scales_with_horizon() {
+        // Alternating +-10 bps every second
+        let samples: Vec<(u64, f64)> = (0..101)
+            .map(|i| (i * 1_000, if i % 2 == 0 { 100.0 } else { 100.1 }))
+            .collect();
+        let one_sec = realized_volatility_bps(&samples, 1_000).unwrap();
+        assert!((one_sec - 10.0).abs() < 0.1, "{}", one_sec);
+
+        let hundred_sec = realized_volatility_bps(&samples, 100_000).unwrap();
+        assert!((hundred_sec - one_sec * 10.0).abs() < 1e-6);
+
+        assert!(realized_volatility_bps(&samples[..1], 1_000).is_none());
+    }
+
+    #[test]
+    fn test_volatility_uses_samples_once_available() {
+        let config = TransferRiskConfig {
+            min_samples: 3,
+            ..Default::default()
+        };
+        let model = TransferRiskModel::new(config);
+        let default_vol = model.volatility_bps(1, Exchange::Binance, Exchange::Upbit, 3_600_000);
+        assert!((default_vol - 100.0).abs() < 1e-9);
+
+        // Flat prices on the buy exchange: no measured volatility
+        for i in 0..5 {
+            model
+                .volatility()
+                .record(Exchange::Binance, 1, 50_000.0, i * 1_000);
+        }
+        let vol = model.volatility_bps(1, Exchange::Binance, Exchange::Upbit, 3_600_000);
+        assert_eq!(vol, 0.0);
+    }
+
+    #[test]
+    fn test_assess_risk_adjusted_and_worst_case_profit() {
+        let model = TransferRiskModel::default();
+        let risk = model.assess(&TransferRiskInputs {
+            pair_id: 1,
+            buy_exchange: Exchange::Binance,
+            sell_exchange: Exchange::Upbit,
+            network: Some("Bitcoin"),
+            notional: fp(10_000.0),
+            profit: fp(100.0),
+        });
+
+        // 30 min confirmations + 10 min crediting = 40 min
+        assert_eq!(risk.transfer_time_ms, 2_400_000);
+        let expected_vol = 100.0 * (2_400_000.0f64 / 3_600_000.0).sqrt();
+        assert!((risk.volatility_bps - expected_vol).abs() < 1e-9);
+
+        // One sigma = 10,000 * 0.8165% = 81.65 USD
+        let one_sigma = 10_000.0 * expected_vol / 10_000.0;
+        let expected = 100.0 - 0.5 * one_sigma;
+        let worst = 100.0 - 2.33 * one_sigma;
+        assert!((risk.expected_profit as f64 / FixedPoint::SCALE as f64 - expected).abs() < 1e-6);
+        assert!((risk.worst_case_profit as f64 / FixedPoint::SCALE as f64 - worst).abs() < 1e-6);
+        assert!(risk.worst_case_profit < 0);
+        assert_eq!(risk.network.as_deref(), Some("Bitcoin"));
+    }
+}
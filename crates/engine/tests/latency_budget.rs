@@ -0,0 +1,175 @@
+//! Latency budget regression tests.
+//!
+//! Times the engine hot paths on synthetic load and fails when the median
+//! exceeds its budget in `benches/budgets.json`. Wall-clock timings are only
+//! meaningful for optimized builds on an otherwise idle runner, so these are
+//! ignored under `cargo test` and run by the latency budget CI job with
+//! `cargo test --release -p arbitrage-engine --test latency_budget -- --ignored --test-threads=1`.
+
+use arbitrage_core::FixedPoint;
+use arbitrage_engine::{
+    apply_to_detector, apply_to_matrix, calculate_optimal_size, DepthFeeConfig, DetectorConfig,
+    OpportunityDetector, OrderbookCache, Side, SyntheticMarket,
+};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::Instant;
+
+const SEED: u64 = 0x5eed;
+const SAMPLES: usize = 31;
+
+fn budget_ns(name: &str) -> f64 {
+    let budgets: HashMap<String, serde_json::Value> =
+        serde_json::from_str(include_str!("../benches/budgets.json")).unwrap();
+    budgets
+        .get(name)
+        .and_then(|budget| budget.as_f64())
+        .unwrap_or_else(|| panic!("no budget for {}", name))
+}
+
+/// Median ns per call of `op` over `SAMPLES` samples of `iters` calls.
+fn median_ns(iters: u32, mut op: impl FnMut()) -> f64 {
+    for _ in 0..iters {
+        op();
+    }
+    let mut samples: Vec<f64> = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iters {
+                op();
+            }
+            start.elapsed().as_nanos() as f64 / iters as f64
+        })
+        .collect();
+    samples.sort_by(|a, b| a.total_cmp(b));
+    samples[SAMPLES / 2]
+}
+
+fn assert_within_budget(name: &str, iters: u32, op: impl FnMut()) {
+    let measured = median_ns(iters, op);
+    let budget = budget_ns(name);
+    assert!(
+        measured <= budget,
+        "{}: {:.0} ns/op exceeds budget of {:.0} ns",
+        name,
+        measured,
+        budget
+    );
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_premium_matrix_budget() {
+    let mut market = SyntheticMarket::new(1, SEED);
+    let rates = market.rates();
+    let mut matrix = market.matrix(1);
+    let ticks: Vec<_> = (0..1_000).map(|_| market.next_tick()).collect();
+
+    let mut next = ticks.iter().cycle();
+    assert_within_budget("premium_matrix/update", 1_000, || {
+        apply_to_matrix(&mut matrix, next.next().unwrap())
+    });
+    assert_within_budget(
+        "premium_matrix/all_premiums_multi_denomination",
+        100,
+        || {
+            black_box(matrix.all_premiums_multi_denomination(&rates));
+        },
+    );
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_detector_budget() {
+    let mut market = SyntheticMarket::new(500, SEED);
+    let detector = OpportunityDetector::new(DetectorConfig::default());
+    market.populate(&detector);
+    let rates = market.rates();
+    let ticks: Vec<_> = (0..1_000).map(|_| market.next_tick()).collect();
+
+    let mut next = ticks.iter().cycle();
+    assert_within_budget("detector/update/500_pairs", 1_000, || {
+        apply_to_detector(&detector, next.next().unwrap())
+    });
+    let mut pair_id = 0;
+    assert_within_budget(
+        "detector/detect_with_conversion_rates/500_pairs",
+        100,
+        || {
+            pair_id = pair_id % market.pairs() + 1;
+            black_box(detector.detect_with_conversion_rates(pair_id, &rates));
+        },
+    );
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_optimal_size_budget() {
+    let mut market = SyntheticMarket::new(1, SEED);
+    let asks = market.book_side(50_000.0, 20, 2.0, true);
+    let bids = market.book_side(50_250.0, 20, 2.0, false);
+
+    assert_within_budget("depth/calculate_optimal_size/20_levels", 1_000, || {
+        black_box(calculate_optimal_size(
+            black_box(&asks),
+            black_box(&bids),
+            DepthFeeConfig::default(),
+        ));
+    });
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_orderbook_budget() {
+    let mut market = SyntheticMarket::new(1, SEED);
+    let bids = market.book_side(50_000.0, 50, 1.0, false);
+    let asks = market.book_side(50_005.0, 50, 1.0, true);
+    let mut book = OrderbookCache::new(50);
+    book.update_snapshot(&bids, &asks);
+
+    let deltas: Vec<_> = (0..1_000)
+        .map(|_| {
+            let side = if market.next_unit() < 0.5 {
+                Side::Bid
+            } else {
+                Side::Ask
+            };
+            let offset = (market.next_unit() - 0.5) * 0.01;
+            let qty = if market.next_unit() < 0.2 {
+                0.0
+            } else {
+                market.next_unit()
+            };
+            (
+                side,
+                FixedPoint::from_f64(50_000.0 * (1.0 + offset)).0,
+                FixedPoint::from_f64(qty).0,
+            )
+        })
+        .collect();
+    let mut next = deltas.iter().cycle();
+    assert_within_budget("orderbook/apply_delta", 1_000, || {
+        let &(side, price, qty) = next.next().unwrap();
+        book.apply_delta(side, price, qty);
+    });
+    assert_within_budget("orderbook/update_snapshot/50_levels", 100, || {
+        book.update_snapshot(black_box(&bids), black_box(&asks))
+    });
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_synthetic_load_budget() {
+    let mut market = SyntheticMarket::new(300, SEED);
+    let detector = OpportunityDetector::new(DetectorConfig::default());
+    market.populate(&detector);
+    let rates = market.rates();
+    let burst: Vec<_> = (0..1_000).map(|_| market.next_tick()).collect();
+
+    assert_within_budget("load/update_and_detect/1000_ticks_300_pairs", 1, || {
+        for tick in &burst {
+            apply_to_detector(&detector, tick);
+            black_box(detector.detect_with_conversion_rates(tick.pair_id, &rates));
+        }
+    });
+}
@@ -0,0 +1,154 @@
+//! Minimal Solidity ABI encoding for router, quoter and ERC-20 calls.
+
+use super::keccak::keccak256;
+use super::{address_word, EvmAddress};
+use crate::{ExecutorError, ExecutorResult};
+
+/// Function argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Address(EvmAddress),
+    /// Unsigned integer of any width up to 128 bits (uint24, uint160, uint256, ...).
+    Uint(u128),
+    /// Raw 32-byte word (e.g. `type(uint256).max`).
+    Word([u8; 32]),
+    /// Dynamic `address[]`.
+    AddressArray(Vec<EvmAddress>),
+}
+
+/// `type(uint256).max`.
+pub const UINT256_MAX: [u8; 32] = [0xff; 32];
+
+/// First four bytes of keccak256 of the canonical signature.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Calldata for a function call. Static tuples are passed as their flattened fields.
+pub fn encode_call(signature: &str, args: &[Token]) -> Vec<u8> {
+    let mut head = Vec::with_capacity(4 + 32 * args.len());
+    head.extend_from_slice(&selector(signature));
+    let mut tail = Vec::new();
+    for arg in args {
+        match arg {
+            Token::Address(address) => head.extend_from_slice(&address_word(address)),
+            Token::Uint(value) => head.extend_from_slice(&uint_word(*value)),
+            Token::Word(word) => head.extend_from_slice(word),
+            Token::AddressArray(addresses) => {
+                let offset = 32 * args.len() + tail.len();
+                head.extend_from_slice(&uint_word(offset as u128));
+                tail.extend_from_slice(&uint_word(addresses.len() as u128));
+                for address in addresses {
+                    tail.extend_from_slice(&address_word(address));
+                }
+            }
+        }
+    }
+    head.extend(tail);
+    head
+}
+
+fn uint_word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// The `index`-th 32-byte word of return data.
+pub fn decode_word(data: &[u8], index: usize) -> ExecutorResult<[u8; 32]> {
+    data.get(32 * index..32 * (index + 1))
+        .and_then(|word| <[u8; 32]>::try_from(word).ok())
+        .ok_or_else(|| {
+            ExecutorError::ExchangeError(format!(
+                "return data too short: {} bytes, need word {}",
+                data.len(),
+                index
+            ))
+        })
+}
+
+/// Unsigned integer at word `index`, saturating at `u128::MAX`.
+pub fn decode_uint(data: &[u8], index: usize) -> ExecutorResult<u128> {
+    Ok(word_to_uint(&decode_word(data, index)?))
+}
+
+/// Address at word `index`.
+pub fn decode_address(data: &[u8], index: usize) -> ExecutorResult<EvmAddress> {
+    let word = decode_word(data, index)?;
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&word[12..]);
+    Ok(address)
+}
+
+/// Dynamic `uint256[]` whose offset is at word `index`.
+pub fn decode_uint_array(data: &[u8], index: usize) -> ExecutorResult<Vec<u128>> {
+    let offset = decode_uint(data, index)?;
+    if offset % 32 != 0 || offset > data.len() as u128 {
+        return Err(ExecutorError::ExchangeError(format!(
+            "invalid array offset {}",
+            offset
+        )));
+    }
+    let start = offset as usize / 32;
+    let len = decode_uint(data, start)? as usize;
+    (0..len.min(data.len() / 32))
+        .map(|i| decode_uint(data, start + 1 + i))
+        .collect()
+}
+
+fn word_to_uint(word: &[u8; 32]) -> u128 {
+    if word[..16].iter().any(|&b| b != 0) {
+        return u128::MAX;
+    }
+    u128::from_be_bytes(word[16..].try_into().expect("16 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selectors() {
+        assert_eq!(
+            hex::encode(selector("approve(address,uint256)")),
+            "095ea7b3"
+        );
+        assert_eq!(
+            hex::encode(selector(
+                "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)"
+            )),
+            "38ed1739"
+        );
+        assert_eq!(
+            hex::encode(selector(
+                "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))"
+            )),
+            "414bf389"
+        );
+    }
+
+    #[test]
+    fn test_encode_dynamic_array_and_decode() {
+        let path = vec![[0x11; 20], [0x22; 20]];
+        let data = encode_call(
+            "getAmountsOut(uint256,address[])",
+            &[Token::Uint(1000), Token::AddressArray(path)],
+        );
+        let args = &data[4..];
+        assert_eq!(args.len(), 32 * 5);
+        assert_eq!(decode_uint(args, 0).unwrap(), 1000);
+        assert_eq!(decode_uint(args, 1).unwrap(), 64);
+        assert_eq!(decode_uint_array(args, 1).unwrap().len(), 2);
+        assert_eq!(decode_address(args, 4).unwrap(), [0x22; 20]);
+
+        // Return value of getAmountsOut: uint256[] [1000, 997]
+        let mut ret = Vec::new();
+        for value in [32u128, 2, 1000, 997] {
+            ret.extend_from_slice(&uint_word(value));
+        }
+        assert_eq!(decode_uint_array(&ret, 0).unwrap(), vec![1000, 997]);
+        assert_eq!(decode_uint(&UINT256_MAX, 0).unwrap(), u128::MAX);
+        assert!(decode_word(&ret, 4).is_err());
+    }
+}
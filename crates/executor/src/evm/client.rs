@@ -0,0 +1,713 @@
+//! [`DexClient`] for Uniswap-style routers on EVM chains.
+
+use super::abi::{self, Token, UINT256_MAX};
+use super::rpc::{CallRequest, JsonRpcClient, TransactionReceipt};
+use super::signer::LocalSigner;
+use super::transaction::Eip1559Transaction;
+use super::{evm_address, EvmAddress};
+use crate::{DexClient, ExecutorError, ExecutorResult, SwapParams, SwapResult};
+use async_trait::async_trait;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, OnceCell};
+use tracing::{debug, info, warn};
+
+/// topic0 of `Transfer(address,address,uint256)`.
+const TRANSFER_TOPIC: [u8; 32] = [
+    0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+    0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+];
+
+/// Router family and contract addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvmDexProtocol {
+    /// Uniswap V2 router and factory (also SushiSwap, PancakeSwap v2 forks).
+    UniswapV2 {
+        router: EvmAddress,
+        factory: EvmAddress,
+    },
+    /// Uniswap V3 SwapRouter, QuoterV2 and factory, trading a single fee tier.
+    UniswapV3 {
+        router: EvmAddress,
+        quoter: EvmAddress,
+        factory: EvmAddress,
+        /// Pool fee in hundredths of a bip (500 = 0.05%).
+        fee: u32,
+    },
+}
+
+impl EvmDexProtocol {
+    fn router(&self) -> EvmAddress {
+        match *self {
+            Self::UniswapV2 { router, .. } | Self::UniswapV3 { router, .. } => router,
+        }
+    }
+}
+
+/// Configuration for [`EvmDexClient`].
+#[derive(Debug, Clone)]
+pub struct EvmDexConfig {
+    /// JSON-RPC endpoint (e.g. `http://127.0.0.1:8545` for anvil).
+    pub rpc_url: String,
+    pub protocol: EvmDexProtocol,
+    /// Expected chain id; None accepts whatever the node reports.
+    pub chain_id: Option<u64>,
+    /// Gas limit as a percentage of `eth_estimateGas`.
+    pub gas_limit_multiplier_pct: u64,
+    /// Cap on max fee per gas (gwei).
+    pub max_fee_per_gas_gwei: u64,
+    /// Approve the router for `type(uint256).max` when the allowance is short.
+    pub auto_approve: bool,
+    /// Interval between receipt polls.
+    pub receipt_poll_interval: Duration,
+    /// Give up waiting for a receipt after this long.
+    pub receipt_timeout: Duration,
+    /// HTTP request timeout.
+    pub request_timeout: Duration,
+}
+
+impl EvmDexConfig {
+    /// Configuration with default gas and polling settings.
+    pub fn new(rpc_url: impl Into<String>, protocol: EvmDexProtocol) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            protocol,
+            chain_id: None,
+            gas_limit_multiplier_pct: 120,
+            max_fee_per_gas_gwei: 100,
+            auto_approve: true,
+            receipt_poll_interval: Duration::from_millis(500),
+            receipt_timeout: Duration::from_secs(120),
+            request_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// DEX client that signs locally and submits over JSON-RPC.
+pub struct EvmDexClient {
+    config: EvmDexConfig,
+    rpc: JsonRpcClient,
+    signer: LocalSigner,
+    chain_id: OnceCell<u64>,
+    /// Next nonce to use; None until read from the node (or after a failed send).
+    nonce: Mutex<Option<u64>>,
+}
+
+impl EvmDexClient {
+    /// Create a client.
+    pub fn new(config: EvmDexConfig, signer: LocalSigner) -> ExecutorResult<Self> {
+        let rpc = JsonRpcClient::new(config.rpc_url.clone(), config.request_timeout)?;
+        Ok(Self {
+            config,
+            rpc,
+            signer,
+            chain_id: OnceCell::new(),
+            nonce: Mutex::new(None),
+        })
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &EvmDexConfig {
+        &self.config
+    }
+
+    /// Sending account.
+    pub fn address(&self) -> EvmAddress {
+        self.signer.address()
+    }
+
+    /// Chain id reported by the node, checked against the configured one.
+    pub async fn chain_id(&self) -> ExecutorResult<u64> {
+        self.chain_id
+            .get_or_try_init(|| async {
+                let chain_id = self.rpc.chain_id().await?;
+                match self.config.chain_id {
+                    Some(expected) if expected != chain_id => {
+                        Err(ExecutorError::InvalidParameters(format!(
+                            "node at {} is on chain {}, expected {}",
+                            self.rpc.url(),
+                            chain_id,
+                            expected
+                        )))
+                    }
+                    _ => Ok(chain_id),
+                }
+            })
+            .await
+            .copied()
+    }
+
+    /// Sign and submit a transaction, then wait for its receipt.
+    ///
+    /// The nonce lock is held until the node accepts the transaction, so
+    /// concurrent sends get consecutive nonces. A rejected send clears the
+    /// cached nonce and the next send re-reads it from the node.
+    pub async fn send_transaction(
+        &self,
+        to: EvmAddress,
+        data: Vec<u8>,
+    ) -> ExecutorResult<TransactionReceipt> {
+        let chain_id = self.chain_id().await?;
+        let request = CallRequest {
+            from: Some(self.address()),
+            to,
+            data,
+        };
+        let gas_limit =
+            self.rpc.estimate_gas(&request).await? * self.config.gas_limit_multiplier_pct / 100;
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.fees().await?;
+
+        let tx_hash = {
+            let mut nonce = self.nonce.lock().await;
+            let next = match *nonce {
+                Some(next) => next,
+                None => self.rpc.transaction_count(&self.address()).await?,
+            };
+            let raw = Eip1559Transaction {
+                chain_id,
+                nonce: next,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                gas_limit,
+                to: Some(to),
+                value: 0,
+                data: request.data,
+            }
+            .sign(&self.signer);
+
+            match self.rpc.send_raw_transaction(&raw).await {
+                Ok(hash) => {
+                    *nonce = Some(next + 1);
+                    hash
+                }
+                Err(e) => {
+                    *nonce = None;
+                    return Err(ExecutorError::SubmissionFailed(e.to_string()));
+                }
+            }
+        };
+        debug!(tx = %hex::encode(tx_hash), gas_limit, "Transaction submitted");
+
+        self.wait_for_receipt(&tx_hash).await
+    }
+
+    /// (max fee, priority fee) per gas: twice the next base fee plus the tip, capped.
+    async fn fees(&self) -> ExecutorResult<(u128, u128)> {
+        let base_fee = self.rpc.next_base_fee().await?;
+        let priority_fee = self.rpc.max_priority_fee().await?;
+        let cap = self.config.max_fee_per_gas_gwei as u128 * 1_000_000_000;
+        if base_fee > cap {
+            return Err(ExecutorError::SubmissionFailed(format!(
+                "base fee {} wei exceeds cap of {} gwei",
+                base_fee, self.config.max_fee_per_gas_gwei
+            )));
+        }
+        let max_fee = (base_fee * 2 + priority_fee).min(cap);
+        Ok((max_fee, priority_fee.min(max_fee - base_fee)))
+    }
+
+    async fn wait_for_receipt(&self, tx_hash: &[u8; 32]) -> ExecutorResult<TransactionReceipt> {
+        let started = Instant::now();
+        loop {
+            if let Some(receipt) = self.rpc.transaction_receipt(tx_hash).await? {
+                return Ok(receipt);
+            }
+            if started.elapsed() >= self.config.receipt_timeout {
+                return Err(ExecutorError::Timeout(format!(
+                    "no receipt for 0x{} after {:?}",
+                    hex::encode(tx_hash),
+                    self.config.receipt_timeout
+                )));
+            }
+            tokio::time::sleep(self.config.receipt_poll_interval).await;
+        }
+    }
+
+    async fn call(&self, to: EvmAddress, data: Vec<u8>) -> ExecutorResult<Vec<u8>> {
+        self.rpc
+            .call(&CallRequest {
+                from: Some(self.address()),
+                to,
+                data,
+            })
+            .await
+    }
+
+    /// Approve the router for the maximum amount if the allowance is below `amount`.
+    async fn ensure_allowance(&self, token: EvmAddress, amount: u128) -> ExecutorResult<()> {
+        let spender = self.config.protocol.router();
+        let allowance = abi::decode_uint(
+            &self
+                .call(
+                    token,
+                    abi::encode_call(
+                        "allowance(address,address)",
+                        &[Token::Address(self.address()), Token::Address(spender)],
+                    ),
+                )
+                .await?,
+            0,
+        )?;
+        if allowance >= amount {
+            return Ok(());
+        }
+
+        info!(token = %hex::encode(token), "Approving router");
+        let receipt = self
+            .send_transaction(
+                token,
+                abi::encode_call(
+                    "approve(address,uint256)",
+                    &[Token::Address(spender), Token::Word(UINT256_MAX)],
+                ),
+            )
+            .await?;
+        if !receipt.status {
+            return Err(ExecutorError::OrderRejected(format!(
+                "approve reverted in tx 0x{}",
+                hex::encode(receipt.transaction_hash)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Pool address for a pair (zero address if none).
+    async fn pool(&self, token_a: EvmAddress, token_b: EvmAddress) -> ExecutorResult<EvmAddress> {
+        let data = match self.config.protocol {
+            EvmDexProtocol::UniswapV2 { factory, .. } => {
+                let data = abi::encode_call(
+                    "getPair(address,address)",
+                    &[Token::Address(token_a), Token::Address(token_b)],
+                );
+                self.call(factory, data).await?
+            }
+            EvmDexProtocol::UniswapV3 { factory, fee, .. } => {
+                let data = abi::encode_call(
+                    "getPool(address,address,uint24)",
+                    &[
+                        Token::Address(token_a),
+                        Token::Address(token_b),
+                        Token::Uint(fee as u128),
+                    ],
+                );
+                self.call(factory, data).await?
+            }
+        };
+        abi::decode_address(&data, 0)
+    }
+}
+
+/// Sum of `token` transfers to `recipient` in the receipt's logs.
+fn received_amount(
+    receipt: &TransactionReceipt,
+    token: &EvmAddress,
+    recipient: &EvmAddress,
+) -> u128 {
+    receipt
+        .logs
+        .iter()
+        .filter(|log| {
+            log.address == *token
+                && log.topics.len() == 3
+                && log.topics[0] == TRANSFER_TOPIC
+                && log.topics[2][12..] == recipient[..]
+        })
+        .filter_map(|log| abi::decode_uint(&log.data, 0).ok())
+        .fold(0u128, u128::saturating_add)
+}
+
+fn to_u64(value: u128, what: &str) -> ExecutorResult<u64> {
+    u64::try_from(value)
+        .map_err(|_| ExecutorError::ExchangeError(format!("{} {} overflows u64", what, value)))
+}
+
+#[async_trait]
+impl DexClient for EvmDexClient {
+    async fn swap(&self, params: &SwapParams) -> ExecutorResult<SwapResult> {
+        let token_in = evm_address(&params.token_in)?;
+        let token_out = evm_address(&params.token_out)?;
+        let recipient = evm_address(&params.recipient)?;
+
+        if self.config.auto_approve {
+            self.ensure_allowance(token_in, params.amount_in as u128)
+                .await?;
+        }
+
+        let data = match self.config.protocol {
+            EvmDexProtocol::UniswapV2 { .. } => abi::encode_call(
+                "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+                &[
+                    Token::Uint(params.amount_in as u128),
+                    Token::Uint(params.min_amount_out as u128),
+                    Token::AddressArray(vec![token_in, token_out]),
+                    Token::Address(recipient),
+                    Token::Uint(params.deadline as u128),
+                ],
+            ),
+            EvmDexProtocol::UniswapV3 { fee, .. } => abi::encode_call(
+                "exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+                &[
+                    Token::Address(token_in),
+                    Token::Address(token_out),
+                    Token::Uint(fee as u128),
+                    Token::Address(recipient),
+                    Token::Uint(params.deadline as u128),
+                    Token::Uint(params.amount_in as u128),
+                    Token::Uint(params.min_amount_out as u128),
+                    Token::Uint(0),
+                ],
+            ),
+        };
+
+        let receipt = self
+            .send_transaction(self.config.protocol.router(), data)
+            .await?;
+        if !receipt.status {
+            warn!(tx = %hex::encode(receipt.transaction_hash), "Swap reverted");
+            return Err(ExecutorError::OrderRejected(format!(
+                "swap reverted in tx 0x{}",
+                hex::encode(receipt.transaction_hash)
+            )));
+        }
+
+        Ok(SwapResult {
+            tx_hash: receipt.transaction_hash,
+            amount_out: to_u64(
+                received_amount(&receipt, &token_out, &recipient),
+                "amount out",
+            )?,
+            gas_used: receipt.gas_used,
+            gas_price: u64::try_from(receipt.effective_gas_price).unwrap_or(u64::MAX),
+            block_number: receipt.block_number,
+        })
+    }
+
+    async fn quote(&self, params: &SwapParams) -> ExecutorResult<u64> {
+        let token_in = evm_address(&params.token_in)?;
+        let token_out = evm_address(&params.token_out)?;
+
+        let amount_out = match self.config.protocol {
+            EvmDexProtocol::UniswapV2 { router, .. } => {
+                let data = abi::encode_call(
+                    "getAmountsOut(uint256,address[])",
+                    &[
+                        Token::Uint(params.amount_in as u128),
+                        Token::AddressArray(vec![token_in, token_out]),
+                    ],
+                );
+                let amounts = abi::decode_uint_array(&self.call(router, data).await?, 0)?;
+                amounts.last().copied().ok_or_else(|| {
+                    ExecutorError::ExchangeError("getAmountsOut returned no amounts".to_string())
+                })?
+            }
+            EvmDexProtocol::UniswapV3 { quoter, fee, .. } => {
+                // QuoterV2 reverts internally and is not a view, but eth_call returns its result
+                let data = abi::encode_call(
+                    "quoteExactInputSingle((address,address,uint256,uint24,uint160))",
+                    &[
+                        Token::Address(token_in),
+                        Token::Address(token_out),
+                        Token::Uint(params.amount_in as u128),
+                        Token::Uint(fee as u128),
+                        Token::Uint(0),
+                    ],
+                );
+                abi::decode_uint(&self.call(quoter, data).await?, 0)?
+            }
+        };
+        to_u64(amount_out, "quote")
+    }
+
+    async fn pool_exists(&self, token_a: &[u8; 32], token_b: &[u8; 32]) -> ExecutorResult<bool> {
+        let pool = self
+            .pool(evm_address(token_a)?, evm_address(token_b)?)
+            .await?;
+        Ok(pool != [0u8; 20])
+    }
+
+    /// V2: reserve of `token_a` in the pair. V3: the pool's in-range liquidity
+    /// (saturated to u64). Zero if there is no pool.
+    async fn get_liquidity(&self, token_a: &[u8; 32], token_b: &[u8; 32]) -> ExecutorResult<u64> {
+        let (token_a, token_b) = (evm_address(token_a)?, evm_address(token_b)?);
+        let pool = self.pool(token_a, token_b).await?;
+        if pool == [0u8; 20] {
+            return Ok(0);
+        }
+
+        match self.config.protocol {
+            EvmDexProtocol::UniswapV2 { .. } => {
+                let reserves = self
+                    .call(pool, abi::encode_call("getReserves()", &[]))
+                    .await?;
+                // token0 is the lower address
+                let index = if token_a < token_b { 0 } else { 1 };
+                to_u64(abi::decode_uint(&reserves, index)?, "reserve")
+            }
+            EvmDexProtocol::UniswapV3 { .. } => {
+                let liquidity = self
+                    .call(pool, abi::encode_call("liquidity()", &[]))
+                    .await?;
+                Ok(u64::try_from(abi::decode_uint(&liquidity, 0)?).unwrap_or(u64::MAX))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm::{address_word, keccak256};
+    use arbitrage_core::{Chain, Exchange};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex as StdMutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const DEV_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const ROUTER: EvmAddress = [0xaa; 20];
+    const TOKEN_IN: EvmAddress = [0x01; 20];
+    const TOKEN_OUT: EvmAddress = [0x02; 20];
+    const RECIPIENT: EvmAddress = [0x03; 20];
+
+    type Handler = dyn Fn(&str, &Value) -> Value + Send + Sync;
+
+    /// Minimal JSON-RPC node: one request per connection.
+    async fn spawn_node(handler: Arc<Handler>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    let body = loop {
+                        let n = stream.read(&mut chunk).await.unwrap();
+                        if n == 0 {
+                            return;
+                        }
+                        buf.extend_from_slice(&chunk[..n]);
+                        let text = String::from_utf8_lossy(&buf);
+                        if let Some(end) = text.find("\r\n\r\n") {
+                            let length = text[..end]
+                                .lines()
+                                .find_map(|line| {
+                                    let (name, value) = line.split_once(':')?;
+                                    name.eq_ignore_ascii_case("content-length")
+                                        .then(|| value.trim().parse::<usize>().ok())?
+                                })
+                                .unwrap_or(0);
+                            if buf.len() >= end + 4 + length {
+                                break buf[end + 4..end + 4 + length].to_vec();
+                            }
+                        }
+                    };
+                    let request: Value = serde_json::from_slice(&body).unwrap();
+                    let method = request["method"].as_str().unwrap();
+                    let result = handler(method, &request["params"]);
+                    let body = json!({"jsonrpc": "2.0", "id": request["id"], "result": result})
+                        .to_string();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+        url
+    }
+
+    fn word(value: u128) -> String {
+        format!("{:064x}", value)
+    }
+
+    fn hex_data(bytes: &[u8]) -> String {
+        format!("0x{}", hex::encode(bytes))
+    }
+
+    /// Nonce of a signed type-2 transaction (second RLP field, single byte here).
+    fn raw_nonce(raw: &[u8]) -> u8 {
+        let header = if raw[1] >= 0xf8 {
+            1 + (raw[1] - 0xf7) as usize
+        } else {
+            1
+        };
+        let chain_id_len = 1 + (raw[1 + header] - 0x80) as usize;
+        let nonce = raw[1 + header + chain_id_len];
+        if nonce == 0x80 {
+            0
+        } else {
+            nonce
+        }
+    }
+
+    #[derive(Default)]
+    struct NodeLog {
+        methods: Vec<String>,
+        raw_txs: Vec<Vec<u8>>,
+    }
+
+    fn v2_node(log: Arc<StdMutex<NodeLog>>, swap_status: &'static str) -> Arc<Handler> {
+        Arc::new(move |method, params| {
+            let mut log = log.lock().unwrap();
+            log.methods.push(method.to_string());
+            match method {
+                "eth_chainId" => json!("0x7a69"),
+                "eth_getTransactionCount" => json!("0x5"),
+                "eth_estimateGas" => json!("0x249f0"),
+                "eth_feeHistory" => json!({"baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"]}),
+                "eth_maxPriorityFeePerGas" => json!("0x3b9aca00"),
+                "eth_call" => {
+                    let data = params[0]["data"].as_str().unwrap();
+                    match &data[2..10] {
+                        // allowance: none yet
+                        "dd62ed3e" => json!(format!("0x{}", word(0))),
+                        // getAmountsOut(1000, path) -> [1000, 997]
+                        "d06ca61f" => json!(format!(
+                            "0x{}{}{}{}",
+                            word(32),
+                            word(2),
+                            word(1000),
+                            word(997)
+                        )),
+                        selector => panic!("unexpected call {}", selector),
+                    }
+                }
+                "eth_sendRawTransaction" => {
+                    let raw = hex::decode(&params[0].as_str().unwrap()[2..]).unwrap();
+                    let hash = keccak256(&raw);
+                    log.raw_txs.push(raw);
+                    json!(hex_data(&hash))
+                }
+                "eth_getTransactionReceipt" => {
+                    let is_swap = log.raw_txs.len() == 2;
+                    json!({
+                        "transactionHash": params[0],
+                        "blockNumber": "0x64",
+                        "status": if is_swap { swap_status } else { "0x1" },
+                        "gasUsed": "0x1d4c0",
+                        "effectiveGasPrice": "0x77359400",
+                        "logs": [{
+                            "address": hex_data(&TOKEN_OUT),
+                            "topics": [
+                                hex_data(&TRANSFER_TOPIC),
+                                hex_data(&address_word(&ROUTER)),
+                                hex_data(&address_word(&RECIPIENT)),
+                            ],
+                            "data": format!("0x{}", word(997)),
+                        }],
+                    })
+                }
+                other => panic!("unexpected method {}", other),
+            }
+        })
+    }
+
+    fn swap_params() -> SwapParams {
+        SwapParams {
+            exchange: Exchange::UniswapV2,
+            chain: Chain::Ethereum,
+            token_in: address_word(&TOKEN_IN),
+            token_out: address_word(&TOKEN_OUT),
+            amount_in: 1000,
+            min_amount_out: 990,
+            deadline: u64::MAX,
+            recipient: address_word(&RECIPIENT),
+        }
+    }
+
+    fn client(url: String) -> EvmDexClient {
+        let mut config = EvmDexConfig::new(
+            url,
+            EvmDexProtocol::UniswapV2 {
+                router: ROUTER,
+                factory: [0xbb; 20],
+            },
+        );
+        config.chain_id = Some(31337);
+        config.receipt_poll_interval = Duration::from_millis(10);
+        EvmDexClient::new(config, LocalSigner::from_hex(DEV_KEY).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_v2_quote_approve_and_swap() {
+        let log = Arc::new(StdMutex::new(NodeLog::default()));
+        let client = client(spawn_node(v2_node(log.clone(), "0x1")).await);
+
+        assert_eq!(client.quote(&swap_params()).await.unwrap(), 997);
+
+        let result = client.swap(&swap_params()).await.unwrap();
+        assert_eq!(result.amount_out, 997);
+        assert_eq!(result.gas_used, 120_000);
+        assert_eq!(result.gas_price, 2_000_000_000);
+        assert_eq!(result.block_number, 100);
+
+        let log = log.lock().unwrap();
+        // approve then swap, with consecutive nonces from one nonce lookup
+        assert_eq!(log.raw_txs.len(), 2);
+        assert_eq!(result.tx_hash, keccak256(&log.raw_txs[1]));
+        assert_eq!(raw_nonce(&log.raw_txs[0]), 5);
+        assert_eq!(raw_nonce(&log.raw_txs[1]), 6);
+        assert_eq!(
+            log.methods
+                .iter()
+                .filter(|m| *m == "eth_getTransactionCount")
+                .count(),
+            1
+        );
+        let swap_data = &log.raw_txs[1];
+        let selector =
+            abi::selector("swapExactTokensForTokens(uint256,uint256,address[],address,uint256)");
+        assert!(swap_data.windows(4).any(|w| w == selector));
+    }
+
+    #[tokio::test]
+    async fn test_reverted_swap_is_rejected() {
+        let log = Arc::new(StdMutex::new(NodeLog::default()));
+        let client = client(spawn_node(v2_node(log, "0x0")).await);
+
+        let result = client.swap(&swap_params()).await;
+        assert!(matches!(result, Err(ExecutorError::OrderRejected(_))));
+    }
+
+    #[tokio::test]
+    async fn test_chain_id_mismatch() {
+        let log = Arc::new(StdMutex::new(NodeLog::default()));
+        let mut client = client(spawn_node(v2_node(log, "0x1")).await);
+        client.config.chain_id = Some(1);
+
+        let result = client.swap(&swap_params()).await;
+        assert!(matches!(result, Err(ExecutorError::InvalidParameters(_))));
+    }
+
+    #[test]
+    fn test_received_amount_filters_transfers() {
+        let transfer = |token: EvmAddress, to: EvmAddress, amount: u128| super::super::ReceiptLog {
+            address: token,
+            topics: vec![TRANSFER_TOPIC, address_word(&ROUTER), address_word(&to)],
+            data: hex::decode(word(amount)).unwrap(),
+        };
+        let receipt = TransactionReceipt {
+            transaction_hash: [0u8; 32],
+            block_number: 1,
+            status: true,
+            gas_used: 0,
+            effective_gas_price: 0,
+            logs: vec![
+                transfer(TOKEN_OUT, RECIPIENT, 600),
+                transfer(TOKEN_OUT, RECIPIENT, 400),
+                transfer(TOKEN_OUT, ROUTER, 5),
+                transfer(TOKEN_IN, RECIPIENT, 7),
+            ],
+        };
+        assert_eq!(received_amount(&receipt, &TOKEN_OUT, &RECIPIENT), 1000);
+        assert_eq!(
+            keccak256(b"Transfer(address,address,uint256)"),
+            TRANSFER_TOPIC
+        );
+    }
+}
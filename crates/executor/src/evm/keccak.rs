@@ -0,0 +1,25 @@
+//! Keccak-256 (the pre-standard SHA-3 padding used by Ethereum).
+
+use sha3::{Digest, Keccak256};
+
+/// Keccak-256 hash.
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak256_vectors() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex::encode(&keccak256(b"transfer(address,uint256)")[..4]),
+            "a9059cbb"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! EVM DEX execution over JSON-RPC.
+//!
+//! [`EvmDexClient`] implements [`DexClient`](crate::DexClient) for Uniswap V2-
+//! and V3-style routers: it quotes with `eth_call`, signs EIP-1559 transactions
+//! locally, manages the account nonce and gas limits, and reads the amount
+//! received from the receipt's ERC-20 `Transfer` logs. It only relies on
+//! standard `eth_*` methods, so it runs against a local anvil or hardhat node.
+//!
+//! [`SwapParams`](crate::SwapParams) carries addresses as 32-byte words; the
+//! EVM address is the last 20 bytes (ABI word layout).
+
+mod abi;
+mod client;
+mod keccak;
+mod rpc;
+mod signer;
+mod transaction;
+
+pub use client::{EvmDexClient, EvmDexConfig, EvmDexProtocol};
+pub use keccak::keccak256;
+pub use rpc::{CallRequest, FeeHistory, JsonRpcClient, ReceiptLog, TransactionReceipt};
+pub use signer::{LocalSigner, Signature};
+pub use transaction::{transaction_hash, Eip1559Transaction};
+
+use crate::{ExecutorError, ExecutorResult};
+
+/// 20-byte EVM account or contract address.
+pub type EvmAddress = [u8; 20];
+
+/// EVM address held in a 32-byte word; fails if the upper 12 bytes are set.
+pub fn evm_address(word: &[u8; 32]) -> ExecutorResult<EvmAddress> {
+    if word[..12].iter().any(|&b| b != 0) {
+        return Err(ExecutorError::InvalidParameters(format!(
+            "0x{} is not an EVM address",
+            hex::encode(word)
+        )));
+    }
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&word[12..]);
+    Ok(address)
+}
+
+/// 32-byte word holding an EVM address.
+pub fn address_word(address: &EvmAddress) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address);
+    word
+}
+
+/// Parse a hex address (with or without `0x`; checksum casing is not verified).
+pub fn parse_evm_address(value: &str) -> ExecutorResult<EvmAddress> {
+    hex::decode(value.trim().trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| EvmAddress::try_from(bytes).ok())
+        .ok_or_else(|| ExecutorError::InvalidParameters(format!("invalid address {}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_words() {
+        let address = parse_evm_address("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2").unwrap();
+        let word = address_word(&address);
+        assert_eq!(&word[..12], &[0u8; 12]);
+        assert_eq!(evm_address(&word).unwrap(), address);
+        assert!(evm_address(&[0xff; 32]).is_err());
+        assert!(parse_evm_address("0x1234").is_err());
+    }
+}
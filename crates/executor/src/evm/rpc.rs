@@ -0,0 +1,361 @@
+//! Ethereum JSON-RPC over HTTP.
+//!
+//! Covers the subset of `eth_*` methods needed to quote, sign and submit swaps.
+//! Quantities are hex strings on the wire and are parsed into integers here.
+
+use super::EvmAddress;
+use crate::{ExecutorError, ExecutorResult};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Read-only call or gas estimate.
+#[derive(Debug, Clone, Default)]
+pub struct CallRequest {
+    pub from: Option<EvmAddress>,
+    pub to: EvmAddress,
+    pub data: Vec<u8>,
+}
+
+impl CallRequest {
+    fn to_json(&self) -> Value {
+        let mut request = json!({
+            "to": encode_data(&self.to),
+            "data": encode_data(&self.data),
+        });
+        if let Some(from) = self.from {
+            request["from"] = Value::String(encode_data(&from));
+        }
+        request
+    }
+}
+
+/// Log emitted by a mined transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptLog {
+    pub address: EvmAddress,
+    pub topics: Vec<[u8; 32]>,
+    pub data: Vec<u8>,
+}
+
+/// Receipt of a mined transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionReceipt {
+    pub transaction_hash: [u8; 32],
+    pub block_number: u64,
+    /// False if the transaction reverted.
+    pub status: bool,
+    pub gas_used: u64,
+    /// Price paid per gas (wei).
+    pub effective_gas_price: u128,
+    pub logs: Vec<ReceiptLog>,
+}
+
+/// Fee history of recent blocks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeHistory {
+    /// Base fee per gas of each block, plus one entry for the next block (wei).
+    pub base_fee_per_gas: Vec<u128>,
+    /// Priority fees per block at the requested percentiles (wei).
+    pub reward: Vec<Vec<u128>>,
+}
+
+impl FeeHistory {
+    /// Base fee of the block after the range.
+    pub fn next_base_fee(&self) -> Option<u128> {
+        self.base_fee_per_gas.last().copied()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFeeHistory {
+    base_fee_per_gas: Vec<String>,
+    #[serde(default)]
+    reward: Vec<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawReceipt {
+    transaction_hash: String,
+    block_number: String,
+    status: Option<String>,
+    gas_used: String,
+    effective_gas_price: Option<String>,
+    #[serde(default)]
+    logs: Vec<RawLog>,
+}
+
+#[derive(Deserialize)]
+struct RawLog {
+    address: String,
+    topics: Vec<String>,
+    data: String,
+}
+
+impl TryFrom<RawReceipt> for TransactionReceipt {
+    type Error = ExecutorError;
+
+    fn try_from(raw: RawReceipt) -> ExecutorResult<Self> {
+        let logs = raw
+            .logs
+            .into_iter()
+            .map(|log| {
+                Ok(ReceiptLog {
+                    address: decode_fixed(&log.address)?,
+                    topics: log
+                        .topics
+                        .iter()
+                        .map(|topic| decode_fixed(topic))
+                        .collect::<ExecutorResult<_>>()?,
+                    data: decode_data(&log.data)?,
+                })
+            })
+            .collect::<ExecutorResult<_>>()?;
+        Ok(Self {
+            transaction_hash: decode_fixed(&raw.transaction_hash)?,
+            block_number: decode_quantity(&raw.block_number)? as u64,
+            // Pre-Byzantium receipts have no status; treat them as successful
+            status: raw
+                .status
+                .map_or(Ok(1), |status| decode_quantity(&status))?
+                == 1,
+            gas_used: decode_quantity(&raw.gas_used)? as u64,
+            effective_gas_price: raw
+                .effective_gas_price
+                .map_or(Ok(0), |price| decode_quantity(&price))?,
+            logs,
+        })
+    }
+}
+
+/// JSON-RPC client for an Ethereum node (anvil, hardhat, geth, hosted RPC).
+pub struct JsonRpcClient {
+    http: reqwest::Client,
+    url: String,
+    next_id: AtomicU64,
+}
+
+impl JsonRpcClient {
+    /// Create a client for the given endpoint.
+    pub fn new(url: impl Into<String>, timeout: Duration) -> ExecutorResult<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .no_proxy()
+            .build()
+            .map_err(|e| ExecutorError::NetworkError(e.to_string()))?;
+        Ok(Self {
+            http,
+            url: url.into(),
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    /// Endpoint URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Send a request and return its `result`.
+    pub async fn request(&self, method: &str, params: Value) -> ExecutorResult<Value> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+        let response: Value = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ExecutorError::NetworkError(format!("{}: {}", method, e)))?
+            .json()
+            .await
+            .map_err(|e| ExecutorError::NetworkError(format!("{}: {}", method, e)))?;
+
+        if let Some(error) = response.get("error") {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(ExecutorError::ExchangeError(format!(
+                "{}: {}",
+                method, message
+            )));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| ExecutorError::ExchangeError(format!("{}: missing result", method)))
+    }
+
+    /// `eth_chainId`.
+    pub async fn chain_id(&self) -> ExecutorResult<u64> {
+        let result = self.request("eth_chainId", json!([])).await?;
+        Ok(decode_quantity(as_str(&result)?)? as u64)
+    }
+
+    /// `eth_getTransactionCount` including pending transactions.
+    pub async fn transaction_count(&self, address: &EvmAddress) -> ExecutorResult<u64> {
+        let result = self
+            .request(
+                "eth_getTransactionCount",
+                json!([encode_data(address), "pending"]),
+            )
+            .await?;
+        Ok(decode_quantity(as_str(&result)?)? as u64)
+    }
+
+    /// `eth_call` against the latest block.
+    pub async fn call(&self, request: &CallRequest) -> ExecutorResult<Vec<u8>> {
+        let result = self
+            .request("eth_call", json!([request.to_json(), "latest"]))
+            .await?;
+        decode_data(as_str(&result)?)
+    }
+
+    /// `eth_estimateGas`.
+    pub async fn estimate_gas(&self, request: &CallRequest) -> ExecutorResult<u64> {
+        let result = self
+            .request("eth_estimateGas", json!([request.to_json()]))
+            .await?;
+        Ok(decode_quantity(as_str(&result)?)? as u64)
+    }
+
+    /// `eth_feeHistory` over the last `block_count` blocks, with priority fee
+    /// rewards at the given percentiles (0-100).
+    pub async fn fee_history(
+        &self,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> ExecutorResult<FeeHistory> {
+        let result = self
+            .request(
+                "eth_feeHistory",
+                json!([format!("0x{:x}", block_count), "latest", reward_percentiles]),
+            )
+            .await?;
+        let raw: RawFeeHistory = serde_json::from_value(result)
+            .map_err(|e| ExecutorError::ExchangeError(format!("invalid fee history: {}", e)))?;
+        Ok(FeeHistory {
+            base_fee_per_gas: raw
+                .base_fee_per_gas
+                .iter()
+                .map(|fee| decode_quantity(fee))
+                .collect::<ExecutorResult<_>>()?,
+            reward: raw
+                .reward
+                .iter()
+                .map(|block| block.iter().map(|fee| decode_quantity(fee)).collect())
+                .collect::<ExecutorResult<_>>()?,
+        })
+    }
+
+    /// Base fee of the next block, from `eth_feeHistory`.
+    pub async fn next_base_fee(&self) -> ExecutorResult<u128> {
+        self.fee_history(1, &[])
+            .await?
+            .next_base_fee()
+            .ok_or_else(|| {
+                ExecutorError::ExchangeError("eth_feeHistory: missing baseFeePerGas".to_string())
+            })
+    }
+
+    /// `eth_maxPriorityFeePerGas`.
+    pub async fn max_priority_fee(&self) -> ExecutorResult<u128> {
+        let result = self.request("eth_maxPriorityFeePerGas", json!([])).await?;
+        decode_quantity(as_str(&result)?)
+    }
+
+    /// `eth_sendRawTransaction`; returns the transaction hash.
+    pub async fn send_raw_transaction(&self, raw: &[u8]) -> ExecutorResult<[u8; 32]> {
+        let result = self
+            .request("eth_sendRawTransaction", json!([encode_data(raw)]))
+            .await?;
+        decode_fixed(as_str(&result)?)
+    }
+
+    /// `eth_getTransactionReceipt`; None while the transaction is pending.
+    pub async fn transaction_receipt(
+        &self,
+        hash: &[u8; 32],
+    ) -> ExecutorResult<Option<TransactionReceipt>> {
+        let result = self
+            .request("eth_getTransactionReceipt", json!([encode_data(hash)]))
+            .await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        let raw: RawReceipt = serde_json::from_value(result)
+            .map_err(|e| ExecutorError::ExchangeError(format!("invalid receipt: {}", e)))?;
+        raw.try_into().map(Some)
+    }
+}
+
+fn as_str(value: &Value) -> ExecutorResult<&str> {
+    value
+        .as_str()
+        .ok_or_else(|| ExecutorError::ExchangeError(format!("expected hex string, got {}", value)))
+}
+
+/// `0x`-prefixed hex of arbitrary bytes.
+pub(crate) fn encode_data(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn decode_data(value: &str) -> ExecutorResult<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| ExecutorError::ExchangeError(format!("invalid hex data {}: {}", value, e)))
+}
+
+fn decode_fixed<const N: usize>(value: &str) -> ExecutorResult<[u8; N]> {
+    decode_data(value)?
+        .try_into()
+        .map_err(|_| ExecutorError::ExchangeError(format!("expected {} bytes, got {}", N, value)))
+}
+
+fn decode_quantity(value: &str) -> ExecutorResult<u128> {
+    let digits = value.trim_start_matches("0x");
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u128::from_str_radix(digits, 16)
+        .map_err(|e| ExecutorError::ExchangeError(format!("invalid quantity {}: {}", value, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_receipt() {
+        let raw: RawReceipt = serde_json::from_value(json!({
+            "transactionHash": format!("0x{}", "ab".repeat(32)),
+            "blockNumber": "0x10",
+            "status": "0x1",
+            "gasUsed": "0x249f0",
+            "effectiveGasPrice": "0x3b9aca00",
+            "logs": [{
+                "address": format!("0x{}", "11".repeat(20)),
+                "topics": [format!("0x{}", "22".repeat(32))],
+                "data": "0x"
+            }]
+        }))
+        .unwrap();
+        let receipt = TransactionReceipt::try_from(raw).unwrap();
+        assert_eq!(receipt.block_number, 16);
+        assert!(receipt.status);
+        assert_eq!(receipt.gas_used, 150_000);
+        assert_eq!(receipt.effective_gas_price, 1_000_000_000);
+        assert_eq!(receipt.logs[0].address, [0x11; 20]);
+        assert!(receipt.logs[0].data.is_empty());
+
+        assert_eq!(decode_quantity("0x0").unwrap(), 0);
+        assert!(decode_quantity("0xzz").is_err());
+        assert!(decode_fixed::<20>("0x1234").is_err());
+    }
+}
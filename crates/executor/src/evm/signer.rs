@@ -0,0 +1,116 @@
+//! secp256k1 transaction signing with a local private key.
+//!
+//! Signing is delegated to `k256`: deterministic RFC 6979 nonces and
+//! constant-time scalar arithmetic, normalized to the low-s form Ethereum
+//! requires.
+
+use super::keccak::keccak256;
+use super::EvmAddress;
+use crate::{ExecutorError, ExecutorResult};
+use k256::ecdsa::SigningKey;
+
+/// Recoverable ECDSA signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+    /// Parity of the nonce point's y coordinate (the EIP-2718 `y_parity`).
+    pub y_parity: bool,
+}
+
+/// Signs with a private key held in memory.
+#[derive(Clone)]
+pub struct LocalSigner {
+    key: SigningKey,
+    address: EvmAddress,
+}
+
+impl std::fmt::Debug for LocalSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("address", &format!("0x{}", hex::encode(self.address)))
+            .finish_non_exhaustive()
+    }
+}
+
+impl LocalSigner {
+    /// Create a signer from a 32-byte private key.
+    pub fn from_bytes(secret: &[u8; 32]) -> ExecutorResult<Self> {
+        let key = SigningKey::from_bytes(secret.into()).map_err(|_| {
+            ExecutorError::InvalidParameters("private key is out of range".to_string())
+        })?;
+        // Uncompressed SEC1 point without the 0x04 tag
+        let public = key.verifying_key().to_encoded_point(false);
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&keccak256(&public.as_bytes()[1..])[12..]);
+        Ok(Self { key, address })
+    }
+
+    /// Create a signer from a hex private key (with or without `0x`).
+    pub fn from_hex(secret: &str) -> ExecutorResult<Self> {
+        let bytes = hex::decode(secret.trim().trim_start_matches("0x"))
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| {
+                ExecutorError::InvalidParameters("private key must be 32 hex bytes".to_string())
+            })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Address controlled by this key.
+    pub fn address(&self) -> EvmAddress {
+        self.address
+    }
+
+    /// Sign a 32-byte message hash.
+    pub fn sign_hash(&self, hash: &[u8; 32]) -> Signature {
+        // A 32-byte prehash is always accepted
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(hash)
+            .expect("32-byte prehash is a valid secp256k1 message");
+        let (r, s) = signature.split_bytes();
+        Signature {
+            r: r.into(),
+            s: s.into(),
+            y_parity: recovery_id.is_y_odd(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// First default account of anvil and hardhat.
+    const DEV_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn test_dev_key_address() {
+        let signer = LocalSigner::from_hex(DEV_KEY).unwrap();
+        assert_eq!(
+            hex::encode(signer.address()),
+            "f39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+        assert!(LocalSigner::from_bytes(&[0u8; 32]).is_err());
+        assert!(LocalSigner::from_bytes(&[0xff; 32]).is_err());
+        assert!(LocalSigner::from_hex("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_sign_hash_is_deterministic_low_s() {
+        let signer = LocalSigner::from_hex(DEV_KEY).unwrap();
+        let hash = keccak256(b"arbitrage");
+        let signature = signer.sign_hash(&hash);
+        assert_eq!(signature, signer.sign_hash(&hash));
+        assert_eq!(
+            hex::encode(signature.r),
+            "3fdad6bf73b85c96b2d45a461ffd4874b66ee5264088b940e42a29a039013838"
+        );
+        assert_eq!(
+            hex::encode(signature.s),
+            "2572e7008fe9048223bf78bd69b6b7edeb14f86423fd307a9077735db7a6020b"
+        );
+        assert!(!signature.y_parity);
+    }
+}
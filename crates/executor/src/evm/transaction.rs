@@ -0,0 +1,150 @@
+//! EIP-1559 (type 2) transactions and their RLP encoding.
+
+use super::keccak::keccak256;
+use super::signer::{LocalSigner, Signature};
+use super::EvmAddress;
+
+/// EIP-2718 type byte of a dynamic-fee transaction.
+const EIP1559_TX_TYPE: u8 = 0x02;
+
+/// Dynamic-fee transaction with an empty access list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip1559Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    /// Recipient; None for contract creation.
+    pub to: Option<EvmAddress>,
+    /// Native value in wei.
+    pub value: u128,
+    pub data: Vec<u8>,
+}
+
+impl Eip1559Transaction {
+    /// Hash signed by the sender: keccak256(0x02 || rlp(fields)).
+    pub fn signing_hash(&self) -> [u8; 32] {
+        keccak256(&self.encode(None))
+    }
+
+    /// Sign and return the raw transaction for `eth_sendRawTransaction`.
+    pub fn sign(&self, signer: &LocalSigner) -> Vec<u8> {
+        let signature = signer.sign_hash(&self.signing_hash());
+        self.encode(Some(&signature))
+    }
+
+    /// Typed envelope: 0x02 || rlp([chain_id, nonce, ..., access_list, (y_parity, r, s)]).
+    fn encode(&self, signature: Option<&Signature>) -> Vec<u8> {
+        let mut fields = vec![
+            rlp_uint(self.chain_id as u128),
+            rlp_uint(self.nonce as u128),
+            rlp_uint(self.max_priority_fee_per_gas),
+            rlp_uint(self.max_fee_per_gas),
+            rlp_uint(self.gas_limit as u128),
+            rlp_bytes(self.to.as_ref().map_or(&[][..], |to| &to[..])),
+            rlp_uint(self.value),
+            rlp_bytes(&self.data),
+            rlp_list(&[]),
+        ];
+        if let Some(signature) = signature {
+            fields.push(rlp_uint(signature.y_parity as u128));
+            fields.push(rlp_bytes(strip_leading_zeros(&signature.r)));
+            fields.push(rlp_bytes(strip_leading_zeros(&signature.s)));
+        }
+
+        let mut out = vec![EIP1559_TX_TYPE];
+        out.extend(rlp_list(&fields));
+        out
+    }
+}
+
+/// Hash of a signed raw transaction (what the node returns from `eth_sendRawTransaction`).
+pub fn transaction_hash(raw: &[u8]) -> [u8; 32] {
+    keccak256(raw)
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+/// RLP of an unsigned integer (big-endian, no leading zeros; zero is the empty string).
+fn rlp_uint(value: u128) -> Vec<u8> {
+    rlp_bytes(strip_leading_zeros(&value.to_be_bytes()))
+}
+
+/// RLP of a byte string.
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut out = rlp_length_prefix(bytes.len(), 0x80);
+    out.extend_from_slice(bytes);
+    out
+}
+
+/// RLP of a list of already-encoded items.
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload: Vec<u8> = items.concat();
+    let mut out = rlp_length_prefix(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
+
+fn rlp_length_prefix(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let len_bytes = strip_leading_zeros(&len.to_be_bytes()).to_vec();
+    let mut out = vec![offset + 55 + len_bytes.len() as u8];
+    out.extend(len_bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rlp_encoding() {
+        assert_eq!(rlp_uint(0), vec![0x80]);
+        assert_eq!(rlp_uint(15), vec![0x0f]);
+        assert_eq!(rlp_uint(1024), vec![0x82, 0x04, 0x00]);
+        assert_eq!(rlp_bytes(b"dog"), vec![0x83, b'd', b'o', b'g']);
+        assert_eq!(rlp_list(&[]), vec![0xc0]);
+        let long = rlp_bytes(&[0xaa; 60]);
+        assert_eq!(&long[..2], &[0xb8, 60]);
+        assert_eq!(long.len(), 62);
+    }
+
+    #[test]
+    fn test_signed_eip1559_transaction() {
+        let signer = LocalSigner::from_hex(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let tx = Eip1559Transaction {
+            chain_id: 31337,
+            nonce: 0,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee_per_gas: 2_000_000_000,
+            gas_limit: 21_000,
+            to: Some([0x70; 20]),
+            value: 1_000_000_000_000_000_000,
+            data: vec![],
+        };
+        let raw = tx.sign(&signer);
+        assert_eq!(raw[0], EIP1559_TX_TYPE);
+        assert_eq!(
+            hex::encode(&raw),
+            "02f874827a6980843b9aca008477359400825208947070707070707070707070707070707070707070\
+             880de0b6b3a764000080c001a078822af6dd0c5e54fb3233996c0ffcfe1f1985e462856bba7d64f8be\
+             2b2dff0ba0380ed1d20235b4a0f08de603ad517169a985f61dbfed13a5775daef07fed6d66"
+        );
+        assert_eq!(
+            hex::encode(transaction_hash(&raw)),
+            "b64e17c1d6ba445c7cafcc50960867095b0367144d21ee22a27eb8cef020c20a"
+        );
+    }
+}
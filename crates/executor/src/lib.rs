@@ -6,9 +6,11 @@
 pub mod cex;
 pub mod dex;
 pub mod error;
+pub mod evm;
 pub mod order;
 
 pub use cex::*;
 pub use dex::*;
 pub use error::*;
+pub use evm::*;
 pub use order::*;
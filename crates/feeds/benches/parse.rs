@@ -0,0 +1,63 @@
+//! Adapter parse-path benchmarks.
+//!
+//! Run with `cargo bench -p arbitrage-feeds`. Per-message budgets that fail
+//! the build live in `benches/budgets.json` (checked by `tests/parse_budget.rs`).
+
+#[path = "../tests/fixtures/messages.rs"]
+mod messages;
+
+use arbitrage_feeds::{
+    BinanceAdapter, BithumbAdapter, BybitAdapter, CoinbaseAdapter, GateIOAdapter, UpbitAdapter,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use messages::*;
+
+fn binance(c: &mut Criterion) {
+    c.bench_function("binance/parse_ticker", |b| {
+        b.iter(|| BinanceAdapter::parse_ticker(black_box(BINANCE_TICKER), 1))
+    });
+    c.bench_function("binance/parse_book_ticker", |b| {
+        b.iter(|| BinanceAdapter::parse_book_ticker(black_box(BINANCE_BOOK_TICKER), 1))
+    });
+    let depth = binance_depth(20);
+    c.bench_function("binance/parse_partial_depth/20_levels", |b| {
+        b.iter(|| BinanceAdapter::parse_partial_depth(black_box(&depth)))
+    });
+}
+
+fn coinbase(c: &mut Criterion) {
+    c.bench_function("coinbase/parse_ticker", |b| {
+        b.iter(|| CoinbaseAdapter::parse_ticker(black_box(COINBASE_TICKER), 1))
+    });
+}
+
+fn bybit(c: &mut Criterion) {
+    c.bench_function("bybit/parse_ticker", |b| {
+        b.iter(|| BybitAdapter::parse_ticker_with_symbol(black_box(BYBIT_TICKER)))
+    });
+}
+
+fn gateio(c: &mut Criterion) {
+    c.bench_function("gateio/parse_ticker", |b| {
+        b.iter(|| GateIOAdapter::parse_ticker_with_symbol(black_box(GATEIO_TICKER)))
+    });
+}
+
+fn upbit(c: &mut Criterion) {
+    c.bench_function("upbit/parse_ticker", |b| {
+        b.iter(|| UpbitAdapter::parse_ticker(black_box(UPBIT_TICKER), 1))
+    });
+    let orderbook = upbit_orderbook(15);
+    c.bench_function("upbit/parse_orderbook/15_levels", |b| {
+        b.iter(|| UpbitAdapter::parse_orderbook(black_box(&orderbook), 1))
+    });
+}
+
+fn bithumb(c: &mut Criterion) {
+    c.bench_function("bithumb/parse_ticker", |b| {
+        b.iter(|| BithumbAdapter::parse_ticker(black_box(BITHUMB_TICKER), 1))
+    });
+}
+
+criterion_group!(benches, binance, coinbase, bybit, gateio, upbit, bithumb);
+criterion_main!(benches);
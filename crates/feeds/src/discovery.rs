@@ -5,6 +5,7 @@
 
 use crate::symbol_mapping::SymbolMappings;
 use crate::FeedError;
+use arbitrage_core::{FixedPoint, MarketRules};
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use tracing::{debug, warn};
@@ -20,6 +21,8 @@ pub struct MarketInfo {
     pub symbol: String,
     /// Whether trading is enabled
     pub trading_enabled: bool,
+    /// Order size rules (min notional, min quantity, lot size)
+    pub rules: MarketRules,
 }
 
 impl MarketInfo {
@@ -164,6 +167,8 @@ impl MarketDiscovery {
             #[serde(rename = "quoteAsset")]
             quote_asset: String,
             status: String,
+            #[serde(default)]
+            filters: Vec<serde_json::Value>,
         }
 
         let url = "https://api.binance.com/api/v3/exchangeInfo";
@@ -187,11 +192,34 @@ impl MarketDiscovery {
                 || (s.base_asset == "USDT" && s.quote_asset == "USD")
                 || (s.base_asset == "USDC" && (s.quote_asset == "USD" || s.quote_asset == "USDT"))
             })
-            .map(|s| MarketInfo {
-                base: s.base_asset,
-                quote: s.quote_asset,
-                symbol: s.symbol,
-                trading_enabled: s.status == "TRADING",
+            .map(|s| {
+                let mut rules = MarketRules::default();
+                for filter in &s.filters {
+                    let field = |name: &str| {
+                        filter
+                            .get(name)
+                            .and_then(|v| v.as_str())
+                            .map(parse_fixed)
+                            .unwrap_or(0)
+                    };
+                    match filter.get("filterType").and_then(|v| v.as_str()) {
+                        Some("LOT_SIZE") => {
+                            rules.min_qty = field("minQty");
+                            rules.lot_size = field("stepSize");
+                        }
+                        Some("NOTIONAL") | Some("MIN_NOTIONAL") => {
+                            rules.min_notional = field("minNotional");
+                        }
+                        _ => {}
+                    }
+                }
+                MarketInfo {
+                    base: s.base_asset,
+                    quote: s.quote_asset,
+                    symbol: s.symbol,
+                    trading_enabled: s.status == "TRADING",
+                    rules,
+                }
             })
             .collect();
 
@@ -214,6 +242,10 @@ impl MarketDiscovery {
             base_currency: String,
             quote_currency: String,
             status: String,
+            #[serde(default)]
+            base_increment: Option<String>,
+            #[serde(default)]
+            min_market_funds: Option<String>,
         }
 
         let url = "https://api.exchange.coinbase.com/products";
@@ -236,6 +268,11 @@ impl MarketDiscovery {
                 || (p.base_currency == "USDT" && (p.quote_currency == "USD" || p.quote_currency == "USDC"))
             })
             .map(|p| MarketInfo {
+                rules: MarketRules {
+                    min_notional: p.min_market_funds.as_deref().map(parse_fixed).unwrap_or(0),
+                    min_qty: 0,
+                    lot_size: p.base_increment.as_deref().map(parse_fixed).unwrap_or(0),
+                },
                 base: p.base_currency,
                 quote: p.quote_currency,
                 symbol: p.id,
@@ -294,6 +331,7 @@ impl MarketDiscovery {
                     quote: "KRW".to_string(),
                     symbol: m.market,
                     trading_enabled,
+                    rules: krw_market_rules(),
                 }
             })
             .collect();
@@ -341,6 +379,7 @@ impl MarketDiscovery {
                 quote: "KRW".to_string(),
                 symbol: format!("KRW-{}", base),
                 trading_enabled: true,
+                rules: krw_market_rules(),
             })
             .collect();
 
@@ -372,6 +411,18 @@ impl MarketDiscovery {
             #[serde(rename = "quoteCoin")]
             quote_coin: String,
             status: String,
+            #[serde(rename = "lotSizeFilter", default)]
+            lot_size_filter: Option<BybitLotSizeFilter>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct BybitLotSizeFilter {
+            #[serde(rename = "basePrecision", default)]
+            base_precision: Option<String>,
+            #[serde(rename = "minOrderQty", default)]
+            min_order_qty: Option<String>,
+            #[serde(rename = "minOrderAmt", default)]
+            min_order_amt: Option<String>,
         }
 
         let url = "https://api.bybit.com/v5/market/instruments-info?category=spot";
@@ -395,11 +446,23 @@ impl MarketDiscovery {
                 // Also include USDC/USDT pair for exchange rate tracking
                 || (s.base_coin == "USDC" && s.quote_coin == "USDT")
             })
-            .map(|s| MarketInfo {
-                base: s.base_coin,
-                quote: s.quote_coin,
-                symbol: s.symbol,
-                trading_enabled: s.status == "Trading",
+            .map(|s| {
+                let rules = s
+                    .lot_size_filter
+                    .as_ref()
+                    .map(|f| MarketRules {
+                        min_notional: f.min_order_amt.as_deref().map(parse_fixed).unwrap_or(0),
+                        min_qty: f.min_order_qty.as_deref().map(parse_fixed).unwrap_or(0),
+                        lot_size: f.base_precision.as_deref().map(parse_fixed).unwrap_or(0),
+                    })
+                    .unwrap_or_default();
+                MarketInfo {
+                    base: s.base_coin,
+                    quote: s.quote_coin,
+                    symbol: s.symbol,
+                    trading_enabled: s.status == "Trading",
+                    rules,
+                }
             })
             .collect();
 
@@ -430,6 +493,10 @@ impl MarketDiscovery {
             #[serde(rename = "quoteCcy")]
             quote_ccy: String,
             state: String,
+            #[serde(rename = "minSz", default)]
+            min_sz: Option<String>,
+            #[serde(rename = "lotSz", default)]
+            lot_sz: Option<String>,
         }
 
         let url = "https://www.okx.com/api/v5/public/instruments?instType=SPOT";
@@ -453,6 +520,11 @@ impl MarketDiscovery {
                 || (s.base_ccy == "USDC" && s.quote_ccy == "USDT")
             })
             .map(|s| MarketInfo {
+                rules: MarketRules {
+                    min_notional: 0,
+                    min_qty: s.min_sz.as_deref().map(parse_fixed).unwrap_or(0),
+                    lot_size: s.lot_sz.as_deref().map(parse_fixed).unwrap_or(0),
+                },
                 base: s.base_ccy,
                 quote: s.quote_ccy,
                 symbol: s.inst_id,
@@ -479,6 +551,12 @@ impl MarketDiscovery {
             base: String,
             quote: String,
             trade_status: String,
+            #[serde(default)]
+            min_base_amount: Option<String>,
+            #[serde(default)]
+            min_quote_amount: Option<String>,
+            #[serde(default)]
+            amount_precision: Option<u32>,
         }
 
         let url = "https://api.gateio.ws/api/v4/spot/currency_pairs";
@@ -502,6 +580,11 @@ impl MarketDiscovery {
                 || (p.base == "USDC" && (p.quote == "USD" || p.quote == "USDT"))
             })
             .map(|p| MarketInfo {
+                rules: MarketRules {
+                    min_notional: p.min_quote_amount.as_deref().map(parse_fixed).unwrap_or(0),
+                    min_qty: p.min_base_amount.as_deref().map(parse_fixed).unwrap_or(0),
+                    lot_size: p.amount_precision.map(lot_from_decimals).unwrap_or(0),
+                },
                 base: p.base,
                 quote: p.quote,
                 symbol: p.id,
@@ -534,6 +617,12 @@ impl MarketDiscovery {
             base: String,
             quote: String,
             status: String,
+            #[serde(default)]
+            ordermin: Option<String>,
+            #[serde(default)]
+            costmin: Option<String>,
+            #[serde(default)]
+            lot_decimals: Option<u32>,
         }
 
         let url = "https://api.kraken.com/0/public/AssetPairs";
@@ -565,6 +654,11 @@ impl MarketDiscovery {
                     quote: quote.to_string(),
                     symbol,
                     trading_enabled: p.status == "online",
+                    rules: MarketRules {
+                        min_notional: p.costmin.as_deref().map(parse_fixed).unwrap_or(0),
+                        min_qty: p.ordermin.as_deref().map(parse_fixed).unwrap_or(0),
+                        lot_size: p.lot_decimals.map(lot_from_decimals).unwrap_or(0),
+                    },
                 }
             })
             .collect();
@@ -891,6 +985,31 @@ fn normalize_kraken_asset(asset: &str) -> String {
     }
 }
 
+/// Minimum order value on the Korean exchanges (Upbit, Bithumb).
+const KRW_MIN_ORDER: f64 = 5_000.0;
+
+fn krw_market_rules() -> MarketRules {
+    MarketRules {
+        min_notional: FixedPoint::from_f64(KRW_MIN_ORDER).0,
+        ..MarketRules::default()
+    }
+}
+
+/// Parse a decimal string from an exchange API into FixedPoint (0 if invalid).
+fn parse_fixed(value: &str) -> u64 {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .map(|v| (v * FixedPoint::SCALE as f64).round() as u64)
+        .unwrap_or(0)
+}
+
+/// Lot size implied by a quantity precision in decimal places.
+fn lot_from_decimals(decimals: u32) -> u64 {
+    FixedPoint::SCALE / 10u64.pow(decimals.min(8))
+}
+
 fn now_ms() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -909,6 +1028,7 @@ mod tests {
             quote: "USDT".to_string(),
             symbol: "BTCUSDT".to_string(),
             trading_enabled: true,
+            rules: MarketRules::default(),
         };
         assert_eq!(market.pair_key(), "BTC/USD");
         assert_eq!(market.normalized_quote(), "USD");
@@ -934,12 +1054,14 @@ mod tests {
                         quote: "USDT".to_string(),
                         symbol: "BTCUSDT".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                     MarketInfo {
                         base: "ETH".to_string(),
                         quote: "USDT".to_string(),
                         symbol: "ETHUSDT".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                 ],
                 updated_at: 0,
@@ -955,12 +1077,14 @@ mod tests {
                         quote: "USD".to_string(),
                         symbol: "BTC-USD".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                     MarketInfo {
                         base: "SOL".to_string(),
                         quote: "USD".to_string(),
                         symbol: "SOL-USD".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                 ],
                 updated_at: 0,
@@ -990,12 +1114,14 @@ mod tests {
                         quote: "USDT".to_string(),
                         symbol: "BTCUSDT".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                     MarketInfo {
                         base: "ETH".to_string(),
                         quote: "USDT".to_string(),
                         symbol: "ETHUSDT".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                 ],
                 updated_at: 0,
@@ -1011,12 +1137,14 @@ mod tests {
                         quote: "USD".to_string(),
                         symbol: "BTC-USD".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                     MarketInfo {
                         base: "SOL".to_string(),
                         quote: "USD".to_string(),
                         symbol: "SOL-USD".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                 ],
                 updated_at: 0,
@@ -1032,12 +1160,14 @@ mod tests {
                         quote: "KRW".to_string(),
                         symbol: "KRW-BTC".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                     MarketInfo {
                         base: "ETH".to_string(),
                         quote: "KRW".to_string(),
                         symbol: "KRW-ETH".to_string(),
                         trading_enabled: true,
+                        rules: MarketRules::default(),
                     },
                 ],
                 updated_at: 0,
@@ -1062,4 +1192,17 @@ mod tests {
         assert_eq!(common.exchange_count("ETH"), 2);
         assert_eq!(common.exchange_count("SOL"), 0); // Not in result
     }
+
+    #[test]
+    fn test_parse_market_rules() {
+        assert_eq!(parse_fixed("0.00001000"), 1_000);
+        assert_eq!(parse_fixed("0.29"), 29_000_000);
+        assert_eq!(parse_fixed("5"), 500_000_000);
+        assert_eq!(parse_fixed("0"), 0);
+        assert_eq!(parse_fixed("n/a"), 0);
+        assert_eq!(lot_from_decimals(0), FixedPoint::SCALE);
+        assert_eq!(lot_from_decimals(4), 10_000);
+        assert_eq!(lot_from_decimals(12), 1);
+        assert_eq!(krw_market_rules().min_notional, 5_000 * FixedPoint::SCALE);
+    }
 }
@@ -107,7 +107,9 @@ impl SubscriptionChange {
 ///
 /// - Each exchange has a dedicated `mpsc::Sender<SubscriptionChange>` channel
 /// - Current subscriptions are tracked in a lock-free `DashMap`
-/// - The `update_subscriptions` method calculates diffs and sends only new markets
+/// - The `update_subscriptions` method calculates diffs, sends only new markets
+///   and unsubscribes markets that are no longer wanted
+/// - Pinned symbols (e.g. stablecoin rate feeds) are never unsubscribed
 ///
 /// ## Example
 ///
@@ -131,6 +133,8 @@ pub struct SubscriptionManager {
     pool_senders: HashMap<Exchange, Vec<mpsc::Sender<SubscriptionChange>>>,
     /// Current subscription state per exchange (lock-free)
     current_subscriptions: Arc<DashMap<Exchange, HashSet<String>>>,
+    /// Symbols kept subscribed regardless of `update_subscriptions` (lock-free)
+    pinned: DashMap<Exchange, HashSet<String>>,
 }
 
 impl SubscriptionManager {
@@ -140,6 +144,7 @@ impl SubscriptionManager {
             senders: HashMap::new(),
             pool_senders: HashMap::new(),
             current_subscriptions: Arc::new(DashMap::new()),
+            pinned: DashMap::new(),
         }
     }
 
@@ -231,6 +236,17 @@ impl SubscriptionManager {
         );
     }
 
+    /// Pin symbols so they stay subscribed when absent from `update_subscriptions`.
+    ///
+    /// Used for feeds that are not discovered markets, such as stablecoin rate
+    /// symbols, which must survive market list changes.
+    pub fn pin_subscriptions(&self, exchange: Exchange, symbols: &[String]) {
+        self.pinned
+            .entry(exchange)
+            .or_default()
+            .extend(symbols.iter().cloned());
+    }
+
     /// Check if an exchange is registered as a pool.
     #[must_use]
     pub fn is_pool(&self, exchange: Exchange) -> bool {
@@ -251,7 +267,12 @@ impl SubscriptionManager {
     /// This method:
     /// 1. Calculates the diff between current subscriptions and new markets
     /// 2. Sends only the new markets (not already subscribed) to the channel
-    /// 3. Updates the internal subscription tracking state
+    /// 3. Unsubscribes tracked markets missing from `new_markets`, except pinned ones
+    /// 4. Updates the internal subscription tracking state
+    ///
+    /// `new_markets` is the complete wanted set; an empty slice unsubscribes every
+    /// unpinned market. Pool connections each receive the full unsubscribe list,
+    /// since the manager does not track which connection holds a symbol.
     ///
     /// Returns `Ok(usize)` with the count of newly subscribed markets,
     /// or `Err` if the channel send fails.
@@ -270,7 +291,7 @@ impl SubscriptionManager {
         exchange: Exchange,
         new_markets: &[String],
     ) -> Result<usize, SubscriptionError> {
-        let new_set: HashSet<String> = new_markets.iter().cloned().collect();
+        let mut new_set: HashSet<String> = new_markets.iter().cloned().collect();
 
         // Get current subscriptions or empty set
         let current = self
@@ -279,8 +300,15 @@ impl SubscriptionManager {
             .map(|r| r.value().clone())
             .unwrap_or_default();
 
+        // Pinned symbols that are subscribed stay subscribed and tracked
+        if let Some(pinned) = self.pinned.get(&exchange) {
+            new_set.extend(current.intersection(pinned.value()).cloned());
+        }
+
         // Calculate diff: new_markets - current = to_subscribe
         let to_subscribe: Vec<String> = new_set.difference(&current).cloned().collect();
+        // current - new_markets - pinned = to_unsubscribe
+        let to_unsubscribe: Vec<String> = current.difference(&new_set).cloned().collect();
 
         // Defensive check: Pool registered but no tracked subscriptions indicates race condition
         if self.pool_senders.contains_key(&exchange) && current.is_empty() && !new_set.is_empty() {
@@ -321,7 +349,30 @@ impl SubscriptionManager {
             }
         }
 
-        // Update current subscriptions to include all new markets
+        if !to_unsubscribe.is_empty() {
+            info!(
+                "{:?}: update_subscriptions - unsubscribing {} markets (first 3: {:?})",
+                exchange,
+                to_unsubscribe.len(),
+                to_unsubscribe.iter().take(3).collect::<Vec<_>>()
+            );
+            let senders: Vec<&mpsc::Sender<SubscriptionChange>> =
+                match self.pool_senders.get(&exchange) {
+                    Some(pool_senders) => pool_senders.iter().collect(),
+                    None => self.senders.get(&exchange).into_iter().collect(),
+                };
+            if senders.is_empty() {
+                return Err(SubscriptionError::ExchangeNotRegistered(exchange));
+            }
+            for sender in senders {
+                sender
+                    .send(SubscriptionChange::Unsubscribe(to_unsubscribe.clone()))
+                    .await
+                    .map_err(|e| SubscriptionError::ChannelSendError(e.to_string()))?;
+            }
+        }
+
+        // Update current subscriptions to the wanted markets
         self.current_subscriptions.insert(exchange, new_set);
 
         Ok(subscribed_count)
@@ -1878,21 +1929,32 @@ impl Default for BinanceSubscriptionBuilder {
     }
 }
 
-impl SubscriptionBuilder for BinanceSubscriptionBuilder {
-    fn build_subscribe_message(&self, symbols: &[String]) -> String {
+impl BinanceSubscriptionBuilder {
+    fn build_message(&self, method: &str, symbols: &[String]) -> String {
         let id = self.id_counter.fetch_add(1, Ordering::Relaxed);
         let streams: Vec<String> = symbols
             .iter()
             .map(|s| format!("\"{}@depth20@100ms\"", s.to_lowercase()))
             .collect();
         format!(
-            r#"{{"method":"SUBSCRIBE","params":[{}],"id":{}}}"#,
+            r#"{{"method":"{}","params":[{}],"id":{}}}"#,
+            method,
             streams.join(","),
             id
         )
     }
 }
 
+impl SubscriptionBuilder for BinanceSubscriptionBuilder {
+    fn build_subscribe_message(&self, symbols: &[String]) -> String {
+        self.build_message("SUBSCRIBE", symbols)
+    }
+
+    fn build_unsubscribe_message(&self, symbols: &[String]) -> Option<String> {
+        Some(self.build_message("UNSUBSCRIBE", symbols))
+    }
+}
+
 /// Coinbase subscription message builder.
 ///
 /// Builds WebSocket subscription messages for Coinbase level2 and heartbeats channels.
@@ -1955,6 +2017,16 @@ impl SubscriptionBuilder for CoinbaseSubscriptionBuilder {
             products_str, jwt
         )
     }
+
+    fn build_unsubscribe_message(&self, symbols: &[String]) -> Option<String> {
+        let products: Vec<String> = symbols.iter().map(|s| format!("\"{}\"", s)).collect();
+        let jwt = CoinbaseAdapter::generate_ws_jwt(self.credentials.as_ref()?).ok()?;
+        Some(format!(
+            r#"{{"type":"unsubscribe","product_ids":[{}],"channel":"level2","jwt":"{}"}}"#,
+            products.join(", "),
+            jwt
+        ))
+    }
 }
 
 /// Bybit subscription message builder.
@@ -1987,13 +2059,23 @@ impl Default for BybitSubscriptionBuilder {
     }
 }
 
-impl SubscriptionBuilder for BybitSubscriptionBuilder {
-    fn build_subscribe_message(&self, symbols: &[String]) -> String {
+impl BybitSubscriptionBuilder {
+    fn build_message(op: &str, symbols: &[String]) -> String {
         let topics: Vec<String> = symbols
             .iter()
             .map(|s| format!("\"orderbook.50.{}\"", s.to_uppercase()))
             .collect();
-        format!(r#"{{"op": "subscribe", "args": [{}]}}"#, topics.join(", "))
+        format!(r#"{{"op": "{}", "args": [{}]}}"#, op, topics.join(", "))
+    }
+}
+
+impl SubscriptionBuilder for BybitSubscriptionBuilder {
+    fn build_subscribe_message(&self, symbols: &[String]) -> String {
+        Self::build_message("subscribe", symbols)
+    }
+
+    fn build_unsubscribe_message(&self, symbols: &[String]) -> Option<String> {
+        Some(Self::build_message("unsubscribe", symbols))
     }
 }
 
@@ -2027,8 +2109,8 @@ impl Default for GateIOSubscriptionBuilder {
     }
 }
 
-impl SubscriptionBuilder for GateIOSubscriptionBuilder {
-    fn build_subscribe_message(&self, symbols: &[String]) -> String {
+impl GateIOSubscriptionBuilder {
+    fn build_message(event: &str, symbols: &[String]) -> String {
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -2038,13 +2120,24 @@ impl SubscriptionBuilder for GateIOSubscriptionBuilder {
             .map(|s| format!("\"ob.{}.50\"", s.to_uppercase()))
             .collect();
         format!(
-            r#"{{"time": {}, "channel": "spot.obu", "event": "subscribe", "payload": [{}]}}"#,
+            r#"{{"time": {}, "channel": "spot.obu", "event": "{}", "payload": [{}]}}"#,
             timestamp,
+            event,
             payloads.join(", ")
         )
     }
 }
 
+impl SubscriptionBuilder for GateIOSubscriptionBuilder {
+    fn build_subscribe_message(&self, symbols: &[String]) -> String {
+        Self::build_message("subscribe", symbols)
+    }
+
+    fn build_unsubscribe_message(&self, symbols: &[String]) -> Option<String> {
+        Some(Self::build_message("unsubscribe", symbols))
+    }
+}
+
 /// Upbit subscription message builder.
 ///
 /// Builds WebSocket subscription messages for Upbit ticker and orderbook channels.
@@ -2351,6 +2444,62 @@ mod tests {
         assert!(received.is_err()); // Channel should be empty
     }
 
+    #[tokio::test]
+    async fn test_subscription_manager_update_subscriptions_unsubscribes_removed() {
+        let mut manager = SubscriptionManager::new();
+        let (tx, mut rx) = SubscriptionManager::create_channel();
+        manager.register_exchange(Exchange::Binance, tx);
+        manager.track_initial_subscriptions(
+            Exchange::Binance,
+            vec![
+                "btcusdt".to_string(),
+                "ethusdt".to_string(),
+                "USDCUSDT".to_string(),
+            ],
+        );
+        manager.pin_subscriptions(Exchange::Binance, &["USDCUSDT".to_string()]);
+
+        let result = manager
+            .update_subscriptions(Exchange::Binance, &["btcusdt".to_string()])
+            .await;
+        assert_eq!(result.unwrap(), 0);
+
+        // Only the unpinned market is unsubscribed
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            SubscriptionChange::Unsubscribe(vec!["ethusdt".to_string()])
+        );
+        assert!(rx.try_recv().is_err());
+        let current = manager
+            .get_current_subscriptions(Exchange::Binance)
+            .unwrap();
+        assert_eq!(current.len(), 2);
+        assert!(current.contains("USDCUSDT"));
+
+        // An empty market list keeps only pinned symbols
+        let _ = manager.update_subscriptions(Exchange::Binance, &[]).await;
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            SubscriptionChange::Unsubscribe(vec!["btcusdt".to_string()])
+        );
+        assert_eq!(manager.subscription_count(Exchange::Binance), 1);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_manager_pool_unsubscribe_reaches_every_connection() {
+        let mut manager = SubscriptionManager::new();
+        let (tx1, mut rx1) = SubscriptionManager::create_channel();
+        let (tx2, mut rx2) = SubscriptionManager::create_channel();
+        manager.register_exchange_pool(Exchange::Binance, vec![tx1, tx2]);
+        manager.track_initial_subscriptions(Exchange::Binance, vec!["btcusdt".to_string()]);
+
+        let _ = manager.update_subscriptions(Exchange::Binance, &[]).await;
+
+        let expected = SubscriptionChange::Unsubscribe(vec!["btcusdt".to_string()]);
+        assert_eq!(rx1.try_recv().unwrap(), expected);
+        assert_eq!(rx2.try_recv().unwrap(), expected);
+    }
+
     #[tokio::test]
     async fn test_subscription_manager_update_unregistered_exchange() {
         let manager = SubscriptionManager::new();
@@ -2649,6 +2798,16 @@ mod tests {
         assert_eq!(msg, expected);
     }
 
+    #[test]
+    fn test_binance_subscription_builder_unsubscribe_message() {
+        let builder = BinanceSubscriptionBuilder::new();
+        let _ = builder.build_subscribe_message(&["BTCUSDT".to_string()]);
+        let msg = builder.build_unsubscribe_message(&["BTCUSDT".to_string()]);
+
+        let expected = r#"{"method":"UNSUBSCRIBE","params":["btcusdt@depth20@100ms"],"id":2}"#;
+        assert_eq!(msg.as_deref(), Some(expected));
+    }
+
     #[test]
     fn test_binance_subscription_builder_is_send_sync() {
         // Verify BinanceSubscriptionBuilder implements Send + Sync (required by SubscriptionBuilder)
@@ -2688,6 +2847,14 @@ mod tests {
         let _ = builder.build_subscribe_message(&["BTC-USD".to_string()]);
     }
 
+    #[test]
+    fn test_coinbase_subscription_builder_unsubscribe_without_credentials() {
+        let builder = CoinbaseSubscriptionBuilder::new();
+        assert!(builder
+            .build_unsubscribe_message(&["BTC-USD".to_string()])
+            .is_none());
+    }
+
     #[test]
     fn test_coinbase_subscription_builder_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -2751,6 +2918,16 @@ mod tests {
         assert!(msg.contains(r#""args": []"#));
     }
 
+    #[test]
+    fn test_bybit_subscription_builder_unsubscribe_message() {
+        let builder = BybitSubscriptionBuilder::new();
+        let msg = builder
+            .build_unsubscribe_message(&["btcusdt".to_string()])
+            .unwrap();
+
+        assert_eq!(msg, r#"{"op": "unsubscribe", "args": ["orderbook.50.BTCUSDT"]}"#);
+    }
+
     #[test]
     fn test_bybit_subscription_builder_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -2816,6 +2993,18 @@ mod tests {
         assert!(msg.contains(r#""payload": []"#));
     }
 
+    #[test]
+    fn test_gateio_subscription_builder_unsubscribe_message() {
+        let builder = GateIOSubscriptionBuilder::new();
+        let msg = builder
+            .build_unsubscribe_message(&["BTC_USDT".to_string()])
+            .unwrap();
+
+        assert!(msg.contains(r#""channel": "spot.obu""#));
+        assert!(msg.contains(r#""event": "unsubscribe""#));
+        assert!(msg.contains(r#""ob.BTC_USDT.50""#));
+    }
+
     #[test]
     fn test_gateio_subscription_builder_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -2830,6 +3019,14 @@ mod tests {
         assert!(format!("{:?}", builder).contains("UpbitSubscriptionBuilder"));
     }
 
+    #[test]
+    fn test_upbit_subscription_builder_has_no_unsubscribe() {
+        let builder = UpbitSubscriptionBuilder::new();
+        assert!(builder
+            .build_unsubscribe_message(&["KRW-BTC".to_string()])
+            .is_none());
+    }
+
     #[test]
     fn test_upbit_subscription_builder_default() {
         let builder = UpbitSubscriptionBuilder::default();
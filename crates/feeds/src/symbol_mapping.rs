@@ -113,7 +113,7 @@ impl SymbolMappings {
 }
 
 /// Get the symbol mappings file path.
-fn get_mappings_path() -> PathBuf {
+pub fn get_mappings_path() -> PathBuf {
     // Try to find project root by looking for .env
     let mut path = std::env::current_dir().unwrap_or_default();
 
@@ -34,6 +34,14 @@ pub trait SubscriptionBuilder: Send + Sync {
     ///
     /// Returns a JSON string to send to the WebSocket server.
     fn build_subscribe_message(&self, symbols: &[String]) -> String;
+
+    /// Build an unsubscribe message for the given symbols.
+    ///
+    /// Returns `None` for exchanges without delta unsubscription (e.g. Upbit and
+    /// Bithumb, where each message replaces the whole subscription list).
+    fn build_unsubscribe_message(&self, _symbols: &[String]) -> Option<String> {
+        None
+    }
 }
 
 /// Message received from WebSocket.
@@ -819,8 +827,31 @@ impl WsClient {
                                 self.config.exchange
                             );
                         }
+                    } else if let SubscriptionChange::Unsubscribe(symbols) = change {
+                        let msg = self
+                            .subscription_builder
+                            .as_ref()
+                            .and_then(|builder| builder.build_unsubscribe_message(&symbols));
+                        let Some(msg) = msg else {
+                            debug!(
+                                "{:?}: Unsubscribe of {} symbols not supported, ignoring",
+                                self.config.exchange,
+                                symbols.len()
+                            );
+                            continue;
+                        };
+                        info!(
+                            "{:?}: Runtime unsubscription for {} symbols",
+                            self.config.exchange,
+                            symbols.len()
+                        );
+                        if let Err(e) = write.send(Message::Text(msg)).await {
+                            warn!(
+                                "{:?}: Failed to send runtime unsubscription: {}",
+                                self.config.exchange, e
+                            );
+                        }
                     }
-                    // Note: Unsubscribe handling can be added in future stories if needed
                 }
             }
         }
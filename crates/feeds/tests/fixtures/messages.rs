@@ -0,0 +1,60 @@
+//! Representative exchange messages shared by the parse benchmarks and the
+//! parse budget tests.
+
+#![allow(dead_code)]
+
+pub const BINANCE_TICKER: &str = r#"{"e":"24hrTicker","E":1700000000000,"s":"BTCUSDT","c":"50000.00","b":"49999.00","a":"50001.00","v":"1000.00"}"#;
+
+pub const BINANCE_BOOK_TICKER: &str =
+    r#"{"u":400900217,"s":"ETHUSDT","b":"3000.00","B":"10.5","a":"3001.00","A":"8.2"}"#;
+
+pub const COINBASE_TICKER: &str = r#"{"type":"ticker","product_id":"BTC-USD","price":"50000.00","best_bid":"49999.00","best_ask":"50001.00","volume_24h":"1000.00","time":"2024-01-01T00:00:00.000000Z"}"#;
+
+pub const BYBIT_TICKER: &str = r#"{"topic":"tickers.BTCUSDT","type":"snapshot","data":{"symbol":"BTCUSDT","lastPrice":"50000.00","highPrice24h":"51000.00","lowPrice24h":"49000.00","prevPrice24h":"49500.00","volume24h":"1000.00","turnover24h":"50000000.00","price24hPcnt":"0.01"},"ts":1700000000000}"#;
+
+pub const GATEIO_TICKER: &str = r#"{"time":1669107766,"time_ms":1669107766406,"channel":"spot.tickers","event":"update","result":{"currency_pair":"BTC_USDT","last":"50000.00","lowest_ask":"50001.00","highest_bid":"49999.00","change_percentage":"1.5","base_volume":"1000.00","quote_volume":"50000000.00","high_24h":"51000.00","low_24h":"49000.00"}}"#;
+
+pub const UPBIT_TICKER: &str = r#"{"type":"ticker","code":"KRW-BTC","trade_price":145000000.0,"opening_price":144000000.0,"high_price":146000000.0,"low_price":143000000.0,"acc_trade_volume_24h":1234.5,"timestamp":1700000000000}"#;
+
+pub const BITHUMB_TICKER: &str = r#"{"type":"ticker","code":"KRW-BTC","trade_price":145000000.0,"opening_price":144000000.0,"high_price":146000000.0,"low_price":143000000.0,"acc_trade_volume_24h":1234.5,"timestamp":1700000000000}"#;
+
+/// Binance partial depth stream message with `levels` levels per side.
+pub fn binance_depth(levels: usize) -> String {
+    let side = |start: f64, step: f64| {
+        (0..levels)
+            .map(|i| {
+                format!(
+                    r#"["{:.2}","{:.4}"]"#,
+                    start + step * i as f64,
+                    0.5 + i as f64 * 0.1
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    format!(
+        r#"{{"stream":"btcusdt@depth20@100ms","data":{{"lastUpdateId":160,"bids":[{}],"asks":[{}]}}}}"#,
+        side(49_999.0, -0.5),
+        side(50_001.0, 0.5)
+    )
+}
+
+/// Upbit orderbook message with `levels` units.
+pub fn upbit_orderbook(levels: usize) -> String {
+    let units = (0..levels)
+        .map(|i| {
+            format!(
+                r#"{{"ask_price":{:.1},"bid_price":{:.1},"ask_size":{:.4},"bid_size":{:.4}}}"#,
+                145_100_000.0 + 10_000.0 * i as f64,
+                145_000_000.0 - 10_000.0 * i as f64,
+                0.5 + i as f64 * 0.1,
+                0.7 + i as f64 * 0.1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{"type":"orderbook","code":"KRW-BTC","timestamp":1700000000000,"orderbook_units":[{}]}}"#,
+        units
+    )
+}
@@ -0,0 +1,115 @@
+//! Parse latency budget regression tests.
+//!
+//! Times each adapter's parse path on the shared fixtures and fails when the
+//! median exceeds its budget in `benches/budgets.json`. Ignored under
+//! `cargo test`; the latency budget CI job runs them with
+//! `cargo test --release -p arbitrage-feeds --test parse_budget -- --ignored --test-threads=1`.
+
+#[path = "fixtures/messages.rs"]
+mod messages;
+
+use arbitrage_feeds::{
+    BinanceAdapter, BithumbAdapter, BybitAdapter, CoinbaseAdapter, GateIOAdapter, UpbitAdapter,
+};
+use messages::*;
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::Instant;
+
+const SAMPLES: usize = 31;
+const ITERS: u32 = 200;
+
+fn budget_ns(name: &str) -> f64 {
+    let budgets: HashMap<String, serde_json::Value> =
+        serde_json::from_str(include_str!("../benches/budgets.json")).unwrap();
+    budgets
+        .get(name)
+        .and_then(|budget| budget.as_f64())
+        .unwrap_or_else(|| panic!("no budget for {}", name))
+}
+
+/// Median ns per call of `op` over `SAMPLES` samples of `ITERS` calls.
+fn assert_within_budget<T>(name: &str, mut op: impl FnMut() -> T) {
+    for _ in 0..ITERS {
+        black_box(op());
+    }
+    let mut samples: Vec<f64> = (0..SAMPLES)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ITERS {
+                black_box(op());
+            }
+            start.elapsed().as_nanos() as f64 / ITERS as f64
+        })
+        .collect();
+    samples.sort_by(|a, b| a.total_cmp(b));
+
+    let measured = samples[SAMPLES / 2];
+    let budget = budget_ns(name);
+    assert!(
+        measured <= budget,
+        "{}: {:.0} ns/message exceeds budget of {:.0} ns",
+        name,
+        measured,
+        budget
+    );
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_binance_parse_budget() {
+    assert_within_budget("binance/parse_ticker", || {
+        BinanceAdapter::parse_ticker(black_box(BINANCE_TICKER), 1)
+    });
+    assert_within_budget("binance/parse_book_ticker", || {
+        BinanceAdapter::parse_book_ticker(black_box(BINANCE_BOOK_TICKER), 1)
+    });
+    let depth = binance_depth(20);
+    assert_within_budget("binance/parse_partial_depth/20_levels", || {
+        BinanceAdapter::parse_partial_depth(black_box(&depth))
+    });
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_coinbase_parse_budget() {
+    assert_within_budget("coinbase/parse_ticker", || {
+        CoinbaseAdapter::parse_ticker(black_box(COINBASE_TICKER), 1)
+    });
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_bybit_parse_budget() {
+    assert_within_budget("bybit/parse_ticker", || {
+        BybitAdapter::parse_ticker_with_symbol(black_box(BYBIT_TICKER))
+    });
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_gateio_parse_budget() {
+    assert_within_budget("gateio/parse_ticker", || {
+        GateIOAdapter::parse_ticker_with_symbol(black_box(GATEIO_TICKER))
+    });
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_upbit_parse_budget() {
+    assert_within_budget("upbit/parse_ticker", || {
+        UpbitAdapter::parse_ticker(black_box(UPBIT_TICKER), 1)
+    });
+    let orderbook = upbit_orderbook(15);
+    assert_within_budget("upbit/parse_orderbook/15_levels", || {
+        UpbitAdapter::parse_orderbook(black_box(&orderbook), 1)
+    });
+}
+
+#[test]
+#[ignore = "timing-sensitive; run by the latency budget CI job"]
+fn test_bithumb_parse_budget() {
+    assert_within_budget("bithumb/parse_ticker", || {
+        BithumbAdapter::parse_ticker(black_box(BITHUMB_TICKER), 1)
+    });
+}